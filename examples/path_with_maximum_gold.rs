@@ -28,7 +28,7 @@
 
 use std::{
     collections::HashSet,
-    iter::{repeat, repeat_with},
+    iter::{repeat_n, repeat_with},
 };
 
 const MAX_GOLD_AMOUNT: u32 = 9999;
@@ -58,15 +58,15 @@ fn main() {
     // Convert it into a HashSet for `O(1)` lookup performance. Optional
     let path: HashSet<_> = path.into_iter().collect();
 
-    for y in 0..GRID_LEN {
-        for x in 0..GRID_LEN {
+    for (y, row) in grid.iter().enumerate() {
+        for (x, gold_amount) in row.iter().enumerate() {
             let cell = Cell { x, y };
 
             if path.contains(&cell) {
                 print!("\x1b[31m");
             }
 
-            print!("{:0width$} ", grid[y][x], width = max_digits_count as _);
+            print!("{gold_amount:0width$} ", width = max_digits_count as _);
 
             if path.contains(&cell) {
                 print!("\x1b[0m");
@@ -116,7 +116,7 @@ fn path_with_maximum_gold(grid: &Grid) -> (u32, Vec<Cell>) {
     }
 
     let mut memoization_table: MemoizationTable =
-        repeat(vec![None; grid_len]).take(grid_len).collect();
+        repeat_n(vec![None; grid_len], grid_len).collect();
 
     let starting_cell = Cell { x: 0, y: 0 };
     let max_amount = path_with_maximum_gold_sol(grid, &mut memoization_table, starting_cell);
@@ -0,0 +1,26 @@
+//! The collections used across the crate: those of `std` when the `std` feature is enabled,
+//! or those of `hashbrown` and `alloc` otherwise.
+
+pub(crate) use alloc::collections::{BTreeSet, BinaryHeap, VecDeque};
+#[cfg(not(feature = "std"))]
+pub(crate) use hashbrown::hash_map;
+#[cfg(feature = "std")]
+pub(crate) use std::collections::{hash_map, HashMap, HashSet};
+
+// Aliases rather than re-exports, so that the allocator parameter of `hashbrown` doesn't have to
+// be inferred
+#[cfg(not(feature = "std"))]
+pub(crate) type HashMap<K, V, S = DefaultHashBuilder> = hashbrown::HashMap<K, V, S>;
+#[cfg(not(feature = "std"))]
+pub(crate) type HashSet<T, S = DefaultHashBuilder> = hashbrown::HashSet<T, S>;
+
+/// The hasher of a [`Graph`](crate::graph::Graph) unless specified otherwise: the randomly seeded
+/// SipHash of `std`, or the hasher of `hashbrown` without the `std` feature.
+#[cfg(feature = "std")]
+pub type DefaultHashBuilder = std::collections::hash_map::RandomState;
+#[cfg(not(feature = "std"))]
+pub type DefaultHashBuilder = hashbrown::DefaultHashBuilder;
+
+/// The hasher of `hashbrown`, which is much faster than SipHash on the integer IDs of vertices,
+/// but isn't resistant to collisions crafted by an attacker.
+pub type FastHashBuilder = hashbrown::DefaultHashBuilder;
@@ -0,0 +1,289 @@
+use super::Edge;
+use crate::collections::HashMap;
+use crate::graph::{measure::Measure, Graph, VertexIdx};
+use alloc::{vec, vec::Vec};
+use core::{hash::BuildHasher, ops::Sub};
+
+/// Finds the minimum spanning arborescence of `graph` rooted at `root_idx`, using the
+/// Chu-Liu/Edmonds algorithm in `O(|V||E|)`.
+///
+/// An arborescence is a directed spanning tree where every vertex is reachable from the root.
+/// Self-loops are ignored.
+///
+/// Returns the edges of the arborescence as `(from, to, weight)` along with their total weight,
+/// or `None` if the root doesn't exist or not every vertex is reachable from it.
+pub fn min_arborescence<V, E, D, S: BuildHasher + Default>(
+    graph: &Graph<V, E, D, S>,
+    root_idx: VertexIdx,
+) -> Option<(Vec<Edge<E>>, E)>
+where
+    E: Measure + Sub<Output = E>,
+{
+    let vertices: Vec<VertexIdx> = graph.vertices.keys().copied().collect();
+    let compact_idx: HashMap<VertexIdx, usize, S> = vertices
+        .iter()
+        .enumerate()
+        .map(|(i, &idx)| (idx, i))
+        .collect();
+    let root = *compact_idx.get(&root_idx)?;
+
+    let mut edges = Vec::new();
+
+    for (from_idx, vertex) in &graph.vertices {
+        for (to_idx, &edge) in vertex.neighbors() {
+            let (from, to) = (compact_idx[from_idx], compact_idx[&to_idx]);
+
+            if from != to && to != root {
+                edges.push((from, to, edge));
+            }
+        }
+    }
+
+    let selected = contract(vertices.len(), root, &edges)?;
+    let mut total = E::zero();
+    let arborescence = selected
+        .into_iter()
+        .map(|i| {
+            let (from, to, edge) = edges[i];
+
+            total = total.add(edge);
+            (vertices[from], vertices[to], edge)
+        })
+        .collect();
+
+    Some((arborescence, total))
+}
+
+/// A cycle of cheapest incoming edges, contracted into a single vertex.
+struct Contraction {
+    /// The vertices of the cycle, along with the indices of their cheapest incoming edges
+    cycle: Vec<(usize, usize)>,
+    /// The index of every edge of the contracted graph in the graph before the contraction
+    origins: Vec<usize>,
+    /// The vertex of the cycle every edge of the contracted graph enters, if any
+    entries: Vec<Option<usize>>,
+}
+
+/// Selects the edges of the minimum arborescence among `edges` of `len` vertices, by
+/// contracting one cycle of cheapest incoming edges at a time, then expanding the cycles back
+/// in reverse order.
+///
+/// Returns indices of `edges`.
+fn contract<E>(mut len: usize, mut root: usize, edges: &[(usize, usize, E)]) -> Option<Vec<usize>>
+where
+    E: Measure + Sub<Output = E>,
+{
+    let mut edges = edges.to_vec();
+    let mut contractions: Vec<Contraction> = Vec::new();
+
+    let mut selected = loop {
+        // The cheapest incoming edge of every vertex
+        let mut cheapest: Vec<Option<usize>> = vec![None; len];
+
+        for (i, &(_, to, edge)) in edges.iter().enumerate() {
+            if cheapest[to].is_none_or(|j| edge.compare(&edges[j].2).is_lt()) {
+                cheapest[to] = Some(i);
+            }
+        }
+
+        let parent = |vertex: usize| edges[cheapest[vertex].expect("Checked below")].0;
+
+        if (0..len).any(|vertex| vertex != root && cheapest[vertex].is_none()) {
+            return None;
+        }
+
+        // Look for a cycle by following the cheapest incoming edges backwards
+        let mut visited_by = vec![None; len];
+        let mut cycle = Vec::new();
+
+        for start in 0..len {
+            let mut vertex = start;
+
+            while vertex != root && visited_by[vertex].is_none() {
+                visited_by[vertex] = Some(start);
+                vertex = parent(vertex);
+            }
+
+            if vertex != root && visited_by[vertex] == Some(start) {
+                let cycle_start = vertex;
+
+                loop {
+                    cycle.push(vertex);
+                    vertex = parent(vertex);
+
+                    if vertex == cycle_start {
+                        break;
+                    }
+                }
+
+                break;
+            }
+        }
+
+        if cycle.is_empty() {
+            break cheapest.into_iter().flatten().collect::<Vec<_>>();
+        }
+
+        // Contract the cycle into a single vertex, placed last
+        let mut in_cycle = vec![false; len];
+
+        for &vertex in &cycle {
+            in_cycle[vertex] = true;
+        }
+
+        let mut new_vertex = vec![0; len];
+        let mut new_len = 0;
+
+        for vertex in 0..len {
+            if !in_cycle[vertex] {
+                new_vertex[vertex] = new_len;
+                new_len += 1;
+            }
+        }
+
+        for &vertex in &cycle {
+            new_vertex[vertex] = new_len;
+        }
+
+        let mut new_edges = Vec::with_capacity(edges.len());
+        let mut origins = Vec::with_capacity(edges.len());
+        let mut entries = Vec::with_capacity(edges.len());
+
+        for (i, &(from, to, edge)) in edges.iter().enumerate() {
+            if new_vertex[from] == new_vertex[to] {
+                continue;
+            }
+
+            // Entering the cycle at `to` replaces the cycle edge that used to enter `to`
+            let edge = if in_cycle[to] {
+                edge - edges[cheapest[to].expect("Cycle vertices have an incoming edge")].2
+            } else {
+                edge
+            };
+
+            new_edges.push((new_vertex[from], new_vertex[to], edge));
+            origins.push(i);
+            entries.push(in_cycle[to].then_some(to));
+        }
+
+        contractions.push(Contraction {
+            cycle: cycle
+                .into_iter()
+                .map(|vertex| {
+                    let edge = cheapest[vertex].expect("Cycle vertices have an incoming edge");
+
+                    (vertex, edge)
+                })
+                .collect(),
+            origins,
+            entries,
+        });
+
+        len = new_len + 1;
+        root = new_vertex[root];
+        edges = new_edges;
+    };
+
+    // Break every cycle where the selected edge enters it, from the last contraction to the first
+    while let Some(contraction) = contractions.pop() {
+        let entry = selected
+            .iter()
+            .find_map(|&i| contraction.entries[i])
+            .expect("The contracted cycle has an incoming edge");
+
+        selected = selected
+            .into_iter()
+            .map(|i| contraction.origins[i])
+            .chain(
+                contraction
+                    .cycle
+                    .into_iter()
+                    .filter(|&(vertex, _)| vertex != entry)
+                    .map(|(_, edge)| edge),
+            )
+            .collect();
+    }
+
+    Some(selected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::min_arborescence;
+    use crate::graph::Graph;
+
+    #[test]
+    fn test_min_arborescence() {
+        // The cheap cycle a ⇄ b has to be broken, entering it from the root through a
+        //
+        //   r --10--> a
+        //   r --12--> b
+        //   a --1---> b
+        //   b --2---> a
+        //   b --3---> c
+        let mut graph = Graph::<(), u32>::new();
+        let [r, a, b, c] = [(); 4].map(|_| graph.insert_vertex(()));
+
+        graph.insert_or_update_edges([(r, a, 10), (r, b, 12), (a, b, 1), (b, a, 2), (b, c, 3)]);
+
+        let (mut edges, total) = min_arborescence(&graph, r).expect("Every vertex is reachable");
+
+        edges.sort();
+
+        let mut expected = vec![(r, a, 10), (a, b, 1), (b, c, 3)];
+
+        expected.sort();
+
+        assert_eq!(total, 14, "Wrong arborescence weight");
+        assert_eq!(edges, expected, "Wrong arborescence edges");
+        assert!(
+            min_arborescence(&graph, c).is_none(),
+            "Vertex r isn't reachable from c"
+        );
+    }
+
+    #[test]
+    fn test_min_arborescence_nested_cycles() {
+        // A long chain v0 ⇄ v1 ⇄ ... ⇄ vn, entered from the root only at its end, contracts
+        // cycles into cycles
+        let mut graph = Graph::<(), u32>::new();
+        let r = graph.insert_vertex(());
+        let chain: Vec<_> = (0..2000).map(|_| graph.insert_vertex(())).collect();
+
+        for pair in chain.windows(2) {
+            graph.insert_or_update_edges([(pair[0], pair[1], 1), (pair[1], pair[0], 1)]);
+        }
+
+        graph.insert_or_update_edge(r, chain[chain.len() - 1], 100);
+
+        let (edges, total) = min_arborescence(&graph, r).expect("Every vertex is reachable");
+
+        assert_eq!(total, 100 + 1999, "Wrong arborescence weight");
+        assert_eq!(edges.len(), 2000, "An arborescence has |V| - 1 edges");
+        assert!(
+            chain
+                .windows(2)
+                .all(|pair| edges.contains(&(pair[1], pair[0], 1))),
+            "Wrong arborescence edges"
+        );
+    }
+
+    #[test]
+    fn test_min_arborescence_f64() {
+        let mut graph = Graph::<(), f64>::new();
+        let [r, a, b] = [(); 3].map(|_| graph.insert_vertex(()));
+
+        graph.insert_or_update_edges([(r, a, 2.5), (r, b, 3.0), (a, b, 0.25), (b, a, 0.5)]);
+
+        let (mut edges, total) = min_arborescence(&graph, r).expect("Every vertex is reachable");
+
+        edges.sort_by_key(|&(from, to, _)| (from, to));
+
+        assert_eq!(total, 2.75, "Wrong arborescence weight");
+        assert_eq!(
+            edges,
+            [(r, a, 2.5), (a, b, 0.25)],
+            "Wrong arborescence edges"
+        );
+    }
+}
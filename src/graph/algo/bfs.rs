@@ -1,5 +1,7 @@
-use crate::graph::{Graph, VertexIdx};
-use std::collections::VecDeque;
+use crate::collections::{hash_map::Entry, DefaultHashBuilder, HashMap, HashSet, VecDeque};
+use crate::graph::{Directed, Graph, GraphView, VertexIdx};
+use alloc::vec::Vec;
+use core::hash::BuildHasher;
 
 #[derive(PartialEq)]
 enum Color {
@@ -33,17 +35,51 @@ impl<V> EnhancedWeight<V> {
     }
 }
 
+/// The output of [`breadth_first_search`]: a copy of the graph whose vertex weights are enhanced
+/// with the BFS results.
+pub type BfsTree<'g, V, E, D = Directed, S = DefaultHashBuilder> =
+    Graph<EnhancedWeight<&'g V>, &'g E, D, S>;
+
+impl<V, E, D, S: BuildHasher + Default> Graph<EnhancedWeight<V>, E, D, S> {
+    /// The distance of `target_idx` from the source, in `O(1)`.
+    ///
+    /// Returns `None` if `target_idx` doesn't exist or wasn't reached.
+    pub fn distance_to(&self, target_idx: VertexIdx) -> Option<u32> {
+        let weight = self.get_vertex(target_idx)?.weight();
+
+        (weight.color != Color::White).then_some(weight.distance)
+    }
+
+    /// The shortest path from the source to `target_idx`, by walking up the parents
+    /// in `O(|path|)`.
+    ///
+    /// Returns `None` if `target_idx` doesn't exist or wasn't reached.
+    pub fn path_to(&self, target_idx: VertexIdx) -> Option<Vec<VertexIdx>> {
+        let mut path = Vec::with_capacity(self.distance_to(target_idx)? as usize + 1);
+        let mut vertex_idx = target_idx;
+
+        path.push(vertex_idx);
+
+        while let Some(parent_idx) = self.get_vertex(vertex_idx)?.weight().parent {
+            vertex_idx = parent_idx;
+            path.push(vertex_idx);
+        }
+
+        path.reverse();
+
+        Some(path)
+    }
+}
+
 /// Performs BFS on a graph `graph` with source vertex `src_vertex_idx`.
 ///
 /// Returns `None` if the source vertex doesn't exist in the graph.
-pub fn breadth_first_search<V, E>(
-    graph: &Graph<V, E>,
+pub fn breadth_first_search<V, E, D, S: BuildHasher + Default>(
+    graph: &Graph<V, E, D, S>,
     src_vertex_idx: VertexIdx,
-) -> Option<Graph<EnhancedWeight<&V>, &E>> {
-    let mut graph: Graph<EnhancedWeight<&V>, &E> = graph.filter_map(
-        |idx| {
-            let vertex = graph.get_vertex(idx)?;
-
+) -> Option<BfsTree<'_, V, E, D, S>> {
+    let mut graph: BfsTree<V, E, D, S> = graph.filter_map(
+        |idx, vertex| {
             Some(if idx == src_vertex_idx {
                 EnhancedWeight {
                     weight: vertex.weight(),
@@ -63,9 +99,7 @@ pub fn breadth_first_search<V, E>(
         |_, _, edge| Some(edge),
     );
 
-    if graph.get_vertex(src_vertex_idx).is_none() {
-        return None;
-    }
+    graph.get_vertex(src_vertex_idx)?;
 
     let mut queue: VecDeque<VertexIdx> = VecDeque::from([src_vertex_idx]);
     let mut neighbors_cache = Vec::new();
@@ -74,9 +108,8 @@ pub fn breadth_first_search<V, E>(
         let vertex = graph.get_vertex(vertex_idx)?;
         let vertex_distance = vertex.weight().distance;
 
-        // A workaround for borrowing a mutable reference while an immutable reference is still in use.
-        // I don't like it, and I'm too lazy to refactor everything, but it works.
-        neighbors_cache.extend(vertex.neighbors().map(|(idx, _)| idx));
+        // Snapshot the neighbors, since they are mutated below
+        neighbors_cache.extend(vertex.neighbor_indices());
 
         for neighbor_idx in neighbors_cache.drain(..) {
             let neighbor = graph.get_mut_vertex(neighbor_idx)?;
@@ -97,3 +130,630 @@ pub fn breadth_first_search<V, E>(
 
     Some(graph)
 }
+
+/// The distances and parents of the vertices reached by a BFS.
+///
+/// The functions that take a [`Graph`], such as [`zero_one_bfs`], hash the maps with its hasher
+/// `S`. Those that take any [`GraphView`], such as [`breadth_first_search_compact`], use
+/// [`DefaultHashBuilder`], since a view has no hasher of its own.
+pub struct BfsResult<S = DefaultHashBuilder> {
+    distances: HashMap<VertexIdx, u32, S>,
+    parents: HashMap<VertexIdx, VertexIdx, S>,
+}
+
+impl<S: BuildHasher + Default> BfsResult<S> {
+    /// Start from `sources`, at distance 0.
+    fn new(sources: impl IntoIterator<Item = VertexIdx>) -> Self {
+        Self {
+            distances: sources.into_iter().map(|src_idx| (src_idx, 0)).collect(),
+            parents: HashMap::default(),
+        }
+    }
+
+    /// The distance of every reached vertex from the (nearest) source.
+    pub fn distances(&self) -> &HashMap<VertexIdx, u32, S> {
+        &self.distances
+    }
+
+    /// The parent of every reached vertex, except the sources.
+    pub fn parents(&self) -> &HashMap<VertexIdx, VertexIdx, S> {
+        &self.parents
+    }
+
+    /// The distance of `target_idx` from the (nearest) source, in `O(1)`.
+    ///
+    /// Returns `None` if `target_idx` wasn't reached.
+    pub fn distance_to(&self, target_idx: VertexIdx) -> Option<u32> {
+        self.distances.get(&target_idx).copied()
+    }
+
+    /// The shortest path from the (nearest) source to `target_idx`, in `O(|path|)`.
+    ///
+    /// Returns `None` if `target_idx` wasn't reached.
+    pub fn path_to(&self, target_idx: VertexIdx) -> Option<Vec<VertexIdx>> {
+        let mut path = Vec::with_capacity(self.distance_to(target_idx)? as usize + 1);
+        let mut vertex_idx = target_idx;
+
+        path.push(vertex_idx);
+
+        while let Some(&parent_idx) = self.parents.get(&vertex_idx) {
+            vertex_idx = parent_idx;
+            path.push(vertex_idx);
+        }
+
+        path.reverse();
+
+        Some(path)
+    }
+}
+
+/// Performs BFS on a graph `graph` with source vertex `src_vertex_idx` in `O(|V'| + |E'|)`,
+/// where `|V'|` and `|E'|` are the vertices and the edges reached.
+///
+/// Unlike [`breadth_first_search`], only the reached vertices are recorded, without copying
+/// the graph. It runs on any [`GraphView`], such as a frozen [`CsrGraph`](crate::graph::CsrGraph).
+///
+/// Returns `None` if the source vertex doesn't exist in the graph.
+pub fn breadth_first_search_compact<G: GraphView>(
+    graph: &G,
+    src_vertex_idx: VertexIdx,
+) -> Option<BfsResult> {
+    if !graph.contains_vertex(src_vertex_idx) {
+        return None;
+    }
+
+    Some(compact_bfs(graph, [src_vertex_idx], None))
+}
+
+/// Performs BFS on a graph `graph` starting from all `sources` at once in `O(|V'| + |E'|)`,
+/// where `|V'|` and `|E'|` are the vertices and the edges reached.
+///
+/// Every reached vertex gets its distance from the nearest source, and [`BfsResult::path_to`]
+/// starts from that source.
+///
+/// Returns `None` if any of the source vertices doesn't exist in the graph.
+pub fn breadth_first_search_multi<G: GraphView>(
+    graph: &G,
+    sources: impl IntoIterator<Item = VertexIdx>,
+) -> Option<BfsResult> {
+    let sources: Vec<VertexIdx> = sources.into_iter().collect();
+
+    for &src_idx in &sources {
+        if !graph.contains_vertex(src_idx) {
+            return None;
+        }
+    }
+
+    Some(compact_bfs(graph, sources, None))
+}
+
+/// Finds a path with the fewest edges from `src_vertex_idx` to `dst_vertex_idx` of `graph` using
+/// BFS, which stops as soon as the destination is dequeued, in `O(|V| + |E|)` at worst.
+///
+/// Returns the vertices of the path in order, or `None` if either vertex doesn't exist or
+/// the destination isn't reachable.
+pub fn shortest_path_unweighted<V, E, D, S: BuildHasher + Default>(
+    graph: &Graph<V, E, D, S>,
+    src_vertex_idx: VertexIdx,
+    dst_vertex_idx: VertexIdx,
+) -> Option<Vec<VertexIdx>> {
+    graph.get_vertex(src_vertex_idx)?;
+    graph.get_vertex(dst_vertex_idx)?;
+
+    let mut result = BfsResult::<S>::new([src_vertex_idx]);
+    let mut queue = VecDeque::from([src_vertex_idx]);
+
+    while let Some(vertex_idx) = queue.pop_front() {
+        if vertex_idx == dst_vertex_idx {
+            return result.path_to(dst_vertex_idx);
+        }
+
+        let distance = result.distances[&vertex_idx];
+
+        for (neighbor_idx, _) in graph.vertices[&vertex_idx].neighbors() {
+            if let Entry::Vacant(entry) = result.distances.entry(neighbor_idx) {
+                entry.insert(distance + 1);
+                result.parents.insert(neighbor_idx, vertex_idx);
+                queue.push_back(neighbor_idx);
+            }
+        }
+    }
+
+    None
+}
+
+/// Computes the shortest distances from `src_vertex_idx` of `graph`, where the edges for which
+/// `is_zero` returns `true` weigh `0` and the rest weigh `1`, using a deque in `O(|V| + |E|)`.
+///
+/// Zero-weight edges are explored before the rest by pushing their ends to the front of
+/// the deque, so each vertex is settled like in Dijkstra's algorithm.
+///
+/// Returns `None` if the source vertex doesn't exist in the graph.
+pub fn zero_one_bfs<V, E, D, S: BuildHasher + Default>(
+    graph: &Graph<V, E, D, S>,
+    src_vertex_idx: VertexIdx,
+    is_zero: impl Fn(&E) -> bool,
+) -> Option<BfsResult<S>> {
+    graph.get_vertex(src_vertex_idx)?;
+
+    let mut result = BfsResult::new([src_vertex_idx]);
+    let mut settled = HashSet::<_, S>::default();
+    let mut deque = VecDeque::from([src_vertex_idx]);
+
+    while let Some(vertex_idx) = deque.pop_front() {
+        if !settled.insert(vertex_idx) {
+            continue;
+        }
+
+        let distance = result.distances[&vertex_idx];
+
+        for (neighbor_idx, edge) in graph.vertices[&vertex_idx].neighbors() {
+            let is_zero = is_zero(edge);
+            let new_distance = if is_zero { distance } else { distance + 1 };
+
+            if result
+                .distances
+                .get(&neighbor_idx)
+                .is_none_or(|&old_distance| new_distance < old_distance)
+            {
+                result.distances.insert(neighbor_idx, new_distance);
+                result.parents.insert(neighbor_idx, vertex_idx);
+
+                if is_zero {
+                    deque.push_front(neighbor_idx);
+                } else {
+                    deque.push_back(neighbor_idx);
+                }
+            }
+        }
+    }
+
+    Some(result)
+}
+
+/// Performs BFS on a graph `graph` with source vertex `src_vertex_idx`, without exploring further
+/// than `max_depth` edges away from the source, in `O(|V'| + |E'|)`, where `|V'|` and `|E'|` are
+/// the vertices and the edges reached.
+///
+/// Useful for neighborhood queries, like every vertex within 3 edges.
+///
+/// Returns `None` if the source vertex doesn't exist in the graph.
+pub fn breadth_first_search_limited<G: GraphView>(
+    graph: &G,
+    src_vertex_idx: VertexIdx,
+    max_depth: u32,
+) -> Option<BfsResult> {
+    if !graph.contains_vertex(src_vertex_idx) {
+        return None;
+    }
+
+    Some(compact_bfs(graph, [src_vertex_idx], Some(max_depth)))
+}
+
+/// BFS starting from all `sources` at once, at distance 0, that doesn't go deeper than `max_depth`.
+fn compact_bfs<G: GraphView>(
+    graph: &G,
+    sources: impl IntoIterator<Item = VertexIdx>,
+    max_depth: Option<u32>,
+) -> BfsResult {
+    let mut result = BfsResult::new([]);
+    let mut queue = VecDeque::new();
+
+    for src_idx in sources {
+        if result.distances.insert(src_idx, 0).is_none() {
+            queue.push_back(src_idx);
+        }
+    }
+
+    while let Some(vertex_idx) = queue.pop_front() {
+        let distance = result.distances[&vertex_idx];
+
+        if max_depth.is_some_and(|max_depth| distance >= max_depth) {
+            continue;
+        }
+
+        let neighbors = graph
+            .neighbors(vertex_idx)
+            .expect("Only existing vertices are reached");
+
+        for (neighbor_idx, _) in neighbors {
+            if let Entry::Vacant(entry) = result.distances.entry(neighbor_idx) {
+                entry.insert(distance + 1);
+                result.parents.insert(neighbor_idx, vertex_idx);
+                queue.push_back(neighbor_idx);
+            }
+        }
+    }
+
+    result
+}
+
+/// Performs a level-synchronous BFS on a graph `graph` with source vertex `src_vertex_idx`,
+/// expanding each level across threads with [`rayon`], in `O(|V| + |E|)` total work.
+///
+/// The neighbors of the whole frontier are gathered in parallel, then the undiscovered ones form
+/// the next frontier. Worth it for graphs with millions of edges. The distances match
+/// [`breadth_first_search_compact`], but the parents may differ.
+///
+/// Returns `None` if the source vertex doesn't exist in the graph.
+#[cfg(feature = "parallel")]
+pub fn par_bfs<V, E, D, S>(
+    graph: &Graph<V, E, D, S>,
+    src_vertex_idx: VertexIdx,
+) -> Option<BfsResult<S>>
+where
+    V: Sync,
+    E: Sync,
+    S: BuildHasher + Default + Sync,
+{
+    use rayon::prelude::*;
+
+    graph.get_vertex(src_vertex_idx)?;
+
+    let mut result = BfsResult::new([src_vertex_idx]);
+    let mut frontier = vec![src_vertex_idx];
+    let mut distance = 0;
+
+    while !frontier.is_empty() {
+        distance += 1;
+
+        let candidates: Vec<(VertexIdx, VertexIdx)> = frontier
+            .par_iter()
+            .flat_map_iter(|&vertex_idx| {
+                graph.vertices[&vertex_idx]
+                    .neighbors()
+                    .filter(|(neighbor_idx, _)| !result.distances.contains_key(neighbor_idx))
+                    .map(move |(neighbor_idx, _)| (neighbor_idx, vertex_idx))
+            })
+            .collect();
+
+        frontier.clear();
+
+        for (neighbor_idx, vertex_idx) in candidates {
+            if let Entry::Vacant(entry) = result.distances.entry(neighbor_idx) {
+                entry.insert(distance);
+                result.parents.insert(neighbor_idx, vertex_idx);
+                frontier.push(neighbor_idx);
+            }
+        }
+    }
+
+    Some(result)
+}
+
+/// The BFS trees covering every vertex of a graph, created with [`bfs_forest`].
+pub struct BfsForest<S = DefaultHashBuilder> {
+    roots: Vec<VertexIdx>,
+    components: HashMap<VertexIdx, usize, S>,
+    result: BfsResult<S>,
+}
+
+impl<S: BuildHasher + Default> BfsForest<S> {
+    /// The root of every tree, indexed by the tree's ID.
+    pub fn roots(&self) -> &[VertexIdx] {
+        &self.roots
+    }
+
+    /// The ID of the tree every vertex belongs to, numbered from `0`.
+    pub fn components(&self) -> &HashMap<VertexIdx, usize, S> {
+        &self.components
+    }
+
+    /// The distance of every vertex from the root of its tree.
+    pub fn distances(&self) -> &HashMap<VertexIdx, u32, S> {
+        self.result.distances()
+    }
+
+    /// The parent of every vertex, except the roots.
+    pub fn parents(&self) -> &HashMap<VertexIdx, VertexIdx, S> {
+        self.result.parents()
+    }
+
+    /// The path from the root of its tree to `target_idx`, in `O(|path|)`.
+    ///
+    /// Returns `None` if `target_idx` doesn't exist.
+    pub fn path_to(&self, target_idx: VertexIdx) -> Option<Vec<VertexIdx>> {
+        self.result.path_to(target_idx)
+    }
+}
+
+/// Performs BFS on a graph `graph` from every vertex that hasn't been reached yet, until every
+/// vertex is reached, in `O(|V| log |V| + |E|)`.
+///
+/// Each BFS grows a new tree, which is a connected component if every edge exists in both
+/// directions. Otherwise, it's whatever the new root reaches among the vertices not reached yet.
+/// Roots are picked in increasing order of their ID.
+pub fn bfs_forest<V, E, D, S: BuildHasher + Default>(graph: &Graph<V, E, D, S>) -> BfsForest<S> {
+    let mut vertices: Vec<VertexIdx> = graph.vertices.keys().copied().collect();
+
+    vertices.sort_unstable();
+
+    let mut forest = BfsForest {
+        roots: Vec::new(),
+        components: HashMap::with_capacity_and_hasher(vertices.len(), S::default()),
+        result: BfsResult {
+            distances: HashMap::with_capacity_and_hasher(vertices.len(), S::default()),
+            parents: HashMap::with_capacity_and_hasher(vertices.len(), S::default()),
+        },
+    };
+    let mut queue = VecDeque::new();
+
+    for root_idx in vertices {
+        if forest.components.contains_key(&root_idx) {
+            continue;
+        }
+
+        let component = forest.roots.len();
+
+        forest.roots.push(root_idx);
+        forest.components.insert(root_idx, component);
+        forest.result.distances.insert(root_idx, 0);
+        queue.push_back(root_idx);
+
+        while let Some(vertex_idx) = queue.pop_front() {
+            let distance = forest.result.distances[&vertex_idx];
+
+            for (neighbor_idx, _) in graph.vertices[&vertex_idx].neighbors() {
+                if let Entry::Vacant(entry) = forest.components.entry(neighbor_idx) {
+                    entry.insert(component);
+                    forest.result.distances.insert(neighbor_idx, distance + 1);
+                    forest.result.parents.insert(neighbor_idx, vertex_idx);
+                    queue.push_back(neighbor_idx);
+                }
+            }
+        }
+    }
+
+    forest
+}
+
+/// A lazy BFS, created with [`bfs_iter`].
+pub struct BfsIter<'g, V, E, D = Directed, S = DefaultHashBuilder> {
+    graph: &'g Graph<V, E, D, S>,
+    queue: VecDeque<(VertexIdx, u32)>,
+    discovered: HashSet<VertexIdx, S>,
+}
+
+impl<V, E, D, S: BuildHasher + Default> Iterator for BfsIter<'_, V, E, D, S> {
+    type Item = (VertexIdx, u32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (vertex_idx, depth) = self.queue.pop_front()?;
+
+        for (neighbor_idx, _) in self.graph.vertices[&vertex_idx].neighbors() {
+            if self.discovered.insert(neighbor_idx) {
+                self.queue.push_back((neighbor_idx, depth + 1));
+            }
+        }
+
+        Some((vertex_idx, depth))
+    }
+}
+
+/// Lazily performs BFS on a graph `graph` with source vertex `src_vertex_idx`, yielding every
+/// reached vertex along with its depth in BFS order. Each call to `next` takes `O(deg)`.
+///
+/// Returns `None` if the source vertex doesn't exist in the graph.
+pub fn bfs_iter<V, E, D, S: BuildHasher + Default>(
+    graph: &Graph<V, E, D, S>,
+    src_vertex_idx: VertexIdx,
+) -> Option<BfsIter<'_, V, E, D, S>> {
+    graph.get_vertex(src_vertex_idx)?;
+
+    Some(BfsIter {
+        graph,
+        queue: VecDeque::from([(src_vertex_idx, 0)]),
+        discovered: [src_vertex_idx].into_iter().collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        bfs_forest, bfs_iter, breadth_first_search, breadth_first_search_compact,
+        breadth_first_search_limited, breadth_first_search_multi, shortest_path_unweighted,
+        zero_one_bfs,
+    };
+    use crate::graph::Graph;
+
+    #[test]
+    fn test_breadth_first_search_compact() {
+        // a → b → c → d, with a shortcut a → c and an unreachable e
+        let mut graph = Graph::<(), ()>::new();
+        let [a, b, c, d, e] = [(); 5].map(|_| graph.insert_vertex(()));
+
+        graph.insert_or_update_edges([(a, b, ()), (b, c, ()), (c, d, ()), (a, c, ())]);
+
+        let result = breadth_first_search_compact(&graph, a).expect("Vertex a exists");
+        let full = breadth_first_search(&graph, a).expect("Vertex a exists");
+
+        for idx in [a, b, c, d] {
+            assert_eq!(
+                result.distances().get(&idx).copied(),
+                full.get_vertex(idx).map(|v| v.weight().distance()),
+                "Both variants must agree on the distances"
+            );
+        }
+
+        assert_eq!(result.path_to(d), Some(vec![a, c, d]), "Wrong path");
+        assert_eq!(result.path_to(a), Some(vec![a]), "Wrong path");
+        assert_eq!(result.path_to(e), None, "Vertex e isn't reachable");
+        assert!(
+            breadth_first_search_compact(&graph, e).is_some(),
+            "Vertex e exists"
+        );
+    }
+
+    #[test]
+    fn test_bfs_iter() {
+        // a → b → c → d, with a shortcut a → c
+        let mut graph = Graph::<(), ()>::new();
+        let [a, b, c, d] = [(); 4].map(|_| graph.insert_vertex(()));
+
+        graph.insert_or_update_edges([(a, b, ()), (b, c, ()), (c, d, ()), (a, c, ())]);
+
+        let mut iter = bfs_iter(&graph, a).expect("Vertex a exists");
+
+        assert_eq!(iter.next(), Some((a, 0)), "The source comes first");
+
+        let mut rest: Vec<_> = iter.collect();
+
+        rest.sort_by_key(|&(_, depth)| depth);
+
+        assert_eq!(rest.len(), 3, "Every vertex must be visited");
+        assert_eq!(rest[2], (d, 2), "Wrong depth for vertex d");
+        assert!(
+            rest[..2].contains(&(b, 1)) && rest[..2].contains(&(c, 1)),
+            "Wrong depths for vertices b and c"
+        );
+    }
+
+    #[test]
+    fn test_breadth_first_search_multi() {
+        // A path a → b → c → d → e with sources at both ends
+        let mut graph = Graph::<(), ()>::new();
+        let [a, b, c, d, e] = [(); 5].map(|_| graph.insert_vertex(()));
+
+        graph.insert_or_update_edges([(a, b, ()), (b, c, ()), (c, d, ()), (d, e, ()), (e, d, ())]);
+
+        let result = breadth_first_search_multi(&graph, [a, e]).expect("Both sources exist");
+
+        assert_eq!(result.distances()[&b], 1, "Vertex b is next to a");
+        assert_eq!(result.distances()[&c], 2, "Vertex c is 2 edges away from a");
+        assert_eq!(
+            result.path_to(d),
+            Some(vec![e, d]),
+            "Vertex d is closer to e"
+        );
+    }
+
+    #[test]
+    fn test_shortest_path_unweighted() {
+        // a → b → c → d, with a shortcut a → c and an unreachable e
+        let mut graph = Graph::<(), ()>::new();
+        let [a, b, c, d, e] = [(); 5].map(|_| graph.insert_vertex(()));
+
+        graph.insert_or_update_edges([(a, b, ()), (b, c, ()), (c, d, ()), (a, c, ())]);
+
+        assert_eq!(
+            shortest_path_unweighted(&graph, a, d),
+            Some(vec![a, c, d]),
+            "Wrong path"
+        );
+        assert_eq!(
+            shortest_path_unweighted(&graph, b, b),
+            Some(vec![b]),
+            "Wrong path"
+        );
+        assert_eq!(
+            shortest_path_unweighted(&graph, a, e),
+            None,
+            "Vertex e isn't reachable"
+        );
+    }
+
+    #[test]
+    fn test_zero_one_bfs() {
+        // a -1→ b -1→ d, and a -0→ c -0→ b, so b and d are cheaper through c
+        let mut graph = Graph::<(), u8>::new();
+        let [a, b, c, d] = [(); 4].map(|_| graph.insert_vertex(()));
+
+        graph.insert_or_update_edges([(a, b, 1), (b, d, 1), (a, c, 0), (c, b, 0)]);
+
+        let result = zero_one_bfs(&graph, a, |&edge| edge == 0).expect("Vertex a exists");
+
+        assert_eq!(result.distances()[&b], 0, "Vertex b is reachable for free");
+        assert_eq!(result.distances()[&d], 1, "Wrong distance for vertex d");
+        assert_eq!(result.path_to(d), Some(vec![a, c, b, d]), "Wrong path");
+    }
+
+    #[test]
+    fn test_breadth_first_search_limited() {
+        // A path a → b → c → d
+        let mut graph = Graph::<(), ()>::new();
+        let [a, b, c, d] = [(); 4].map(|_| graph.insert_vertex(()));
+
+        graph.insert_or_update_edges([(a, b, ()), (b, c, ()), (c, d, ())]);
+
+        let result = breadth_first_search_limited(&graph, a, 2).expect("Vertex a exists");
+
+        assert_eq!(
+            result.distances().len(),
+            3,
+            "Only a, b and c are within 2 edges"
+        );
+        assert_eq!(
+            result.distances().get(&c),
+            Some(&2),
+            "Wrong distance for vertex c"
+        );
+        assert_eq!(result.path_to(d), None, "Vertex d is too far");
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_par_bfs() {
+        // A binary tree of depth 10, plus edges between the vertices of each level
+        let mut graph = Graph::<(), ()>::new();
+        let vertices: Vec<_> = (0..2047).map(|_| graph.insert_vertex(())).collect();
+
+        for i in 1..vertices.len() {
+            graph.insert_or_update_edge(vertices[(i - 1) / 2], vertices[i], ());
+            graph.insert_or_update_edge(vertices[i - 1], vertices[i], ());
+        }
+
+        let serial = breadth_first_search_compact(&graph, vertices[0]).expect("The root exists");
+        let parallel = super::par_bfs(&graph, vertices[0]).expect("The root exists");
+
+        assert_eq!(
+            serial.distances(),
+            parallel.distances(),
+            "Both variants must agree on the distances"
+        );
+    }
+
+    #[test]
+    fn test_bfs_forest() {
+        // Two components: a ↔ b ↔ c and d ↔ e
+        let mut graph = Graph::<(), ()>::new();
+        let [a, b, c, d, e] = [(); 5].map(|_| graph.insert_vertex(()));
+
+        for (from_idx, to_idx) in [(a, b), (b, c), (d, e)] {
+            graph.insert_or_update_edge(from_idx, to_idx, ());
+            graph.insert_or_update_edge(to_idx, from_idx, ());
+        }
+
+        let forest = bfs_forest(&graph);
+
+        assert_eq!(forest.roots().len(), 2, "There are 2 components");
+        assert_eq!(
+            forest.components()[&a],
+            forest.components()[&c],
+            "Vertices a and c are connected"
+        );
+        assert_ne!(
+            forest.components()[&a],
+            forest.components()[&e],
+            "Vertices a and e aren't connected"
+        );
+        assert_eq!(forest.distances().len(), 5, "Every vertex must be reached");
+        assert_eq!(forest.path_to(c), Some(vec![a, b, c]), "Wrong path");
+    }
+
+    #[test]
+    fn test_bfs_tree_path_to() {
+        // a → b → c → d, with a shortcut a → c and an unreachable e
+        let mut graph = Graph::<(), ()>::new();
+        let [a, b, c, d, e] = [(); 5].map(|_| graph.insert_vertex(()));
+
+        graph.insert_or_update_edges([(a, b, ()), (b, c, ()), (c, d, ()), (a, c, ())]);
+
+        let tree = breadth_first_search(&graph, a).expect("Vertex a exists");
+
+        assert_eq!(tree.distance_to(d), Some(2), "Wrong distance for vertex d");
+        assert_eq!(tree.path_to(d), Some(vec![a, c, d]), "Wrong path");
+        assert_eq!(tree.path_to(a), Some(vec![a]), "Wrong path");
+        assert_eq!(tree.distance_to(e), None, "Vertex e isn't reachable");
+        assert_eq!(tree.path_to(e), None, "Vertex e isn't reachable");
+    }
+}
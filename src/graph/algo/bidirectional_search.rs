@@ -0,0 +1,182 @@
+use crate::collections::HashMap;
+use crate::graph::{Graph, VertexIdx};
+use alloc::{vec, vec::Vec};
+use core::hash::BuildHasher;
+
+/// Finds a path with the fewest edges from `src_idx` to `dst_idx` of `graph` by running BFS from
+/// both ends until they meet, in `O(|V| + |E|)` at worst.
+///
+/// Each step expands a whole level of the side with the smaller frontier, forwards from the source
+/// or backwards (against the edges) from the destination. On graphs that branch out quickly, this
+/// explores far fewer vertices than a single BFS.
+///
+/// The backward search follows the [predecessors](Graph::predecessors) of each vertex, so the
+/// graph should have a [reverse index](Graph::with_reverse_index). Without one, the predecessors
+/// of every vertex are collected first in `Θ(|V| + |E|)`, which makes this no cheaper than
+/// [`shortest_path_unweighted`](super::shortest_path_unweighted).
+///
+/// Returns the vertices of the path in order, or `None` if either vertex doesn't exist or
+/// the destination isn't reachable.
+pub fn bidirectional_search<V, E, D, S: BuildHasher + Default>(
+    graph: &Graph<V, E, D, S>,
+    src_idx: VertexIdx,
+    dst_idx: VertexIdx,
+) -> Option<Vec<VertexIdx>> {
+    graph.get_vertex(src_idx)?;
+    graph.get_vertex(dst_idx)?;
+
+    if src_idx == dst_idx {
+        return Some(vec![src_idx]);
+    }
+
+    // Without a reverse index, finding the predecessors of a vertex scans the whole graph
+    let predecessors = (!graph.has_reverse_index()).then(|| {
+        let mut predecessors: HashMap<VertexIdx, Vec<VertexIdx>, S> = HashMap::default();
+
+        for (&from_idx, vertex) in &graph.vertices {
+            for (to_idx, _) in vertex.neighbors() {
+                predecessors.entry(to_idx).or_default().push(from_idx);
+            }
+        }
+
+        predecessors
+    });
+    let predecessors_of = |idx| match &predecessors {
+        Some(predecessors) => predecessors.get(&idx).cloned().unwrap_or_default(),
+        None => graph
+            .predecessors(idx)
+            .expect("Reached vertices exist")
+            .collect(),
+    };
+
+    // The parent of each vertex reached by either side, towards its end
+    let mut forward: HashMap<_, _, S> = [(src_idx, None)].into_iter().collect();
+    let mut backward: HashMap<_, _, S> = [(dst_idx, None)].into_iter().collect();
+    let mut forward_frontier = vec![src_idx];
+    let mut backward_frontier = vec![dst_idx];
+
+    let meeting_idx = loop {
+        if forward_frontier.is_empty() || backward_frontier.is_empty() {
+            return None;
+        }
+
+        let meeting_idx = if forward_frontier.len() <= backward_frontier.len() {
+            expand_level(&mut forward_frontier, &mut forward, &backward, |idx| {
+                graph.vertices[&idx]
+                    .neighbors()
+                    .map(|(to_idx, _)| to_idx)
+                    .collect()
+            })
+        } else {
+            expand_level(
+                &mut backward_frontier,
+                &mut backward,
+                &forward,
+                predecessors_of,
+            )
+        };
+
+        if let Some(meeting_idx) = meeting_idx {
+            break meeting_idx;
+        }
+    };
+
+    let mut path = vec![meeting_idx];
+    let mut vertex_idx = meeting_idx;
+
+    while let Some(parent_idx) = forward[&vertex_idx] {
+        path.push(parent_idx);
+        vertex_idx = parent_idx;
+    }
+
+    path.reverse();
+    vertex_idx = meeting_idx;
+
+    while let Some(parent_idx) = backward[&vertex_idx] {
+        path.push(parent_idx);
+        vertex_idx = parent_idx;
+    }
+
+    Some(path)
+}
+
+/// Replaces `frontier` with the next level of its side. Since the whole level is expanded,
+/// every vertex where the sides meet is equally far from both ends.
+///
+/// Returns a vertex that the other side has also reached, if any.
+fn expand_level<S: BuildHasher>(
+    frontier: &mut Vec<VertexIdx>,
+    parents: &mut HashMap<VertexIdx, Option<VertexIdx>, S>,
+    other_parents: &HashMap<VertexIdx, Option<VertexIdx>, S>,
+    next: impl Fn(VertexIdx) -> Vec<VertexIdx>,
+) -> Option<VertexIdx> {
+    let mut meeting_idx = None;
+
+    for vertex_idx in core::mem::take(frontier) {
+        for next_idx in next(vertex_idx) {
+            if parents.contains_key(&next_idx) {
+                continue;
+            }
+
+            parents.insert(next_idx, Some(vertex_idx));
+            frontier.push(next_idx);
+
+            if other_parents.contains_key(&next_idx) {
+                meeting_idx.get_or_insert(next_idx);
+            }
+        }
+    }
+
+    meeting_idx
+}
+
+#[cfg(test)]
+mod tests {
+    use super::bidirectional_search;
+    use crate::graph::{algo::shortest_path_unweighted, Graph};
+
+    #[test]
+    fn test_bidirectional_search() {
+        // a → b → c → d → e, with a shortcut b → d and an edge back e → a
+        let mut graph = Graph::<(), ()>::new();
+        let [a, b, c, d, e] = [(); 5].map(|_| graph.insert_vertex(()));
+
+        graph.insert_or_update_edges([
+            (a, b, ()),
+            (b, c, ()),
+            (c, d, ()),
+            (d, e, ()),
+            (b, d, ()),
+            (e, a, ()),
+        ]);
+
+        assert_eq!(
+            bidirectional_search(&graph, a, e),
+            Some(vec![a, b, d, e]),
+            "Wrong path"
+        );
+        assert_eq!(
+            bidirectional_search(&graph, c, b).map(|path| path.len()),
+            shortest_path_unweighted(&graph, c, b).map(|path| path.len()),
+            "Both searches must find equally short paths"
+        );
+        assert_eq!(
+            bidirectional_search(&graph, d, d),
+            Some(vec![d]),
+            "Wrong path"
+        );
+
+        let indexed = graph.clone().with_reverse_index();
+
+        assert_eq!(
+            bidirectional_search(&indexed, a, e),
+            Some(vec![a, b, d, e]),
+            "The reverse index must give the same path"
+        );
+        assert_eq!(
+            bidirectional_search(&indexed, e, c),
+            Some(vec![e, a, b, c]),
+            "Wrong path"
+        );
+    }
+}
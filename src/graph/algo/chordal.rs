@@ -0,0 +1,93 @@
+use super::lex_bfs;
+use crate::collections::{HashMap, HashSet};
+use crate::graph::{Graph, VertexIdx};
+use alloc::vec::Vec;
+use core::hash::BuildHasher;
+
+/// Checks whether `graph` is chordal, that is every cycle of at least 4 vertices has a chord,
+/// in `O(|V| + |E|)`.
+///
+/// The reverse of a [`lex_bfs`] ordering is verified to be a perfect elimination ordering:
+/// the neighbors of each vertex that come after it must form a clique. It's enough to check
+/// that they are adjacent to the first of them.
+/// The graph is interpreted as undirected, ignoring self-loops.
+///
+/// Returns the perfect elimination ordering if the graph is chordal, otherwise `None`.
+pub fn is_chordal<V, E, D, S: BuildHasher + Default>(
+    graph: &Graph<V, E, D, S>,
+) -> Option<Vec<VertexIdx>> {
+    let mut adjacency: HashMap<VertexIdx, HashSet<VertexIdx, S>, S> = graph
+        .vertices
+        .keys()
+        .map(|&idx| (idx, HashSet::default()))
+        .collect();
+
+    for (&from_idx, vertex) in &graph.vertices {
+        for (to_idx, _) in vertex.neighbors() {
+            if from_idx != to_idx {
+                adjacency.entry(from_idx).or_default().insert(to_idx);
+                adjacency.entry(to_idx).or_default().insert(from_idx);
+            }
+        }
+    }
+
+    let mut ordering = lex_bfs(graph);
+
+    ordering.reverse();
+
+    let positions: HashMap<VertexIdx, usize, S> = ordering
+        .iter()
+        .enumerate()
+        .map(|(i, &idx)| (idx, i))
+        .collect();
+
+    for &vertex_idx in &ordering {
+        let position = positions[&vertex_idx];
+        let later: Vec<&VertexIdx> = adjacency[&vertex_idx]
+            .iter()
+            .filter(|idx| positions[*idx] > position)
+            .collect();
+        let Some(&first_idx) = later.iter().min_by_key(|idx| positions[**idx]) else {
+            continue;
+        };
+
+        if later
+            .iter()
+            .any(|&idx| idx != first_idx && !adjacency[first_idx].contains(idx))
+        {
+            return None;
+        }
+    }
+
+    Some(ordering)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::is_chordal;
+    use crate::graph::Graph;
+
+    #[test]
+    fn test_is_chordal() {
+        // The square a - b - c - d isn't chordal, until the chord a - c is added
+        //  a - b
+        //  |   |
+        //  d - c
+        let mut graph = Graph::<(), ()>::new();
+        let [a, b, c, d] = [(); 4].map(|_| graph.insert_vertex(()));
+
+        graph.insert_or_update_edges([(a, b, ()), (b, c, ()), (c, d, ()), (d, a, ())]);
+
+        assert!(is_chordal(&graph).is_none(), "The square has no chord");
+
+        graph.insert_or_update_edge(a, c, ());
+
+        let ordering = is_chordal(&graph).expect("The square has a chord");
+
+        assert_eq!(ordering.len(), 4, "Every vertex must be ordered");
+        assert!(
+            [b, d].contains(&ordering[0]),
+            "Only b and d can be eliminated first"
+        );
+    }
+}
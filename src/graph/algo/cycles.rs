@@ -0,0 +1,325 @@
+use crate::collections::{HashMap, HashSet};
+use crate::graph::{Graph, VertexIdx};
+use alloc::{vec, vec::Vec};
+use core::hash::BuildHasher;
+
+/// A lazy iterator over the elementary cycles of a graph, created by [`simple_cycles`].
+pub struct SimpleCycles {
+    vertices: Vec<VertexIdx>,
+    adjacency: Vec<Vec<usize>>,
+    next_start: usize,
+    search: Option<CircuitSearch>,
+}
+
+/// The state of the circuit search from a single start vertex, kept between calls to `next`.
+struct CircuitSearch {
+    start: usize,
+    in_component: Vec<bool>,
+    blocked: Vec<bool>,
+    blocked_by: Vec<HashSet<usize>>,
+    path: Vec<usize>,
+    /// The vertices of `path` along with the position of the next neighbor to examine
+    stack: Vec<(usize, usize)>,
+    /// Whether a cycle has been found through each vertex of `path`
+    closed: Vec<bool>,
+}
+
+/// Enumerates lazily all the elementary cycles of `graph` using Johnson's algorithm in
+/// `O((|V| + |E|)(c + 1))`, where `c` is the number of cycles.
+///
+/// Each cycle is yielded as its vertices in order, without repeating the first one.
+/// Self-loops are yielded as single-vertex cycles. The memory used is `O(|V| + |E|)`
+/// regardless of the number of cycles.
+pub fn simple_cycles<V, E, D, S: BuildHasher + Default>(graph: &Graph<V, E, D, S>) -> SimpleCycles {
+    let vertices: Vec<VertexIdx> = graph.vertices.keys().copied().collect();
+    let compact_idx: HashMap<VertexIdx, usize, S> = vertices
+        .iter()
+        .enumerate()
+        .map(|(i, &idx)| (idx, i))
+        .collect();
+    let mut adjacency = vec![Vec::new(); vertices.len()];
+
+    for (from_idx, vertex) in &graph.vertices {
+        for (to_idx, _) in vertex.neighbors() {
+            let (from, to) = (compact_idx[from_idx], compact_idx[&to_idx]);
+
+            adjacency[from].push(to);
+        }
+    }
+
+    SimpleCycles {
+        vertices,
+        adjacency,
+        next_start: 0,
+        search: None,
+    }
+}
+
+impl SimpleCycles {
+    /// Finds the strongly connected components of the subgraph induced by the vertices with
+    /// compact indices of at least `first`, using Tarjan's algorithm in `O(|V| + |E|)`.
+    ///
+    /// Returns the least vertex on a cycle of that subgraph, along with the vertices of its
+    /// component, if any.
+    fn least_component(&self, first: usize) -> Option<(usize, Vec<usize>)> {
+        let len = self.vertices.len();
+        let mut order = vec![usize::MAX; len];
+        let mut lowlink = vec![0; len];
+        let mut on_stack = vec![false; len];
+        let mut stack = Vec::new();
+        let mut counter = 0;
+        let mut least: Option<(usize, Vec<usize>)> = None;
+
+        for root in first..len {
+            if order[root] != usize::MAX {
+                continue;
+            }
+
+            order[root] = counter;
+            lowlink[root] = counter;
+            counter += 1;
+            stack.push(root);
+            on_stack[root] = true;
+
+            // The vertices being visited along with the position of the next neighbor to examine
+            let mut calls = vec![(root, 0)];
+
+            while let Some((vertex, next_neighbor)) = calls.last_mut() {
+                let vertex = *vertex;
+
+                if let Some(&neighbor) = self.adjacency[vertex].get(*next_neighbor) {
+                    *next_neighbor += 1;
+
+                    if neighbor < first {
+                        continue;
+                    }
+
+                    if order[neighbor] == usize::MAX {
+                        order[neighbor] = counter;
+                        lowlink[neighbor] = counter;
+                        counter += 1;
+                        stack.push(neighbor);
+                        on_stack[neighbor] = true;
+                        calls.push((neighbor, 0));
+                    } else if on_stack[neighbor] {
+                        lowlink[vertex] = lowlink[vertex].min(order[neighbor]);
+                    }
+
+                    continue;
+                }
+
+                calls.pop();
+
+                if let Some(&(parent, _)) = calls.last() {
+                    lowlink[parent] = lowlink[parent].min(lowlink[vertex]);
+                }
+
+                if lowlink[vertex] != order[vertex] {
+                    continue;
+                }
+
+                let mut component = Vec::new();
+
+                loop {
+                    let member = stack.pop().expect("The root is on the stack");
+
+                    on_stack[member] = false;
+                    component.push(member);
+
+                    if member == vertex {
+                        break;
+                    }
+                }
+
+                // A lone vertex can only be on a cycle through a self-loop
+                let is_trivial = component.len() == 1 && !self.adjacency[vertex].contains(&vertex);
+                let min = *component.iter().min().expect("Components aren't empty");
+
+                if !is_trivial && least.as_ref().is_none_or(|&(least, _)| min < least) {
+                    least = Some((min, component));
+                }
+            }
+        }
+
+        least
+    }
+
+    /// Starts the search from the least vertex on a cycle that hasn't been searched from yet,
+    /// skipping the vertices that aren't on any cycle of the remaining subgraph.
+    fn start_search(&mut self) -> bool {
+        let len = self.vertices.len();
+        let Some((start, component)) = self.least_component(self.next_start) else {
+            self.next_start = len;
+            return false;
+        };
+        let mut in_component = vec![false; len];
+        let mut blocked = vec![false; len];
+
+        for vertex in component {
+            in_component[vertex] = true;
+        }
+
+        blocked[start] = true;
+        self.next_start = start + 1;
+        self.search = Some(CircuitSearch {
+            start,
+            in_component,
+            blocked,
+            blocked_by: vec![HashSet::new(); len],
+            path: vec![start],
+            stack: vec![(start, 0)],
+            closed: vec![false],
+        });
+
+        true
+    }
+}
+
+impl CircuitSearch {
+    fn unblock(&mut self, vertex: usize) {
+        let mut stack = vec![vertex];
+
+        while let Some(vertex) = stack.pop() {
+            if self.blocked[vertex] {
+                self.blocked[vertex] = false;
+                stack.extend(self.blocked_by[vertex].drain());
+            }
+        }
+    }
+
+    /// Resumes the search until the next cycle is found, or the search is exhausted.
+    fn next_cycle(&mut self, adjacency: &[Vec<usize>]) -> Option<Vec<usize>> {
+        while let Some((vertex, next_neighbor)) = self.stack.last_mut() {
+            let vertex = *vertex;
+            let neighbors = &adjacency[vertex];
+            let mut component_neighbor = None;
+
+            while let Some(&neighbor) = neighbors.get(*next_neighbor) {
+                *next_neighbor += 1;
+
+                if self.in_component[neighbor] {
+                    component_neighbor = Some(neighbor);
+                    break;
+                }
+            }
+
+            if let Some(neighbor) = component_neighbor {
+                if neighbor == self.start {
+                    *self.closed.last_mut().expect("The path isn't empty") = true;
+
+                    return Some(self.path.clone());
+                } else if !self.blocked[neighbor] {
+                    self.blocked[neighbor] = true;
+                    self.path.push(neighbor);
+                    self.closed.push(false);
+                    self.stack.push((neighbor, 0));
+                }
+            } else {
+                self.stack.pop();
+                self.path.pop();
+
+                if self.closed.pop().expect("The path isn't empty") {
+                    if let Some(closed) = self.closed.last_mut() {
+                        *closed = true;
+                    }
+
+                    self.unblock(vertex);
+                } else {
+                    for &neighbor in &adjacency[vertex] {
+                        if self.in_component[neighbor] {
+                            self.blocked_by[neighbor].insert(vertex);
+                        }
+                    }
+                }
+            }
+        }
+
+        None
+    }
+}
+
+impl Iterator for SimpleCycles {
+    type Item = Vec<VertexIdx>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(search) = &mut self.search {
+                if let Some(cycle) = search.next_cycle(&self.adjacency) {
+                    return Some(cycle.into_iter().map(|i| self.vertices[i]).collect());
+                }
+
+                self.search = None;
+            }
+
+            if !self.start_search() {
+                return None;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::simple_cycles;
+    use crate::graph::{random::RandomConfig, Graph};
+    use fastrand::Rng;
+
+    #[test]
+    fn test_simple_cycles() {
+        // a ⇄ b → c → a, plus a self-loop on c
+        let mut graph = Graph::<(), ()>::new();
+        let [a, b, c] = [(); 3].map(|_| graph.insert_vertex(()));
+
+        graph.insert_or_update_edges([(a, b, ()), (b, a, ()), (b, c, ()), (c, a, ()), (c, c, ())]);
+
+        let mut cycles: Vec<_> = simple_cycles(&graph)
+            .map(|mut cycle| {
+                // Rotate each cycle to start from its smallest vertex, for comparison
+                let min_pos = (0..cycle.len())
+                    .min_by_key(|&i| cycle[i])
+                    .expect("Cycles aren't empty");
+
+                cycle.rotate_left(min_pos);
+                cycle
+            })
+            .collect();
+
+        cycles.sort();
+
+        assert_eq!(cycles, vec![vec![a, b], vec![a, b, c], vec![c]]);
+
+        // Every simple cycle, found by extending every path from its least vertex
+        let config = RandomConfig {
+            min_vertices: 9,
+            max_vertices: 9,
+            density: 0.25,
+            self_loops: true,
+        };
+        let (graph, vertices) =
+            Graph::<(), ()>::random(&mut Rng::with_seed(4), config, |_| (), |_| ());
+        let mut expected = 0;
+
+        for &start in &vertices {
+            let mut paths = vec![vec![start]];
+
+            while let Some(path) = paths.pop() {
+                let last = *path.last().expect("Paths aren't empty");
+
+                for to_idx in graph[last].neighbor_indices() {
+                    if to_idx == start {
+                        expected += 1;
+                    } else if to_idx > start && !path.contains(&to_idx) {
+                        paths.push([path.as_slice(), &[to_idx]].concat());
+                    }
+                }
+            }
+        }
+
+        assert!(expected > 1, "The graph must have cycles");
+        assert_eq!(
+            simple_cycles(&graph).count(),
+            expected,
+            "Wrong number of cycles"
+        );
+    }
+}
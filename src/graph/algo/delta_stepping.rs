@@ -0,0 +1,214 @@
+use super::dijkstra::{DistanceHeap, ShortestPaths};
+use crate::collections::{HashMap, HashSet};
+use crate::graph::{
+    measure::{Measure, Ordered},
+    Graph, VertexIdx,
+};
+use alloc::vec::Vec;
+use core::{
+    cmp::{Ordering, Reverse},
+    hash::BuildHasher,
+};
+use rayon::prelude::*;
+
+/// Finds the shortest paths from `src_idx` to every vertex reachable from it using the
+/// Δ-stepping algorithm, relaxing the edges of many vertices at once across threads with
+/// [`rayon`]. The distances match [`dijkstra`](super::dijkstra), but the parents may differ.
+///
+/// The vertices are settled in buckets of distances `delta` wide, rather than one at a time:
+/// within a bucket, the light edges (up to `delta`) are relaxed in parallel until no vertex
+/// enters it anymore, then the heavy ones once. A `delta` around the average edge weight is a
+/// good start; smaller ones approach Dijkstra's algorithm, larger ones Bellman-Ford's. Worth it
+/// for graphs with millions of edges and a large diameter, such as road networks.
+///
+/// Since [`Measure`] can't be divided, each bucket starts at the smallest distance left instead
+/// of a multiple of `delta`.
+///
+/// The edge weights must not be less than [`Measure::zero`].
+///
+/// Returns `None` if the source vertex doesn't exist in the graph.
+///
+/// # Panics
+///
+/// If `delta` isn't greater than [`Measure::zero`].
+pub fn delta_stepping<V, E, D, S>(
+    graph: &Graph<V, E, D, S>,
+    src_idx: VertexIdx,
+    delta: E,
+) -> Option<ShortestPaths<E>>
+where
+    V: Sync,
+    E: Measure + Send + Sync,
+    S: BuildHasher + Default + Sync,
+{
+    assert!(
+        delta.compare(&E::zero()) == Ordering::Greater,
+        "Delta must be positive"
+    );
+
+    graph.get_vertex(src_idx)?;
+
+    let mut paths = ShortestPaths {
+        distances: HashMap::from([(src_idx, E::zero())]),
+        parents: HashMap::new(),
+    };
+    let mut heap = DistanceHeap::from([Reverse((Ordered(E::zero()), src_idx))]);
+    let is_light = |weight: &E| weight.compare(&delta) != Ordering::Greater;
+
+    while let Some(&Reverse((Ordered(min_distance), _))) = heap.peek() {
+        let end = min_distance.add(delta);
+        let mut bucket: HashSet<_, S> = HashSet::default();
+
+        loop {
+            let frontier = take_bucket(&mut heap, &paths, end);
+
+            if frontier.is_empty() {
+                break;
+            }
+
+            let requests = relaxations(graph, &paths, &frontier, is_light);
+
+            apply(&mut paths, &mut heap, requests);
+            bucket.extend(frontier);
+        }
+
+        let bucket: Vec<_> = bucket.into_iter().collect();
+        let requests = relaxations(graph, &paths, &bucket, |weight| !is_light(weight));
+
+        apply(&mut paths, &mut heap, requests);
+    }
+
+    Some(paths)
+}
+
+/// Pop the vertices of `heap` closer than `end`, skipping outdated entries of vertices that were
+/// reached through a shorter path since.
+fn take_bucket<E: Measure>(
+    heap: &mut DistanceHeap<E>,
+    paths: &ShortestPaths<E>,
+    end: E,
+) -> Vec<VertexIdx> {
+    let mut frontier = Vec::new();
+
+    while let Some(&Reverse((Ordered(distance), vertex_idx))) = heap.peek() {
+        if distance.compare(&end) != Ordering::Less {
+            break;
+        }
+
+        heap.pop();
+
+        if distance.compare(&paths.distances[&vertex_idx]) == Ordering::Equal {
+            frontier.push(vertex_idx);
+        }
+    }
+
+    frontier
+}
+
+/// Gather the tentative distances through the edges of `vertices` whose weight satisfies
+/// `filter`, as `(to, distance, from)`, across threads.
+fn relaxations<V, E, D, S>(
+    graph: &Graph<V, E, D, S>,
+    paths: &ShortestPaths<E>,
+    vertices: &[VertexIdx],
+    filter: impl Fn(&E) -> bool + Sync,
+) -> Vec<(VertexIdx, E, VertexIdx)>
+where
+    V: Sync,
+    E: Measure + Send + Sync,
+    S: BuildHasher + Default + Sync,
+{
+    vertices
+        .par_iter()
+        .flat_map_iter(|&vertex_idx| {
+            let distance = paths.distances[&vertex_idx];
+
+            graph.vertices[&vertex_idx]
+                .neighbors()
+                .filter(|(_, weight)| filter(weight))
+                .map(move |(neighbor_idx, &weight)| {
+                    (neighbor_idx, distance.add(weight), vertex_idx)
+                })
+        })
+        .collect()
+}
+
+/// Apply the `requests` that improve the distances, queuing the improved vertices.
+fn apply<E: Measure>(
+    paths: &mut ShortestPaths<E>,
+    heap: &mut DistanceHeap<E>,
+    requests: Vec<(VertexIdx, E, VertexIdx)>,
+) {
+    for (vertex_idx, distance, parent_idx) in requests {
+        if paths
+            .distances
+            .get(&vertex_idx)
+            .is_some_and(|old_distance| distance.compare(old_distance) != Ordering::Less)
+        {
+            continue;
+        }
+
+        paths.distances.insert(vertex_idx, distance);
+        paths.parents.insert(vertex_idx, parent_idx);
+        heap.push(Reverse((Ordered(distance), vertex_idx)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collections::FastHashBuilder;
+    use crate::graph::{
+        algo::dijkstra,
+        generate::{grid, Connectivity},
+        random::RandomConfig,
+        Directed,
+    };
+    use fastrand::Rng;
+
+    #[test]
+    fn test_delta_stepping() {
+        let config = RandomConfig {
+            min_vertices: 200,
+            max_vertices: 200,
+            density: 0.02,
+            self_loops: true,
+        };
+        let (graph, vertices) =
+            Graph::<(), u32>::random(&mut Rng::with_seed(5), config, |_| (), |rng| rng.u32(0..50));
+
+        for delta in [1, 10, 25, 1000] {
+            let paths = delta_stepping(&graph, vertices[0], delta).expect("The source exists");
+            let expected = dijkstra(&graph, vertices[0]).expect("The source exists");
+
+            assert_eq!(
+                paths.distances(),
+                expected.distances(),
+                "The distances must match Dijkstra's algorithm"
+            );
+        }
+
+        let (grid, ids): (Graph<(), u64, Directed, FastHashBuilder>, _) = grid(
+            30,
+            30,
+            Connectivity::Eight,
+            |_| (),
+            |(x1, y1), (x2, y2)| (x1 * 7 + y1 * 3 + x2 + y2 * 5) as u64 % 11 + 1,
+        );
+        let paths = delta_stepping(&grid, ids[0][0], 4).expect("The source exists");
+        let expected = dijkstra(&grid, ids[0][0]).expect("The source exists");
+
+        assert_eq!(
+            paths.distances(),
+            expected.distances(),
+            "The distances must match Dijkstra's algorithm"
+        );
+        assert_eq!(
+            paths
+                .path_to(ids[29][29])
+                .and_then(|path| path.first().copied()),
+            Some(ids[0][0]),
+            "The paths start from the source"
+        );
+    }
+}
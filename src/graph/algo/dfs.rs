@@ -0,0 +1,259 @@
+use crate::collections::{HashMap, HashSet};
+use crate::graph::{Graph, GraphView, VertexIdx};
+use alloc::{vec, vec::Vec};
+use core::hash::BuildHasher;
+
+/// The kind of an edge `u → v`, relative to the forest of a DFS.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EdgeKind {
+    /// `v` was discovered through this edge.
+    Tree,
+    /// `v` is an ancestor of `u` (or `u` itself), so the edge closes a cycle.
+    Back,
+    /// `v` is a descendant of `u`, discovered through another path.
+    Forward,
+    /// `v` is neither an ancestor nor a descendant of `u`.
+    Cross,
+}
+
+/// Performs DFS on a graph `graph` with source vertex `src_idx` in `O(|V'| + |E'|)`, where `|V'|`
+/// and `|E'|` are the vertices and the edges reached. It runs on any [`GraphView`], such as
+/// a frozen [`CsrGraph`](crate::graph::CsrGraph).
+///
+/// Returns the reached vertices in the order they were discovered, or `None` if the source vertex
+/// doesn't exist in the graph.
+pub fn depth_first_search<G: GraphView>(graph: &G, src_idx: VertexIdx) -> Option<Vec<VertexIdx>> {
+    let mut order = vec![src_idx];
+    let mut discovered = HashSet::from([src_idx]);
+    let mut stack = vec![graph.neighbors(src_idx)?];
+
+    while let Some(neighbors) = stack.last_mut() {
+        let Some((neighbor_idx, _)) = neighbors.next() else {
+            stack.pop();
+            continue;
+        };
+
+        if discovered.insert(neighbor_idx) {
+            order.push(neighbor_idx);
+            stack.push(
+                graph
+                    .neighbors(neighbor_idx)
+                    .expect("Edges point to existing vertices"),
+            );
+        }
+    }
+
+    Some(order)
+}
+
+/// Classifies every edge of `graph` as a tree, back, forward or cross edge of a DFS that starts
+/// from every vertex that hasn't been discovered yet, in `O(|V| + |E|)`.
+///
+/// The graph has a cycle if and only if there's a back edge. Self-loops are back edges.
+/// Roots are picked in increasing order of their ID.
+pub fn classify_edges<V, E, D, S: BuildHasher + Default>(
+    graph: &Graph<V, E, D, S>,
+) -> HashMap<(VertexIdx, VertexIdx), EdgeKind, S> {
+    let mut roots: Vec<VertexIdx> = graph.vertices.keys().copied().collect();
+
+    roots.sort_unstable();
+
+    let mut kinds = HashMap::default();
+    // The discovery time of every vertex, along with whether it has finished
+    let mut discovered: HashMap<VertexIdx, (usize, bool), S> =
+        HashMap::with_capacity_and_hasher(graph.len(), S::default());
+    let mut time = 0;
+
+    for root_idx in roots {
+        if discovered.contains_key(&root_idx) {
+            continue;
+        }
+
+        discovered.insert(root_idx, (time, false));
+        time += 1;
+
+        let mut stack = vec![(root_idx, graph.vertices[&root_idx].neighbors())];
+
+        while let Some((vertex_idx, neighbors)) = stack.last_mut() {
+            let vertex_idx = *vertex_idx;
+
+            let Some((neighbor_idx, _)) = neighbors.next() else {
+                discovered
+                    .get_mut(&vertex_idx)
+                    .expect("The vertex has been discovered")
+                    .1 = true;
+                stack.pop();
+                continue;
+            };
+
+            let kind = match discovered.get(&neighbor_idx) {
+                None => {
+                    discovered.insert(neighbor_idx, (time, false));
+                    time += 1;
+                    stack.push((neighbor_idx, graph.vertices[&neighbor_idx].neighbors()));
+
+                    EdgeKind::Tree
+                }
+                Some(&(_, false)) => EdgeKind::Back,
+                Some(&(neighbor_time, true)) if neighbor_time > discovered[&vertex_idx].0 => {
+                    EdgeKind::Forward
+                }
+                Some(_) => EdgeKind::Cross,
+            };
+
+            kinds.insert((vertex_idx, neighbor_idx), kind);
+        }
+    }
+
+    kinds
+}
+
+/// Finds a path with the fewest edges from `src_idx` to `dst_idx` of `graph` using iterative
+/// deepening DFS, which repeats a DFS limited to depths `0, 1, ..., max_depth` in
+/// `O(b^d)` time, where `b` is the branching factor and `d` the depth of the path.
+///
+/// Unlike BFS, it only keeps the current path in memory, taking `O(d)` space. Each DFS avoids
+/// the vertices on its current path, but may revisit the others.
+///
+/// Returns the vertices of the path in order along with the depth it was found at, or `None` if
+/// either vertex doesn't exist or there's no path of at most `max_depth` edges.
+pub fn iddfs<V, E, D, S: BuildHasher + Default>(
+    graph: &Graph<V, E, D, S>,
+    src_idx: VertexIdx,
+    dst_idx: VertexIdx,
+    max_depth: u32,
+) -> Option<(Vec<VertexIdx>, u32)> {
+    graph.get_vertex(src_idx)?;
+    graph.get_vertex(dst_idx)?;
+
+    if src_idx == dst_idx {
+        return Some((vec![src_idx], 0));
+    }
+
+    for depth in 1..=max_depth {
+        let mut on_path: HashSet<_, S> = [src_idx].into_iter().collect();
+        let mut stack = vec![(src_idx, graph.vertices[&src_idx].neighbors())];
+        // Whether any path was cut off by the depth limit, so that a deeper DFS could go further
+        let mut is_cut_off = false;
+
+        while let Some((vertex_idx, neighbors)) = stack.last_mut() {
+            let vertex_idx = *vertex_idx;
+
+            let Some((neighbor_idx, _)) = neighbors.next() else {
+                on_path.remove(&vertex_idx);
+                stack.pop();
+                continue;
+            };
+
+            if on_path.contains(&neighbor_idx) {
+                continue;
+            }
+
+            if neighbor_idx == dst_idx {
+                let mut path: Vec<VertexIdx> = stack.iter().map(|&(idx, _)| idx).collect();
+
+                path.push(dst_idx);
+
+                return Some((path, depth));
+            }
+
+            if stack.len() as u32 == depth {
+                is_cut_off = true;
+                continue;
+            }
+
+            on_path.insert(neighbor_idx);
+            stack.push((neighbor_idx, graph.vertices[&neighbor_idx].neighbors()));
+        }
+
+        if !is_cut_off {
+            return None;
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{classify_edges, depth_first_search, iddfs, EdgeKind};
+    use crate::graph::Graph;
+
+    #[test]
+    fn test_depth_first_search() {
+        // a → b → c, a → d, with an edge back c → a, on both representations
+        let mut graph = Graph::<(), ()>::new();
+        let [a, b, c, d, e] = [(); 5].map(|_| graph.insert_vertex(()));
+
+        graph.insert_or_update_edges([(a, b, ()), (b, c, ()), (a, d, ()), (c, a, ())]);
+
+        let order = depth_first_search(&graph, a).expect("Vertex a exists");
+
+        assert!(
+            order == [a, b, c, d] || order == [a, d, b, c],
+            "Wrong discovery order"
+        );
+        assert_eq!(
+            depth_first_search(&graph.freeze(), a),
+            Some(vec![a, b, c, d]),
+            "Frozen graphs visit the neighbors in order of ID"
+        );
+        assert_eq!(
+            depth_first_search(&Graph::<(), ()>::new(), e),
+            None,
+            "Vertex e isn't in an empty graph"
+        );
+    }
+
+    #[test]
+    fn test_classify_edges() {
+        // a → b → c with a shortcut a → c, an edge back c → a, and d → c from another tree
+        let mut graph = Graph::<(), ()>::new();
+        let [a, b, c, d] = [(); 4].map(|_| graph.insert_vertex(()));
+
+        graph.insert_or_update_edges([(a, b, ()), (b, c, ()), (a, c, ()), (c, a, ()), (d, c, ())]);
+
+        let kinds = classify_edges(&graph);
+
+        assert_eq!(kinds.len(), 5, "Every edge must be classified");
+        assert_eq!(kinds[&(c, a)], EdgeKind::Back, "c → a closes a cycle");
+        assert_eq!(
+            kinds[&(d, c)],
+            EdgeKind::Cross,
+            "d → c goes to another tree"
+        );
+        // Depending on which edge of a is explored first, a → c is either a tree or forward edge
+        assert!(
+            matches!(
+                (kinds[&(a, c)], kinds[&(b, c)]),
+                (EdgeKind::Forward, EdgeKind::Tree) | (EdgeKind::Tree, EdgeKind::Cross)
+            ),
+            "Wrong kinds for a → c and b → c"
+        );
+    }
+
+    #[test]
+    fn test_iddfs() {
+        // a → b → c → d → e, with a shortcut b → d and an edge back c → a
+        let mut graph = Graph::<(), ()>::new();
+        let [a, b, c, d, e] = [(); 5].map(|_| graph.insert_vertex(()));
+
+        graph.insert_or_update_edges([
+            (a, b, ()),
+            (b, c, ()),
+            (c, d, ()),
+            (d, e, ()),
+            (b, d, ()),
+            (c, a, ()),
+        ]);
+
+        assert_eq!(
+            iddfs(&graph, a, e, 10),
+            Some((vec![a, b, d, e], 3)),
+            "Wrong path"
+        );
+        assert_eq!(iddfs(&graph, a, e, 2), None, "The path is too deep");
+        assert_eq!(iddfs(&graph, e, a, 10), None, "Vertex a isn't reachable");
+        assert_eq!(iddfs(&graph, c, c, 10), Some((vec![c], 0)), "Wrong path");
+    }
+}
@@ -0,0 +1,315 @@
+use crate::collections::{BinaryHeap, HashMap};
+use crate::graph::{
+    measure::{Measure, Ordered},
+    visit::Visitor,
+    GraphView, VertexIdx,
+};
+use alloc::{vec, vec::Vec};
+use core::{
+    borrow::Borrow,
+    cmp::{Ordering, Reverse},
+};
+
+/// The vertices to settle, closest first, along with the distance they were queued at.
+pub(crate) type DistanceHeap<E> = BinaryHeap<Reverse<(Ordered<E>, VertexIdx)>>;
+
+/// The distances and parents of the vertices reached by [`dijkstra`].
+pub struct ShortestPaths<E> {
+    pub(crate) distances: HashMap<VertexIdx, E>,
+    pub(crate) parents: HashMap<VertexIdx, VertexIdx>,
+}
+
+impl<E: Copy> ShortestPaths<E> {
+    /// The distance of every reached vertex from the source.
+    pub fn distances(&self) -> &HashMap<VertexIdx, E> {
+        &self.distances
+    }
+
+    /// The parent of every reached vertex, except the source.
+    pub fn parents(&self) -> &HashMap<VertexIdx, VertexIdx> {
+        &self.parents
+    }
+
+    /// The distance of `target_idx` from the source, in `O(1)`.
+    ///
+    /// Returns `None` if `target_idx` wasn't reached.
+    pub fn distance_to(&self, target_idx: VertexIdx) -> Option<E> {
+        self.distances.get(&target_idx).copied()
+    }
+
+    /// The shortest path from the source to `target_idx`, in `O(|path|)`.
+    ///
+    /// Returns `None` if `target_idx` wasn't reached.
+    pub fn path_to(&self, target_idx: VertexIdx) -> Option<Vec<VertexIdx>> {
+        self.distances.get(&target_idx)?;
+
+        let mut path = vec![target_idx];
+        let mut vertex_idx = target_idx;
+
+        while let Some(&parent_idx) = self.parents.get(&vertex_idx) {
+            vertex_idx = parent_idx;
+            path.push(vertex_idx);
+        }
+
+        path.reverse();
+
+        Some(path)
+    }
+}
+
+/// Finds the shortest paths from `src_idx` to every vertex reachable from it using Dijkstra's
+/// algorithm with a binary heap, in `O((|V| + |E|) log |V|)`. It runs on any [`GraphView`],
+/// such as a frozen [`CsrGraph`](crate::graph::CsrGraph).
+///
+/// The edge weights must not be less than [`Measure::zero`].
+///
+/// Returns `None` if the source vertex doesn't exist in the graph.
+pub fn dijkstra<G>(graph: &G, src_idx: VertexIdx) -> Option<ShortestPaths<G::Edge>>
+where
+    G: GraphView,
+    G::Edge: Measure,
+{
+    dijkstra_visit(graph, src_idx, &mut ())
+}
+
+/// Like [`dijkstra`], but reports the steps of the algorithm to `visitor`:
+///
+/// - [`discover_vertex`](Visitor::discover_vertex) and
+///   [`distance_updated`](Visitor::distance_updated) for the source, at distance zero
+/// - [`examine_vertex`](Visitor::examine_vertex) whenever a vertex is taken from the heap, at
+///   its final distance
+/// - [`examine_edge`](Visitor::examine_edge) for every edge of it, followed by either
+///   [`edge_relaxed`](Visitor::edge_relaxed), [`discover_vertex`](Visitor::discover_vertex) if
+///   the neighbor wasn't reached before, and [`distance_updated`](Visitor::distance_updated), or
+///   [`edge_not_relaxed`](Visitor::edge_not_relaxed)
+/// - [`finish_vertex`](Visitor::finish_vertex) once all of its edges have been examined
+pub fn dijkstra_visit<G, Vis>(
+    graph: &G,
+    src_idx: VertexIdx,
+    visitor: &mut Vis,
+) -> Option<ShortestPaths<G::Edge>>
+where
+    G: GraphView,
+    G::Edge: Measure,
+    Vis: Visitor<G::Edge>,
+{
+    if !graph.contains_vertex(src_idx) {
+        return None;
+    }
+
+    visitor.discover_vertex(src_idx);
+    visitor.distance_updated(src_idx, &G::Edge::zero());
+
+    let mut paths = ShortestPaths {
+        distances: HashMap::from([(src_idx, G::Edge::zero())]),
+        parents: HashMap::new(),
+    };
+    let mut heap = BinaryHeap::from([Reverse((Ordered(G::Edge::zero()), src_idx))]);
+
+    relax(graph, &mut paths, &mut heap, visitor);
+
+    Some(paths)
+}
+
+/// The main loop of [`dijkstra_visit`]: settle the vertices of `heap` in order of distance,
+/// relaxing their edges, until it's empty. The distances of `paths` must be upper bounds, and
+/// every vertex whose distance may improve the others must be in `heap`.
+pub(crate) fn relax<G, Vis>(
+    graph: &G,
+    paths: &mut ShortestPaths<G::Edge>,
+    heap: &mut DistanceHeap<G::Edge>,
+    visitor: &mut Vis,
+) where
+    G: GraphView,
+    G::Edge: Measure,
+    Vis: Visitor<G::Edge>,
+{
+    while let Some(Reverse((Ordered(distance), vertex_idx))) = heap.pop() {
+        // Skip outdated entries of vertices that were reached through a shorter path since
+        if distance.compare(&paths.distances[&vertex_idx]) == Ordering::Greater {
+            continue;
+        }
+
+        visitor.examine_vertex(vertex_idx);
+
+        let neighbors = graph
+            .neighbors(vertex_idx)
+            .expect("Only existing vertices are reached");
+
+        for (neighbor_idx, weight) in neighbors {
+            let weight = weight.borrow();
+            let new_distance = distance.add(*weight);
+
+            visitor.examine_edge(vertex_idx, neighbor_idx, weight);
+
+            let old_distance = paths.distances.get(&neighbor_idx);
+
+            if old_distance
+                .is_some_and(|old_distance| new_distance.compare(old_distance) != Ordering::Less)
+            {
+                visitor.edge_not_relaxed(vertex_idx, neighbor_idx, weight);
+                continue;
+            }
+
+            visitor.edge_relaxed(vertex_idx, neighbor_idx, weight);
+
+            if old_distance.is_none() {
+                visitor.discover_vertex(neighbor_idx);
+            }
+
+            visitor.distance_updated(neighbor_idx, &new_distance);
+            paths.distances.insert(neighbor_idx, new_distance);
+            paths.parents.insert(neighbor_idx, vertex_idx);
+            heap.push(Reverse((Ordered(new_distance), neighbor_idx)));
+        }
+
+        visitor.finish_vertex(vertex_idx);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Graph;
+
+    #[test]
+    fn test_dijkstra() {
+        //      1       2
+        //  a ----→ b ----→ c
+        //   \_____________↗  \ 1
+        //          5          ↘
+        //                      d     e
+        let mut graph = Graph::<(), u32>::new();
+        let [a, b, c, d, e] = [(); 5].map(|_| graph.insert_vertex(()));
+
+        graph.insert_or_update_edges([(a, b, 1), (b, c, 2), (a, c, 5), (c, d, 1)]);
+
+        let paths = dijkstra(&graph, a).expect("Vertex a exists");
+
+        assert_eq!(paths.distance_to(c), Some(3), "Wrong distance to c");
+        assert_eq!(paths.path_to(d), Some(vec![a, b, c, d]), "Wrong path to d");
+        assert_eq!(paths.path_to(e), None, "Vertex e isn't reachable");
+
+        let frozen = dijkstra(&graph.freeze(), a).expect("Vertex a exists");
+
+        assert_eq!(
+            frozen.distances(),
+            paths.distances(),
+            "Both representations must give the same distances"
+        );
+    }
+
+    #[test]
+    fn test_dijkstra_visit() {
+        #[derive(Debug, PartialEq, Eq)]
+        enum Event {
+            Dequeue(VertexIdx),
+            Relaxed(VertexIdx, VertexIdx),
+            NotRelaxed(VertexIdx, VertexIdx),
+            Distance(VertexIdx, u32),
+        }
+
+        #[derive(Default)]
+        struct Recorder(Vec<Event>);
+
+        impl Visitor<u32> for Recorder {
+            fn examine_vertex(&mut self, vertex_idx: VertexIdx) {
+                self.0.push(Event::Dequeue(vertex_idx));
+            }
+
+            fn edge_relaxed(&mut self, from: VertexIdx, to: VertexIdx, _: &u32) {
+                self.0.push(Event::Relaxed(from, to));
+            }
+
+            fn edge_not_relaxed(&mut self, from: VertexIdx, to: VertexIdx, _: &u32) {
+                self.0.push(Event::NotRelaxed(from, to));
+            }
+
+            fn distance_updated(&mut self, vertex_idx: VertexIdx, &distance: &u32) {
+                self.0.push(Event::Distance(vertex_idx, distance));
+            }
+        }
+
+        //      1       1
+        //  a ----→ b ----→ c
+        //   \_____________↗
+        //          5
+        let mut graph = Graph::<(), u32>::new();
+        let [a, b, c] = [(); 3].map(|_| graph.insert_vertex(()));
+
+        graph.insert_or_update_edges([(a, b, 1), (b, c, 1), (a, c, 5)]);
+
+        let mut recorder = Recorder::default();
+        let paths = dijkstra_visit(&graph, a, &mut recorder).expect("Vertex a exists");
+
+        assert_eq!(paths.distance_to(c), Some(2), "Wrong distance to c");
+
+        // The edges of `a` may be examined in any order
+        let (first, rest) = recorder.0.split_at(2);
+
+        assert_eq!(
+            first,
+            [Event::Distance(a, 0), Event::Dequeue(a)],
+            "Wrong first events"
+        );
+        assert!(
+            rest[..4].contains(&Event::Relaxed(a, c)) && rest[..4].contains(&Event::Distance(c, 5)),
+            "The long edge to c must be relaxed first"
+        );
+        assert_eq!(
+            rest[4..],
+            [
+                Event::Dequeue(b),
+                Event::Relaxed(b, c),
+                Event::Distance(c, 2),
+                Event::Dequeue(c),
+            ],
+            "Wrong events after dequeuing a"
+        );
+    }
+
+    #[test]
+    fn test_dijkstra_measure() {
+        // Lexicographic (time, money) costs
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+        struct Cost(u32, u32);
+
+        impl Measure for Cost {
+            fn zero() -> Self {
+                Cost(0, 0)
+            }
+
+            fn add(self, other: Self) -> Self {
+                Cost(self.0 + other.0, self.1 + other.1)
+            }
+
+            fn compare(&self, other: &Self) -> Ordering {
+                self.cmp(other)
+            }
+        }
+
+        let mut graph = Graph::<(), Cost>::new();
+        let [a, b, c] = [(); 3].map(|_| graph.insert_vertex(()));
+
+        graph.insert_or_update_edges([(a, b, Cost(1, 5)), (b, c, Cost(1, 5)), (a, c, Cost(2, 1))]);
+
+        let paths = dijkstra(&graph, a).expect("Vertex a exists");
+
+        assert_eq!(
+            paths.distance_to(c),
+            Some(Cost(2, 1)),
+            "Ties are broken by money"
+        );
+        assert_eq!(paths.path_to(c), Some(vec![a, c]), "Wrong path to c");
+
+        let mut graph = Graph::<(), f64>::new();
+        let [a, b] = [(); 2].map(|_| graph.insert_vertex(()));
+
+        graph.insert_or_update_edge(a, b, 0.5);
+        assert_eq!(
+            dijkstra(&graph, a).and_then(|paths| paths.distance_to(b)),
+            Some(0.5),
+            "Floats are measures too"
+        );
+    }
+}
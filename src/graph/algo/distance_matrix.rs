@@ -0,0 +1,177 @@
+use super::dijkstra;
+use crate::collections::{hash_map::Entry, HashMap, VecDeque};
+use crate::graph::{measure::Measure, Graph, VertexIdx};
+use core::hash::BuildHasher;
+
+/// How [`distance_matrix`] measures the length of a path, given as its last argument.
+pub trait Weighting<E> {
+    /// The length of a path.
+    type Distance: Copy;
+
+    /// The length of the shortest path from `src_idx` to every vertex reachable from it.
+    fn distances_from<V, D, S>(
+        graph: &Graph<V, E, D, S>,
+        src_idx: VertexIdx,
+    ) -> HashMap<VertexIdx, Self::Distance, S>
+    where
+        S: BuildHasher + Default;
+}
+
+/// Counts the edges of a path, ignoring their weights, with BFS in `O(|V| + |E|)`. The counts are
+/// `usize`, so they don't overflow small edge weight types.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Unweighted;
+
+/// Sums the edge weights of a path, with [`dijkstra`] in `O((|V| + |E|) log |V|)`. The weights
+/// must not be less than [`Measure::zero`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Weighted;
+
+impl<E> Weighting<E> for Unweighted {
+    type Distance = usize;
+
+    fn distances_from<V, D, S>(
+        graph: &Graph<V, E, D, S>,
+        src_idx: VertexIdx,
+    ) -> HashMap<VertexIdx, usize, S>
+    where
+        S: BuildHasher + Default,
+    {
+        let mut distances: HashMap<_, _, S> = [(src_idx, 0)].into_iter().collect();
+        let mut queue = VecDeque::from([src_idx]);
+
+        while let Some(vertex_idx) = queue.pop_front() {
+            let distance = distances[&vertex_idx] + 1;
+
+            for neighbor_idx in graph.vertices[&vertex_idx].neighbor_indices() {
+                if let Entry::Vacant(entry) = distances.entry(neighbor_idx) {
+                    entry.insert(distance);
+                    queue.push_back(neighbor_idx);
+                }
+            }
+        }
+
+        distances
+    }
+}
+
+impl<E: Measure> Weighting<E> for Weighted {
+    type Distance = E;
+
+    fn distances_from<V, D, S>(
+        graph: &Graph<V, E, D, S>,
+        src_idx: VertexIdx,
+    ) -> HashMap<VertexIdx, E, S>
+    where
+        S: BuildHasher + Default,
+    {
+        dijkstra(graph, src_idx)
+            .expect("The source vertex exists")
+            .distances()
+            .iter()
+            .map(|(&idx, &distance)| (idx, distance))
+            .collect()
+    }
+}
+
+/// Finds the distance between every ordered pair of vertices of `graph`, measured as given by
+/// `weighting`, by searching from each vertex: in `O(|V| (|V| + |E|))` with [`Unweighted`], or
+/// `O(|V| (|V| + |E|) log |V|)` with [`Weighted`].
+///
+/// The distances are keyed by `(from, to)`, and are `None` if `to` isn't reachable from `from`.
+///
+/// ```
+/// # use badlgorithms::graph::{algo::{distance_matrix, Unweighted, Weighted}, Graph};
+/// let mut graph = Graph::<(), u32>::new();
+/// let [a, b, c] = [(); 3].map(|_| graph.insert_vertex(()));
+///
+/// graph.insert_or_update_edges([(a, b, 1), (b, c, 1), (a, c, 5)]);
+///
+/// assert_eq!(distance_matrix(&graph, Unweighted)[&(a, c)], Some(1));
+/// assert_eq!(distance_matrix(&graph, Weighted)[&(a, c)], Some(2));
+/// assert_eq!(distance_matrix(&graph, Weighted)[&(c, a)], None);
+/// ```
+pub fn distance_matrix<V, E, D, S, W>(
+    graph: &Graph<V, E, D, S>,
+    _weighting: W,
+) -> HashMap<(VertexIdx, VertexIdx), Option<W::Distance>, S>
+where
+    S: BuildHasher + Default,
+    W: Weighting<E>,
+{
+    matrix(graph, |src_idx| W::distances_from(graph, src_idx))
+}
+
+/// Gather the distances from every vertex, as found by `distances_from`, into a matrix.
+fn matrix<V, E, D, S, T: Copy>(
+    graph: &Graph<V, E, D, S>,
+    mut distances_from: impl FnMut(VertexIdx) -> HashMap<VertexIdx, T, S>,
+) -> HashMap<(VertexIdx, VertexIdx), Option<T>, S>
+where
+    S: BuildHasher + Default,
+{
+    let mut matrix = HashMap::with_capacity_and_hasher(graph.len() * graph.len(), S::default());
+
+    for src_idx in graph.vertices.keys().copied() {
+        let distances = distances_from(src_idx);
+
+        for dst_idx in graph.vertices.keys().copied() {
+            matrix.insert((src_idx, dst_idx), distances.get(&dst_idx).copied());
+        }
+    }
+
+    matrix
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Undirected;
+
+    #[test]
+    fn test_distance_matrix() {
+        //      1       1
+        //  a ----→ b ----→ c     d
+        //   \_____________↗
+        //          5
+        let mut graph = Graph::<(), u8>::new();
+        let [a, b, c, d] = [(); 4].map(|_| graph.insert_vertex(()));
+
+        graph.insert_or_update_edges([(a, b, 1), (b, c, 1), (a, c, 5)]);
+
+        let unweighted = distance_matrix(&graph, Unweighted);
+        let weighted = distance_matrix(&graph, Weighted);
+
+        assert_eq!(unweighted.len(), 16, "Every ordered pair must be present");
+        assert_eq!(unweighted[&(a, c)], Some(1), "a has an edge to c");
+        assert_eq!(weighted[&(a, c)], Some(2), "The path through b is lighter");
+        assert_eq!(
+            weighted[&(b, b)],
+            Some(0),
+            "A vertex is at distance zero of itself"
+        );
+        assert_eq!(weighted[&(c, a)], None, "The edges are directed");
+        assert_eq!(unweighted[&(a, d)], None, "d isn't connected");
+
+        // The hops are counted in `usize`, so they don't overflow the edge weights
+        let mut path = Graph::<(), u8>::new();
+        let vertices: Vec<_> = (0..300).map(|_| path.insert_vertex(())).collect();
+
+        path.insert_or_update_edges(vertices.windows(2).map(|pair| (pair[0], pair[1], 0)));
+
+        assert_eq!(
+            distance_matrix(&path, Unweighted)[&(vertices[0], vertices[299])],
+            Some(299)
+        );
+
+        let mut undirected = Graph::<(), f64, Undirected>::new();
+        let [a, b] = [(); 2].map(|_| undirected.insert_vertex(()));
+
+        undirected.insert_or_update_undirected_edge(a, b, 2.5);
+
+        let weighted = distance_matrix(&undirected, Weighted);
+
+        assert_eq!(weighted[&(b, a)], Some(2.5), "The edges are undirected");
+        assert_eq!(distance_matrix(&undirected, Unweighted)[&(b, a)], Some(1));
+    }
+}
@@ -0,0 +1,205 @@
+use crate::collections::{HashMap, HashSet};
+use crate::graph::{Graph, VertexIdx};
+use alloc::{vec, vec::Vec};
+use core::hash::BuildHasher;
+
+/// Colors the edges of `graph` so that no two edges sharing a vertex have the same color, using
+/// at most `Δ + 1` colors (where `Δ` is the maximum degree), with the Misra–Gries algorithm
+/// in `O(|E|(|V| + Δ²))`.
+///
+/// The graph is interpreted as undirected: an edge in either direction connects its two vertices,
+/// and both directions get the same color. Self-loops are ignored.
+///
+/// Returns the color of every edge, keyed by its `(from, to)` pair. Colors are numbered from `0`.
+pub fn edge_coloring<V, E, D, S: BuildHasher + Default + Clone>(
+    graph: &Graph<V, E, D, S>,
+) -> HashMap<(VertexIdx, VertexIdx), usize, S> {
+    let vertices: Vec<VertexIdx> = graph.vertices.keys().copied().collect();
+    let compact_idx: HashMap<VertexIdx, usize, S> = vertices
+        .iter()
+        .enumerate()
+        .map(|(i, &idx)| (idx, i))
+        .collect();
+    let mut adjacency: Vec<HashSet<usize, S>> = vec![HashSet::default(); vertices.len()];
+
+    for (from_idx, vertex) in &graph.vertices {
+        for (to_idx, _) in vertex.neighbors() {
+            let (from, to) = (compact_idx[from_idx], compact_idx[&to_idx]);
+
+            if from != to {
+                adjacency[from].insert(to);
+                adjacency[to].insert(from);
+            }
+        }
+    }
+
+    let mut coloring = Coloring {
+        colors: HashMap::default(),
+        neighbor_by_color: vec![HashMap::default(); vertices.len()],
+    };
+
+    for x in 0..vertices.len() {
+        for &f in &adjacency[x] {
+            if coloring.color(x, f).is_none() {
+                coloring.color_edge(&adjacency, x, f);
+            }
+        }
+    }
+
+    graph
+        .vertices
+        .iter()
+        .flat_map(|(&from_idx, vertex)| {
+            vertex
+                .neighbors()
+                .filter(move |(to_idx, _)| *to_idx != from_idx)
+                .map(move |(to_idx, _)| (from_idx, to_idx))
+        })
+        .map(|(from_idx, to_idx)| {
+            let color = coloring
+                .color(compact_idx[&from_idx], compact_idx[&to_idx])
+                .expect("Every edge is colored");
+
+            ((from_idx, to_idx), color)
+        })
+        .collect()
+}
+
+struct Coloring<S> {
+    /// The color of each edge, keyed by its vertices in increasing order
+    colors: HashMap<(usize, usize), usize, S>,
+    /// The neighbor of each vertex through the edge of each color
+    neighbor_by_color: Vec<HashMap<usize, usize, S>>,
+}
+
+impl<S: BuildHasher + Default> Coloring<S> {
+    fn color(&self, u: usize, v: usize) -> Option<usize> {
+        self.colors.get(&(u.min(v), u.max(v))).copied()
+    }
+
+    fn set_color(&mut self, u: usize, v: usize, color: Option<usize>) {
+        if let Some(old_color) = self.colors.remove(&(u.min(v), u.max(v))) {
+            self.neighbor_by_color[u].remove(&old_color);
+            self.neighbor_by_color[v].remove(&old_color);
+        }
+
+        if let Some(color) = color {
+            self.colors.insert((u.min(v), u.max(v)), color);
+            self.neighbor_by_color[u].insert(color, v);
+            self.neighbor_by_color[v].insert(color, u);
+        }
+    }
+
+    fn is_free(&self, u: usize, color: usize) -> bool {
+        !self.neighbor_by_color[u].contains_key(&color)
+    }
+
+    /// The smallest color that no edge of `u` has.
+    fn free_color(&self, u: usize) -> usize {
+        (0..)
+            .find(|&color| self.is_free(u, color))
+            .expect("There are more colors than edges")
+    }
+
+    /// Colors the uncolored edge `x - f` without exceeding `Δ + 1` colors.
+    fn color_edge(&mut self, adjacency: &[HashSet<usize, S>], x: usize, f: usize) {
+        // A maximal fan of `x` starting from `f`: the edge to each next vertex of the fan has
+        // a color that's free on the previous one
+        let mut fan = vec![f];
+        let mut in_fan: HashSet<_, S> = [f].into_iter().collect();
+
+        while let Some(next) = adjacency[x].iter().copied().find(|&z| {
+            let last = *fan.last().expect("The fan isn't empty");
+
+            !in_fan.contains(&z) && self.color(x, z).is_some_and(|c| self.is_free(last, c))
+        }) {
+            fan.push(next);
+            in_fan.insert(next);
+        }
+
+        let c = self.free_color(x);
+        let d = self.free_color(*fan.last().expect("The fan isn't empty"));
+
+        // Invert the path starting from `x` whose edges alternate between the colors `d` and `c`
+        let mut path = Vec::new();
+        let (mut vertex, mut color) = (x, d);
+
+        while let Some(&next) = self.neighbor_by_color[vertex].get(&color) {
+            path.push((vertex, next, color));
+            vertex = next;
+            color = if color == d { c } else { d };
+        }
+
+        for &(u, v, _) in &path {
+            self.set_color(u, v, None);
+        }
+
+        for (u, v, color) in path {
+            self.set_color(u, v, Some(if color == d { c } else { d }));
+        }
+
+        // Rotate the prefix of the fan up to the first vertex `d` is free on, then color
+        // the edge to that vertex with `d`
+        let w = fan
+            .iter()
+            .position(|&z| self.is_free(z, d))
+            .expect("`d` is free on some vertex of the fan");
+
+        for i in 0..w {
+            let color = self.color(x, fan[i + 1]);
+
+            self.set_color(x, fan[i + 1], None);
+            self.set_color(x, fan[i], color);
+        }
+
+        self.set_color(x, fan[w], Some(d));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::edge_coloring;
+    use crate::collections::HashSet;
+    use crate::graph::Graph;
+
+    #[test]
+    fn test_edge_coloring() {
+        // The complete graph on 5 vertices needs Δ + 1 = 5 colors, since it has an odd number
+        // of vertices. The edge b → a duplicates a → b.
+        let mut graph = Graph::<(), ()>::new();
+        let vertices: [_; 5] = [(); 5].map(|_| graph.insert_vertex(()));
+
+        for (i, &from_idx) in vertices.iter().enumerate() {
+            for &to_idx in &vertices[i + 1..] {
+                graph.insert_or_update_edge(from_idx, to_idx, ());
+            }
+        }
+
+        graph.insert_or_update_edge(vertices[1], vertices[0], ());
+
+        let coloring = edge_coloring(&graph);
+
+        assert_eq!(coloring.len(), 11, "Every edge must be colored");
+        assert_eq!(
+            coloring[&(vertices[0], vertices[1])],
+            coloring[&(vertices[1], vertices[0])],
+            "Both directions must have the same color"
+        );
+        assert!(
+            coloring.values().all(|&color| color <= 4),
+            "Too many colors"
+        );
+
+        for &vertex_idx in &vertices {
+            let mut seen = HashSet::new();
+
+            for (&(from_idx, to_idx), &color) in &coloring {
+                if (from_idx == vertex_idx || to_idx == vertex_idx)
+                    && !(from_idx == vertices[1] && to_idx == vertices[0])
+                {
+                    assert!(seen.insert(color), "Adjacent edges share a color");
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,204 @@
+use super::Edge;
+use crate::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use crate::graph::{Direction, Graph, VertexIdx};
+use alloc::vec::Vec;
+use core::hash::BuildHasher;
+
+/// Finds a small set of edges whose removal makes `graph` acyclic, using the greedy ordering
+/// heuristic of Eades, Lin and Smyth in `O((|V| + |E|) log |V|)`.
+///
+/// Vertices are ordered by repeatedly moving sinks to the end, sources to the start and otherwise
+/// the vertex with the largest out-degree minus in-degree to the start. The edges pointing
+/// backwards in that order (including self-loops) form the feedback arc set.
+///
+/// Returns the edges as `(from, to)` pairs. The set isn't guaranteed to be minimum.
+pub fn feedback_arc_set<V, E, D, S: BuildHasher + Default>(
+    graph: &Graph<V, E, D, S>,
+) -> Vec<(VertexIdx, VertexIdx)> {
+    let mut state = EadesState::new(graph);
+    let mut start = Vec::with_capacity(graph.len());
+    let mut end = VecDeque::new();
+
+    while !state.remaining.is_empty() {
+        if let Some(sink_idx) = state.sinks.pop() {
+            if state.remaining.contains(&sink_idx) {
+                end.push_front(sink_idx);
+                state.remove(graph, sink_idx);
+            }
+        } else if let Some(source_idx) = state.sources.pop() {
+            if state.remaining.contains(&source_idx) {
+                start.push(source_idx);
+                state.remove(graph, source_idx);
+            }
+        } else if let Some(&(_, vertex_idx)) = state.by_delta.last() {
+            start.push(vertex_idx);
+            state.remove(graph, vertex_idx);
+        }
+    }
+
+    let positions: HashMap<VertexIdx, usize, S> = start
+        .into_iter()
+        .chain(end)
+        .enumerate()
+        .map(|(position, idx)| (idx, position))
+        .collect();
+
+    graph
+        .vertices
+        .iter()
+        .flat_map(|(&from_idx, vertex)| {
+            vertex
+                .neighbors()
+                .map(move |(to_idx, _)| (from_idx, to_idx))
+        })
+        .filter(|(from_idx, to_idx)| positions[from_idx] >= positions[to_idx])
+        .collect()
+}
+
+/// The degrees of the vertices that haven't been ordered yet, ignoring self-loops.
+struct EadesState<S> {
+    predecessors: HashMap<VertexIdx, Vec<VertexIdx>, S>,
+    out_degrees: HashMap<VertexIdx, isize, S>,
+    in_degrees: HashMap<VertexIdx, isize, S>,
+    /// The remaining vertices ordered by their out-degree minus their in-degree
+    by_delta: BTreeSet<(isize, VertexIdx)>,
+    remaining: HashSet<VertexIdx, S>,
+    sinks: Vec<VertexIdx>,
+    sources: Vec<VertexIdx>,
+}
+
+impl<S: BuildHasher + Default> EadesState<S> {
+    fn new<V, E, D>(graph: &Graph<V, E, D, S>) -> Self {
+        let mut predecessors: HashMap<VertexIdx, Vec<VertexIdx>, S> = HashMap::default();
+        let mut out_degrees: HashMap<VertexIdx, isize, S> =
+            HashMap::with_capacity_and_hasher(graph.len(), S::default());
+        let mut in_degrees: HashMap<VertexIdx, isize, S> =
+            HashMap::with_capacity_and_hasher(graph.len(), S::default());
+
+        for (&from_idx, vertex) in &graph.vertices {
+            out_degrees.entry(from_idx).or_default();
+            in_degrees.entry(from_idx).or_default();
+
+            for (to_idx, _) in vertex.neighbors().filter(|(to_idx, _)| *to_idx != from_idx) {
+                predecessors.entry(to_idx).or_default().push(from_idx);
+                *out_degrees.entry(from_idx).or_default() += 1;
+                *in_degrees.entry(to_idx).or_default() += 1;
+            }
+        }
+
+        let by_delta = graph
+            .vertices
+            .keys()
+            .map(|idx| (out_degrees[idx] - in_degrees[idx], *idx))
+            .collect();
+        let sinks = graph
+            .vertices
+            .keys()
+            .filter(|idx| out_degrees[*idx] == 0)
+            .copied()
+            .collect();
+        let sources = graph
+            .vertices
+            .keys()
+            .filter(|idx| in_degrees[*idx] == 0)
+            .copied()
+            .collect();
+
+        Self {
+            predecessors,
+            out_degrees,
+            in_degrees,
+            by_delta,
+            remaining: graph.vertices.keys().copied().collect(),
+            sinks,
+            sources,
+        }
+    }
+
+    fn delta(&self, vertex_idx: VertexIdx) -> (isize, VertexIdx) {
+        (
+            self.out_degrees[&vertex_idx] - self.in_degrees[&vertex_idx],
+            vertex_idx,
+        )
+    }
+
+    fn remove<V, E, D>(&mut self, graph: &Graph<V, E, D, S>, vertex_idx: VertexIdx) {
+        self.remaining.remove(&vertex_idx);
+        self.by_delta.remove(&self.delta(vertex_idx));
+
+        for (to_idx, _) in graph.vertices[&vertex_idx].neighbors() {
+            if self.remaining.contains(&to_idx) {
+                self.by_delta.remove(&self.delta(to_idx));
+                *self
+                    .in_degrees
+                    .get_mut(&to_idx)
+                    .expect("Every vertex has a degree") -= 1;
+                self.by_delta.insert(self.delta(to_idx));
+
+                if self.in_degrees[&to_idx] == 0 {
+                    self.sources.push(to_idx);
+                }
+            }
+        }
+
+        for &from_idx in self.predecessors.get(&vertex_idx).into_iter().flatten() {
+            if self.remaining.contains(&from_idx) {
+                self.by_delta.remove(&self.delta(from_idx));
+                *self
+                    .out_degrees
+                    .get_mut(&from_idx)
+                    .expect("Every vertex has a degree") -= 1;
+                self.by_delta.insert(self.delta(from_idx));
+
+                if self.out_degrees[&from_idx] == 0 {
+                    self.sinks.push(from_idx);
+                }
+            }
+        }
+    }
+}
+
+/// Removes the edges found by [`feedback_arc_set`] from `graph`, making it acyclic.
+///
+/// Returns the removed edges along with their weights.
+pub fn remove_feedback_arcs<V, E, D, S>(graph: &mut Graph<V, E, D, S>) -> Vec<Edge<E>>
+where
+    D: Direction,
+    S: BuildHasher + Default,
+{
+    feedback_arc_set(graph)
+        .into_iter()
+        .filter_map(|(from_idx, to_idx)| {
+            let edge = graph.remove_edge(from_idx, to_idx)?;
+
+            Some((from_idx, to_idx, edge))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::remove_feedback_arcs;
+    use crate::graph::{algo::toposort, Graph};
+
+    #[test]
+    fn test_remove_feedback_arcs() {
+        // Two overlapping cycles a → b → c → a and b → c → d → b, plus a self-loop on d
+        let mut graph = Graph::<(), ()>::new();
+        let [a, b, c, d] = [(); 4].map(|_| graph.insert_vertex(()));
+
+        graph.insert_or_update_edges([
+            (a, b, ()),
+            (b, c, ()),
+            (c, a, ()),
+            (c, d, ()),
+            (d, b, ()),
+            (d, d, ()),
+        ]);
+
+        let removed = remove_feedback_arcs(&mut graph);
+
+        assert!(toposort(&graph).is_ok(), "The graph must be acyclic");
+        assert!(removed.len() <= 3, "Too many edges removed: {removed:?}");
+    }
+}
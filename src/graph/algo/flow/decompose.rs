@@ -0,0 +1,269 @@
+use super::MaxFlow;
+use crate::collections::HashMap;
+use crate::graph::{
+    measure::{Measure, Ordered},
+    VertexIdx,
+};
+use alloc::{vec, vec::Vec};
+use core::{hash::BuildHasher, ops::Sub};
+
+pub struct FlowDecomposition<E> {
+    paths: Vec<(Vec<VertexIdx>, E)>,
+    cycles: Vec<(Vec<VertexIdx>, E)>,
+}
+
+impl<E> FlowDecomposition<E> {
+    /// The paths from the source to the sink, along with the flow each one carries.
+    pub fn paths(&self) -> &[(Vec<VertexIdx>, E)] {
+        &self.paths
+    }
+
+    /// The cycles, along with the flow each one carries. The first vertex isn't repeated.
+    pub fn cycles(&self) -> &[(Vec<VertexIdx>, E)] {
+        &self.cycles
+    }
+}
+
+/// Decomposes the feasible `flow` into paths from the source to the sink and cycles,
+/// in `O(|V||E|)`. Summing the flow of every path and cycle through an edge gives back
+/// the flow through that edge.
+///
+/// At most `|E|` paths and cycles are produced, since each one saturates at least one edge.
+/// The flows must not be less than [`Measure::zero`].
+pub fn decompose<E, S>(flow: &MaxFlow<E, S>) -> FlowDecomposition<E>
+where
+    E: Measure + Sub<Output = E>,
+    S: BuildHasher + Default,
+{
+    let zero = E::zero();
+    let mut remaining: HashMap<VertexIdx, HashMap<VertexIdx, E, S>, S> = HashMap::default();
+
+    for (&(from_idx, to_idx), &edge_flow) in flow.flows() {
+        if edge_flow.compare(&zero).is_gt() {
+            remaining
+                .entry(from_idx)
+                .or_default()
+                .insert(to_idx, edge_flow);
+        }
+    }
+
+    let mut decomposition = FlowDecomposition {
+        paths: Vec::new(),
+        cycles: Vec::new(),
+    };
+
+    // Peel off the paths from the source first, then the cycles left anywhere else
+    while let Some(start_idx) = remaining
+        .contains_key(&flow.source_idx())
+        .then_some(flow.source_idx())
+        .or_else(|| remaining.keys().next().copied())
+    {
+        let mut walk = vec![start_idx];
+        let mut positions: HashMap<_, _, S> = [(start_idx, 0)].into_iter().collect();
+
+        loop {
+            let vertex_idx = *walk.last().expect("The walk isn't empty");
+            let Some(next_idx) = remaining
+                .get(&vertex_idx)
+                .and_then(|out| out.keys().next().copied())
+            else {
+                // The flow isn't conserved at this vertex, thus it isn't feasible
+                remaining.remove(&start_idx);
+                break;
+            };
+
+            if let Some(&position) = positions.get(&next_idx) {
+                let cycle = walk.split_off(position);
+
+                for &vertex_idx in &cycle {
+                    positions.remove(&vertex_idx);
+                }
+
+                let cycle_flow = subtract_bottleneck(&mut remaining, &cycle, true);
+
+                decomposition.cycles.push((cycle, cycle_flow));
+
+                if walk.is_empty() {
+                    break;
+                }
+            } else if next_idx == flow.sink_idx() && start_idx == flow.source_idx() {
+                walk.push(next_idx);
+
+                let path_flow = subtract_bottleneck(&mut remaining, &walk, false);
+
+                decomposition.paths.push((walk, path_flow));
+                break;
+            } else {
+                positions.insert(next_idx, walk.len());
+                walk.push(next_idx);
+            }
+        }
+    }
+
+    decomposition
+}
+
+/// Subtracts the smallest flow along `vertices` (closing the cycle if `is_cycle`) from every edge
+/// along them, removing the edges left without flow. Returns the subtracted flow.
+fn subtract_bottleneck<E, S>(
+    remaining: &mut HashMap<VertexIdx, HashMap<VertexIdx, E, S>, S>,
+    vertices: &[VertexIdx],
+    is_cycle: bool,
+) -> E
+where
+    E: Measure + Sub<Output = E>,
+    S: BuildHasher,
+{
+    let edges = || {
+        vertices
+            .windows(2)
+            .map(|pair| (pair[0], pair[1]))
+            .chain(is_cycle.then(|| (*vertices.last().expect("Cycles aren't empty"), vertices[0])))
+    };
+
+    let bottleneck = edges()
+        .map(|(from_idx, to_idx)| Ordered(remaining[&from_idx][&to_idx]))
+        .min()
+        .expect("There's at least one edge")
+        .0;
+
+    for (from_idx, to_idx) in edges() {
+        let out = remaining.get_mut(&from_idx).expect("The edge has flow");
+        let edge_flow = out.get_mut(&to_idx).expect("The edge has flow");
+
+        *edge_flow = *edge_flow - bottleneck;
+
+        if !edge_flow.compare(&E::zero()).is_gt() {
+            out.remove(&to_idx);
+
+            if out.is_empty() {
+                remaining.remove(&from_idx);
+            }
+        }
+    }
+
+    bottleneck
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decompose, FlowDecomposition};
+    use crate::collections::{HashMap, HashSet};
+    use crate::graph::{
+        algo::flow::{max_flow, MaxFlow},
+        Graph, VertexIdx,
+    };
+
+    /// Sum the flow of every path and cycle through each edge.
+    fn recompose(decomposition: &FlowDecomposition<u32>) -> HashMap<(VertexIdx, VertexIdx), u32> {
+        let mut flows = HashMap::new();
+
+        for (path, path_flow) in decomposition.paths() {
+            for pair in path.windows(2) {
+                *flows.entry((pair[0], pair[1])).or_default() += path_flow;
+            }
+        }
+
+        for (cycle, cycle_flow) in decomposition.cycles() {
+            for (i, &from_idx) in cycle.iter().enumerate() {
+                *flows
+                    .entry((from_idx, cycle[(i + 1) % cycle.len()]))
+                    .or_default() += cycle_flow;
+            }
+        }
+
+        flows
+    }
+
+    #[test]
+    fn test_decompose() {
+        //     a
+        //   3↗ ↘2
+        //  s  1↓  t
+        //   2↘ ↗4
+        //     b
+        let mut graph = Graph::<(), u32>::new();
+        let [s, a, b, t] = [(); 4].map(|_| graph.insert_vertex(()));
+
+        graph.insert_or_update_edges([(s, a, 3), (s, b, 2), (a, b, 1), (a, t, 2), (b, t, 4)]);
+
+        let flow = max_flow(&graph, s, t).expect("Both vertices exist");
+        let decomposition = decompose(&flow);
+        let total: u32 = decomposition.paths().iter().map(|(_, f)| f).sum();
+
+        assert_eq!(total, flow.value(), "The paths must carry the whole flow");
+        assert!(decomposition.cycles().is_empty(), "The graph is acyclic");
+        assert_eq!(
+            &recompose(&decomposition),
+            flow.flows(),
+            "The paths must add up to the flow"
+        );
+
+        for (path, _) in decomposition.paths() {
+            assert_eq!(path.first(), Some(&s), "Paths start from the source");
+            assert_eq!(path.last(), Some(&t), "Paths end to the sink");
+        }
+
+        // A path s → a → t, with a circulation a ⇄ b ⇄ c through it and c → d → e → c away
+        // from it
+        let [c, d, e] = [(); 3].map(|_| graph.insert_vertex(()));
+        let flow = MaxFlow {
+            source_idx: s,
+            sink_idx: t,
+            value: 3,
+            flows: [
+                ((s, a), 3),
+                ((a, t), 3),
+                ((a, b), 1),
+                ((b, a), 1),
+                ((b, c), 2),
+                ((c, b), 2),
+                ((c, d), 1),
+                ((d, e), 1),
+                ((e, c), 1),
+            ]
+            .into_iter()
+            .collect(),
+            source_side: HashSet::new(),
+        };
+        let decomposition = decompose(&flow);
+
+        assert_eq!(
+            decomposition.paths(),
+            [(vec![s, a, t], 3)],
+            "The path must carry the whole flow"
+        );
+        assert_eq!(
+            decomposition.cycles().len(),
+            3,
+            "Every circulation must be peeled off"
+        );
+        assert_eq!(
+            &recompose(&decomposition),
+            flow.flows(),
+            "The paths and cycles must add up to the flow"
+        );
+    }
+
+    #[test]
+    fn test_decompose_f64() {
+        let mut graph = Graph::<(), f64>::new();
+        let [s, a, b, t] = [(); 4].map(|_| graph.insert_vertex(()));
+
+        graph.insert_or_update_edges([(s, a, 1.5), (s, b, 0.5), (a, t, 1.0), (b, t, 2.0)]);
+
+        let flow = max_flow(&graph, s, t).expect("Both vertices exist");
+        let decomposition = decompose(&flow);
+        let mut paths = decomposition.paths().to_vec();
+
+        paths.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+
+        assert_eq!(flow.value(), 1.5, "Wrong maximum flow");
+        assert_eq!(
+            paths,
+            [(vec![s, b, t], 0.5), (vec![s, a, t], 1.0)],
+            "Wrong paths"
+        );
+        assert!(decomposition.cycles().is_empty(), "The graph is acyclic");
+    }
+}
@@ -0,0 +1,217 @@
+use super::max_flow;
+use crate::collections::{DefaultHashBuilder, HashMap};
+use crate::graph::{
+    algo::Edge,
+    measure::{Measure, Ordered},
+    Directed, Graph, VertexIdx,
+};
+use alloc::vec::Vec;
+use core::{hash::BuildHasher, ops::Sub};
+
+/// A tree over the vertices of a graph, where the minimum cut between any two vertices equals
+/// the lightest edge on the tree path between them. Created by [`gomory_hu`].
+pub struct GomoryHuTree<E, S = DefaultHashBuilder> {
+    /// The parent of every vertex except the root, along with the weight of the edge to it
+    parents: HashMap<VertexIdx, (VertexIdx, E), S>,
+}
+
+impl<E: Measure, S: BuildHasher + Default> GomoryHuTree<E, S> {
+    /// The edges of the tree as `(child, parent, min_cut_value)`.
+    pub fn edges(&self) -> impl Iterator<Item = Edge<E>> + '_ {
+        self.parents
+            .iter()
+            .map(|(&child_idx, &(parent_idx, value))| (child_idx, parent_idx, value))
+    }
+
+    /// The value of the minimum cut separating `a` and `b` in `O(|V|)`.
+    ///
+    /// Returns `None` if either vertex isn't in the tree or they are the same vertex.
+    pub fn min_cut_value(&self, a: VertexIdx, b: VertexIdx) -> Option<E> {
+        if a == b {
+            return None;
+        }
+
+        // The lightest edge from `a` to each of its ancestors
+        let mut lightest_from_a: HashMap<_, _, S> = [(a, None)].into_iter().collect();
+        let mut vertex_idx = a;
+        let mut lightest = None;
+
+        while let Some(&(parent_idx, value)) = self.parents.get(&vertex_idx) {
+            lightest = Some(lightest.map_or(value, |l: E| Ordered(l).min(Ordered(value)).0));
+            lightest_from_a.insert(parent_idx, lightest);
+            vertex_idx = parent_idx;
+        }
+
+        let mut vertex_idx = b;
+        let mut lightest_from_b: Option<E> = None;
+
+        loop {
+            if let Some(&lightest_from_a) = lightest_from_a.get(&vertex_idx) {
+                return match (lightest_from_a, lightest_from_b) {
+                    (Some(a), Some(b)) => Some(Ordered(a).min(Ordered(b)).0),
+                    (a, b) => a.or(b),
+                };
+            }
+
+            let &(parent_idx, value) = self.parents.get(&vertex_idx)?;
+
+            lightest_from_b =
+                Some(lightest_from_b.map_or(value, |l| Ordered(l).min(Ordered(value)).0));
+            vertex_idx = parent_idx;
+        }
+    }
+}
+
+/// Constructs the Gomory–Hu tree of `graph` with Gusfield's algorithm, using `|V| - 1` maximum
+/// flow computations (see [`max_flow`]) without contracting any vertices.
+///
+/// The graph is [interpreted as undirected](crate::graph::algo#undirected-interpretation),
+/// with the weights as capacities.
+pub fn gomory_hu<V, E, D, S: BuildHasher + Default>(graph: &Graph<V, E, D, S>) -> GomoryHuTree<E, S>
+where
+    E: Measure + Sub<Output = E>,
+{
+    let mut undirected = Graph::<(), E, Directed, S>::default();
+    let vertices: Vec<VertexIdx> = graph.vertices.keys().copied().collect();
+
+    for &vertex_idx in &vertices {
+        undirected.insert_vertex_with_idx((), vertex_idx);
+    }
+
+    for (&from_idx, vertex) in &graph.vertices {
+        for (to_idx, &capacity) in vertex.neighbors() {
+            let reverse = graph.vertices[&to_idx].edges.get(&from_idx);
+            let capacity = reverse.map_or(capacity, |&reverse| {
+                Ordered(capacity).min(Ordered(reverse)).0
+            });
+
+            undirected.insert_or_update_edge(from_idx, to_idx, capacity);
+            undirected.insert_or_update_edge(to_idx, from_idx, capacity);
+        }
+    }
+
+    let Some(&root_idx) = vertices.first() else {
+        return GomoryHuTree {
+            parents: HashMap::default(),
+        };
+    };
+
+    let mut parents: HashMap<VertexIdx, (VertexIdx, E), S> = vertices[1..]
+        .iter()
+        .map(|&idx| (idx, (root_idx, E::zero())))
+        .collect();
+
+    for &source_idx in &vertices[1..] {
+        let (sink_idx, _) = parents[&source_idx];
+        let flow = max_flow(&undirected, source_idx, sink_idx)
+            .expect("Both vertices exist and are distinct");
+        let source_side = flow.source_side();
+
+        parents.insert(source_idx, (sink_idx, flow.value()));
+
+        for &vertex_idx in &vertices[1..] {
+            if vertex_idx != source_idx
+                && source_side.contains(&vertex_idx)
+                && parents[&vertex_idx].0 == sink_idx
+            {
+                parents.get_mut(&vertex_idx).expect("Not the root").0 = source_idx;
+            }
+        }
+
+        // If the sink's parent ended up on the source side, the source takes the sink's place
+        if let Some(&(grandparent_idx, sink_value)) = parents.get(&sink_idx) {
+            if source_side.contains(&grandparent_idx) {
+                parents.insert(source_idx, (grandparent_idx, sink_value));
+                parents.insert(sink_idx, (source_idx, flow.value()));
+            }
+        }
+    }
+
+    GomoryHuTree { parents }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::gomory_hu;
+    use crate::graph::{
+        algo::{flow::max_flow, stoer_wagner},
+        Graph,
+    };
+
+    #[test]
+    fn test_gomory_hu() {
+        let mut graph = Graph::<(), u32>::new();
+        let idx: Vec<_> = (0..6).map(|_| graph.insert_vertex(())).collect();
+
+        graph.insert_or_update_edges([
+            (idx[0], idx[1], 1),
+            (idx[0], idx[2], 7),
+            (idx[1], idx[2], 1),
+            (idx[1], idx[3], 3),
+            (idx[1], idx[4], 2),
+            (idx[2], idx[4], 4),
+            (idx[3], idx[4], 1),
+            (idx[3], idx[5], 6),
+            (idx[4], idx[5], 2),
+        ]);
+
+        let tree = gomory_hu(&graph);
+
+        assert_eq!(tree.edges().count(), 5, "A tree has |V| - 1 edges");
+
+        let mut undirected = graph.filter_map(|_, _| Some(()), |_, _, &edge| Some(edge));
+
+        for (from, to, edge) in graph
+            .vertices_iter()
+            .flat_map(|(from, vertex)| vertex.neighbors().map(move |(to, &edge)| (from, to, edge)))
+        {
+            undirected.insert_or_update_edge(to, from, edge);
+        }
+
+        for &a in &idx {
+            for &b in &idx {
+                if a != b {
+                    let flow = max_flow(&undirected, a, b).expect("Both vertices exist");
+
+                    assert_eq!(
+                        tree.min_cut_value(a, b),
+                        Some(flow.value()),
+                        "Wrong minimum cut between {a:?} and {b:?}"
+                    );
+                }
+            }
+        }
+
+        let global_min_cut = tree.edges().map(|(_, _, value)| value).min();
+
+        assert_eq!(global_min_cut, stoer_wagner(&graph).map(|(cut, _, _)| cut));
+
+        // Opposite arcs of different weights count as one edge of the lighter weight
+        let mut graph = Graph::<(), u32>::new();
+        let a = graph.insert_vertex(());
+        let b = graph.insert_vertex(());
+
+        graph.insert_or_update_edges([(a, b, 7), (b, a, 2)]);
+
+        assert_eq!(
+            gomory_hu(&graph).min_cut_value(a, b),
+            Some(2),
+            "Wrong capacity with opposite arcs"
+        );
+    }
+
+    #[test]
+    fn test_gomory_hu_f64() {
+        // A path a - b - c
+        let mut graph = Graph::<(), f64>::new();
+        let [a, b, c] = [(); 3].map(|_| graph.insert_vertex(()));
+
+        graph.insert_or_update_edges([(a, b, 2.5), (b, c, 0.75)]);
+
+        let tree = gomory_hu(&graph);
+
+        assert_eq!(tree.min_cut_value(a, b), Some(2.5), "Wrong minimum cut");
+        assert_eq!(tree.min_cut_value(a, c), Some(0.75), "Wrong minimum cut");
+        assert_eq!(tree.min_cut_value(b, c), Some(0.75), "Wrong minimum cut");
+    }
+}
@@ -0,0 +1,183 @@
+use crate::collections::{DefaultHashBuilder, HashMap, HashSet, VecDeque};
+use crate::graph::{
+    measure::{Measure, Ordered},
+    Graph, VertexIdx,
+};
+use alloc::{vec, vec::Vec};
+use core::{hash::BuildHasher, ops::Sub};
+
+pub use {
+    decompose::{decompose, FlowDecomposition},
+    gomory_hu::{gomory_hu, GomoryHuTree},
+};
+
+pub mod decompose;
+pub mod gomory_hu;
+
+pub struct MaxFlow<E, S = DefaultHashBuilder> {
+    source_idx: VertexIdx,
+    sink_idx: VertexIdx,
+    value: E,
+    flows: HashMap<(VertexIdx, VertexIdx), E, S>,
+    source_side: HashSet<VertexIdx, S>,
+}
+
+impl<E: Copy, S: BuildHasher> MaxFlow<E, S> {
+    pub fn source_idx(&self) -> VertexIdx {
+        self.source_idx
+    }
+
+    pub fn sink_idx(&self) -> VertexIdx {
+        self.sink_idx
+    }
+
+    /// The total amount of flow from the source to the sink.
+    pub fn value(&self) -> E {
+        self.value
+    }
+
+    /// The flow through the edge from `from` to `to`, if it carries any.
+    pub fn flow(&self, from: VertexIdx, to: VertexIdx) -> Option<E> {
+        self.flows.get(&(from, to)).copied()
+    }
+
+    /// The flow through every edge that carries any, keyed by `(from, to)`.
+    pub fn flows(&self) -> &HashMap<(VertexIdx, VertexIdx), E, S> {
+        &self.flows
+    }
+
+    pub fn into_flows(self) -> HashMap<(VertexIdx, VertexIdx), E, S> {
+        self.flows
+    }
+
+    /// The source side of a minimum cut: the vertices still reachable from the source
+    /// in the residual graph.
+    pub fn source_side(&self) -> &HashSet<VertexIdx, S> {
+        &self.source_side
+    }
+}
+
+/// Computes the maximum flow from `source_idx` to `sink_idx` of `graph`, using the edge weights
+/// as capacities, with the Edmonds–Karp algorithm in `O(|V||E|²)`.
+///
+/// The capacities must not be less than [`Measure::zero`].
+///
+/// Returns `None` if either vertex doesn't exist or they are the same vertex.
+pub fn max_flow<V, E, D, S: BuildHasher + Default>(
+    graph: &Graph<V, E, D, S>,
+    source_idx: VertexIdx,
+    sink_idx: VertexIdx,
+) -> Option<MaxFlow<E, S>>
+where
+    E: Measure + Sub<Output = E>,
+{
+    if source_idx == sink_idx {
+        return None;
+    }
+
+    let vertices: Vec<VertexIdx> = graph.vertices.keys().copied().collect();
+    let compact_idx: HashMap<VertexIdx, usize, S> = vertices
+        .iter()
+        .enumerate()
+        .map(|(i, &idx)| (idx, i))
+        .collect();
+    let (source, sink) = (*compact_idx.get(&source_idx)?, *compact_idx.get(&sink_idx)?);
+    let zero = E::zero();
+
+    // Each edge is stored as a pair of arcs: the forward one at an even index followed by its
+    // reverse. The residual capacity of the reverse arc is the flow through the forward one.
+    let mut heads = Vec::new();
+    let mut residuals = Vec::new();
+    let mut arcs: Vec<Vec<usize>> = vec![Vec::new(); vertices.len()];
+
+    for (from_idx, vertex) in &graph.vertices {
+        for (to_idx, &capacity) in vertex.neighbors() {
+            let (from, to) = (compact_idx[from_idx], compact_idx[&to_idx]);
+
+            if from == to {
+                continue;
+            }
+
+            arcs[from].push(heads.len());
+            heads.push(to);
+            residuals.push(capacity);
+            arcs[to].push(heads.len());
+            heads.push(from);
+            residuals.push(zero);
+        }
+    }
+
+    let mut value = zero;
+    let mut parent_arcs: Vec<Option<usize>> = vec![None; vertices.len()];
+
+    loop {
+        // Find the shortest augmenting path
+        parent_arcs.fill(None);
+
+        let mut queue = VecDeque::from([source]);
+        let mut visited = vec![false; vertices.len()];
+
+        visited[source] = true;
+
+        while let Some(vertex) = queue.pop_front() {
+            if vertex == sink {
+                break;
+            }
+
+            for &arc in &arcs[vertex] {
+                let head = heads[arc];
+
+                if !visited[head] && residuals[arc].compare(&zero).is_gt() {
+                    visited[head] = true;
+                    parent_arcs[head] = Some(arc);
+                    queue.push_back(head);
+                }
+            }
+        }
+
+        if !visited[sink] {
+            let source_side = (0..vertices.len())
+                .filter(|&i| visited[i])
+                .map(|i| vertices[i])
+                .collect();
+            let flows = (0..heads.len())
+                .step_by(2)
+                .filter(|&arc| residuals[arc + 1].compare(&zero).is_gt())
+                .map(|arc| {
+                    let (from, to) = (heads[arc + 1], heads[arc]);
+
+                    ((vertices[from], vertices[to]), residuals[arc + 1])
+                })
+                .collect();
+
+            return Some(MaxFlow {
+                source_idx,
+                sink_idx,
+                value,
+                flows,
+                source_side,
+            });
+        }
+
+        let mut bottleneck = None;
+        let mut vertex = sink;
+
+        while let Some(arc) = parent_arcs[vertex] {
+            bottleneck = Some(bottleneck.map_or(residuals[arc], |b: E| {
+                Ordered(b).min(Ordered(residuals[arc])).0
+            }));
+            vertex = heads[arc ^ 1];
+        }
+
+        let bottleneck = bottleneck.expect("The path has at least one arc");
+        let mut vertex = sink;
+
+        while let Some(arc) = parent_arcs[vertex] {
+            residuals[arc] = residuals[arc] - bottleneck;
+            residuals[arc ^ 1] = residuals[arc ^ 1].add(bottleneck);
+            vertex = heads[arc ^ 1];
+        }
+
+        value = value.add(bottleneck);
+    }
+}
@@ -0,0 +1,313 @@
+use crate::collections::{DefaultHashBuilder, HashMap};
+use crate::graph::{measure::Measure, Graph, VertexIdx};
+use alloc::{vec, vec::Vec};
+use core::{cmp::Ordering, hash::BuildHasher};
+
+/// The graph contains a cycle of negative weight. The vertex is on a closed walk of negative
+/// weight, so its distance to itself is unbounded.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct NegativeCycle(pub VertexIdx);
+
+impl NegativeCycle {
+    pub fn vertex_idx(&self) -> VertexIdx {
+        self.0
+    }
+}
+
+/// The distances between every ordered pair of vertices found by [`floyd_warshall`], as a dense
+/// `|V| × |V|` matrix.
+#[derive(Clone, Debug)]
+pub struct AllPairsDistances<E, S = DefaultHashBuilder> {
+    vertices: Vec<VertexIdx>,
+    /// The row and the column of each vertex in the matrix
+    positions: HashMap<VertexIdx, usize, S>,
+    /// The distances in row-major order, from the vertex of the row to that of the column
+    matrix: Vec<Option<E>>,
+}
+
+impl<E: Copy, S: BuildHasher> AllPairsDistances<E, S> {
+    /// The vertices in the order of the rows and the columns of the matrix.
+    pub fn vertices(&self) -> &[VertexIdx] {
+        &self.vertices
+    }
+
+    /// The distance from `from_idx` to `to_idx`, in `O(1)`.
+    ///
+    /// Returns `None` if `to_idx` isn't reachable from `from_idx` or either vertex doesn't exist.
+    pub fn distance(&self, from_idx: VertexIdx, to_idx: VertexIdx) -> Option<E> {
+        let from = *self.positions.get(&from_idx)?;
+        let to = *self.positions.get(&to_idx)?;
+
+        self.matrix[from * self.vertices.len() + to]
+    }
+
+    /// The distances from `from_idx` to every vertex, in the order of [`Self::vertices`].
+    ///
+    /// Returns `None` if the vertex doesn't exist.
+    pub fn row(&self, from_idx: VertexIdx) -> Option<&[Option<E>]> {
+        let n = self.vertices.len();
+        let from = *self.positions.get(&from_idx)?;
+
+        Some(&self.matrix[from * n..(from + 1) * n])
+    }
+}
+
+/// Finds the distance between every ordered pair of vertices of `graph` using the Floyd–Warshall
+/// algorithm on a dense matrix, in `O(|V|³)` time and `O(|V|²)` memory. Unlike [`dijkstra`],
+/// the edge weights may be negative.
+///
+/// With the `parallel` feature, `par_floyd_warshall` spreads the work across threads instead,
+/// which pays off from a few hundred vertices.
+///
+/// Returns [`NegativeCycle`] as soon as a cycle of negative weight makes some distances unbounded.
+///
+/// [`dijkstra`]: super::dijkstra()
+///
+/// ```
+/// # use badlgorithms::graph::{algo::floyd_warshall, Graph};
+/// let mut graph = Graph::<(), i32>::new();
+/// let [a, b, c] = [(); 3].map(|_| graph.insert_vertex(()));
+///
+/// graph.insert_or_update_edges([(a, b, 4), (b, c, -2), (a, c, 3)]);
+///
+/// let distances = floyd_warshall(&graph).expect("There's no negative cycle");
+///
+/// assert_eq!(distances.distance(a, c), Some(2));
+/// assert_eq!(distances.distance(c, a), None);
+/// ```
+pub fn floyd_warshall<V, E: Measure, D, S: BuildHasher + Default>(
+    graph: &Graph<V, E, D, S>,
+) -> Result<AllPairsDistances<E, S>, NegativeCycle> {
+    let mut distances = initial_distances(graph);
+    let n = distances.vertices.len();
+
+    for k in 0..n {
+        let row_k = distances.matrix[k * n..(k + 1) * n].to_vec();
+
+        for row in distances.matrix.chunks_mut(n) {
+            relax_row(row, k, &row_k);
+        }
+
+        check_negative_cycles(&distances)?;
+    }
+
+    Ok(distances)
+}
+
+/// Like [`floyd_warshall`], but updates the rows of the matrix across threads with [`rayon`] for
+/// every intermediate vertex.
+#[cfg(feature = "parallel")]
+pub fn par_floyd_warshall<V, E, D, S>(
+    graph: &Graph<V, E, D, S>,
+) -> Result<AllPairsDistances<E, S>, NegativeCycle>
+where
+    E: Measure + Send + Sync,
+    S: BuildHasher + Default,
+{
+    use rayon::prelude::*;
+
+    let mut distances = initial_distances(graph);
+    let n = distances.vertices.len();
+
+    for k in 0..n {
+        let row_k = distances.matrix[k * n..(k + 1) * n].to_vec();
+
+        distances
+            .matrix
+            .par_chunks_mut(n)
+            .for_each(|row| relax_row(row, k, &row_k));
+
+        check_negative_cycles(&distances)?;
+    }
+
+    Ok(distances)
+}
+
+/// The matrix of the edges of `graph`, keeping the lightest of each pair, with zeros on the
+/// diagonal unless there's a lighter self-loop.
+fn initial_distances<V, E: Measure, D, S: BuildHasher + Default>(
+    graph: &Graph<V, E, D, S>,
+) -> AllPairsDistances<E, S> {
+    let vertices: Vec<_> = graph.vertices.keys().copied().collect();
+    let positions: HashMap<_, _, S> = vertices
+        .iter()
+        .enumerate()
+        .map(|(position, &vertex_idx)| (vertex_idx, position))
+        .collect();
+    let n = vertices.len();
+    let mut matrix = vec![None; n * n];
+
+    for i in 0..n {
+        matrix[i * n + i] = Some(E::zero());
+    }
+
+    for (from, &from_idx) in vertices.iter().enumerate() {
+        for (to_idx, &weight) in graph.vertices[&from_idx].neighbors() {
+            improve(&mut matrix[from * n + positions[&to_idx]], weight);
+        }
+    }
+
+    AllPairsDistances {
+        vertices,
+        positions,
+        matrix,
+    }
+}
+
+/// Shorten the paths from the vertex of `row` by going through the `k`th vertex, whose own row is
+/// `row_k`.
+fn relax_row<E: Measure>(row: &mut [Option<E>], k: usize, row_k: &[Option<E>]) {
+    let Some(to_k) = row[k] else {
+        return;
+    };
+
+    for (distance, from_k) in row.iter_mut().zip(row_k) {
+        if let Some(from_k) = from_k {
+            improve(distance, to_k.add(*from_k));
+        }
+    }
+}
+
+fn improve<E: Measure>(distance: &mut Option<E>, candidate: E) {
+    if distance.is_none_or(|distance| candidate.compare(&distance) == Ordering::Less) {
+        *distance = Some(candidate);
+    }
+}
+
+/// A vertex is on a negative cycle iff its distance to itself becomes negative. Checked after every
+/// intermediate vertex, since the distances through a negative cycle keep doubling and would
+/// overflow long before the end.
+fn check_negative_cycles<E: Measure, S>(
+    distances: &AllPairsDistances<E, S>,
+) -> Result<(), NegativeCycle> {
+    let n = distances.vertices.len();
+
+    for (i, &vertex_idx) in distances.vertices.iter().enumerate() {
+        if distances.matrix[i * n + i]
+            .is_some_and(|distance| distance.compare(&E::zero()) == Ordering::Less)
+        {
+            return Err(NegativeCycle(vertex_idx));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{algo::dijkstra, random::RandomConfig, Undirected};
+    use fastrand::Rng;
+
+    #[test]
+    fn test_floyd_warshall() {
+        let config = RandomConfig {
+            min_vertices: 40,
+            max_vertices: 40,
+            density: 0.1,
+            self_loops: true,
+        };
+        let (graph, vertices) =
+            Graph::<(), u32>::random(&mut Rng::with_seed(9), config, |_| (), |rng| rng.u32(..30));
+        let distances = floyd_warshall(&graph).expect("The weights are non-negative");
+
+        for &from_idx in &vertices {
+            let paths = dijkstra(&graph, from_idx).expect("The vertex exists");
+
+            for &to_idx in &vertices {
+                assert_eq!(
+                    distances.distance(from_idx, to_idx),
+                    paths.distance_to(to_idx),
+                    "The distances must match Dijkstra's algorithm"
+                );
+            }
+        }
+
+        //  a → b → c
+        //   ↖_____↙
+        let mut graph = Graph::<(), i32>::new();
+        let [a, b, c] = [(); 3].map(|_| graph.insert_vertex(()));
+
+        graph.insert_or_update_edges([(a, b, 1), (b, c, -3), (c, a, 1)]);
+
+        assert!(
+            floyd_warshall(&graph).is_err_and(|cycle| [a, b, c].contains(&cycle.vertex_idx())),
+            "The cycle has weight -1"
+        );
+
+        graph.insert_or_update_edge(c, a, 2);
+
+        let distances = floyd_warshall(&graph).expect("The cycle has weight 0");
+
+        assert_eq!(distances.distance(b, a), Some(-1), "b reaches a through c");
+        assert_eq!(
+            distances.row(a).map(<[_]>::len),
+            Some(3),
+            "A row has a distance to every vertex"
+        );
+
+        // Without stopping at the first negative cycle, the distances would keep doubling and
+        // overflow long before the last vertex
+        let (graph, _) = Graph::<(), i32>::random(
+            &mut Rng::with_seed(3),
+            RandomConfig {
+                min_vertices: 45,
+                max_vertices: 45,
+                density: 1.0,
+                self_loops: false,
+            },
+            |_| (),
+            |_| -1,
+        );
+
+        assert!(
+            floyd_warshall(&graph).is_err(),
+            "Every edge is on a negative cycle"
+        );
+
+        let mut undirected = Graph::<(), i32, Undirected>::new();
+        let [a, b] = [(); 2].map(|_| undirected.insert_vertex(()));
+
+        undirected.insert_or_update_undirected_edge(a, b, -1);
+
+        assert!(
+            floyd_warshall(&undirected).is_err(),
+            "A negative undirected edge is a negative cycle"
+        );
+
+        // The vertex weights don't have to be shared across threads
+        let mut cells = Graph::<core::cell::Cell<u8>, u8>::new();
+        let [a, b] = [0, 1].map(|weight| cells.insert_vertex(weight.into()));
+
+        cells.insert_or_update_edge(a, b, 3);
+
+        assert_eq!(
+            floyd_warshall(&cells).map(|distances| distances.distance(a, b)),
+            Ok(Some(3)),
+            "Wrong distance"
+        );
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_par_floyd_warshall() {
+        let config = RandomConfig {
+            min_vertices: 300,
+            max_vertices: 300,
+            density: 0.02,
+            self_loops: false,
+        };
+        let (graph, vertices) =
+            Graph::<(), i64>::random(&mut Rng::with_seed(2), config, |_| (), |rng| rng.i64(0..20));
+        let parallel = par_floyd_warshall(&graph).expect("The weights are non-negative");
+        let sequential = floyd_warshall(&graph).expect("The weights are non-negative");
+
+        for &from_idx in &vertices {
+            assert_eq!(
+                parallel.row(from_idx),
+                sequential.row(from_idx),
+                "Both variants must agree on the distances"
+            );
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use crate::collections::{HashMap, VecDeque};
+use crate::graph::{Graph, VertexIdx};
+use alloc::{vec, vec::Vec};
+use core::hash::BuildHasher;
+
+/// The shortest possible cycle in a graph without self-loops and parallel edges
+const MIN_GIRTH: usize = 3;
+
+/// Finds the shortest cycle of `graph` by running a BFS from every vertex in `O(|V|(|V| + |E|))`.
+/// Each BFS stops as soon as it can't find a shorter cycle than the one already found,
+/// and the whole search stops once a triangle is found.
+///
+/// The graph is interpreted as undirected, ignoring self-loops and parallel edges.
+///
+/// Returns the length of the cycle (its girth) along with its vertices in order,
+/// or `None` if the graph is acyclic.
+pub fn girth<V, E, D, S: BuildHasher + Default>(
+    graph: &Graph<V, E, D, S>,
+) -> Option<(usize, Vec<VertexIdx>)> {
+    let vertices: Vec<VertexIdx> = graph.vertices.keys().copied().collect();
+    let compact_idx: HashMap<VertexIdx, usize, S> = vertices
+        .iter()
+        .enumerate()
+        .map(|(i, &idx)| (idx, i))
+        .collect();
+    let mut adjacency: Vec<Vec<usize>> = vec![Vec::new(); vertices.len()];
+
+    for (from_idx, vertex) in &graph.vertices {
+        for (to_idx, _) in vertex.neighbors() {
+            let (from, to) = (compact_idx[from_idx], compact_idx[&to_idx]);
+
+            if from != to {
+                adjacency[from].push(to);
+                adjacency[to].push(from);
+            }
+        }
+    }
+
+    for neighbors in &mut adjacency {
+        neighbors.sort_unstable();
+        neighbors.dedup();
+    }
+
+    let mut best: Option<(usize, Vec<usize>)> = None;
+    let mut distances: Vec<Option<usize>> = vec![None; vertices.len()];
+    let mut parents: Vec<Option<usize>> = vec![None; vertices.len()];
+
+    for start in 0..vertices.len() {
+        distances.fill(None);
+        parents.fill(None);
+        distances[start] = Some(0);
+
+        let mut queue = VecDeque::from([start]);
+
+        'bfs: while let Some(vertex) = queue.pop_front() {
+            let distance = distances[vertex].expect("Queued vertices are discovered");
+
+            // Any cycle found from now on is at least `2 * distance + 1` long
+            if best
+                .as_ref()
+                .is_some_and(|(len, _)| 2 * distance + 1 >= *len)
+            {
+                break;
+            }
+
+            for &neighbor in &adjacency[vertex] {
+                if parents[vertex] == Some(neighbor) {
+                    continue;
+                }
+
+                let Some(neighbor_distance) = distances[neighbor] else {
+                    distances[neighbor] = Some(distance + 1);
+                    parents[neighbor] = Some(vertex);
+                    queue.push_back(neighbor);
+                    continue;
+                };
+
+                let len = distance + neighbor_distance + 1;
+
+                if best.as_ref().is_none_or(|(best_len, _)| len < *best_len) {
+                    // Join the two tree paths from `start` at the non-tree edge
+                    let path_to = |mut vertex: usize| {
+                        let mut path = vec![vertex];
+
+                        while let Some(parent) = parents[vertex] {
+                            path.push(parent);
+                            vertex = parent;
+                        }
+
+                        path
+                    };
+
+                    let mut cycle = path_to(vertex);
+                    let mut other_half = path_to(neighbor);
+
+                    cycle.reverse();
+                    other_half.pop();
+                    cycle.extend(other_half);
+                    best = Some((len, cycle));
+
+                    if len == MIN_GIRTH {
+                        break 'bfs;
+                    }
+                }
+            }
+        }
+
+        if best.as_ref().is_some_and(|(len, _)| *len == MIN_GIRTH) {
+            break;
+        }
+    }
+
+    best.map(|(len, cycle)| (len, cycle.into_iter().map(|i| vertices[i]).collect()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::girth;
+    use crate::graph::Graph;
+
+    #[test]
+    fn test_girth() {
+        // A square a-b-c-d with a tail d-e
+        let mut graph = Graph::<(), ()>::new();
+        let [a, b, c, d, e] = [(); 5].map(|_| graph.insert_vertex(()));
+
+        graph.insert_or_update_edges([(a, b, ()), (b, c, ()), (c, d, ()), (d, a, ()), (d, e, ())]);
+
+        let (len, cycle) = girth(&graph).expect("The graph has a cycle");
+
+        assert_eq!(len, 4, "Wrong girth");
+        assert_eq!(cycle.len(), 4, "Wrong number of vertices in the cycle");
+        assert!(!cycle.contains(&e), "Vertex e isn't on any cycle");
+
+        graph.remove_edge(d, a);
+
+        assert!(girth(&graph).is_none(), "The graph is a tree");
+    }
+}
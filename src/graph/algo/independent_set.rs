@@ -0,0 +1,173 @@
+use crate::collections::{BTreeSet, HashMap, HashSet};
+use crate::graph::{Graph, VertexIdx};
+use alloc::vec::Vec;
+use core::hash::BuildHasher;
+
+/// Finds an independent set of `graph` using the minimum-degree greedy heuristic in
+/// `O((|V| + |E|) log |V|)`: repeatedly select the vertex with the fewest remaining neighbors
+/// and discard its neighbors.
+///
+/// The graph is interpreted as undirected. Vertices with self-loops are never selected.
+pub fn greedy_independent_set<V, E, D, S: BuildHasher + Default>(
+    graph: &Graph<V, E, D, S>,
+) -> HashSet<VertexIdx, S> {
+    let mut adjacency = undirected_adjacency(graph);
+    let mut by_degree: BTreeSet<(usize, VertexIdx)> = adjacency
+        .iter()
+        .map(|(&idx, neighbors)| (neighbors.len(), idx))
+        .collect();
+    let mut independent_set = HashSet::default();
+
+    while let Some((_, vertex_idx)) = by_degree.pop_first() {
+        let neighbors = adjacency.remove(&vertex_idx).unwrap_or_default();
+
+        independent_set.insert(vertex_idx);
+
+        // Discard the neighbors and update the degrees of their own neighbors
+        for neighbor_idx in neighbors {
+            let Some(second_neighbors) = adjacency.remove(&neighbor_idx) else {
+                continue;
+            };
+
+            by_degree.remove(&(second_neighbors.len(), neighbor_idx));
+
+            for second_idx in second_neighbors {
+                let Some(second) = adjacency.get_mut(&second_idx) else {
+                    continue;
+                };
+
+                by_degree.remove(&(second.len(), second_idx));
+                second.remove(&neighbor_idx);
+                by_degree.insert((second.len(), second_idx));
+            }
+        }
+    }
+
+    independent_set
+}
+
+/// Finds a maximum independent set of `graph` using branch and bound, seeded with
+/// [`greedy_independent_set`].
+///
+/// The running time is exponential, thus it's only suitable for small graphs.
+/// The graph is interpreted as undirected. Vertices with self-loops are never selected.
+pub fn max_independent_set<V, E, D, S: BuildHasher + Default>(
+    graph: &Graph<V, E, D, S>,
+) -> HashSet<VertexIdx, S> {
+    let adjacency = undirected_adjacency(graph);
+    let mut best: Vec<VertexIdx> = greedy_independent_set(graph).into_iter().collect();
+    let candidates: HashSet<VertexIdx, S> = adjacency.keys().copied().collect();
+
+    branch_and_bound(&adjacency, candidates, &mut Vec::new(), &mut best);
+
+    best.into_iter().collect()
+}
+
+fn branch_and_bound<S: BuildHasher + Default>(
+    adjacency: &HashMap<VertexIdx, HashSet<VertexIdx, S>, S>,
+    mut candidates: HashSet<VertexIdx, S>,
+    current: &mut Vec<VertexIdx>,
+    best: &mut Vec<VertexIdx>,
+) {
+    // Even selecting every candidate can't beat the best set found so far
+    if current.len() + candidates.len() <= best.len() {
+        return;
+    }
+
+    let max_degree_vertex = candidates
+        .iter()
+        .map(|idx| {
+            let degree = adjacency[idx]
+                .iter()
+                .filter(|neighbor| candidates.contains(*neighbor))
+                .count();
+
+            (degree, *idx)
+        })
+        .max();
+
+    match max_degree_vertex {
+        // The remaining candidates are independent of each other
+        None | Some((0, _)) => {
+            best.clear();
+            best.extend(current.iter().chain(&candidates));
+        }
+        Some((_, vertex_idx)) => {
+            candidates.remove(&vertex_idx);
+
+            // Select it, discarding its neighbors
+            let remaining = candidates
+                .iter()
+                .filter(|idx| !adjacency[&vertex_idx].contains(*idx))
+                .copied()
+                .collect();
+
+            current.push(vertex_idx);
+            branch_and_bound(adjacency, remaining, current, best);
+            current.pop();
+
+            // Or don't select it
+            branch_and_bound(adjacency, candidates, current, best);
+        }
+    }
+}
+
+/// The undirected adjacency of `graph`, excluding vertices with self-loops altogether.
+fn undirected_adjacency<V, E, D, S: BuildHasher + Default>(
+    graph: &Graph<V, E, D, S>,
+) -> HashMap<VertexIdx, HashSet<VertexIdx, S>, S> {
+    let mut adjacency: HashMap<VertexIdx, HashSet<VertexIdx, S>, S> = graph
+        .vertices
+        .iter()
+        .filter(|(idx, vertex)| !vertex.is_adjacent(**idx))
+        .map(|(&idx, _)| (idx, HashSet::default()))
+        .collect();
+
+    for (&from_idx, vertex) in &graph.vertices {
+        for (to_idx, _) in vertex.neighbors() {
+            if adjacency.contains_key(&from_idx) && adjacency.contains_key(&to_idx) {
+                adjacency.entry(from_idx).or_default().insert(to_idx);
+                adjacency.entry(to_idx).or_default().insert(from_idx);
+            }
+        }
+    }
+
+    adjacency
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{greedy_independent_set, max_independent_set};
+    use crate::collections::HashSet;
+    use crate::graph::{Graph, VertexIdx};
+
+    fn is_independent(graph: &Graph<(), ()>, set: &HashSet<VertexIdx>) -> bool {
+        set.iter().all(|&idx| {
+            graph
+                .get_vertex(idx)
+                .is_some_and(|vertex| vertex.neighbors().all(|(to, _)| !set.contains(&to)))
+        })
+    }
+
+    #[test]
+    fn test_independent_set() {
+        // A cycle of 7 vertices has a maximum independent set of 3 vertices
+        let mut graph = Graph::<(), ()>::new();
+        let idx: Vec<_> = (0..7).map(|_| graph.insert_vertex(())).collect();
+
+        graph.insert_or_update_edges((0..7).map(|i| (idx[i], idx[(i + 1) % 7], ())));
+
+        let greedy = greedy_independent_set(&graph);
+        let maximum = max_independent_set(&graph);
+
+        assert!(
+            is_independent(&graph, &greedy),
+            "Greedy set isn't independent"
+        );
+        assert!(
+            is_independent(&graph, &maximum),
+            "Maximum set isn't independent"
+        );
+        assert_eq!(maximum.len(), 3, "Wrong maximum independent set size");
+    }
+}
@@ -0,0 +1,216 @@
+use crate::collections::HashMap;
+use crate::graph::{Graph, VertexIdx};
+use alloc::{vec, vec::Vec};
+use core::hash::BuildHasher;
+
+/// Computes the core number of every vertex of `graph` using the Batagelj–Zaversnik algorithm
+/// in `O(|V| + |E|)`.
+///
+/// The core number of a vertex is the largest `k` such that the vertex belongs to the `k`-core,
+/// the maximal subgraph whose vertices all have degree at least `k`.
+/// The graph is interpreted as undirected, ignoring self-loops and parallel edges.
+pub fn k_core<V, E, D, S: BuildHasher + Default>(
+    graph: &Graph<V, E, D, S>,
+) -> HashMap<VertexIdx, usize, S> {
+    peel(graph).into_iter().collect()
+}
+
+/// Orders the vertices of `graph` by repeatedly removing a vertex of minimum degree,
+/// in `O(|V| + |E|)`.
+///
+/// Each vertex has at most as many neighbors after it as the degeneracy of the graph, the
+/// smallest such bound of any ordering, which equals the largest core number (see [`k_core`]).
+/// So a clique search started from each vertex in this order, only extending it with the vertices
+/// after it, has at most that many candidates per start.
+/// The graph is interpreted as undirected, ignoring self-loops and parallel edges.
+///
+/// Returns the ordering along with the degeneracy.
+pub fn degeneracy_ordering<V, E, D, S: BuildHasher + Default>(
+    graph: &Graph<V, E, D, S>,
+) -> (Vec<VertexIdx>, usize) {
+    let peeled = peel(graph);
+    let degeneracy = peeled.iter().map(|&(_, core)| core).max().unwrap_or(0);
+
+    (peeled.into_iter().map(|(idx, _)| idx).collect(), degeneracy)
+}
+
+/// Repeatedly removes a vertex of minimum degree. Returns the vertices in the order they were
+/// removed, along with their core number.
+fn peel<V, E, D, S: BuildHasher + Default>(graph: &Graph<V, E, D, S>) -> Vec<(VertexIdx, usize)> {
+    let vertices: Vec<VertexIdx> = graph.vertices.keys().copied().collect();
+    let compact_idx: HashMap<VertexIdx, usize, S> = vertices
+        .iter()
+        .enumerate()
+        .map(|(i, &idx)| (idx, i))
+        .collect();
+    let mut adjacency: Vec<Vec<usize>> = vec![Vec::new(); vertices.len()];
+
+    for (from_idx, vertex) in &graph.vertices {
+        let from = compact_idx[from_idx];
+
+        for (to_idx, _) in vertex.neighbors() {
+            let to = compact_idx[&to_idx];
+
+            if from != to {
+                adjacency[from].push(to);
+                adjacency[to].push(from);
+            }
+        }
+    }
+
+    for neighbors in &mut adjacency {
+        neighbors.sort_unstable();
+        neighbors.dedup();
+    }
+
+    // The vertices sorted by degree in `order`, with `position` as its inverse and `starts` as
+    // the index in `order` of the first vertex of each degree
+    let mut degrees: Vec<usize> = adjacency.iter().map(Vec::len).collect();
+    let max_degree = degrees.iter().copied().max().unwrap_or(0);
+    let mut starts = vec![0; max_degree + 1];
+
+    for &degree in &degrees {
+        starts[degree] += 1;
+    }
+
+    let mut start = 0;
+
+    for count in &mut starts {
+        (*count, start) = (start, start + *count);
+    }
+
+    let mut position = vec![0; vertices.len()];
+    let mut order = vec![0; vertices.len()];
+
+    for (v, &degree) in degrees.iter().enumerate() {
+        position[v] = starts[degree];
+        order[position[v]] = v;
+        starts[degree] += 1;
+    }
+
+    for degree in (1..=max_degree).rev() {
+        starts[degree] = starts[degree - 1];
+    }
+
+    starts[0] = 0;
+
+    // Peel the vertices in order, moving each neighbor with a higher degree to the start of its
+    // bucket before shrinking the bucket past it, so `order` stays sorted
+    for i in 0..vertices.len() {
+        let v = order[i];
+
+        for &u in &adjacency[v] {
+            if degrees[u] > degrees[v] {
+                let (degree, u_position) = (degrees[u], position[u]);
+                let (w_position, w) = (starts[degree], order[starts[degree]]);
+
+                if u != w {
+                    order.swap(u_position, w_position);
+                    position[u] = w_position;
+                    position[w] = u_position;
+                }
+
+                starts[degree] += 1;
+                degrees[u] -= 1;
+            }
+        }
+    }
+
+    order
+        .into_iter()
+        .map(|v| (vertices[v], degrees[v]))
+        .collect()
+}
+
+/// Extracts the `k`-core of `graph`, that is the subgraph induced by the vertices with
+/// a core number of at least `k`, as computed by [`k_core`].
+pub fn k_core_subgraph<V, E, D, S: BuildHasher + Default>(
+    graph: &Graph<V, E, D, S>,
+    k: usize,
+) -> Graph<&V, &E, D, S> {
+    let cores = k_core(graph);
+
+    graph.filter_map(
+        |idx, vertex| (cores[&idx] >= k).then_some(vertex.weight()),
+        |_, _, edge| Some(edge),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{degeneracy_ordering, k_core, k_core_subgraph};
+    use crate::collections::HashSet;
+    use crate::graph::{random::RandomConfig, Graph, Undirected};
+    use fastrand::Rng;
+
+    #[test]
+    fn test_k_core() {
+        // A triangle a-b-c with a tail c-d
+        let mut graph = Graph::<(), ()>::new();
+        let [a, b, c, d] = [(); 4].map(|_| graph.insert_vertex(()));
+
+        graph.insert_or_update_edges([(a, b, ()), (b, c, ()), (c, a, ()), (c, d, ())]);
+
+        let cores = k_core(&graph);
+
+        assert_eq!(cores[&a], 2, "Wrong core number for vertex a");
+        assert_eq!(cores[&c], 2, "Wrong core number for vertex c");
+        assert_eq!(cores[&d], 1, "Wrong core number for vertex d");
+
+        let two_core = k_core_subgraph(&graph, 2);
+
+        assert_eq!(two_core.len(), 3, "The 2-core is the triangle");
+        assert!(
+            two_core.get_vertex(d).is_none(),
+            "Vertex d isn't in the 2-core"
+        );
+
+        let config = RandomConfig {
+            min_vertices: 60,
+            max_vertices: 60,
+            density: 0.1,
+            self_loops: true,
+        };
+        let (graph, _) =
+            Graph::<(), (), Undirected>::random(&mut Rng::with_seed(3), config, |_| (), |_| ());
+        let cores = k_core(&graph);
+
+        // Remove the vertices with fewer than `k` neighbors left until there are none
+        for k in 0..=cores.values().copied().max().unwrap_or(0) + 1 {
+            let mut remaining: HashSet<_> = graph.vertices_iter().map(|(idx, _)| idx).collect();
+
+            while let Some(&idx) = remaining.iter().find(|&&idx| {
+                graph[idx]
+                    .neighbor_indices()
+                    .filter(|&to_idx| to_idx != idx && remaining.contains(&to_idx))
+                    .count()
+                    < k
+            }) {
+                remaining.remove(&idx);
+            }
+
+            let expected: HashSet<_> = cores
+                .iter()
+                .filter(|&(_, &core)| core >= k)
+                .map(|(&idx, _)| idx)
+                .collect();
+
+            assert_eq!(remaining, expected, "Wrong {k}-core");
+        }
+    }
+
+    #[test]
+    fn test_degeneracy_ordering() {
+        // A triangle a-b-c with a tail c-d
+        let mut graph = Graph::<(), ()>::new();
+        let [a, b, c, d] = [(); 4].map(|_| graph.insert_vertex(()));
+
+        graph.insert_or_update_edges([(a, b, ()), (b, c, ()), (c, a, ()), (c, d, ())]);
+
+        let (ordering, degeneracy) = degeneracy_ordering(&graph);
+
+        assert_eq!(degeneracy, 2, "Wrong degeneracy");
+        assert_eq!(ordering.len(), 4, "Every vertex must be ordered");
+        assert_eq!(ordering[0], d, "The tail has the minimum degree");
+    }
+}
@@ -0,0 +1,185 @@
+use crate::collections::{DefaultHashBuilder, HashMap};
+use crate::graph::{measure::Measure, Graph, VertexIdx};
+use alloc::{vec, vec::Vec};
+use core::{cmp::Ordering, hash::BuildHasher};
+
+pub struct KEdgePaths<E, S = DefaultHashBuilder> {
+    compact_idx: HashMap<VertexIdx, usize, S>,
+    counts: Vec<Vec<u64>>,
+    shortest: Vec<Vec<Option<E>>>,
+}
+
+impl<E: Copy, S: BuildHasher> KEdgePaths<E, S> {
+    /// The number of walks from `from` to `to`, saturating at `u64::MAX`.
+    ///
+    /// Returns `None` if either vertex doesn't exist.
+    pub fn count(&self, from: VertexIdx, to: VertexIdx) -> Option<u64> {
+        let (from, to) = (*self.compact_idx.get(&from)?, *self.compact_idx.get(&to)?);
+
+        Some(self.counts[from][to])
+    }
+
+    /// The total weight of the lightest walk from `from` to `to`.
+    ///
+    /// Returns `None` if either vertex doesn't exist or there's no such walk.
+    pub fn shortest(&self, from: VertexIdx, to: VertexIdx) -> Option<E> {
+        let (from, to) = (*self.compact_idx.get(&from)?, *self.compact_idx.get(&to)?);
+
+        self.shortest[from][to]
+    }
+}
+
+/// Computes, for every pair of vertices of `graph`, the number of walks and the lightest walk
+/// made of exactly `k` edges, by exponentiating the adjacency matrix over the counting and the
+/// min-plus semirings in `O(|V|³ log k)`.
+///
+/// Walks may repeat vertices and edges, including self-loops. The only walks of `0` edges are
+/// the empty ones from each vertex to itself, of weight [`Measure::zero`].
+pub fn k_edge_paths<V, E: Measure, D, S: BuildHasher + Default>(
+    graph: &Graph<V, E, D, S>,
+    k: usize,
+) -> KEdgePaths<E, S> {
+    let compact_idx: HashMap<VertexIdx, usize, S> = graph
+        .vertices
+        .keys()
+        .enumerate()
+        .map(|(i, &idx)| (idx, i))
+        .collect();
+    let len = compact_idx.len();
+    let mut counts = vec![vec![0; len]; len];
+    let mut shortest = vec![vec![None; len]; len];
+
+    for (from_idx, vertex) in &graph.vertices {
+        for (to_idx, &edge) in vertex.neighbors() {
+            let (from, to) = (compact_idx[from_idx], compact_idx[&to_idx]);
+
+            counts[from][to] = 1;
+            shortest[from][to] = Some(edge);
+        }
+    }
+
+    let mut paths = KEdgePaths {
+        counts: identity(len, 0, 1),
+        shortest: identity(len, None, Some(E::zero())),
+        compact_idx,
+    };
+    let mut k = k;
+
+    // Exponentiation by squaring
+    while k > 0 {
+        if k & 1 == 1 {
+            paths.counts = count_product(&paths.counts, &counts);
+            paths.shortest = min_plus_product(&paths.shortest, &shortest);
+        }
+
+        k >>= 1;
+
+        if k > 0 {
+            counts = count_product(&counts, &counts);
+            shortest = min_plus_product(&shortest, &shortest);
+        }
+    }
+
+    paths
+}
+
+fn identity<T: Clone>(len: usize, zero: T, one: T) -> Vec<Vec<T>> {
+    (0..len)
+        .map(|i| {
+            let mut row = vec![zero.clone(); len];
+
+            row[i] = one.clone();
+            row
+        })
+        .collect()
+}
+
+fn count_product(a: &[Vec<u64>], b: &[Vec<u64>]) -> Vec<Vec<u64>> {
+    let mut product = vec![vec![0u64; b.first().map_or(0, Vec::len)]; a.len()];
+
+    for (a_row, product_row) in a.iter().zip(&mut product) {
+        for (&a_value, b_row) in a_row.iter().zip(b) {
+            if a_value == 0 {
+                continue;
+            }
+
+            for (value, &b_value) in product_row.iter_mut().zip(b_row) {
+                *value = value.saturating_add(a_value.saturating_mul(b_value));
+            }
+        }
+    }
+
+    product
+}
+
+fn min_plus_product<E: Measure>(a: &[Vec<Option<E>>], b: &[Vec<Option<E>>]) -> Vec<Vec<Option<E>>> {
+    let mut product = vec![vec![None; b.first().map_or(0, Vec::len)]; a.len()];
+
+    for (a_row, product_row) in a.iter().zip(&mut product) {
+        for (&a_value, b_row) in a_row.iter().zip(b) {
+            let Some(a_value) = a_value else {
+                continue;
+            };
+
+            for (value, &b_value) in product_row.iter_mut().zip(b_row) {
+                if let Some(b_value) = b_value {
+                    let new_value = a_value.add(b_value);
+
+                    if value.is_none_or(|old_value| new_value.compare(&old_value) == Ordering::Less)
+                    {
+                        *value = Some(new_value);
+                    }
+                }
+            }
+        }
+    }
+
+    product
+}
+
+#[cfg(test)]
+mod tests {
+    use super::k_edge_paths;
+    use crate::graph::Graph;
+
+    #[test]
+    fn test_k_edge_paths() {
+        // A directed triangle a → b → c → a, plus a heavier edge a → c
+        //   a --1--→ b
+        //   3↖ ↘5  ↙2
+        //       c
+        let mut graph = Graph::<(), u32>::new();
+        let [a, b, c] = [(); 3].map(|_| graph.insert_vertex(()));
+
+        graph.insert_or_update_edges([(a, b, 1), (b, c, 2), (c, a, 3), (a, c, 5)]);
+
+        let paths = k_edge_paths(&graph, 3);
+
+        // a → b → c → a
+        assert_eq!(paths.count(a, a), Some(1), "Wrong number of walks");
+        assert_eq!(paths.shortest(a, a), Some(6), "Wrong shortest walk");
+        // a → c → a → c
+        assert_eq!(paths.count(a, c), Some(1), "Wrong number of walks");
+        assert_eq!(paths.shortest(a, c), Some(13), "Wrong shortest walk");
+        assert_eq!(
+            paths.shortest(c, b),
+            None,
+            "There's no walk from c to b of 3 edges"
+        );
+
+        let empty = k_edge_paths(&graph, 0);
+
+        assert_eq!(empty.count(a, a), Some(1), "The empty walk has no edges");
+        assert_eq!(empty.shortest(a, b), None, "The empty walk doesn't leave a");
+
+        let halved = graph.map(|_, _| (), |_, _, &edge| f64::from(edge) / 2.0);
+        let paths = k_edge_paths(&halved, 3);
+
+        assert_eq!(
+            paths.shortest(a, a),
+            Some(3.0),
+            "Float weights must work too"
+        );
+        assert_eq!(paths.shortest(a, c), Some(6.5), "Wrong shortest walk");
+    }
+}
@@ -0,0 +1,183 @@
+use crate::collections::HashMap;
+use crate::graph::{Graph, VertexIdx};
+use alloc::{vec, vec::Vec};
+use core::hash::BuildHasher;
+
+/// Orders the vertices of `graph` using lexicographic breadth-first search (LexBFS) with
+/// partition refinement in `O(|V| + |E|)`.
+///
+/// Among the vertices not yet visited, LexBFS always visits one whose already visited neighbors
+/// are lexicographically largest (ranked by how early they were visited). On chordal graphs,
+/// the reverse of this order is a perfect elimination ordering.
+/// The graph is interpreted as undirected, ignoring self-loops.
+pub fn lex_bfs<V, E, D, S: BuildHasher + Default>(graph: &Graph<V, E, D, S>) -> Vec<VertexIdx> {
+    let vertices: Vec<VertexIdx> = graph.vertices.keys().copied().collect();
+    let compact_idx: HashMap<VertexIdx, usize, S> = vertices
+        .iter()
+        .enumerate()
+        .map(|(i, &idx)| (idx, i))
+        .collect();
+    let mut adjacency: Vec<Vec<usize>> = vec![Vec::new(); vertices.len()];
+
+    for (from_idx, vertex) in &graph.vertices {
+        for (to_idx, _) in vertex.neighbors() {
+            let (from, to) = (compact_idx[from_idx], compact_idx[&to_idx]);
+
+            if from != to {
+                adjacency[from].push(to);
+                adjacency[to].push(from);
+            }
+        }
+    }
+
+    for neighbors in &mut adjacency {
+        neighbors.sort_unstable();
+        neighbors.dedup();
+    }
+
+    let mut partition = Partition::new(vertices.len());
+    let mut ordering = Vec::with_capacity(vertices.len());
+
+    while let Some(vertex) = partition.pop_first() {
+        ordering.push(vertices[vertex]);
+        partition.refine(&adjacency[vertex]);
+    }
+
+    ordering
+}
+
+/// An ordered partition of the unvisited vertices, kept as a doubly linked list of sets.
+/// Empty sets are only unlinked once they reach the front.
+struct Partition {
+    members: Vec<Vec<usize>>,
+    prev: Vec<Option<usize>>,
+    next: Vec<Option<usize>>,
+    head: Option<usize>,
+    /// The set of every unvisited vertex and its position in it
+    set_of: Vec<Option<(usize, usize)>>,
+    /// The set split off (in front) of each set, during the current refinement
+    split_of: Vec<Option<usize>>,
+}
+
+impl Partition {
+    fn new(len: usize) -> Self {
+        let head = (len > 0).then_some(0);
+
+        Self {
+            members: vec![(0..len).collect()],
+            prev: vec![None],
+            next: vec![None],
+            head,
+            set_of: (0..len).map(|i| Some((0, i))).collect(),
+            split_of: vec![None],
+        }
+    }
+
+    fn pop_first(&mut self) -> Option<usize> {
+        loop {
+            let head = self.head?;
+
+            if let Some(vertex) = self.members[head].pop() {
+                self.set_of[vertex] = None;
+                return Some(vertex);
+            }
+
+            self.head = self.next[head];
+
+            if let Some(next) = self.head {
+                self.prev[next] = None;
+            }
+        }
+    }
+
+    /// Moves the unvisited `neighbors` of the last visited vertex in front of their sets.
+    fn refine(&mut self, neighbors: &[usize]) {
+        let mut touched = Vec::new();
+
+        for &neighbor in neighbors {
+            let Some((set, position)) = self.set_of[neighbor] else {
+                continue;
+            };
+
+            let new_set = match self.split_of[set] {
+                Some(new_set) => new_set,
+                None => {
+                    let new_set = self.members.len();
+
+                    self.members.push(Vec::new());
+                    self.split_of.push(None);
+                    self.prev.push(self.prev[set]);
+                    self.next.push(Some(set));
+
+                    match self.prev[set] {
+                        Some(prev) => self.next[prev] = Some(new_set),
+                        None => self.head = Some(new_set),
+                    }
+
+                    self.prev[set] = Some(new_set);
+                    self.split_of[set] = Some(new_set);
+                    touched.push(set);
+                    new_set
+                }
+            };
+
+            self.members[set].swap_remove(position);
+
+            if let Some(&moved) = self.members[set].get(position) {
+                self.set_of[moved] = Some((set, position));
+            }
+
+            self.set_of[neighbor] = Some((new_set, self.members[new_set].len()));
+            self.members[new_set].push(neighbor);
+        }
+
+        for set in touched {
+            self.split_of[set] = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::lex_bfs;
+    use crate::collections::HashMap;
+    use crate::graph::Graph;
+
+    #[test]
+    fn test_lex_bfs() {
+        // A chordal graph: the square a - b - c - d split by the chord b - d, plus a pendant e
+        //  a - b - e
+        //  | / |
+        //  d - c
+        let mut graph = Graph::<(), ()>::new();
+        let [a, b, c, d, e] = [(); 5].map(|_| graph.insert_vertex(()));
+        let edges = [(a, b), (b, c), (c, d), (d, a), (b, d), (b, e)];
+
+        graph.insert_or_update_edges(edges.map(|(from, to)| (from, to, ())));
+
+        let ordering = lex_bfs(&graph);
+        let positions: HashMap<_, _> = ordering
+            .iter()
+            .enumerate()
+            .map(|(i, &idx)| (idx, i))
+            .collect();
+        let is_adjacent = |x, y| edges.contains(&(x, y)) || edges.contains(&(y, x));
+
+        assert_eq!(ordering.len(), 5, "Every vertex must be ordered");
+
+        // The reversed ordering must be a perfect elimination ordering: the neighbors of every
+        // vertex that LexBFS visited before it form a clique
+        for &vertex_idx in &ordering {
+            let earlier: Vec<_> = ordering[..positions[&vertex_idx]]
+                .iter()
+                .filter(|&&idx| is_adjacent(idx, vertex_idx))
+                .collect();
+
+            for (i, &&x) in earlier.iter().enumerate() {
+                for &&y in &earlier[i + 1..] {
+                    assert!(is_adjacent(x, y), "Not a perfect elimination ordering");
+                }
+            }
+        }
+    }
+}
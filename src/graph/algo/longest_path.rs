@@ -0,0 +1,100 @@
+use super::toposort::{toposort, Cycle};
+use crate::collections::HashMap;
+use crate::graph::{measure::Measure, Graph, VertexIdx};
+use alloc::{vec, vec::Vec};
+use core::{cmp::Ordering, hash::BuildHasher};
+
+/// Finds the longest (critical) path of a DAG `graph` in `O(|V| + |E|)`, using the edge weights
+/// as lengths.
+///
+/// Returns the vertices of the path in order, along with its total length. The path of an empty
+/// graph is empty.
+///
+/// Returns [`Cycle`] if the graph isn't a DAG.
+pub fn dag_longest_path<V, E: Measure, D, S: BuildHasher + Default>(
+    graph: &Graph<V, E, D, S>,
+) -> Result<(Vec<VertexIdx>, E), Cycle> {
+    let order = toposort(graph)?;
+    // Every vertex starts a path of length zero, until a longer one ends to it
+    let mut lengths: HashMap<VertexIdx, (E, Option<VertexIdx>), S> =
+        order.iter().map(|&idx| (idx, (E::zero(), None))).collect();
+
+    for &vertex_idx in &order {
+        let (length, _) = lengths[&vertex_idx];
+
+        for (neighbor_idx, &edge) in graph.vertices[&vertex_idx].neighbors() {
+            let neighbor = lengths
+                .get_mut(&neighbor_idx)
+                .expect("Every vertex has a length");
+
+            let new_length = length.add(edge);
+
+            if new_length.compare(&neighbor.0) == Ordering::Greater {
+                *neighbor = (new_length, Some(vertex_idx));
+            }
+        }
+    }
+
+    let Some((&last_idx, &(total_length, _))) = lengths
+        .iter()
+        .max_by(|(_, (a, _)), (_, (b, _))| a.compare(b))
+    else {
+        return Ok((Vec::new(), E::zero()));
+    };
+
+    let mut path = vec![last_idx];
+
+    while let Some(parent_idx) = lengths[path.last().expect("The path isn't empty")].1 {
+        path.push(parent_idx);
+    }
+
+    path.reverse();
+    Ok((path, total_length))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{dag_longest_path, Cycle};
+    use crate::graph::Graph;
+
+    #[test]
+    fn test_dag_longest_path() {
+        // a -3-> b -4-> d
+        //  \           ↑
+        //   ---2-> c -6
+        let mut graph = Graph::<(), u32>::new();
+
+        let a_idx = graph.insert_vertex(());
+        let b_idx = graph.insert_vertex(());
+        let c_idx = graph.insert_vertex(());
+        let d_idx = graph.insert_vertex(());
+
+        graph.insert_or_update_edges([
+            (a_idx, b_idx, 3),
+            (b_idx, d_idx, 4),
+            (a_idx, c_idx, 2),
+            (c_idx, d_idx, 6),
+        ]);
+
+        assert_eq!(
+            dag_longest_path(&graph),
+            Ok((vec![a_idx, c_idx, d_idx], 8)),
+            "Wrong critical path"
+        );
+
+        let halved = graph.map(|_, _| (), |_, _, &edge| f64::from(edge) / 2.0);
+
+        assert_eq!(
+            dag_longest_path(&halved),
+            Ok((vec![a_idx, c_idx, d_idx], 4.0)),
+            "Float weights must work too"
+        );
+
+        graph.insert_or_update_edge(d_idx, a_idx, 1);
+
+        assert!(
+            matches!(dag_longest_path(&graph), Err(Cycle(_))),
+            "Cyclic graphs have no longest path"
+        );
+    }
+}
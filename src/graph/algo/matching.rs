@@ -0,0 +1,997 @@
+use crate::collections::{HashMap, VecDeque};
+use crate::graph::{
+    measure::{Measure, Ordered},
+    Graph, VertexIdx,
+};
+use alloc::{vec, vec::Vec};
+use core::{
+    cmp::Ordering,
+    hash::BuildHasher,
+    ops::{Div, Sub},
+};
+
+/// Finds a maximum cardinality matching of `graph` using Edmonds' blossom algorithm in `O(|V|³)`.
+///
+/// Unlike augmenting path algorithms for bipartite graphs, odd cycles (blossoms) are contracted
+/// while searching, so it works on general graphs.
+/// The graph is interpreted as undirected, ignoring self-loops.
+///
+/// Returns the matched pairs of vertices.
+pub fn max_matching<V, E, D, S: BuildHasher + Default>(
+    graph: &Graph<V, E, D, S>,
+) -> Vec<(VertexIdx, VertexIdx)> {
+    let vertices: Vec<VertexIdx> = graph.vertices.keys().copied().collect();
+    let compact_idx: HashMap<VertexIdx, usize, S> = vertices
+        .iter()
+        .enumerate()
+        .map(|(i, &idx)| (idx, i))
+        .collect();
+    let mut adjacency: Vec<Vec<usize>> = vec![Vec::new(); vertices.len()];
+
+    for (from_idx, vertex) in &graph.vertices {
+        for (to_idx, _) in vertex.neighbors() {
+            let (from, to) = (compact_idx[from_idx], compact_idx[&to_idx]);
+
+            if from != to {
+                adjacency[from].push(to);
+                adjacency[to].push(from);
+            }
+        }
+    }
+
+    for neighbors in &mut adjacency {
+        neighbors.sort_unstable();
+        neighbors.dedup();
+    }
+
+    let mut blossom = Blossom::new(adjacency);
+
+    for root in 0..vertices.len() {
+        if blossom.mates[root].is_none() {
+            if let Some(end) = blossom.find_augmenting_path(root) {
+                blossom.augment(end);
+            }
+        }
+    }
+
+    blossom
+        .mates
+        .iter()
+        .enumerate()
+        .filter_map(|(vertex, mate)| {
+            let mate = (*mate)?;
+
+            (vertex < mate).then(|| (vertices[vertex], vertices[mate]))
+        })
+        .collect()
+}
+
+struct Blossom {
+    adjacency: Vec<Vec<usize>>,
+    mates: Vec<Option<usize>>,
+    /// The parent of each vertex in the alternating tree
+    parents: Vec<Option<usize>>,
+    /// The base of the (contracted) blossom each vertex belongs to
+    bases: Vec<usize>,
+    used: Vec<bool>,
+    in_blossom: Vec<bool>,
+    queue: VecDeque<usize>,
+}
+
+impl Blossom {
+    fn new(adjacency: Vec<Vec<usize>>) -> Self {
+        let len = adjacency.len();
+
+        Self {
+            adjacency,
+            mates: vec![None; len],
+            parents: vec![None; len],
+            bases: (0..len).collect(),
+            used: vec![false; len],
+            in_blossom: vec![false; len],
+            queue: VecDeque::new(),
+        }
+    }
+
+    /// Finds the lowest common ancestor of `a` and `b` in the alternating tree.
+    fn lowest_common_ancestor(&self, mut a: usize, mut b: usize) -> usize {
+        let mut visited = vec![false; self.adjacency.len()];
+
+        loop {
+            a = self.bases[a];
+            visited[a] = true;
+
+            match self.mates[a] {
+                Some(mate) => a = self.parents[mate].expect("Matched tree vertices have parents"),
+                None => break,
+            }
+        }
+
+        loop {
+            b = self.bases[b];
+
+            if visited[b] {
+                return b;
+            }
+
+            let mate = self.mates[b].expect("The root is an ancestor of both vertices");
+
+            b = self.parents[mate].expect("Matched tree vertices have parents");
+        }
+    }
+
+    /// Marks the blossom vertices on the path from `vertex` up to the blossom's `base`,
+    /// rewiring their parents so the path can later be walked in either direction.
+    fn mark_path(&mut self, mut vertex: usize, base: usize, mut child: usize) {
+        while self.bases[vertex] != base {
+            let mate = self.mates[vertex].expect("Blossom paths alternate");
+
+            self.in_blossom[self.bases[vertex]] = true;
+            self.in_blossom[self.bases[mate]] = true;
+            self.parents[vertex] = Some(child);
+            child = mate;
+            vertex = self.parents[mate].expect("Matched tree vertices have parents");
+        }
+    }
+
+    /// Grows an alternating tree from the unmatched `root`, returning the unmatched vertex
+    /// at the end of an augmenting path, if there's one.
+    fn find_augmenting_path(&mut self, root: usize) -> Option<usize> {
+        self.used.fill(false);
+        self.parents.fill(None);
+
+        for (vertex, base) in self.bases.iter_mut().enumerate() {
+            *base = vertex;
+        }
+
+        self.used[root] = true;
+        self.queue.clear();
+        self.queue.push_back(root);
+
+        while let Some(vertex) = self.queue.pop_front() {
+            for i in 0..self.adjacency[vertex].len() {
+                let to = self.adjacency[vertex][i];
+
+                if self.bases[vertex] == self.bases[to] || self.mates[vertex] == Some(to) {
+                    continue;
+                }
+
+                let closes_odd_cycle =
+                    to == root || self.mates[to].is_some_and(|mate| self.parents[mate].is_some());
+
+                if closes_odd_cycle {
+                    let base = self.lowest_common_ancestor(vertex, to);
+
+                    self.in_blossom.fill(false);
+                    self.mark_path(vertex, base, to);
+                    self.mark_path(to, base, vertex);
+
+                    for i in 0..self.adjacency.len() {
+                        if self.in_blossom[self.bases[i]] {
+                            self.bases[i] = base;
+
+                            if !self.used[i] {
+                                self.used[i] = true;
+                                self.queue.push_back(i);
+                            }
+                        }
+                    }
+                } else if self.parents[to].is_none() {
+                    self.parents[to] = Some(vertex);
+
+                    let Some(mate) = self.mates[to] else {
+                        return Some(to);
+                    };
+
+                    self.used[mate] = true;
+                    self.queue.push_back(mate);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Flips the matched and unmatched edges along the augmenting path ending at `end`.
+    fn augment(&mut self, end: usize) {
+        let mut vertex = Some(end);
+
+        while let Some(current) = vertex {
+            let parent = self.parents[current].expect("Augmenting path vertices have parents");
+            let next = self.mates[parent];
+
+            self.mates[current] = Some(parent);
+            self.mates[parent] = Some(current);
+            vertex = next;
+        }
+    }
+}
+
+/// Finds a minimum-weight perfect matching of the complete graph on the vertices `0..len`, an
+/// even number, where `weight(a, b)` is the non-negative weight of the edge between `a < b`, using
+/// the weighted variant of Edmonds' blossom algorithm in `O(len³)`.
+///
+/// The weights are turned into `bound - weight`, with a `bound` heavy enough that every
+/// maximum-weight matching is perfect, so that the dual variables never become negative and
+/// unsigned weights work too. The intermediate sums reach about `len` times the heaviest weight.
+pub(crate) fn min_weight_perfect_matching<E>(
+    len: usize,
+    weight: impl Fn(usize, usize) -> E,
+) -> Vec<(usize, usize)>
+where
+    E: Measure + Sub<Output = E> + Div<Output = E> + From<u8>,
+{
+    let mut edges = Vec::with_capacity(len * len.saturating_sub(1) / 2);
+
+    for a in 0..len {
+        edges.extend((a + 1..len).map(|b| (a, b, weight(a, b))));
+    }
+
+    let heaviest = edges
+        .iter()
+        .map(|&(_, _, weight)| Ordered(weight))
+        .max()
+        .map_or(E::zero(), |heaviest| heaviest.0);
+
+    // A perfect matching outweighs any smaller one if the bound exceeds `len / 2` times the
+    // heaviest weight
+    let mut bound = E::from(1);
+
+    for _ in 0..=len / 2 {
+        bound = bound.add(heaviest);
+    }
+
+    for edge in &mut edges {
+        edge.2 = bound - edge.2;
+    }
+
+    let mut blossom = WeightedBlossom::new(len, edges);
+
+    blossom.solve();
+
+    (0..len)
+        .filter_map(|vertex| {
+            let mate = blossom.endpoints[blossom.mates[vertex]?];
+
+            (vertex < mate).then_some((vertex, mate))
+        })
+        .collect()
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Label {
+    Free,
+    /// The even (outer) vertices of an alternating tree
+    S,
+    /// The odd (inner) vertices of an alternating tree
+    T,
+    /// An S-blossom already visited by [`WeightedBlossom::scan_blossom`]
+    Visited,
+}
+
+/// The maximum-weight matching algorithm of Galil, as implemented by Joris van Rantwijk.
+///
+/// Indices below `len` are vertices and the rest are (contracted) blossoms. The edge `k` has the
+/// endpoints `2k` and `2k + 1`, so `p ^ 1` is the other endpoint of the edge of `p`.
+struct WeightedBlossom<E> {
+    len: usize,
+    edges: Vec<(usize, usize, E)>,
+    /// The vertex of each endpoint
+    endpoints: Vec<usize>,
+    /// The remote endpoints of the edges of each vertex
+    neighbor_endpoints: Vec<Vec<usize>>,
+    /// The remote endpoint of the matched edge of each vertex
+    mates: Vec<Option<usize>>,
+    labels: Vec<Label>,
+    /// The endpoint through which each vertex or top-level blossom got its label
+    label_ends: Vec<Option<usize>>,
+    /// The top-level blossom containing each vertex
+    in_blossom: Vec<usize>,
+    parents: Vec<Option<usize>>,
+    /// The sub-blossoms of each blossom, in cycle order starting from the one with the base
+    children: Vec<Vec<usize>>,
+    /// The endpoints of the edges connecting each sub-blossom to the next one
+    child_endpoints: Vec<Vec<usize>>,
+    bases: Vec<Option<usize>>,
+    /// The least-slack edge to a different S-blossom, or from a free vertex to an S-blossom
+    best_edges: Vec<Option<usize>>,
+    /// The least-slack edges of each S-blossom to each other S-blossom
+    blossom_best_edges: Vec<Option<Vec<usize>>>,
+    unused_blossoms: Vec<usize>,
+    /// Twice the dual variable of each vertex, and the dual variable of each blossom
+    duals: Vec<E>,
+    /// The edges whose slack is known to be zero
+    allowed: Vec<bool>,
+    queue: Vec<usize>,
+}
+
+impl<E> WeightedBlossom<E>
+where
+    E: Measure + Sub<Output = E> + Div<Output = E> + From<u8>,
+{
+    fn new(len: usize, edges: Vec<(usize, usize, E)>) -> Self {
+        let mut endpoints = Vec::with_capacity(2 * edges.len());
+        let mut neighbor_endpoints = vec![Vec::new(); len];
+
+        for (k, &(a, b, _)) in edges.iter().enumerate() {
+            endpoints.extend([a, b]);
+            neighbor_endpoints[a].push(2 * k + 1);
+            neighbor_endpoints[b].push(2 * k);
+        }
+
+        let heaviest = edges
+            .iter()
+            .map(|&(_, _, weight)| Ordered(weight))
+            .max()
+            .map_or(E::zero(), |heaviest| heaviest.0);
+        let mut duals = vec![heaviest; len];
+
+        duals.resize(2 * len, E::zero());
+
+        Self {
+            len,
+            allowed: vec![false; edges.len()],
+            edges,
+            endpoints,
+            neighbor_endpoints,
+            mates: vec![None; len],
+            labels: vec![Label::Free; 2 * len],
+            label_ends: vec![None; 2 * len],
+            in_blossom: (0..len).collect(),
+            parents: vec![None; 2 * len],
+            children: vec![Vec::new(); 2 * len],
+            child_endpoints: vec![Vec::new(); 2 * len],
+            bases: (0..len)
+                .map(Some)
+                .chain((len..2 * len).map(|_| None))
+                .collect(),
+            best_edges: vec![None; 2 * len],
+            blossom_best_edges: vec![None; 2 * len],
+            unused_blossoms: (len..2 * len).collect(),
+            duals,
+            queue: Vec::new(),
+        }
+    }
+
+    fn slack(&self, k: usize) -> E {
+        let (a, b, weight) = self.edges[k];
+
+        self.duals[a].add(self.duals[b]) - weight.add(weight)
+    }
+
+    fn is_less(a: E, b: E) -> bool {
+        a.compare(&b) == Ordering::Less
+    }
+
+    fn is_zero(value: E) -> bool {
+        value.compare(&E::zero()) == Ordering::Equal
+    }
+
+    /// The vertices inside the blossom `b`.
+    fn leaves(&self, b: usize) -> Vec<usize> {
+        let mut leaves = Vec::new();
+        let mut stack = vec![b];
+
+        while let Some(b) = stack.pop() {
+            if b < self.len {
+                leaves.push(b);
+            } else {
+                stack.extend(self.children[b].iter().rev());
+            }
+        }
+
+        leaves
+    }
+
+    /// Labels the free vertex `w` and its top-level blossom, reached through the endpoint `p`.
+    /// The mate of the base of a T-blossom is labeled S in turn.
+    fn assign_label(&mut self, w: usize, label: Label, p: Option<usize>) {
+        let b = self.in_blossom[w];
+
+        self.labels[w] = label;
+        self.labels[b] = label;
+        self.label_ends[w] = p;
+        self.label_ends[b] = p;
+        self.best_edges[w] = None;
+        self.best_edges[b] = None;
+
+        if label == Label::S {
+            let leaves = self.leaves(b);
+
+            self.queue.extend(leaves);
+        } else {
+            let base = self.bases[b].expect("Top-level blossoms have bases");
+            let mate = self.mates[base].expect("T-blossoms have matched bases");
+
+            self.assign_label(self.endpoints[mate], Label::S, Some(mate ^ 1));
+        }
+    }
+
+    /// Walks up the alternating trees of the S-vertices `v` and `w` together, returning the base
+    /// of the new blossom if they share a tree, or `None` if they make an augmenting path.
+    fn scan_blossom(&mut self, mut v: usize, w: usize) -> Option<usize> {
+        let mut path = Vec::new();
+        let mut base = None;
+        let mut other = Some(w);
+
+        loop {
+            let b = self.in_blossom[v];
+
+            if self.labels[b] == Label::Visited {
+                base = self.bases[b];
+                break;
+            }
+
+            path.push(b);
+            self.labels[b] = Label::Visited;
+
+            let next = self.label_ends[b].map(|p| {
+                let t = self.in_blossom[self.endpoints[p]];
+
+                self.endpoints[self.label_ends[t].expect("T-blossoms have label ends")]
+            });
+
+            // Alternate between the two paths
+            match other {
+                Some(w) => {
+                    other = next;
+                    v = w;
+                }
+                None => match next {
+                    Some(next) => v = next,
+                    None => break,
+                },
+            }
+        }
+
+        for b in path {
+            self.labels[b] = Label::S;
+        }
+
+        base
+    }
+
+    /// Contracts the cycle closed by the edge `k` through the S-blossoms up to `base` into a new
+    /// S-blossom.
+    fn add_blossom(&mut self, base: usize, k: usize) {
+        let (v, w, _) = self.edges[k];
+        let base_blossom = self.in_blossom[base];
+        let mut bv = self.in_blossom[v];
+        let mut bw = self.in_blossom[w];
+        let b = self
+            .unused_blossoms
+            .pop()
+            .expect("There are fewer blossoms than vertices");
+        let mut children = Vec::new();
+        let mut child_endpoints = Vec::new();
+
+        self.bases[b] = Some(base);
+        self.parents[b] = None;
+        self.parents[base_blossom] = Some(b);
+
+        while bv != base_blossom {
+            let p = self.label_ends[bv].expect("Blossoms below the base have label ends");
+
+            self.parents[bv] = Some(b);
+            children.push(bv);
+            child_endpoints.push(p);
+            bv = self.in_blossom[self.endpoints[p]];
+        }
+
+        children.push(base_blossom);
+        children.reverse();
+        child_endpoints.reverse();
+        child_endpoints.push(2 * k);
+
+        while bw != base_blossom {
+            let p = self.label_ends[bw].expect("Blossoms below the base have label ends");
+
+            self.parents[bw] = Some(b);
+            children.push(bw);
+            child_endpoints.push(p ^ 1);
+            bw = self.in_blossom[self.endpoints[p]];
+        }
+
+        self.children[b] = children;
+        self.child_endpoints[b] = child_endpoints;
+        self.labels[b] = Label::S;
+        self.label_ends[b] = self.label_ends[base_blossom];
+        self.duals[b] = E::zero();
+
+        for leaf in self.leaves(b) {
+            if self.labels[self.in_blossom[leaf]] == Label::T {
+                self.queue.push(leaf);
+            }
+
+            self.in_blossom[leaf] = b;
+        }
+
+        let mut best_edge_to: Vec<Option<usize>> = vec![None; 2 * self.len];
+
+        for child in self.children[b].clone() {
+            let candidates: Vec<usize> = match self.blossom_best_edges[child].take() {
+                Some(candidates) => candidates,
+                None => self
+                    .leaves(child)
+                    .into_iter()
+                    .flat_map(|leaf| self.neighbor_endpoints[leaf].iter().map(|p| p / 2))
+                    .collect(),
+            };
+
+            for k in candidates {
+                let (i, mut j, _) = self.edges[k];
+
+                if self.in_blossom[j] == b {
+                    j = i;
+                }
+
+                let bj = self.in_blossom[j];
+
+                if bj != b
+                    && self.labels[bj] == Label::S
+                    && best_edge_to[bj]
+                        .is_none_or(|best| Self::is_less(self.slack(k), self.slack(best)))
+                {
+                    best_edge_to[bj] = Some(k);
+                }
+            }
+
+            self.best_edges[child] = None;
+        }
+
+        let best_edges: Vec<usize> = best_edge_to.into_iter().flatten().collect();
+
+        self.best_edges[b] = best_edges
+            .iter()
+            .copied()
+            .min_by(|&a, &b| self.slack(a).compare(&self.slack(b)));
+        self.blossom_best_edges[b] = Some(best_edges);
+    }
+
+    /// Turns the blossom `b` back into its sub-blossoms, relabeling them if it was a T-blossom in
+    /// the middle of a stage. At the end of a stage, sub-blossoms with zero dual are expanded too.
+    fn expand_blossom(&mut self, b: usize, end_stage: bool) {
+        for child in self.children[b].clone() {
+            self.parents[child] = None;
+
+            if child < self.len {
+                self.in_blossom[child] = child;
+            } else if end_stage && Self::is_zero(self.duals[child]) {
+                self.expand_blossom(child, end_stage);
+            } else {
+                for leaf in self.leaves(child) {
+                    self.in_blossom[leaf] = child;
+                }
+            }
+        }
+
+        if !end_stage && self.labels[b] == Label::T {
+            let label_end = self.label_ends[b].expect("T-blossoms have label ends");
+            let entry_child = self.in_blossom[self.endpoints[label_end ^ 1]];
+            let len = self.children[b].len() as isize;
+            let at = |j: isize| j.rem_euclid(len) as usize;
+            let mut j = self.children[b]
+                .iter()
+                .position(|&child| child == entry_child)
+                .expect("The entry is a sub-blossom") as isize;
+            // Go the even way around the cycle, from the entry to the base
+            let (step, trick) = if j & 1 == 1 {
+                j -= len;
+                (1, 0usize)
+            } else {
+                (-1, 1)
+            };
+            let mut p = label_end;
+
+            while j != 0 {
+                let q = self.child_endpoints[b][at(j - trick as isize)];
+
+                self.labels[self.endpoints[p ^ 1]] = Label::Free;
+                self.labels[self.endpoints[q ^ trick ^ 1]] = Label::Free;
+                self.assign_label(self.endpoints[p ^ 1], Label::T, Some(p));
+                self.allowed[q / 2] = true;
+                j += step;
+                p = self.child_endpoints[b][at(j - trick as isize)] ^ trick;
+                self.allowed[p / 2] = true;
+                j += step;
+            }
+
+            let base_child = self.children[b][at(j)];
+            let end = self.endpoints[p ^ 1];
+
+            self.labels[end] = Label::T;
+            self.labels[base_child] = Label::T;
+            self.label_ends[end] = Some(p);
+            self.label_ends[base_child] = Some(p);
+            self.best_edges[base_child] = None;
+            j += step;
+
+            // The sub-blossoms on the odd way around may have been reached from outside
+            while self.children[b][at(j)] != entry_child {
+                let child = self.children[b][at(j)];
+
+                j += step;
+
+                if self.labels[child] == Label::S {
+                    continue;
+                }
+
+                let labeled = self
+                    .leaves(child)
+                    .into_iter()
+                    .find(|&leaf| self.labels[leaf] != Label::Free);
+
+                if let Some(leaf) = labeled {
+                    let base = self.bases[child].expect("Sub-blossoms have bases");
+                    let mate = self.mates[base].expect("Bases inside a blossom are matched");
+
+                    self.labels[leaf] = Label::Free;
+                    self.labels[self.endpoints[mate]] = Label::Free;
+                    self.assign_label(leaf, Label::T, self.label_ends[leaf]);
+                }
+            }
+        }
+
+        self.labels[b] = Label::Free;
+        self.label_ends[b] = None;
+        self.children[b].clear();
+        self.child_endpoints[b].clear();
+        self.bases[b] = None;
+        self.best_edges[b] = None;
+        self.blossom_best_edges[b] = None;
+        self.unused_blossoms.push(b);
+    }
+
+    /// Swaps the matched and unmatched edges along the even path from the vertex `v` to the base
+    /// of the blossom `b`, making `v` its new base.
+    fn augment_blossom(&mut self, b: usize, v: usize) {
+        let mut t = v;
+
+        while self.parents[t] != Some(b) {
+            t = self.parents[t].expect("The vertex is inside the blossom");
+        }
+
+        if t >= self.len {
+            self.augment_blossom(t, v);
+        }
+
+        let len = self.children[b].len() as isize;
+        let at = |j: isize| j.rem_euclid(len) as usize;
+        let i = self.children[b]
+            .iter()
+            .position(|&child| child == t)
+            .expect("The vertex is inside a sub-blossom");
+        let mut j = i as isize;
+        let (step, trick) = if i & 1 == 1 {
+            j -= len;
+            (1, 0usize)
+        } else {
+            (-1, 1)
+        };
+
+        while j != 0 {
+            j += step;
+
+            let t = self.children[b][at(j)];
+            let p = self.child_endpoints[b][at(j - trick as isize)] ^ trick;
+
+            if t >= self.len {
+                self.augment_blossom(t, self.endpoints[p]);
+            }
+
+            j += step;
+
+            let t = self.children[b][at(j)];
+
+            if t >= self.len {
+                self.augment_blossom(t, self.endpoints[p ^ 1]);
+            }
+
+            self.mates[self.endpoints[p]] = Some(p ^ 1);
+            self.mates[self.endpoints[p ^ 1]] = Some(p);
+        }
+
+        self.children[b].rotate_left(i);
+        self.child_endpoints[b].rotate_left(i);
+        self.bases[b] = self.bases[self.children[b][0]];
+    }
+
+    /// Augments the matching along the path through the edge `k` between two S-vertices.
+    fn augment_matching(&mut self, k: usize) {
+        let (v, w, _) = self.edges[k];
+
+        for (mut s, mut p) in [(v, 2 * k + 1), (w, 2 * k)] {
+            loop {
+                let bs = self.in_blossom[s];
+
+                if bs >= self.len {
+                    self.augment_blossom(bs, s);
+                }
+
+                self.mates[s] = Some(p);
+
+                let Some(label_end) = self.label_ends[bs] else {
+                    break;
+                };
+
+                let bt = self.in_blossom[self.endpoints[label_end]];
+                let label_end = self.label_ends[bt].expect("T-blossoms have label ends");
+                let j = self.endpoints[label_end ^ 1];
+
+                s = self.endpoints[label_end];
+
+                if bt >= self.len {
+                    self.augment_blossom(bt, j);
+                }
+
+                self.mates[j] = Some(label_end);
+                p = label_end ^ 1;
+            }
+        }
+    }
+
+    /// Runs a stage per augmentation, until the dual variables of the vertices reach zero.
+    fn solve(&mut self) {
+        for _ in 0..self.len {
+            self.labels.fill(Label::Free);
+            self.best_edges.fill(None);
+            self.blossom_best_edges[self.len..].fill(None);
+            self.allowed.fill(false);
+            self.queue.clear();
+
+            for v in 0..self.len {
+                if self.mates[v].is_none() && self.labels[self.in_blossom[v]] == Label::Free {
+                    self.assign_label(v, Label::S, None);
+                }
+            }
+
+            if !self.grow() {
+                break;
+            }
+
+            for b in self.len..2 * self.len {
+                if self.parents[b].is_none()
+                    && self.bases[b].is_some()
+                    && self.labels[b] == Label::S
+                    && Self::is_zero(self.duals[b])
+                {
+                    self.expand_blossom(b, true);
+                }
+            }
+        }
+    }
+
+    /// Grows the alternating trees, adjusting the dual variables when they get stuck. Returns
+    /// whether the matching was augmented.
+    fn grow(&mut self) -> bool {
+        loop {
+            while let Some(v) = self.queue.pop() {
+                for i in 0..self.neighbor_endpoints[v].len() {
+                    let p = self.neighbor_endpoints[v][i];
+                    let (k, w) = (p / 2, self.endpoints[p]);
+
+                    if self.in_blossom[v] == self.in_blossom[w] {
+                        continue;
+                    }
+
+                    let mut slack = None;
+
+                    if !self.allowed[k] {
+                        let k_slack = self.slack(k);
+
+                        self.allowed[k] = k_slack.compare(&E::zero()) != Ordering::Greater;
+                        slack = Some(k_slack);
+                    }
+
+                    let bw = self.in_blossom[w];
+
+                    if self.allowed[k] {
+                        match self.labels[bw] {
+                            Label::Free => self.assign_label(w, Label::T, Some(p ^ 1)),
+                            Label::S => match self.scan_blossom(v, w) {
+                                Some(base) => self.add_blossom(base, k),
+                                None => {
+                                    self.augment_matching(k);
+
+                                    return true;
+                                }
+                            },
+                            _ if self.labels[w] == Label::Free => {
+                                self.labels[w] = Label::T;
+                                self.label_ends[w] = Some(p ^ 1);
+                            }
+                            _ => {}
+                        }
+                    } else {
+                        let slack = slack.expect("Disallowed edges have their slack computed");
+                        let owner = if self.labels[bw] == Label::S {
+                            self.in_blossom[v]
+                        } else if self.labels[w] == Label::Free {
+                            w
+                        } else {
+                            continue;
+                        };
+
+                        if self.best_edges[owner]
+                            .is_none_or(|best| Self::is_less(slack, self.slack(best)))
+                        {
+                            self.best_edges[owner] = Some(k);
+                        }
+                    }
+                }
+            }
+
+            // The least change of the dual variables that allows progress
+            let mut delta = self.duals[..self.len]
+                .iter()
+                .copied()
+                .min_by(|a, b| a.compare(b))
+                .expect("There are vertices");
+            let mut progress = None;
+
+            for v in 0..self.len {
+                if self.labels[self.in_blossom[v]] == Label::Free {
+                    if let Some(k) = self.best_edges[v] {
+                        let slack = self.slack(k);
+
+                        if Self::is_less(slack, delta) {
+                            delta = slack;
+                            progress = Some(Progress::Free(k));
+                        }
+                    }
+                }
+            }
+
+            for b in 0..2 * self.len {
+                if self.parents[b].is_none() && self.labels[b] == Label::S {
+                    if let Some(k) = self.best_edges[b] {
+                        // Even for integers, as both ends are S-vertices
+                        let half_slack = self.slack(k) / E::from(2);
+
+                        if Self::is_less(half_slack, delta) {
+                            delta = half_slack;
+                            progress = Some(Progress::Outer(k));
+                        }
+                    }
+                }
+            }
+
+            for b in self.len..2 * self.len {
+                if self.bases[b].is_some()
+                    && self.parents[b].is_none()
+                    && self.labels[b] == Label::T
+                    && Self::is_less(self.duals[b], delta)
+                {
+                    delta = self.duals[b];
+                    progress = Some(Progress::Expand(b));
+                }
+            }
+
+            for v in 0..self.len {
+                match self.labels[self.in_blossom[v]] {
+                    Label::S => self.duals[v] = self.duals[v] - delta,
+                    Label::T => self.duals[v] = self.duals[v].add(delta),
+                    _ => {}
+                }
+            }
+
+            for b in self.len..2 * self.len {
+                if self.bases[b].is_some() && self.parents[b].is_none() {
+                    match self.labels[b] {
+                        Label::S => self.duals[b] = self.duals[b].add(delta),
+                        Label::T => self.duals[b] = self.duals[b] - delta,
+                        _ => {}
+                    }
+                }
+            }
+
+            match progress {
+                // A vertex dual reached zero, so no augmenting path is left
+                None => return false,
+                Some(Progress::Free(k)) => {
+                    let (mut i, j, _) = self.edges[k];
+
+                    if self.labels[self.in_blossom[i]] == Label::Free {
+                        i = j;
+                    }
+
+                    self.allowed[k] = true;
+                    self.queue.push(i);
+                }
+                Some(Progress::Outer(k)) => {
+                    self.allowed[k] = true;
+                    self.queue.push(self.edges[k].0);
+                }
+                Some(Progress::Expand(b)) => self.expand_blossom(b, false),
+            }
+        }
+    }
+}
+
+/// What becomes possible after changing the dual variables in [`WeightedBlossom::grow`].
+enum Progress {
+    /// The edge from a free vertex to an S-blossom has zero slack
+    Free(usize),
+    /// The edge between two S-blossoms has zero slack
+    Outer(usize),
+    /// The dual of the T-blossom is zero, so it can be expanded
+    Expand(usize),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{max_matching, min_weight_perfect_matching};
+    use crate::collections::HashSet;
+    use crate::graph::Graph;
+    use fastrand::Rng;
+
+    #[test]
+    fn test_max_matching() {
+        // A 5-cycle a-b-c-d-e with a pendant vertex f attached to a.
+        // A perfect matching exists, but only by matching through the odd cycle.
+        let mut graph = Graph::<(), ()>::new();
+        let [a, b, c, d, e, f] = [(); 6].map(|_| graph.insert_vertex(()));
+
+        graph.insert_or_update_edges([
+            (a, b, ()),
+            (b, c, ()),
+            (c, d, ()),
+            (d, e, ()),
+            (e, a, ()),
+            (f, a, ()),
+        ]);
+
+        let matching = max_matching(&graph);
+        let matched: HashSet<_> = matching.iter().flat_map(|&(x, y)| [x, y]).collect();
+
+        assert_eq!(matching.len(), 3, "Wrong matching size");
+        assert_eq!(matched.len(), 6, "A vertex is matched twice");
+    }
+
+    #[test]
+    fn test_min_weight_perfect_matching() {
+        // The least total weight of matching `vertices`, trying every pairing
+        fn brute_force(vertices: &[usize], weights: &[Vec<u32>]) -> u32 {
+            let Some((&first, rest)) = vertices.split_first() else {
+                return 0;
+            };
+
+            (0..rest.len())
+                .map(|i| {
+                    let mut others = rest.to_vec();
+                    let mate = others.remove(i);
+
+                    weights[first][mate] + brute_force(&others, weights)
+                })
+                .min()
+                .expect("An even number of vertices")
+        }
+
+        let mut rng = Rng::with_seed(3);
+
+        for len in [0, 2, 4, 6, 8, 10] {
+            for _ in 0..20 {
+                // Only the upper triangle is used, as the pairs are always ordered
+                let weights: Vec<Vec<u32>> = (0..len)
+                    .map(|_| (0..len).map(|_| rng.u32(0..50)).collect())
+                    .collect();
+                let matching = min_weight_perfect_matching(len, |a, b| weights[a][b]);
+                let matched: HashSet<_> = matching.iter().flat_map(|&(a, b)| [a, b]).collect();
+                let total: u32 = matching.iter().map(|&(a, b)| weights[a][b]).sum();
+
+                assert_eq!(matched.len(), len, "The matching must be perfect");
+                assert_eq!(
+                    total,
+                    brute_force(&(0..len).collect::<Vec<_>>(), &weights),
+                    "The matching must have minimum weight"
+                );
+
+                let float_matching =
+                    min_weight_perfect_matching(len, |a, b| f64::from(weights[a][b]));
+                let float_total: u32 = float_matching.iter().map(|&(a, b)| weights[a][b]).sum();
+
+                assert_eq!(float_total, total, "Float weights must work too");
+            }
+        }
+    }
+}
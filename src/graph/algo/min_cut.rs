@@ -0,0 +1,208 @@
+use crate::collections::HashMap;
+use crate::graph::{measure::Measure, Graph, VertexIdx};
+use alloc::{vec, vec::Vec};
+use core::hash::BuildHasher;
+
+/// Computes the global minimum cut of `graph` using the Stoer–Wagner algorithm in `O(|V|³)`.
+///
+/// The graph is [interpreted as undirected](crate::graph::algo#undirected-interpretation).
+/// Self-loops are ignored and the edge weights must not be less than [`Measure::zero`].
+///
+/// Returns the weight of the cut along with the two sides of the partition,
+/// or `None` if the graph has less than two vertices.
+pub fn stoer_wagner<V, E, D, S: BuildHasher + Default>(
+    graph: &Graph<V, E, D, S>,
+) -> Option<(E, Vec<VertexIdx>, Vec<VertexIdx>)>
+where
+    E: Measure,
+{
+    let vertices: Vec<VertexIdx> = graph.vertices.keys().copied().collect();
+    let compact_idx: HashMap<VertexIdx, usize, S> = vertices
+        .iter()
+        .enumerate()
+        .map(|(i, &idx)| (idx, i))
+        .collect();
+    let len = vertices.len();
+
+    if len < 2 {
+        return None;
+    }
+
+    let mut lightest: Vec<Vec<Option<E>>> = vec![vec![None; len]; len];
+
+    for (from_idx, vertex) in &graph.vertices {
+        let from = compact_idx[from_idx];
+
+        for (to_idx, &edge) in vertex.neighbors() {
+            let to = compact_idx[&to_idx];
+
+            if from != to {
+                let weight = lightest[from][to].map_or(edge, |weight| {
+                    if edge.compare(&weight).is_lt() {
+                        edge
+                    } else {
+                        weight
+                    }
+                });
+
+                lightest[from][to] = Some(weight);
+                lightest[to][from] = Some(weight);
+            }
+        }
+    }
+
+    let mut weights: Vec<Vec<E>> = lightest
+        .into_iter()
+        .map(|row| {
+            row.into_iter()
+                .map(|weight| weight.unwrap_or_else(E::zero))
+                .collect()
+        })
+        .collect();
+
+    // The original vertices each (super)vertex consists of, after merging
+    let mut groups: Vec<Vec<usize>> = (0..len).map(|i| vec![i]).collect();
+    let mut merged = vec![false; len];
+    let mut best: Option<(E, Vec<usize>)> = None;
+
+    for phase in 0..len - 1 {
+        let mut connectivity = vec![E::zero(); len];
+        let mut added = merged.clone();
+        let mut prev: Option<usize> = None;
+
+        // Each phase adds the most tightly connected vertex until one is left
+        for step in 0..len - phase {
+            let next = (0..len)
+                .filter(|&i| !added[i])
+                .max_by(|&a, &b| connectivity[a].compare(&connectivity[b]))
+                .expect("There are vertices left to add");
+
+            if step == len - phase - 1 {
+                let prev = prev.expect("There are at least two vertices in each phase");
+
+                if best
+                    .as_ref()
+                    .is_none_or(|(cut, _)| connectivity[next].compare(cut).is_lt())
+                {
+                    best = Some((connectivity[next], groups[next].clone()));
+                }
+
+                // Merge the last two vertices
+                let group = core::mem::take(&mut groups[next]);
+                groups[prev].extend(group);
+                merged[next] = true;
+
+                for (i, weight) in weights[next].clone().into_iter().enumerate() {
+                    let merged_weight = weights[prev][i].add(weight);
+
+                    weights[prev][i] = merged_weight;
+                    weights[i][prev] = merged_weight;
+                }
+
+                weights[prev][prev] = E::zero();
+            } else {
+                added[next] = true;
+                prev = Some(next);
+
+                for (connectivity, &weight) in connectivity.iter_mut().zip(&weights[next]) {
+                    *connectivity = connectivity.add(weight);
+                }
+            }
+        }
+    }
+
+    let (cut, side) = best?;
+    let mut in_side = vec![false; len];
+
+    for &i in &side {
+        in_side[i] = true;
+    }
+
+    let (side, rest) = vertices.into_iter().enumerate().fold(
+        (Vec::new(), Vec::new()),
+        |(mut side, mut rest), (i, idx)| {
+            if in_side[i] {
+                side.push(idx);
+            } else {
+                rest.push(idx);
+            }
+
+            (side, rest)
+        },
+    );
+
+    Some((cut, side, rest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::stoer_wagner;
+    use crate::collections::HashSet;
+    use crate::graph::Graph;
+
+    #[test]
+    fn test_stoer_wagner() {
+        // Two triangles connected by a single light edge:
+        //   a - b       d - e
+        //    \ /   1     \ /
+        //     c ------- f
+        let mut graph = Graph::<(), u32>::new();
+        let idx: Vec<_> = (0..6).map(|_| graph.insert_vertex(())).collect();
+
+        graph.insert_or_update_edges([
+            (idx[0], idx[1], 5),
+            (idx[1], idx[2], 4),
+            (idx[2], idx[0], 3),
+            (idx[3], idx[4], 5),
+            (idx[4], idx[5], 4),
+            (idx[5], idx[3], 3),
+            (idx[2], idx[5], 1),
+        ]);
+
+        let (cut, a, b) = stoer_wagner(&graph).expect("The graph has enough vertices");
+        let a: HashSet<_> = a.into_iter().collect();
+        let b: HashSet<_> = b.into_iter().collect();
+        let left: HashSet<_> = idx[..3].iter().copied().collect();
+        let right: HashSet<_> = idx[3..].iter().copied().collect();
+
+        assert_eq!(cut, 1, "Wrong minimum cut");
+        assert!(
+            (a == left && b == right) || (a == right && b == left),
+            "Wrong partition"
+        );
+        assert!(stoer_wagner(&Graph::<(), u32>::new()).is_none());
+
+        // Opposite arcs of different weights count as one edge of the lighter weight
+        let mut graph = Graph::<(), u32>::new();
+        let a = graph.insert_vertex(());
+        let b = graph.insert_vertex(());
+
+        graph.insert_or_update_edges([(a, b, 7), (b, a, 2)]);
+
+        let (cut, _, _) = stoer_wagner(&graph).expect("The graph has enough vertices");
+
+        assert_eq!(cut, 2, "Wrong minimum cut with opposite arcs");
+    }
+
+    #[test]
+    fn test_stoer_wagner_f64() {
+        let mut graph = Graph::<(), f64>::new();
+        let idx: Vec<_> = (0..4).map(|_| graph.insert_vertex(())).collect();
+
+        graph.insert_or_update_edges([
+            (idx[0], idx[1], 2.5),
+            (idx[1], idx[2], 0.5),
+            (idx[2], idx[3], 3.0),
+            (idx[3], idx[0], 0.25),
+        ]);
+
+        let (cut, a, _) = stoer_wagner(&graph).expect("The graph has enough vertices");
+        let a: HashSet<_> = a.into_iter().collect();
+
+        assert_eq!(cut, 0.75, "Wrong minimum cut");
+        assert!(
+            a == HashSet::from_iter([idx[0], idx[1]]) || a == HashSet::from_iter([idx[2], idx[3]]),
+            "Wrong partition"
+        );
+    }
+}
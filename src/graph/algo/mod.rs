@@ -1 +1,84 @@
+//! Graph algorithms.
+//!
+//! # Undirected interpretation
+//!
+//! Some algorithms interpret a graph as undirected, regardless of its direction parameter:
+//! an edge in either direction connects its two vertices. If both directions exist with
+//! different weights, the lighter one is the weight of the undirected edge.
+
+use super::VertexIdx;
+
+pub use {
+    arborescence::min_arborescence,
+    bfs::{
+        bfs_forest, bfs_iter, breadth_first_search_compact, breadth_first_search_limited,
+        breadth_first_search_multi, shortest_path_unweighted, zero_one_bfs, BfsForest, BfsIter,
+        BfsResult, BfsTree,
+    },
+    bidirectional_search::bidirectional_search,
+    chordal::is_chordal,
+    cycles::simple_cycles,
+    dfs::{classify_edges, depth_first_search, iddfs, EdgeKind},
+    dijkstra::{dijkstra, dijkstra_visit, ShortestPaths},
+    distance_matrix::{distance_matrix, Unweighted, Weighted, Weighting},
+    edge_coloring::edge_coloring,
+    feedback_arc_set::{feedback_arc_set, remove_feedback_arcs},
+    floyd_warshall::{floyd_warshall, AllPairsDistances},
+    girth::girth,
+    independent_set::{greedy_independent_set, max_independent_set},
+    k_core::{degeneracy_ordering, k_core, k_core_subgraph},
+    k_edge_paths::{k_edge_paths, KEdgePaths},
+    lex_bfs::lex_bfs,
+    longest_path::dag_longest_path,
+    matching::max_matching,
+    min_cut::stoer_wagner,
+    mst::prim,
+    random_walk::{random_walk, stationary_distribution},
+    rcm::rcm_ordering,
+    reachability::{path_exists, reachable_from, ReachabilityIndex},
+    stable_matching::{stable_matching, stable_matching_from_graph},
+    steiner::steiner_tree,
+    toposort::{topo_iter, toposort, TopoIter},
+    triangles::triangles,
+    tsp::tsp_christofides,
+    widest_path::widest_path,
+};
+
+#[cfg(feature = "parallel")]
+pub use {bfs::par_bfs, delta_stepping::delta_stepping, floyd_warshall::par_floyd_warshall};
+
+pub mod arborescence;
 pub mod bfs;
+pub mod bidirectional_search;
+pub mod chordal;
+pub mod cycles;
+#[cfg(feature = "parallel")]
+pub mod delta_stepping;
+pub mod dfs;
+pub mod dijkstra;
+pub mod distance_matrix;
+pub mod edge_coloring;
+pub mod feedback_arc_set;
+pub mod flow;
+pub mod floyd_warshall;
+pub mod girth;
+pub mod independent_set;
+pub mod k_core;
+pub mod k_edge_paths;
+pub mod lex_bfs;
+pub mod longest_path;
+pub mod matching;
+pub mod min_cut;
+pub mod mst;
+pub mod random_walk;
+pub mod rcm;
+pub mod reachability;
+pub mod stable_matching;
+pub mod steiner;
+pub mod toposort;
+pub mod triangles;
+pub mod tsp;
+pub mod widest_path;
+
+/// An edge from a vertex to another, along with its weight: `(from, to, weight)`.
+pub type Edge<E> = (VertexIdx, VertexIdx, E);
@@ -0,0 +1,113 @@
+use super::Edge;
+use crate::collections::{BinaryHeap, HashMap, HashSet};
+use crate::graph::{
+    measure::{Measure, Ordered},
+    Graph, VertexIdx,
+};
+use alloc::vec::Vec;
+use core::{cmp::Reverse, hash::BuildHasher};
+
+/// Computes a minimum spanning forest of `graph` using Prim's algorithm in `O(|E| log |V|)`.
+///
+/// The graph is [interpreted as undirected](crate::graph::algo#undirected-interpretation).
+/// Self-loops are ignored.
+///
+/// Returns the edges of the forest as `(from, to, weight)`, in the order they were selected.
+pub fn prim<V, E, D, S: BuildHasher + Default>(graph: &Graph<V, E, D, S>) -> Vec<Edge<E>>
+where
+    E: Measure,
+{
+    let mut adjacency: HashMap<VertexIdx, HashMap<VertexIdx, E, S>, S> = HashMap::default();
+
+    for (&from_idx, vertex) in &graph.vertices {
+        for (to_idx, &edge) in vertex.neighbors() {
+            if from_idx == to_idx {
+                continue;
+            }
+
+            for (a, b) in [(from_idx, to_idx), (to_idx, from_idx)] {
+                adjacency
+                    .entry(a)
+                    .or_default()
+                    .entry(b)
+                    .and_modify(|weight| *weight = Ordered(*weight).min(Ordered(edge)).0)
+                    .or_insert(edge);
+            }
+        }
+    }
+
+    let mut visited: HashSet<_, S> = HashSet::with_capacity_and_hasher(graph.len(), S::default());
+    let mut heap = BinaryHeap::new();
+    let mut forest = Vec::with_capacity(graph.len().saturating_sub(1));
+
+    for &root_idx in graph.vertices.keys() {
+        if !visited.insert(root_idx) {
+            continue;
+        }
+
+        for (&to_idx, &edge) in adjacency.get(&root_idx).into_iter().flatten() {
+            heap.push(Reverse((Ordered(edge), root_idx, to_idx)));
+        }
+
+        while let Some(Reverse((Ordered(edge), from_idx, to_idx))) = heap.pop() {
+            if !visited.insert(to_idx) {
+                continue;
+            }
+
+            forest.push((from_idx, to_idx, edge));
+
+            for (&next_idx, &next_edge) in &adjacency[&to_idx] {
+                if !visited.contains(&next_idx) {
+                    heap.push(Reverse((Ordered(next_edge), to_idx, next_idx)));
+                }
+            }
+        }
+    }
+
+    forest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::prim;
+    use crate::graph::Graph;
+
+    #[test]
+    fn test_prim() {
+        //  a -1- b -3- c     e -2- f
+        //   \4  /2
+        //     d
+        let mut graph = Graph::<(), u32>::new();
+        let [a, b, c, d, e, f] = [(); 6].map(|_| graph.insert_vertex(()));
+
+        graph.insert_or_update_edges([
+            (a, b, 1),
+            (b, c, 3),
+            (a, d, 4),
+            (d, b, 2),
+            (e, f, 2),
+            (a, a, 0),
+        ]);
+
+        // The lighter of both directions is used
+        graph.insert_or_update_edge(c, b, 10);
+        graph.insert_or_update_edge(f, e, 1);
+
+        let forest = prim(&graph);
+        let mut edges: Vec<_> = forest
+            .iter()
+            .map(|&(from, to, weight)| (from.min(to), from.max(to), weight))
+            .collect();
+
+        edges.sort_unstable();
+        assert_eq!(
+            edges,
+            [(a, b, 1), (b, c, 3), (b, d, 2), (e, f, 1)],
+            "Wrong minimum spanning forest"
+        );
+        assert!(
+            prim(&Graph::<(), u32>::new()).is_empty(),
+            "No vertices, no edges"
+        );
+    }
+}
@@ -0,0 +1,188 @@
+use crate::collections::HashMap;
+use crate::graph::{Graph, VertexIdx};
+use alloc::vec::Vec;
+use core::hash::BuildHasher;
+use fastrand::Rng;
+
+/// Walks `steps` edges of `graph` at random from `start_idx`, choosing each outgoing edge with
+/// probability proportional to its weight, in `O(steps log Δ + |V'| Δ log Δ)`, where `|V'|` is
+/// the number of distinct vertices visited.
+///
+/// Edges whose weight isn't positive are never taken. The walk stops early at a vertex
+/// without any edge to take.
+///
+/// Returns the visited vertices in order, starting from `start_idx`, or `None` if it doesn't exist.
+pub fn random_walk<V, E, D, S: BuildHasher + Default>(
+    graph: &Graph<V, E, D, S>,
+    start_idx: VertexIdx,
+    steps: usize,
+    rng: &mut Rng,
+) -> Option<Vec<VertexIdx>>
+where
+    E: Copy + Into<f64>,
+{
+    graph.get_vertex(start_idx)?;
+
+    let mut transitions = Transitions::new(graph);
+    let mut walk = Vec::with_capacity(steps + 1);
+    let mut vertex_idx = start_idx;
+
+    walk.push(vertex_idx);
+
+    for _ in 0..steps {
+        let Some(next_idx) = transitions.next(vertex_idx, rng) else {
+            break;
+        };
+
+        vertex_idx = next_idx;
+        walk.push(vertex_idx);
+    }
+
+    Some(walk)
+}
+
+/// Estimates the stationary distribution of the Markov chain defined by `graph`, where each
+/// outgoing edge is taken with probability proportional to its weight, from the visit frequencies
+/// of a random walk of `steps` steps in `O(steps log Δ + |V| + |E| log Δ)`.
+///
+/// The walk starts from a random vertex, and restarts from a random vertex whenever it reaches
+/// a vertex without any edge to take (weights that aren't positive are never taken).
+/// The estimate converges as `steps` grows, if the chain is irreducible and aperiodic.
+///
+/// Returns the estimated probability of every visited vertex. The probabilities sum to `1`,
+/// unless the graph is empty or `steps` is `0`.
+pub fn stationary_distribution<V, E, D, S: BuildHasher + Default>(
+    graph: &Graph<V, E, D, S>,
+    steps: usize,
+    rng: &mut Rng,
+) -> HashMap<VertexIdx, f64, S>
+where
+    E: Copy + Into<f64>,
+{
+    let mut vertices: Vec<VertexIdx> = graph.vertices.keys().copied().collect();
+
+    // Sort the vertices so that the same seed gives the same estimate
+    vertices.sort_unstable();
+
+    let mut visits: HashMap<VertexIdx, usize, S> =
+        HashMap::with_capacity_and_hasher(vertices.len(), S::default());
+    let Some(&first_idx) = rng.choice(&vertices) else {
+        return HashMap::default();
+    };
+    let mut transitions = Transitions::new(graph);
+    let mut vertex_idx = first_idx;
+
+    for _ in 0..steps {
+        *visits.entry(vertex_idx).or_default() += 1;
+
+        vertex_idx = match transitions.next(vertex_idx, rng) {
+            Some(next_idx) => next_idx,
+            None => *rng.choice(&vertices).expect("The graph isn't empty"),
+        };
+    }
+
+    let total = visits.values().sum::<usize>() as f64;
+
+    visits
+        .into_iter()
+        .map(|(idx, count)| (idx, count as f64 / total))
+        .collect()
+}
+
+/// The outgoing edges of each vertex with a positive weight, computed on demand.
+struct Transitions<'g, V, E, D, S> {
+    graph: &'g Graph<V, E, D, S>,
+    /// The cumulative weights of the edges of each vertex, along with their targets
+    cumulative_weights: HashMap<VertexIdx, Vec<(f64, VertexIdx)>, S>,
+}
+
+impl<'g, V, E, D, S> Transitions<'g, V, E, D, S>
+where
+    E: Copy + Into<f64>,
+    S: BuildHasher + Default,
+{
+    fn new(graph: &'g Graph<V, E, D, S>) -> Self {
+        Self {
+            graph,
+            cumulative_weights: HashMap::default(),
+        }
+    }
+
+    /// Chooses the next vertex of a walk at `vertex_idx` at random, if there's any edge to take.
+    fn next(&mut self, vertex_idx: VertexIdx, rng: &mut Rng) -> Option<VertexIdx> {
+        let graph = self.graph;
+        let cumulative_weights = self
+            .cumulative_weights
+            .entry(vertex_idx)
+            .or_insert_with(|| {
+                let mut edges: Vec<(VertexIdx, f64)> = graph.vertices[&vertex_idx]
+                    .neighbors()
+                    .map(|(to_idx, &edge)| (to_idx, edge.into()))
+                    .filter(|&(_, weight)| weight > 0.0)
+                    .collect();
+
+                // Sort the edges so that the same seed gives the same walk
+                edges.sort_unstable_by_key(|&(to_idx, _)| to_idx);
+
+                let mut total = 0.0;
+
+                edges
+                    .into_iter()
+                    .map(|(to_idx, weight)| {
+                        total += weight;
+                        (total, to_idx)
+                    })
+                    .collect()
+            });
+        let &(total, _) = cumulative_weights.last()?;
+        let target = rng.f64() * total;
+        let position = cumulative_weights.partition_point(|&(weight, _)| weight <= target);
+        // Guard against rounding errors pushing the target past the last edge
+        let (_, next_idx) = cumulative_weights[position.min(cumulative_weights.len() - 1)];
+
+        Some(next_idx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{random_walk, stationary_distribution};
+    use crate::graph::Graph;
+    use fastrand::Rng;
+
+    #[test]
+    fn test_random_walk() {
+        // A cycle a → b → c → a where b prefers going back to a 3 times over going to c,
+        // and a dead end d reachable from c
+        let mut graph = Graph::<(), u32>::new();
+        let [a, b, c, d] = [(); 4].map(|_| graph.insert_vertex(()));
+
+        graph.insert_or_update_edges([(a, b, 1), (b, a, 3), (b, c, 1), (c, a, 1), (c, d, 0)]);
+
+        let mut rng = Rng::with_seed(42);
+        let walk = random_walk(&graph, a, 1_000, &mut rng).expect("Vertex a exists");
+
+        assert_eq!(walk.len(), 1_001, "The walk never gets stuck");
+        assert_eq!(walk[0], a, "The walk starts from a");
+        assert!(!walk.contains(&d), "Edges weighing 0 are never taken");
+
+        for pair in walk.windows(2) {
+            assert!(
+                graph.get_vertex(pair[0]).unwrap().is_adjacent(pair[1]),
+                "The walk must follow the edges"
+            );
+        }
+
+        // The stationary distribution is a: 4/9, b: 4/9, c: 1/9
+        let distribution = stationary_distribution(&graph, 100_000, &mut rng);
+
+        assert!(
+            (distribution[&a] - 4.0 / 9.0).abs() < 0.02,
+            "Wrong estimate"
+        );
+        assert!(
+            (distribution[&c] - 1.0 / 9.0).abs() < 0.02,
+            "Wrong estimate"
+        );
+    }
+}
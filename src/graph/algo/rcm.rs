@@ -0,0 +1,160 @@
+use crate::collections::{HashMap, HashSet, VecDeque};
+use crate::graph::{Graph, VertexIdx};
+use alloc::{vec, vec::Vec};
+use core::hash::BuildHasher;
+
+/// Orders the vertices of `graph` with the Reverse Cuthill–McKee algorithm in
+/// `O(k(|V| + |E|) + |E| log Δ)`, which tends to reduce the bandwidth of its adjacency matrix.
+/// `k` is the number of BFS passes spent looking for a pseudo-peripheral vertex in a component,
+/// which is at most its diameter plus one, as every pass but the last increases the eccentricity.
+///
+/// Each connected component is traversed in BFS order from a pseudo-peripheral vertex,
+/// visiting neighbors by increasing degree, and the final order is reversed.
+/// The graph is interpreted as undirected, ignoring self-loops.
+///
+/// Returns the permutation, where the vertex at position `i` becomes the `i`-th row/column.
+pub fn rcm_ordering<V, E, D, S: BuildHasher + Default>(
+    graph: &Graph<V, E, D, S>,
+) -> Vec<VertexIdx> {
+    let mut adjacency: HashMap<VertexIdx, Vec<VertexIdx>, S> = graph
+        .vertices
+        .keys()
+        .map(|&idx| (idx, Vec::new()))
+        .collect();
+
+    for (&from_idx, vertex) in &graph.vertices {
+        for (to_idx, _) in vertex.neighbors() {
+            if from_idx != to_idx {
+                adjacency.entry(from_idx).or_default().push(to_idx);
+                adjacency.entry(to_idx).or_default().push(from_idx);
+            }
+        }
+    }
+
+    for neighbors in adjacency.values_mut() {
+        neighbors.sort_unstable();
+        neighbors.dedup();
+    }
+
+    let degree = |idx: &VertexIdx| adjacency[idx].len();
+
+    // Visit neighbors (and components) by increasing degree, breaking ties by index
+    let sorted_adjacency: HashMap<VertexIdx, Vec<VertexIdx>, S> = adjacency
+        .iter()
+        .map(|(&vertex_idx, neighbors)| {
+            let mut neighbors = neighbors.clone();
+
+            neighbors.sort_by_key(|idx| (degree(idx), *idx));
+            (vertex_idx, neighbors)
+        })
+        .collect();
+
+    let mut by_degree: Vec<VertexIdx> = adjacency.keys().copied().collect();
+
+    by_degree.sort_by_key(|idx| (degree(idx), *idx));
+
+    let mut ordering = Vec::with_capacity(graph.len());
+    let mut visited: HashSet<_, S> = HashSet::with_capacity_and_hasher(graph.len(), S::default());
+
+    for start_idx in by_degree {
+        if visited.contains(&start_idx) {
+            continue;
+        }
+
+        let start_idx = pseudo_peripheral_vertex(&sorted_adjacency, start_idx);
+        let mut queue = VecDeque::from([start_idx]);
+
+        visited.insert(start_idx);
+
+        while let Some(vertex_idx) = queue.pop_front() {
+            ordering.push(vertex_idx);
+
+            for &neighbor_idx in &sorted_adjacency[&vertex_idx] {
+                if visited.insert(neighbor_idx) {
+                    queue.push_back(neighbor_idx);
+                }
+            }
+        }
+    }
+
+    ordering.reverse();
+    ordering
+}
+
+/// Approximates a vertex of maximum eccentricity in the component of `start_idx`, by repeatedly
+/// jumping to the lowest-degree vertex of the last BFS level while the eccentricity increases.
+fn pseudo_peripheral_vertex<S: BuildHasher + Default>(
+    adjacency: &HashMap<VertexIdx, Vec<VertexIdx>, S>,
+    mut start_idx: VertexIdx,
+) -> VertexIdx {
+    let mut eccentricity = 0;
+
+    loop {
+        let mut levels: HashMap<_, _, S> = [(start_idx, 0)].into_iter().collect();
+        let mut queue = VecDeque::from([start_idx]);
+        let mut last_level = vec![start_idx];
+
+        while let Some(vertex_idx) = queue.pop_front() {
+            let level = levels[&vertex_idx];
+
+            for &neighbor_idx in &adjacency[&vertex_idx] {
+                if levels.contains_key(&neighbor_idx) {
+                    continue;
+                }
+
+                levels.insert(neighbor_idx, level + 1);
+                queue.push_back(neighbor_idx);
+
+                if level + 1 > levels[&last_level[0]] {
+                    last_level.clear();
+                }
+
+                last_level.push(neighbor_idx);
+            }
+        }
+
+        let new_eccentricity = levels[&last_level[0]];
+        let candidate_idx = *last_level
+            .iter()
+            .min_by_key(|idx| (adjacency[*idx].len(), **idx))
+            .expect("The last level isn't empty");
+
+        if new_eccentricity <= eccentricity {
+            return start_idx;
+        }
+
+        eccentricity = new_eccentricity;
+        start_idx = candidate_idx;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::rcm_ordering;
+    use crate::collections::HashMap;
+    use crate::graph::Graph;
+
+    #[test]
+    fn test_rcm_ordering() {
+        // A path a - b - c - d - e with its vertices inserted in a scrambled order.
+        // A path has bandwidth 1 when ordered along it.
+        let mut graph = Graph::<(), ()>::new();
+        let [c, a, e, b, d] = [(); 5].map(|_| graph.insert_vertex(()));
+
+        graph.insert_or_update_edges([(a, b, ()), (b, c, ()), (c, d, ()), (d, e, ())]);
+
+        let ordering = rcm_ordering(&graph);
+        let positions: HashMap<_, _> = ordering
+            .iter()
+            .enumerate()
+            .map(|(i, &idx)| (idx, i))
+            .collect();
+        let bandwidth = [(a, b), (b, c), (c, d), (d, e)]
+            .into_iter()
+            .map(|(x, y)| positions[&x].abs_diff(positions[&y]))
+            .max();
+
+        assert_eq!(ordering.len(), 5, "Every vertex must be ordered");
+        assert_eq!(bandwidth, Some(1), "Wrong bandwidth");
+    }
+}
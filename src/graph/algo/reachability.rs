@@ -0,0 +1,174 @@
+use super::toposort::{toposort, Cycle};
+use crate::collections::{DefaultHashBuilder, HashMap, HashSet, VecDeque};
+use crate::graph::{Graph, GraphView, VertexIdx};
+use alloc::{vec, vec::Vec};
+use core::hash::BuildHasher;
+
+/// Finds every vertex reachable from `src_idx`, including itself, with BFS in `O(|V'| + |E'|)`,
+/// where `|V'|` and `|E'|` are the vertices and the edges reached. It runs on any [`GraphView`].
+///
+/// Returns `None` if the source vertex doesn't exist in the graph.
+pub fn reachable_from<G: GraphView>(graph: &G, src_idx: VertexIdx) -> Option<HashSet<VertexIdx>> {
+    if !graph.contains_vertex(src_idx) {
+        return None;
+    }
+
+    let mut reached = HashSet::from([src_idx]);
+
+    search(graph, src_idx, &mut reached, |_| false);
+
+    Some(reached)
+}
+
+/// Checks whether there's a path from `src_idx` to `dst_idx` with BFS, stopping as soon as
+/// `dst_idx` is reached, in `O(|V'| + |E'|)`, where `|V'|` and `|E'|` are the vertices and the
+/// edges reached. It runs on any [`GraphView`].
+///
+/// Every existing vertex reaches itself. Returns `false` if either vertex doesn't exist.
+///
+/// For many queries on the same DAG, see [`ReachabilityIndex`].
+pub fn path_exists<G: GraphView>(graph: &G, src_idx: VertexIdx, dst_idx: VertexIdx) -> bool {
+    if !graph.contains_vertex(src_idx) || !graph.contains_vertex(dst_idx) {
+        return false;
+    }
+
+    src_idx == dst_idx
+        || search(graph, src_idx, &mut HashSet::from([src_idx]), |idx| {
+            idx == dst_idx
+        })
+}
+
+/// BFS from `src_idx` that adds the vertices it reaches to `reached`, until `stop` returns `true`
+/// for one of them. Returns whether it stopped early.
+fn search<G: GraphView>(
+    graph: &G,
+    src_idx: VertexIdx,
+    reached: &mut HashSet<VertexIdx>,
+    mut stop: impl FnMut(VertexIdx) -> bool,
+) -> bool {
+    let mut queue = VecDeque::from([src_idx]);
+
+    while let Some(vertex_idx) = queue.pop_front() {
+        let neighbors = graph
+            .neighbors(vertex_idx)
+            .expect("Only existing vertices are reached");
+
+        for (neighbor_idx, _) in neighbors {
+            if reached.insert(neighbor_idx) {
+                if stop(neighbor_idx) {
+                    return true;
+                }
+
+                queue.push_back(neighbor_idx);
+            }
+        }
+    }
+
+    false
+}
+
+/// A precomputed transitive closure of a DAG, which answers whether a vertex reaches another in
+/// `O(1)`. It takes `O(|V|² / 64)` memory, as a bitset of the descendants of each vertex.
+///
+/// The index doesn't borrow the graph, so it's outdated once the graph is modified.
+#[derive(Clone, Debug)]
+pub struct ReachabilityIndex<S = DefaultHashBuilder> {
+    /// The position of each vertex in [`Self::descendants`]
+    positions: HashMap<VertexIdx, usize, S>,
+    /// The bitset of the positions reachable from the vertex of each position
+    descendants: Vec<Vec<u64>>,
+}
+
+impl<S: BuildHasher + Default> ReachabilityIndex<S> {
+    /// Build the index of a DAG from its topological order, in `O(|V| + |E| |V| / 64)`.
+    ///
+    /// Returns [`Cycle`] if the graph isn't a DAG.
+    pub fn new<V, E, D>(graph: &Graph<V, E, D, S>) -> Result<Self, Cycle> {
+        let order = toposort(graph)?;
+        let words = order.len().div_ceil(64);
+        let positions: HashMap<_, _, S> = order
+            .iter()
+            .enumerate()
+            .map(|(position, &vertex_idx)| (vertex_idx, position))
+            .collect();
+        let mut descendants = vec![Vec::new(); order.len()];
+
+        // The descendants of a vertex come after it, so they're complete by the time it's reached
+        for (position, &vertex_idx) in order.iter().enumerate().rev() {
+            let mut bits = vec![0; words];
+
+            bits[position / 64] |= 1 << (position % 64);
+
+            for (neighbor_idx, _) in graph.vertices[&vertex_idx].neighbors() {
+                for (bit, neighbor_bit) in
+                    bits.iter_mut().zip(&descendants[positions[&neighbor_idx]])
+                {
+                    *bit |= neighbor_bit;
+                }
+            }
+
+            descendants[position] = bits;
+        }
+
+        Ok(Self {
+            positions,
+            descendants,
+        })
+    }
+
+    /// Checks whether there's a path from `src_idx` to `dst_idx` in `O(1)`, like [`path_exists`].
+    ///
+    /// Returns `false` if either vertex wasn't in the graph when the index was built.
+    pub fn reaches(&self, src_idx: VertexIdx, dst_idx: VertexIdx) -> bool {
+        let (Some(&src), Some(&dst)) = (self.positions.get(&src_idx), self.positions.get(&dst_idx))
+        else {
+            return false;
+        };
+
+        self.descendants[src][dst / 64] & (1 << (dst % 64)) != 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reachability() {
+        //  a → b → c    e
+        //   ↘     ↗
+        //     d
+        let mut graph = Graph::<(), ()>::new();
+        let [a, b, c, d, e] = [(); 5].map(|_| graph.insert_vertex(()));
+
+        graph.insert_or_update_edges([(a, b, ()), (b, c, ()), (a, d, ()), (d, c, ())]);
+
+        assert_eq!(
+            reachable_from(&graph, b),
+            Some(HashSet::from([b, c])),
+            "Wrong vertices reached from b"
+        );
+        assert!(path_exists(&graph, a, c), "a reaches c");
+        assert!(path_exists(&graph, e, e), "Every vertex reaches itself");
+        assert!(!path_exists(&graph, c, a), "c doesn't reach a");
+        assert!(!path_exists(&graph, a, e), "e isn't connected");
+
+        let index = ReachabilityIndex::new(&graph).expect("The graph is a DAG");
+
+        for src_idx in [a, b, c, d, e] {
+            for dst_idx in [a, b, c, d, e] {
+                assert_eq!(
+                    index.reaches(src_idx, dst_idx),
+                    path_exists(&graph, src_idx, dst_idx),
+                    "The index must agree with the search"
+                );
+            }
+        }
+
+        graph.insert_or_update_edge(c, a, ());
+        assert!(
+            ReachabilityIndex::new(&graph).is_err(),
+            "The graph has a cycle"
+        );
+    }
+}
@@ -0,0 +1,202 @@
+use crate::collections::{HashMap, HashSet, VecDeque};
+use crate::graph::{Graph, VertexIdx};
+use alloc::vec::Vec;
+use core::hash::{BuildHasher, Hash};
+
+/// Finds a stable matching between proposers and acceptors using the Gale–Shapley algorithm
+/// in `O(n)`, where `n` is the total length of the preference lists.
+///
+/// Each preference list is ordered from the most to the least preferred. Partners missing from a
+/// list are considered unacceptable, thus some participants may end up unmatched.
+/// The matching is optimal for the proposers, among all stable matchings.
+///
+/// Returns the partner of each matched proposer.
+pub fn stable_matching<P, A, S>(
+    proposer_prefs: &HashMap<P, Vec<A>, S>,
+    acceptor_prefs: &HashMap<A, Vec<P>, S>,
+) -> HashMap<P, A, S>
+where
+    P: Copy + Hash + Eq,
+    A: Copy + Hash + Eq,
+    S: BuildHasher + Default,
+{
+    // The rank of each proposer in each acceptor's list, for `O(1)` comparisons
+    let ranks: HashMap<A, HashMap<P, usize, S>, S> = acceptor_prefs
+        .iter()
+        .map(|(&acceptor, prefs)| {
+            let ranks = prefs
+                .iter()
+                .enumerate()
+                .map(|(rank, &proposer)| (proposer, rank));
+
+            (acceptor, ranks.collect())
+        })
+        .collect();
+
+    let mut next_proposal: HashMap<P, usize, S> =
+        HashMap::with_capacity_and_hasher(proposer_prefs.len(), S::default());
+    let mut engagements: HashMap<A, P, S> =
+        HashMap::with_capacity_and_hasher(acceptor_prefs.len(), S::default());
+    let mut free: VecDeque<P> = proposer_prefs.keys().copied().collect();
+
+    while let Some(proposer) = free.pop_front() {
+        let next = next_proposal.entry(proposer).or_default();
+        // Proposers that ran out of acceptors stay unmatched
+        let Some(&acceptor) = proposer_prefs[&proposer].get(*next) else {
+            continue;
+        };
+
+        *next += 1;
+
+        let Some(proposer_rank) = ranks.get(&acceptor).and_then(|ranks| ranks.get(&proposer))
+        else {
+            free.push_back(proposer);
+            continue;
+        };
+
+        match engagements.get(&acceptor) {
+            Some(current) if ranks[&acceptor][current] < *proposer_rank => {
+                free.push_back(proposer);
+            }
+            current => {
+                if let Some(&current) = current {
+                    free.push_back(current);
+                }
+
+                engagements.insert(acceptor, proposer);
+            }
+        }
+    }
+
+    engagements
+        .into_iter()
+        .map(|(acceptor, proposer)| (proposer, acceptor))
+        .collect()
+}
+
+/// Reads the preference lists from the edge weights of the bipartite `graph` and finds a stable
+/// matching with [`stable_matching`].
+///
+/// An edge from a proposer to an acceptor (and vice versa) means the acceptor is acceptable to
+/// the proposer, with its weight as the rank: lighter edges are preferred. Every vertex not in
+/// `proposers` is an acceptor. Edges between vertices of the same side are ignored.
+pub fn stable_matching_from_graph<V, E: Ord, D, S: BuildHasher + Default>(
+    graph: &Graph<V, E, D, S>,
+    proposers: &HashSet<VertexIdx, S>,
+) -> HashMap<VertexIdx, VertexIdx, S> {
+    let mut proposer_prefs = HashMap::default();
+    let mut acceptor_prefs = HashMap::default();
+
+    for (&vertex_idx, vertex) in &graph.vertices {
+        let is_proposer = proposers.contains(&vertex_idx);
+        let mut prefs: Vec<(VertexIdx, &E)> = vertex
+            .neighbors()
+            .filter(|(neighbor_idx, _)| proposers.contains(neighbor_idx) != is_proposer)
+            .collect();
+
+        prefs.sort_by(|a, b| a.1.cmp(b.1));
+
+        let prefs = prefs.into_iter().map(|(idx, _)| idx).collect();
+
+        if is_proposer {
+            proposer_prefs.insert(vertex_idx, prefs);
+        } else {
+            acceptor_prefs.insert(vertex_idx, prefs);
+        }
+    }
+
+    stable_matching(&proposer_prefs, &acceptor_prefs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{stable_matching, stable_matching_from_graph};
+    use crate::collections::HashMap;
+    use crate::graph::Graph;
+    use fastrand::Rng;
+
+    #[test]
+    fn test_stable_matching() {
+        let proposer_prefs = HashMap::from([
+            ("a", vec!["x", "y", "z"]),
+            ("b", vec!["x", "z", "y"]),
+            ("c", vec!["y", "x", "z"]),
+        ]);
+        let acceptor_prefs = HashMap::from([
+            ("x", vec!["b", "a", "c"]),
+            ("y", vec!["a", "c", "b"]),
+            ("z", vec!["a", "b", "c"]),
+        ]);
+
+        let matching = stable_matching(&proposer_prefs, &acceptor_prefs);
+
+        assert_eq!(
+            matching,
+            HashMap::from([("a", "y"), ("b", "x"), ("c", "z")]),
+            "Wrong stable matching"
+        );
+    }
+
+    #[test]
+    fn test_stable_matching_from_graph() {
+        let mut rng = Rng::with_seed(4);
+        let mut graph = Graph::<(), u8>::new();
+        let proposers: Vec<_> = (0..8).map(|_| graph.insert_vertex(())).collect();
+        let acceptors: Vec<_> = (0..7).map(|_| graph.insert_vertex(())).collect();
+
+        // Each side ranks the other by the weights of its own edges, and most pairs are acceptable
+        for &proposer in &proposers {
+            for &acceptor in &acceptors {
+                if rng.u8(..4) != 0 {
+                    graph.insert_or_update_edge(proposer, acceptor, rng.u8(..));
+                    graph.insert_or_update_edge(acceptor, proposer, rng.u8(..));
+                }
+            }
+        }
+
+        let matching = stable_matching_from_graph(&graph, &proposers.iter().copied().collect());
+        let partners: HashMap<_, _> = matching
+            .iter()
+            .map(|(&proposer, &acceptor)| (acceptor, proposer))
+            .collect();
+
+        assert_eq!(
+            partners.len(),
+            matching.len(),
+            "Every acceptor has one partner at most"
+        );
+        assert!(
+            matching.len() >= 5,
+            "Most participants must be matched, got {}",
+            matching.len()
+        );
+
+        // A vertex prefers `candidate` over its partner if it's unmatched or the edge to
+        // `candidate` is lighter
+        let prefers = |vertex, candidate, partner: Option<&_>| {
+            partner.is_none_or(|&partner| {
+                graph.get_edge(vertex, candidate) < graph.get_edge(vertex, partner)
+            })
+        };
+
+        for &proposer in &proposers {
+            for &acceptor in &acceptors {
+                let is_acceptable = graph.get_edge(proposer, acceptor).is_some()
+                    && graph.get_edge(acceptor, proposer).is_some();
+
+                if matching.get(&proposer) == Some(&acceptor) {
+                    assert!(
+                        is_acceptable,
+                        "{proposer:?} and {acceptor:?} can't be matched"
+                    );
+                } else if is_acceptable {
+                    assert!(
+                        !(prefers(proposer, acceptor, matching.get(&proposer))
+                            && prefers(acceptor, proposer, partners.get(&acceptor))),
+                        "{proposer:?} and {acceptor:?} would rather be together"
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,280 @@
+use super::prim;
+use crate::collections::{BinaryHeap, HashMap, HashSet};
+use crate::graph::{
+    measure::{Measure, Ordered},
+    Direction, Graph, VertexIdx,
+};
+use alloc::{vec, vec::Vec};
+use core::{
+    cmp::{Ordering, Reverse},
+    hash::BuildHasher,
+};
+
+/// The lightest edge between each pair of vertices, along with its original direction.
+type Adjacency<E, S> = HashMap<VertexIdx, HashMap<VertexIdx, (E, (VertexIdx, VertexIdx)), S>, S>;
+
+/// Approximates a minimum Steiner tree of `graph`, that is a tree of minimum total weight that
+/// connects every vertex of `terminals`, using the metric closure algorithm of Kou, Markowsky and
+/// Berman in `O(|T|(|V| + |E|) log |V| + |T|²)`, where `|T|` is the number of terminals.
+///
+/// A minimum spanning tree of the shortest distances between the terminals is expanded into
+/// the shortest paths it consists of. The minimum spanning tree of those paths is then pruned from
+/// its leaves that aren't terminals. The tree weighs at most twice as much as the optimal one.
+///
+/// The graph is [interpreted as undirected](crate::graph::algo#undirected-interpretation).
+/// If both directions exist, only the lighter one is kept in the tree of a directed graph,
+/// while an undirected graph keeps both arcs of each tree edge. Self-loops are ignored.
+///
+/// Returns the tree as a subgraph that keeps the vertex IDs of `graph`, or `None` if a terminal
+/// doesn't exist or the terminals aren't connected.
+pub fn steiner_tree<'g, V, E: Measure, D: Direction, S: BuildHasher + Default>(
+    graph: &'g Graph<V, E, D, S>,
+    terminals: &[VertexIdx],
+) -> Option<Graph<&'g V, &'g E, D, S>> {
+    let mut terminals = terminals.to_vec();
+
+    terminals.sort_unstable();
+    terminals.dedup();
+
+    for &terminal_idx in &terminals {
+        graph.get_vertex(terminal_idx)?;
+    }
+
+    let mut adjacency: Adjacency<E, S> = HashMap::default();
+
+    for (&from_idx, vertex) in &graph.vertices {
+        for (to_idx, &edge) in vertex.neighbors() {
+            if from_idx == to_idx {
+                continue;
+            }
+
+            for (a, b) in [(from_idx, to_idx), (to_idx, from_idx)] {
+                adjacency
+                    .entry(a)
+                    .or_default()
+                    .entry(b)
+                    .and_modify(|lightest| {
+                        if edge.compare(&lightest.0) == Ordering::Less {
+                            *lightest = (edge, (from_idx, to_idx));
+                        }
+                    })
+                    .or_insert((edge, (from_idx, to_idx)));
+            }
+        }
+    }
+
+    let shortest_paths: Vec<_> = terminals
+        .iter()
+        .map(|&terminal_idx| shortest_paths(&adjacency, terminal_idx))
+        .collect();
+
+    // Prim's algorithm on the complete graph of the distances between the terminals
+    let mut in_tree = vec![false; terminals.len()];
+    let mut closest: Vec<Option<(E, usize)>> = vec![None; terminals.len()];
+    let mut path_edges: HashSet<_, S> = HashSet::default();
+
+    for _ in 0..terminals.len() {
+        let next = match (0..terminals.len())
+            .filter(|&i| !in_tree[i])
+            .min_by_key(|&i| closest[i].map(|(distance, _)| Ordered(distance)))
+        {
+            Some(next) => next,
+            None => break,
+        };
+
+        match closest[next] {
+            // Expand the edge of the closure into its shortest path
+            Some((_, parent)) => {
+                let (_, previous) = &shortest_paths[parent];
+                let mut vertex_idx = terminals[next];
+
+                while let Some(&previous_idx) = previous.get(&vertex_idx) {
+                    path_edges.insert(adjacency[&previous_idx][&vertex_idx].1);
+                    vertex_idx = previous_idx;
+                }
+            }
+            // Every terminal but the first must be reachable from the tree
+            None if in_tree.iter().any(|&b| b) => return None,
+            None => {}
+        }
+
+        in_tree[next] = true;
+
+        let (distances, _) = &shortest_paths[next];
+
+        for i in (0..terminals.len()).filter(|&i| !in_tree[i]) {
+            let Some(&distance) = distances.get(&terminals[i]) else {
+                continue;
+            };
+
+            if closest[i].is_none_or(|(closest_distance, _)| {
+                distance.compare(&closest_distance) == Ordering::Less
+            }) {
+                closest[i] = Some((distance, next));
+            }
+        }
+    }
+
+    let mut path_vertices: HashSet<VertexIdx, S> = terminals.iter().copied().collect();
+
+    path_vertices.extend(
+        path_edges
+            .iter()
+            .flat_map(|&(from_idx, to_idx)| [from_idx, to_idx]),
+    );
+
+    let paths = graph.filter_map(
+        |idx, _| path_vertices.contains(&idx).then_some(()),
+        |(from_idx, _), (to_idx, _), edge| {
+            path_edges.contains(&(from_idx, to_idx)).then_some(*edge)
+        },
+    );
+
+    let mut tree: HashMap<VertexIdx, HashSet<VertexIdx, S>, S> = path_vertices
+        .iter()
+        .map(|&idx| (idx, HashSet::default()))
+        .collect();
+
+    for (from_idx, to_idx, _) in prim(&paths) {
+        tree.entry(from_idx).or_default().insert(to_idx);
+        tree.entry(to_idx).or_default().insert(from_idx);
+    }
+
+    // Prune the leaves that aren't terminals, until every leaf is a terminal
+    let mut leaves: Vec<VertexIdx> = tree
+        .iter()
+        .filter(|(idx, neighbors)| neighbors.len() <= 1 && terminals.binary_search(idx).is_err())
+        .map(|(&idx, _)| idx)
+        .collect();
+
+    while let Some(leaf_idx) = leaves.pop() {
+        let Some(neighbors) = tree.remove(&leaf_idx) else {
+            continue;
+        };
+
+        for neighbor_idx in neighbors {
+            let neighbor = tree.get_mut(&neighbor_idx).expect("The tree is undirected");
+
+            neighbor.remove(&leaf_idx);
+
+            if neighbor.len() <= 1 && terminals.binary_search(&neighbor_idx).is_err() {
+                leaves.push(neighbor_idx);
+            }
+        }
+    }
+
+    Some(graph.filter_map(
+        |idx, vertex| tree.contains_key(&idx).then_some(vertex.weight()),
+        |(from_idx, _), (to_idx, _), edge| {
+            let is_tree_edge = tree[&from_idx].contains(&to_idx)
+                && (!D::IS_DIRECTED || adjacency[&from_idx][&to_idx].1 == (from_idx, to_idx));
+
+            is_tree_edge.then_some(edge)
+        },
+    ))
+}
+
+/// Dijkstra's algorithm from `source_idx`. Returns the distance to every reachable vertex
+/// and the previous vertex along its shortest path.
+fn shortest_paths<E, S>(
+    adjacency: &Adjacency<E, S>,
+    source_idx: VertexIdx,
+) -> (HashMap<VertexIdx, E, S>, HashMap<VertexIdx, VertexIdx, S>)
+where
+    E: Measure,
+    S: BuildHasher + Default,
+{
+    let mut distances: HashMap<_, _, S> = [(source_idx, E::zero())].into_iter().collect();
+    let mut previous = HashMap::default();
+    let mut visited: HashSet<_, S> = HashSet::default();
+    let mut heap = BinaryHeap::from([Reverse((Ordered(E::zero()), source_idx))]);
+
+    while let Some(Reverse((Ordered(distance), vertex_idx))) = heap.pop() {
+        if !visited.insert(vertex_idx) {
+            continue;
+        }
+
+        for (&neighbor_idx, &(edge, _)) in adjacency.get(&vertex_idx).into_iter().flatten() {
+            let new_distance = distance.add(edge);
+
+            if distances
+                .get(&neighbor_idx)
+                .is_none_or(|old_distance| new_distance.compare(old_distance) == Ordering::Less)
+            {
+                distances.insert(neighbor_idx, new_distance);
+                previous.insert(neighbor_idx, vertex_idx);
+                heap.push(Reverse((Ordered(new_distance), neighbor_idx)));
+            }
+        }
+    }
+
+    (distances, previous)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::steiner_tree;
+    use crate::graph::{Graph, Undirected};
+
+    #[test]
+    fn test_steiner_tree() {
+        // Three terminals t1, t2, t3 around a hub h, and a heavy direct edge t1 - t2.
+        // Going through the hub is cheaper than connecting the terminals directly.
+        //    t1 -5- t2
+        //     1\   /1
+        //        h
+        //        |1
+        //       t3 - x (2)
+        let mut graph = Graph::<(), u32>::new();
+        let [t1, t2, t3, h, x] = [(); 5].map(|_| graph.insert_vertex(()));
+
+        graph.insert_or_update_edges([(t1, t2, 5), (h, t1, 1), (t2, h, 1), (h, t3, 1), (t3, x, 2)]);
+
+        let tree = steiner_tree(&graph, &[t1, t2, t3]).expect("The terminals are connected");
+        let weight: u32 = tree
+            .vertices_iter()
+            .flat_map(|(_, vertex)| vertex.neighbors().map(|(_, &&edge)| edge))
+            .sum();
+
+        assert_eq!(
+            tree.len(),
+            4,
+            "The tree must consist of the terminals and the hub"
+        );
+        assert!(tree.get_vertex(x).is_none(), "Vertex x isn't needed");
+        assert_eq!(weight, 3, "Wrong tree weight");
+        drop(tree);
+
+        let halved = graph.map(|_, _| (), |_, _, &edge| f64::from(edge) / 2.0);
+        let tree = steiner_tree(&halved, &[t1, t2, t3]).expect("The terminals are connected");
+
+        assert_eq!(tree.len(), 4, "Float weights must find the same tree");
+        drop(tree);
+
+        let mut undirected = Graph::<(), u32, Undirected>::new();
+        let [t1, t2, t3, h, x] = [(); 5].map(|_| undirected.insert_vertex(()));
+
+        undirected.insert_or_update_edges([
+            (t1, t2, 5),
+            (h, t1, 1),
+            (t2, h, 1),
+            (h, t3, 1),
+            (t3, x, 2),
+        ]);
+
+        let tree = steiner_tree(&undirected, &[t1, t2, t3]).expect("The terminals are connected");
+
+        assert_eq!(tree.len(), 4, "Wrong number of vertices");
+        assert_eq!(tree.edge_count(), 6, "Each tree edge goes both ways");
+        assert_eq!(tree.get_edge(t1, h), Some(&&1), "Wrong edge weight");
+        assert_eq!(tree.get_edge(h, t1), Some(&&1), "Wrong edge weight");
+        drop(tree);
+
+        let lonely = graph.insert_vertex(());
+
+        assert!(
+            steiner_tree(&graph, &[t1, lonely]).is_none(),
+            "The terminals aren't connected"
+        );
+    }
+}
@@ -0,0 +1,200 @@
+use crate::collections::{DefaultHashBuilder, HashMap, HashSet, VecDeque};
+use crate::graph::{Directed, Graph, VertexIdx};
+use alloc::vec::Vec;
+use core::hash::BuildHasher;
+
+/// The graph contains a cycle. The vertex is guaranteed to be part of one.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Cycle(pub VertexIdx);
+
+impl Cycle {
+    pub fn vertex_idx(&self) -> VertexIdx {
+        self.0
+    }
+}
+
+/// Sorts the vertices of `graph` topologically using Kahn's algorithm in `O(|V| + |E|)`.
+///
+/// Returns [`Cycle`] if the graph isn't a DAG. See [`topo_iter`] to sort lazily.
+pub fn toposort<V, E, D, S: BuildHasher + Default>(
+    graph: &Graph<V, E, D, S>,
+) -> Result<Vec<VertexIdx>, Cycle> {
+    let mut order = Vec::with_capacity(graph.len());
+
+    for vertex_idx in topo_iter(graph) {
+        order.push(vertex_idx?);
+    }
+
+    Ok(order)
+}
+
+/// Lazily sorts the vertices of `graph` topologically using Kahn's algorithm, yielding each vertex
+/// once all of its predecessors have been yielded. Creating the iterator takes `O(|V| + |E|)`, and
+/// each call to `next` takes `O(deg)`.
+///
+/// If a cycle prevents the remaining vertices from being sorted, the last item is [`Cycle`].
+///
+/// ```
+/// # use badlgorithms::graph::{algo::topo_iter, Graph};
+/// let mut graph = Graph::<(), ()>::new();
+/// let [a, b, c] = [(); 3].map(|_| graph.insert_vertex(()));
+///
+/// graph.insert_or_update_edges([(a, b, ()), (b, c, ()), (c, b, ())]);
+///
+/// let mut order = topo_iter(&graph);
+///
+/// assert_eq!(order.next(), Some(Ok(a)));
+/// assert!(order.next().is_some_and(|item| item.is_err()));
+/// assert_eq!(order.next(), None);
+/// ```
+pub fn topo_iter<V, E, D, S: BuildHasher + Default>(
+    graph: &Graph<V, E, D, S>,
+) -> TopoIter<'_, V, E, D, S> {
+    let mut in_degrees: HashMap<VertexIdx, usize, S> =
+        graph.vertices.keys().map(|&idx| (idx, 0)).collect();
+
+    for vertex in graph.vertices.values() {
+        for (neighbor_idx, _) in vertex.neighbors() {
+            *in_degrees.entry(neighbor_idx).or_default() += 1;
+        }
+    }
+
+    let queue = in_degrees
+        .iter()
+        .filter(|(_, &in_degree)| in_degree == 0)
+        .map(|(&idx, _)| idx)
+        .collect();
+
+    TopoIter {
+        graph,
+        in_degrees,
+        queue,
+        yielded: 0,
+        finished: false,
+    }
+}
+
+/// A lazy topological sort, created with [`topo_iter`].
+pub struct TopoIter<'g, V, E, D = Directed, S = DefaultHashBuilder> {
+    graph: &'g Graph<V, E, D, S>,
+    /// The number of predecessors of each vertex that haven't been yielded yet
+    in_degrees: HashMap<VertexIdx, usize, S>,
+    queue: VecDeque<VertexIdx>,
+    yielded: usize,
+    finished: bool,
+}
+
+impl<V, E, D, S: BuildHasher + Default> TopoIter<'_, V, E, D, S> {
+    /// Find a vertex on a cycle among the vertices that were never yielded.
+    fn find_cycle(&self) -> Cycle {
+        // Every vertex that never reached in-degree 0 has a predecessor that didn't either,
+        // so walking backwards through them must eventually repeat a vertex, which is on a cycle.
+        let remaining = |idx: &VertexIdx| self.in_degrees[idx] > 0;
+        let mut predecessors: HashMap<VertexIdx, VertexIdx, S> = HashMap::default();
+
+        for (&from_idx, vertex) in &self.graph.vertices {
+            if !remaining(&from_idx) {
+                continue;
+            }
+
+            for (to_idx, _) in vertex.neighbors() {
+                if remaining(&to_idx) {
+                    predecessors.entry(to_idx).or_insert(from_idx);
+                }
+            }
+        }
+
+        let mut seen: HashSet<_, S> = HashSet::default();
+        let mut vertex_idx = *self
+            .in_degrees
+            .keys()
+            .find(|idx| remaining(idx))
+            .expect("At least one vertex remains");
+
+        while seen.insert(vertex_idx) {
+            vertex_idx = predecessors[&vertex_idx];
+        }
+
+        Cycle(vertex_idx)
+    }
+}
+
+impl<V, E, D, S: BuildHasher + Default> Iterator for TopoIter<'_, V, E, D, S> {
+    type Item = Result<VertexIdx, Cycle>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        let Some(vertex_idx) = self.queue.pop_front() else {
+            self.finished = true;
+
+            return (self.yielded < self.graph.len()).then(|| Err(self.find_cycle()));
+        };
+
+        for (neighbor_idx, _) in self.graph.vertices[&vertex_idx].neighbors() {
+            let in_degree = self
+                .in_degrees
+                .get_mut(&neighbor_idx)
+                .expect("Every vertex has an in-degree");
+
+            *in_degree -= 1;
+
+            if *in_degree == 0 {
+                self.queue.push_back(neighbor_idx);
+            }
+        }
+
+        self.yielded += 1;
+
+        Some(Ok(vertex_idx))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_topo_iter() {
+        //  a → b → c
+        //   \_____↗
+        let mut graph = Graph::<(), ()>::new();
+        let [a, b, c] = [(); 3].map(|_| graph.insert_vertex(()));
+
+        graph.insert_or_update_edges([(a, b, ()), (b, c, ()), (a, c, ())]);
+
+        assert_eq!(
+            topo_iter(&graph).collect::<Vec<_>>(),
+            [Ok(a), Ok(b), Ok(c)],
+            "Wrong topological order"
+        );
+        assert_eq!(
+            topo_iter(&graph).take(1).collect::<Vec<_>>(),
+            [Ok(a)],
+            "The iterator can stop early"
+        );
+
+        //  a → b ⇄ c → d
+        let d = graph.insert_vertex(());
+
+        graph.insert_or_update_edges([(c, b, ()), (c, d, ())]);
+
+        let mut order = topo_iter(&graph);
+
+        assert_eq!(order.next(), Some(Ok(a)), "a has no predecessors");
+        assert!(
+            order
+                .next()
+                .is_some_and(|item| item.is_err_and(|Cycle(idx)| idx == b || idx == c)),
+            "The cycle must be reported"
+        );
+        assert_eq!(order.next(), None, "Nothing follows the cycle");
+        assert_eq!(
+            toposort(&graph).map_err(|cycle| [b, c].contains(&cycle.vertex_idx())),
+            Err(true),
+            "The cycle must be reported"
+        );
+    }
+}
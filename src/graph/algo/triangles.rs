@@ -0,0 +1,197 @@
+use crate::collections::{DefaultHashBuilder, HashMap};
+use crate::graph::{Graph, VertexIdx};
+use alloc::{vec, vec::Vec};
+use core::{cmp::Ordering, hash::BuildHasher};
+
+pub struct Triangles<S = DefaultHashBuilder> {
+    counts: HashMap<VertexIdx, usize, S>,
+    local_clustering: HashMap<VertexIdx, f64, S>,
+    global_clustering: f64,
+    total: usize,
+}
+
+impl<S: BuildHasher> Triangles<S> {
+    /// The number of triangles the vertex `vertex_idx` is part of.
+    pub fn count(&self, vertex_idx: VertexIdx) -> Option<usize> {
+        self.counts.get(&vertex_idx).copied()
+    }
+
+    /// The number of distinct triangles in the graph.
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// The fraction of pairs of neighbors of the vertex `vertex_idx` that are adjacent to each other.
+    pub fn local_clustering(&self, vertex_idx: VertexIdx) -> Option<f64> {
+        self.local_clustering.get(&vertex_idx).copied()
+    }
+
+    /// The mean of the local clustering coefficients of all vertices.
+    pub fn average_clustering(&self) -> f64 {
+        if self.local_clustering.is_empty() {
+            0.0
+        } else {
+            self.local_clustering.values().sum::<f64>() / self.local_clustering.len() as f64
+        }
+    }
+
+    /// The transitivity of the graph: the fraction of connected triples that are closed into triangles.
+    pub fn global_clustering(&self) -> f64 {
+        self.global_clustering
+    }
+
+    pub fn counts(&self) -> &HashMap<VertexIdx, usize, S> {
+        &self.counts
+    }
+}
+
+/// Counts the triangles of `graph` and computes its clustering coefficients in `O(|E|^(3/2))`.
+///
+/// The graph is interpreted as undirected, ignoring self-loops and parallel edges.
+pub fn triangles<V, E, D, S: BuildHasher + Default>(graph: &Graph<V, E, D, S>) -> Triangles<S> {
+    let vertices: Vec<VertexIdx> = graph.vertices.keys().copied().collect();
+    let compact_idx: HashMap<VertexIdx, usize, S> = vertices
+        .iter()
+        .enumerate()
+        .map(|(i, &idx)| (idx, i))
+        .collect();
+
+    // Sorted, deduplicated undirected adjacency lists to intersect them by merging
+    let mut adjacency: Vec<Vec<usize>> = vec![Vec::new(); vertices.len()];
+
+    for (from_idx, vertex) in &graph.vertices {
+        let from = compact_idx[from_idx];
+
+        for (to_idx, _) in vertex.neighbors() {
+            let to = compact_idx[&to_idx];
+
+            if from != to {
+                adjacency[from].push(to);
+                adjacency[to].push(from);
+            }
+        }
+    }
+
+    for neighbors in &mut adjacency {
+        neighbors.sort_unstable();
+        neighbors.dedup();
+    }
+
+    // Orient each edge towards the vertex of higher degree, so no vertex has more than
+    // `O(√|E|)` edges out, which bounds the work of each intersection
+    let rank = |u: usize| (adjacency[u].len(), u);
+    let higher: Vec<Vec<usize>> = (0..vertices.len())
+        .map(|u| {
+            adjacency[u]
+                .iter()
+                .copied()
+                .filter(|&v| rank(v) > rank(u))
+                .collect()
+        })
+        .collect();
+
+    let mut counts = vec![0; vertices.len()];
+    let mut total = 0;
+
+    // Count each triangle exactly once, from its vertex of lowest rank
+    for u in 0..vertices.len() {
+        for &v in &higher[u] {
+            let (mut i, mut j) = (0, 0);
+            let (u_neighbors, v_neighbors) = (&higher[u], &higher[v]);
+
+            while i < u_neighbors.len() && j < v_neighbors.len() {
+                match u_neighbors[i].cmp(&v_neighbors[j]) {
+                    Ordering::Less => i += 1,
+                    Ordering::Greater => j += 1,
+                    Ordering::Equal => {
+                        let w = u_neighbors[i];
+
+                        counts[u] += 1;
+                        counts[v] += 1;
+                        counts[w] += 1;
+                        total += 1;
+                        i += 1;
+                        j += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    let mut triples = 0;
+    let mut local_clustering = HashMap::with_capacity_and_hasher(vertices.len(), S::default());
+
+    for (i, &vertex_idx) in vertices.iter().enumerate() {
+        let degree = adjacency[i].len();
+        let vertex_triples = degree * degree.saturating_sub(1) / 2;
+
+        triples += vertex_triples;
+        local_clustering.insert(
+            vertex_idx,
+            if vertex_triples == 0 {
+                0.0
+            } else {
+                counts[i] as f64 / vertex_triples as f64
+            },
+        );
+    }
+
+    Triangles {
+        counts: vertices.into_iter().zip(counts).collect(),
+        global_clustering: if triples == 0 {
+            0.0
+        } else {
+            3.0 * total as f64 / triples as f64
+        },
+        local_clustering,
+        total,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::triangles;
+    use crate::graph::Graph;
+
+    #[test]
+    fn test_triangles() {
+        // A square a-b-c-d with the diagonal a-c, forming two triangles
+        let mut graph = Graph::<(), ()>::new();
+        let [a, b, c, d] = [(); 4].map(|_| graph.insert_vertex(()));
+
+        graph.insert_or_update_edges([
+            (a, b, ()),
+            (b, c, ()),
+            (c, d, ()),
+            (d, a, ()),
+            (a, c, ()),
+            (c, a, ()),
+        ]);
+
+        let triangles = triangles(&graph);
+
+        assert_eq!(triangles.total(), 2, "Wrong number of triangles");
+        assert_eq!(triangles.count(a), Some(2));
+        assert_eq!(triangles.count(b), Some(1));
+        assert_eq!(triangles.local_clustering(a), Some(2.0 / 3.0));
+        assert_eq!(triangles.local_clustering(d), Some(1.0));
+        assert_eq!(triangles.global_clustering(), 6.0 / 8.0);
+
+        // A complete graph of 6 vertices, where every triple is a triangle
+        let mut complete = Graph::<(), ()>::new();
+        let vertices: Vec<_> = (0..6).map(|_| complete.insert_vertex(())).collect();
+
+        for (i, &from) in vertices.iter().enumerate() {
+            complete.insert_or_update_edges(vertices[i + 1..].iter().map(|&to| (from, to, ())));
+        }
+
+        let triangles = super::triangles(&complete);
+
+        assert_eq!(triangles.total(), 20, "Wrong number of triangles");
+        assert!(
+            vertices.iter().all(|&idx| triangles.count(idx) == Some(10)),
+            "Every vertex is part of 10 triangles"
+        );
+        assert_eq!(triangles.global_clustering(), 1.0);
+    }
+}
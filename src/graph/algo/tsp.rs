@@ -0,0 +1,220 @@
+use super::{matching::min_weight_perfect_matching, mst::prim};
+use crate::collections::{HashMap, HashSet};
+use crate::graph::{
+    measure::{Measure, Ordered},
+    Graph, VertexIdx,
+};
+use alloc::{vec, vec::Vec};
+use core::{
+    hash::BuildHasher,
+    ops::{Div, Sub},
+};
+
+/// Approximates the travelling salesman problem on the complete metric `graph` using
+/// Christofides' algorithm in `O(|V|³)`.
+///
+/// 1. Compute a minimum spanning tree (see [`prim`]).
+/// 2. Match the vertices with odd degree in the tree with minimum total weight, using the
+///    weighted blossom algorithm.
+/// 3. Find an Eulerian circuit of the tree combined with the matching.
+/// 4. Shortcut the circuit into a Hamiltonian cycle by skipping already visited vertices.
+///
+/// If the edge weights satisfy the triangle inequality, the tour is at most 1.5 times longer than
+/// the optimal one. The matching needs to subtract and halve weights, and its intermediate sums
+/// reach about `|V|` times the heaviest edge, like the tour itself.
+///
+/// The graph is [interpreted as undirected](crate::graph::algo#undirected-interpretation).
+/// Returns the order in which the vertices are visited (the tour returns to the first vertex)
+/// and the total weight of the tour, or `None` if the graph isn't complete.
+pub fn tsp_christofides<V, E, D, S: BuildHasher + Default>(
+    graph: &Graph<V, E, D, S>,
+) -> Option<(Vec<VertexIdx>, E)>
+where
+    E: Measure + Sub<Output = E> + Div<Output = E> + From<u8>,
+{
+    let weight = |a: VertexIdx, b: VertexIdx| -> Option<E> {
+        let forward = graph.vertices.get(&a)?.edges.get(&b);
+        let backward = graph.vertices.get(&b)?.edges.get(&a);
+
+        match (forward, backward) {
+            (Some(&forward), Some(&backward)) => Some(Ordered(forward).min(Ordered(backward)).0),
+            (forward, backward) => forward.or(backward).copied(),
+        }
+    };
+
+    let vertices: Vec<VertexIdx> = graph.vertices.keys().copied().collect();
+
+    for (i, &a) in vertices.iter().enumerate() {
+        for &b in &vertices[i + 1..] {
+            weight(a, b)?;
+        }
+    }
+
+    let Some(&start_idx) = vertices.first() else {
+        return Some((Vec::new(), E::zero()));
+    };
+
+    let tree = prim(graph);
+    let mut degrees: HashMap<VertexIdx, usize, S> = HashMap::default();
+
+    for &(from_idx, to_idx, _) in &tree {
+        *degrees.entry(from_idx).or_default() += 1;
+        *degrees.entry(to_idx).or_default() += 1;
+    }
+
+    let odd_vertices: Vec<VertexIdx> = degrees
+        .into_iter()
+        .filter(|(_, degree)| degree % 2 == 1)
+        .map(|(idx, _)| idx)
+        .collect();
+    let matching = min_weight_perfect_matching(odd_vertices.len(), |a, b| {
+        weight(odd_vertices[a], odd_vertices[b]).expect("The graph is complete")
+    })
+    .into_iter()
+    .map(|(a, b)| (odd_vertices[a], odd_vertices[b]));
+
+    // The union of the tree and the matching is a connected multigraph with even degrees
+    let multi_edges: Vec<(VertexIdx, VertexIdx)> = tree
+        .into_iter()
+        .map(|(from_idx, to_idx, _)| (from_idx, to_idx))
+        .chain(matching)
+        .collect();
+
+    let mut tour = Vec::with_capacity(vertices.len());
+    let mut visited: HashSet<_, S> =
+        HashSet::with_capacity_and_hasher(vertices.len(), S::default());
+
+    for vertex_idx in eulerian_circuit::<S>(start_idx, &multi_edges) {
+        if visited.insert(vertex_idx) {
+            tour.push(vertex_idx);
+        }
+    }
+
+    let mut total = E::zero();
+
+    if tour.len() > 1 {
+        for (i, &vertex_idx) in tour.iter().enumerate() {
+            let next_idx = tour[(i + 1) % tour.len()];
+
+            total = total.add(weight(vertex_idx, next_idx).expect("The graph is complete"));
+        }
+    }
+
+    Some((tour, total))
+}
+
+/// Finds an Eulerian circuit starting from `start_idx` in the connected multigraph `edges`,
+/// whose vertices all have even degree, using Hierholzer's algorithm.
+fn eulerian_circuit<S: BuildHasher + Default>(
+    start_idx: VertexIdx,
+    edges: &[(VertexIdx, VertexIdx)],
+) -> Vec<VertexIdx> {
+    let mut incident: HashMap<VertexIdx, Vec<usize>, S> = HashMap::default();
+
+    for (edge_id, &(a, b)) in edges.iter().enumerate() {
+        incident.entry(a).or_default().push(edge_id);
+        incident.entry(b).or_default().push(edge_id);
+    }
+
+    let mut used = vec![false; edges.len()];
+    let mut stack = vec![start_idx];
+    let mut circuit = Vec::with_capacity(edges.len() + 1);
+
+    while let Some(&vertex_idx) = stack.last() {
+        let unused_edge = incident.get_mut(&vertex_idx).and_then(|incident| {
+            while let Some(edge_id) = incident.pop() {
+                if !used[edge_id] {
+                    return Some(edge_id);
+                }
+            }
+
+            None
+        });
+
+        match unused_edge {
+            Some(edge_id) => {
+                let (a, b) = edges[edge_id];
+
+                used[edge_id] = true;
+                stack.push(if a == vertex_idx { b } else { a });
+            }
+            None => circuit.extend(stack.pop()),
+        }
+    }
+
+    circuit
+}
+
+#[cfg(test)]
+mod tests {
+    use super::tsp_christofides;
+    use crate::graph::Graph;
+
+    #[test]
+    fn test_tsp_christofides() {
+        // Points on a line at 0, 1, 3 and 6 with their distances as weights.
+        // The optimal tour goes to the end and back, with a length of 12.
+        let positions = [0u32, 1, 3, 6];
+        let mut graph = Graph::<u32, u32>::new();
+        let idx: Vec<_> = positions.map(|x| graph.insert_vertex(x)).to_vec();
+
+        graph.insert_or_update_edges((0..4).flat_map(|i| {
+            let idx = &idx;
+
+            (i + 1..4).map(move |j| (idx[i], idx[j], positions[j] - positions[i]))
+        }));
+
+        let (tour, total) = tsp_christofides(&graph).expect("The graph is complete");
+
+        assert_eq!(tour.len(), 4, "The tour must visit every vertex once");
+        assert!(total <= 18, "The tour is longer than 1.5 times the optimal");
+
+        let scaled = graph.map(|_, _| (), |_, _, &edge| f64::from(edge) / 4.0);
+        let (tour, total) = tsp_christofides(&scaled).expect("The graph is complete");
+
+        assert_eq!(tour.len(), 4, "Float weights must work too");
+        assert!(
+            total <= 4.5,
+            "The tour is longer than 1.5 times the optimal"
+        );
+
+        // The heavier direction of an edge must be ignored, as it is by the tree
+        graph.insert_or_update_edge(idx[3], idx[0], 100);
+
+        let (_, total) = tsp_christofides(&graph).expect("The graph is complete");
+
+        assert!(total <= 18, "The lighter direction must be used");
+
+        graph.remove_edge(idx[3], idx[0]);
+        graph.remove_edge(idx[0], idx[3]);
+
+        assert!(
+            tsp_christofides(&graph).is_none(),
+            "Incomplete graphs aren't supported"
+        );
+
+        // A hub at distance 1 of every leaf, whose leaves are at distance 2 of each other. The
+        // tree is the star, so every leaf has an odd degree, and every tour has a length of
+        // `leaves + 1`, however many leaves have to be matched.
+        for leaves in [3, 20, 41] {
+            let mut star = Graph::<(), u32>::new();
+            let hub = star.insert_vertex(());
+            let leaf_indices: Vec<_> = (0..leaves).map(|_| star.insert_vertex(())).collect();
+
+            star.insert_or_update_edges(leaf_indices.iter().map(|&leaf| (hub, leaf, 1)));
+
+            for (i, &a) in leaf_indices.iter().enumerate() {
+                star.insert_or_update_edges(leaf_indices[i + 1..].iter().map(|&b| (a, b, 2)));
+            }
+
+            let (tour, total) = tsp_christofides(&star).expect("The graph is complete");
+
+            assert_eq!(
+                tour.len(),
+                leaves + 1,
+                "The tour must visit every vertex once"
+            );
+            assert_eq!(total, leaves as u32 * 2, "Wrong tour weight");
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use crate::collections::{BinaryHeap, HashMap, HashSet};
+use crate::graph::{
+    measure::{Measure, Ordered},
+    Graph, VertexIdx,
+};
+use alloc::{vec, vec::Vec};
+use core::hash::BuildHasher;
+
+/// Finds the widest (maximum bottleneck) path from `src_idx` to `dst_idx` of `graph`, that is
+/// the path whose lightest edge is the heaviest, using a variant of Dijkstra's algorithm
+/// in `O(|E| log |V|)`. The edge weights are interpreted as capacities.
+///
+/// Returns the vertices of the path in order, along with its bottleneck, or `None` if either
+/// vertex doesn't exist, `dst_idx` isn't reachable from `src_idx` or they are the same vertex
+/// (an empty path has no bottleneck).
+pub fn widest_path<V, E: Measure, D, S: BuildHasher + Default>(
+    graph: &Graph<V, E, D, S>,
+    src_idx: VertexIdx,
+    dst_idx: VertexIdx,
+) -> Option<(Vec<VertexIdx>, E)> {
+    graph.get_vertex(dst_idx)?;
+
+    if src_idx == dst_idx {
+        return None;
+    }
+
+    // The widest bottleneck found so far to each vertex, along with the previous vertex
+    let mut widths: HashMap<VertexIdx, (Ordered<E>, VertexIdx), S> = HashMap::default();
+    let mut visited: HashSet<_, S> = [src_idx].into_iter().collect();
+    let mut heap: BinaryHeap<(Ordered<E>, VertexIdx)> = graph
+        .get_vertex(src_idx)?
+        .neighbors()
+        .filter(|(to_idx, _)| *to_idx != src_idx)
+        .map(|(to_idx, &edge)| (Ordered(edge), to_idx))
+        .collect();
+
+    for &(edge, to_idx) in &heap {
+        widths.insert(to_idx, (edge, src_idx));
+    }
+
+    while let Some((width, vertex_idx)) = heap.pop() {
+        if !visited.insert(vertex_idx) {
+            continue;
+        }
+
+        if vertex_idx == dst_idx {
+            break;
+        }
+
+        for (neighbor_idx, &edge) in graph.vertices[&vertex_idx].neighbors() {
+            let new_width = width.min(Ordered(edge));
+
+            if !visited.contains(&neighbor_idx)
+                && widths
+                    .get(&neighbor_idx)
+                    .is_none_or(|&(old_width, _)| new_width > old_width)
+            {
+                widths.insert(neighbor_idx, (new_width, vertex_idx));
+                heap.push((new_width, neighbor_idx));
+            }
+        }
+    }
+
+    if !visited.contains(&dst_idx) {
+        return None;
+    }
+
+    let (Ordered(bottleneck), _) = widths[&dst_idx];
+    let mut path = vec![dst_idx];
+    let mut vertex_idx = dst_idx;
+
+    while vertex_idx != src_idx {
+        (_, vertex_idx) = widths[&vertex_idx];
+        path.push(vertex_idx);
+    }
+
+    path.reverse();
+
+    Some((path, bottleneck))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::widest_path;
+    use crate::graph::Graph;
+
+    #[test]
+    fn test_widest_path() {
+        // The shortest route s → t is narrow, while the longer one through a and b is wider
+        //   s ---1--→ t
+        //   5↓       ↑4
+        //   a --6--→ b
+        let mut graph = Graph::<(), u32>::new();
+        let [s, a, b, t] = [(); 4].map(|_| graph.insert_vertex(()));
+
+        graph.insert_or_update_edges([(s, t, 1), (s, a, 5), (a, b, 6), (b, t, 4)]);
+
+        assert_eq!(
+            widest_path(&graph, s, t),
+            Some((vec![s, a, b, t], 4)),
+            "Wrong widest path"
+        );
+        assert_eq!(widest_path(&graph, t, s), None, "s isn't reachable from t");
+        assert_eq!(
+            widest_path(&graph, s, s),
+            None,
+            "An empty path has no bottleneck"
+        );
+
+        let halved = graph.map(|_, _| (), |_, _, &edge| f64::from(edge) / 2.0);
+
+        assert_eq!(
+            widest_path(&halved, s, t),
+            Some((vec![s, a, b, t], 2.0)),
+            "Float weights must work too"
+        );
+    }
+}
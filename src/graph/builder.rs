@@ -0,0 +1,143 @@
+use super::{algo::Edge, Directed, Graph, VertexIdx};
+use crate::collections::HashMap;
+use alloc::vec::Vec;
+use core::hash::Hash;
+
+/// A builder of [`Graph`]s whose vertices are referenced by a key `K` (a name, an ID from a
+/// database, etc.) instead of their [`VertexIdx`].
+///
+/// ```
+/// # use badlgorithms::graph::{builder::GraphBuilder, Graph};
+/// #
+/// let (graph, labels): (Graph<u8, f64>, _) = GraphBuilder::new()
+///     .vertex("a", 1)
+///     .edge("a", "b", 3.0)
+///     .edge("b", "c", 4.0)
+///     .build();
+///
+/// assert_eq!(graph.get_vertex(labels["a"]).map(|vertex| *vertex.weight()), Some(1));
+/// assert_eq!(graph.get_vertex(labels["c"]).map(|vertex| *vertex.weight()), Some(0));
+/// ```
+#[derive(Clone, Debug)]
+pub struct GraphBuilder<K, V, E, D = Directed> {
+    graph: Graph<V, E, D>,
+    labels: HashMap<K, VertexIdx>,
+    edges: Vec<Edge<E>>,
+}
+
+impl<K, V, E, D> Default for GraphBuilder<K, V, E, D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, E, D> GraphBuilder<K, V, E, D> {
+    pub fn new() -> Self {
+        Self {
+            graph: Graph::new(),
+            labels: HashMap::new(),
+            edges: Vec::new(),
+        }
+    }
+}
+
+impl<K: Hash + Eq, V, E, D> GraphBuilder<K, V, E, D> {
+    /// Insert the vertex `key` with weight `weight`, or update its weight if it already exists.
+    pub fn vertex(mut self, key: K, weight: V) -> Self {
+        match self.labels.get(&key) {
+            Some(&vertex_idx) => {
+                if let Some(vertex) = self.graph.get_mut_vertex(vertex_idx) {
+                    vertex.weight = weight;
+                }
+            }
+            None => {
+                let vertex_idx = self.graph.insert_vertex(weight);
+
+                self.labels.insert(key, vertex_idx);
+            }
+        }
+
+        self
+    }
+
+    /// Insert or update the edge from the vertex `from` to the vertex `to`. Missing vertices are
+    /// inserted with the default weight.
+    pub fn edge(mut self, from: K, to: K, weight: E) -> Self
+    where
+        V: Default,
+    {
+        let from_idx = self.vertex_idx(from);
+        let to_idx = self.vertex_idx(to);
+
+        self.edges.push((from_idx, to_idx, weight));
+        self
+    }
+
+    /// Like [`Self::edge`], for multiple edges of type `(from, to, weight)`.
+    pub fn edges(self, edges: impl IntoIterator<Item = (K, K, E)>) -> Self
+    where
+        V: Default,
+    {
+        edges.into_iter().fold(self, |builder, (from, to, weight)| {
+            builder.edge(from, to, weight)
+        })
+    }
+
+    /// Build the graph, inserting the edges in the order they were given.
+    ///
+    /// Returns the graph and the ID of the vertex of each key.
+    pub fn build(self) -> (Graph<V, E, D>, HashMap<K, VertexIdx>)
+    where
+        Graph<V, E, D>: Extend<Edge<E>>,
+    {
+        let Self {
+            mut graph,
+            labels,
+            edges,
+        } = self;
+
+        graph.extend(edges);
+        (graph, labels)
+    }
+
+    fn vertex_idx(&mut self, key: K) -> VertexIdx
+    where
+        V: Default,
+    {
+        *self
+            .labels
+            .entry(key)
+            .or_insert_with(|| self.graph.insert_vertex(V::default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Undirected;
+
+    #[test]
+    fn test_graph_builder() {
+        let (graph, labels) = GraphBuilder::<_, u8, u8, Undirected>::new()
+            .edge("a", "b", 1)
+            .edges([("b", "c", 2), ("c", "a", 3)])
+            .vertex("b", 5)
+            .vertex("d", 6)
+            .build();
+
+        assert_eq!(labels.len(), 4, "Every key has a vertex");
+        assert_eq!(
+            graph.get_vertex(labels["b"]).map(|vertex| *vertex.weight()),
+            Some(5),
+            "Wrong weight for b"
+        );
+        assert_eq!(
+            graph
+                .get_vertex(labels["c"])
+                .and_then(|vertex| vertex.edges.get(&labels["b"])),
+            Some(&2),
+            "Undirected edges go both ways"
+        );
+        assert_eq!(graph.out_degree(labels["d"]), Some(0), "d has no edges");
+    }
+}
@@ -0,0 +1,346 @@
+use super::{small_map::SmallMap, Directed, Graph, RemovedVertex, Vertex, VertexIdx};
+use crate::collections::{DefaultHashBuilder, HashMap};
+use core::hash::BuildHasher;
+use std::{
+    num::NonZero,
+    sync::{RwLock, RwLockReadGuard, RwLockWriteGuard},
+    thread,
+};
+
+type Shard<V, E, S> = HashMap<VertexIdx, Vertex<V, E, S>, S>;
+
+/// A directed graph that can be read and mutated from multiple threads at once, through `&self`.
+///
+/// The vertices are spread across shards, each behind its own [`RwLock`], so operations on
+/// vertices of different shards don't block each other. An operation on an edge locks the shards
+/// of both of its vertices, and [`Self::remove_vertex`] locks every shard.
+///
+/// Use [`Self::into_graph`] to get a [`Graph`] once every thread is done.
+///
+/// Like in [`Graph`], every map hashes with `S` and only [`ConcurrentGraph::new`] is limited to
+/// the default hasher.
+#[derive(Debug)]
+pub struct ConcurrentGraph<V, E, S = DefaultHashBuilder> {
+    shards: Box<[RwLock<Shard<V, E, S>>]>,
+}
+
+/// Creates an empty graph with 4 shards per available thread.
+impl<V, E, S: Default> Default for ConcurrentGraph<V, E, S> {
+    fn default() -> Self {
+        let threads = thread::available_parallelism().map_or(1, NonZero::get);
+
+        Self::with_shards(threads * 4)
+    }
+}
+
+impl<V, E> ConcurrentGraph<V, E> {
+    /// Create an empty graph with 4 shards per available thread.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<V, E, S: Default> ConcurrentGraph<V, E, S> {
+    /// Create an empty graph with `shards` shards, or one if `shards` is zero.
+    pub fn with_shards(shards: usize) -> Self {
+        Self {
+            shards: (0..shards.max(1))
+                .map(|_| RwLock::new(HashMap::default()))
+                .collect(),
+        }
+    }
+}
+
+impl<V, E, S: BuildHasher + Default> ConcurrentGraph<V, E, S> {
+    /// The number of vertices, in `O(shards)`.
+    pub fn len(&self) -> usize {
+        (0..self.shards.len()).map(|i| self.read(i).len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Insert a vertex with weight `V` in `O(1)`. Returns its ID.
+    pub fn insert_vertex(&self, weight: V) -> VertexIdx {
+        let idx = VertexIdx::new();
+
+        self.write(self.shard_of(idx)).insert(
+            idx,
+            Vertex {
+                edges: SmallMap::default(),
+                predecessors: SmallMap::default(),
+                weight,
+            },
+        );
+
+        idx
+    }
+
+    /// Call `f` with a vertex, while its shard is locked for reading.
+    ///
+    /// Returns the result of `f`, if the vertex exists.
+    pub fn with_vertex<R>(
+        &self,
+        vertex_idx: VertexIdx,
+        f: impl FnOnce(&Vertex<V, E, S>) -> R,
+    ) -> Option<R> {
+        Some(f(self.read(self.shard_of(vertex_idx)).get(&vertex_idx)?))
+    }
+
+    /// Call `f` with the weight of a vertex, while its shard is locked for writing.
+    ///
+    /// Returns the result of `f`, if the vertex exists.
+    pub fn update_vertex<R>(
+        &self,
+        vertex_idx: VertexIdx,
+        f: impl FnOnce(&mut V) -> R,
+    ) -> Option<R> {
+        let mut shard = self.write(self.shard_of(vertex_idx));
+
+        Some(f(&mut shard.get_mut(&vertex_idx)?.weight))
+    }
+
+    /// Retrieve a copy of the weight of the edge from `from` to `to` in `O(1)`.
+    pub fn get_edge(&self, from: VertexIdx, to: VertexIdx) -> Option<E>
+    where
+        E: Clone,
+    {
+        self.read(self.shard_of(from))
+            .get(&from)?
+            .edges
+            .get(&to)
+            .cloned()
+    }
+
+    /// Insert or update an edge from a vertex `from` to a vertex `to` with weight `weight` in `O(1)`.
+    ///
+    /// Returns itself to allow for chaining, if the vertices specified exist in this graph.
+    pub fn insert_or_update_edge(
+        &self,
+        from: VertexIdx,
+        to: VertexIdx,
+        weight: E,
+    ) -> Option<&Self> {
+        self.with_arc(from, to, |from_vertex, to_vertex| {
+            from_vertex.edges.insert(to, weight);
+            to_vertex
+                .unwrap_or(from_vertex)
+                .predecessors
+                .insert(from, ());
+        })?;
+
+        Some(self)
+    }
+
+    /// Remove an edge from a vertex `from` to a vertex `to` in `O(1)`.
+    ///
+    /// Returns that edge's weight, if it exists.
+    pub fn remove_edge(&self, from: VertexIdx, to: VertexIdx) -> Option<E> {
+        self.with_arc(from, to, |from_vertex, to_vertex| {
+            let weight = from_vertex.edges.remove(&to)?;
+
+            to_vertex.unwrap_or(from_vertex).predecessors.remove(&from);
+            Some(weight)
+        })?
+    }
+
+    /// Remove a vertex and its edges by its ID in `O(shards + deg)`, like
+    /// [`Graph::remove_vertex`]. Every shard is locked for writing meanwhile.
+    pub fn remove_vertex(&self, vertex_idx: VertexIdx) -> Option<RemovedVertex<V, E, S>> {
+        let mut shards: Vec<_> = (0..self.shards.len()).map(|i| self.write(i)).collect();
+        let removed_vertex = shards[self.shard_of(vertex_idx)].remove(&vertex_idx)?;
+        let mut incoming_edges = Vec::with_capacity(removed_vertex.predecessors.len());
+
+        for &from_idx in removed_vertex.predecessors.keys() {
+            if let Some(from_vertex) = shards[self.shard_of(from_idx)].get_mut(&from_idx) {
+                let weight = from_vertex
+                    .edges
+                    .remove(&vertex_idx)
+                    .expect("Predecessors have an edge to the vertex");
+
+                incoming_edges.push((from_idx, weight));
+            }
+        }
+
+        for &to_idx in removed_vertex.edges.keys() {
+            if let Some(to_vertex) = shards[self.shard_of(to_idx)].get_mut(&to_idx) {
+                to_vertex.predecessors.remove(&vertex_idx);
+            }
+        }
+
+        Some((removed_vertex, incoming_edges))
+    }
+
+    /// Convert to a [`Graph`] with the same vertex IDs, in `O(|V| + |E|)`.
+    pub fn into_graph(self) -> Graph<V, E, Directed, S> {
+        let vertices = self
+            .shards
+            .into_vec()
+            .into_iter()
+            .flat_map(|shard| {
+                shard
+                    .into_inner()
+                    .expect("No thread panicked while holding the lock")
+            })
+            .collect();
+
+        Graph::from_parts(vertices).expect("Edges point to existing vertices")
+    }
+
+    /// Call `f` with the vertices `from` and `to`, while their shards are locked for writing.
+    /// `to` is `None` if it's the same vertex as `from`.
+    ///
+    /// Returns the result of `f`, if both vertices exist.
+    fn with_arc<R>(
+        &self,
+        from: VertexIdx,
+        to: VertexIdx,
+        f: impl FnOnce(&mut Vertex<V, E, S>, Option<&mut Vertex<V, E, S>>) -> R,
+    ) -> Option<R> {
+        let (from_shard_idx, to_shard_idx) = (self.shard_of(from), self.shard_of(to));
+
+        if from_shard_idx == to_shard_idx {
+            let mut shard = self.write(from_shard_idx);
+
+            if from == to {
+                return Some(f(shard.get_mut(&from)?, None));
+            }
+
+            let [Some(from_vertex), Some(to_vertex)] = shard.get_disjoint_mut([&from, &to]) else {
+                return None;
+            };
+
+            return Some(f(from_vertex, Some(to_vertex)));
+        }
+
+        // Shards are always locked in the same order to avoid deadlocks
+        let (mut from_shard, mut to_shard) = if from_shard_idx < to_shard_idx {
+            let from_shard = self.write(from_shard_idx);
+
+            (from_shard, self.write(to_shard_idx))
+        } else {
+            let to_shard = self.write(to_shard_idx);
+
+            (self.write(from_shard_idx), to_shard)
+        };
+
+        Some(f(from_shard.get_mut(&from)?, Some(to_shard.get_mut(&to)?)))
+    }
+
+    fn shard_of(&self, vertex_idx: VertexIdx) -> usize {
+        vertex_idx.0 % self.shards.len()
+    }
+
+    fn read(&self, shard_idx: usize) -> RwLockReadGuard<'_, Shard<V, E, S>> {
+        self.shards[shard_idx]
+            .read()
+            .expect("No thread panicked while holding the lock")
+    }
+
+    fn write(&self, shard_idx: usize) -> RwLockWriteGuard<'_, Shard<V, E, S>> {
+        self.shards[shard_idx]
+            .write()
+            .expect("No thread panicked while holding the lock")
+    }
+}
+
+impl<V, E, S: BuildHasher + Default> From<Graph<V, E, Directed, S>> for ConcurrentGraph<V, E, S> {
+    fn from(graph: Graph<V, E, Directed, S>) -> Self {
+        let concurrent = Self::default();
+
+        // The concurrent graph always tracks the edges to each vertex
+        for (vertex_idx, vertex) in graph.with_reverse_index().into_parts() {
+            concurrent
+                .write(concurrent.shard_of(vertex_idx))
+                .insert(vertex_idx, vertex);
+        }
+
+        concurrent
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collections::FastHashBuilder;
+
+    #[test]
+    fn test_send_sync() {
+        fn is_send<S: Send>() {}
+        fn is_sync<S: Sync>() {}
+
+        is_send::<ConcurrentGraph<String, String>>();
+        is_sync::<ConcurrentGraph<String, String>>();
+    }
+
+    #[test]
+    fn test_concurrent_graph() {
+        let graph = ConcurrentGraph::<usize, usize>::with_shards(3);
+        let hub = graph.insert_vertex(0);
+
+        // Every thread inserts a path of vertices, each with an edge to and from the hub
+        thread::scope(|scope| {
+            for _ in 0..4 {
+                scope.spawn(|| {
+                    let mut previous_idx = hub;
+
+                    for i in 1..=25 {
+                        let vertex_idx = graph.insert_vertex(i);
+
+                        graph
+                            .insert_or_update_edge(previous_idx, vertex_idx, i)
+                            .and_then(|graph| graph.insert_or_update_edge(vertex_idx, hub, i))
+                            .expect("The vertices exist");
+                        graph.update_vertex(hub, |count| *count += 1);
+                        previous_idx = vertex_idx;
+                    }
+                });
+            }
+        });
+
+        assert_eq!(graph.len(), 101, "Wrong number of vertices");
+        assert_eq!(
+            graph.with_vertex(hub, |vertex| (
+                *vertex.weight(),
+                vertex.degree(),
+                vertex.in_degree()
+            )),
+            Some((100, 4, 100)),
+            "Wrong weight or degrees of the hub"
+        );
+
+        assert_eq!(
+            graph.with_vertex(hub, |vertex| vertex.predecessors().count()),
+            Some(100),
+            "Wrong predecessors of the hub"
+        );
+
+        let (removed, incoming_edges) = graph.remove_vertex(hub).expect("The hub exists");
+
+        assert_eq!(removed.degree(), 4, "Wrong edges from the hub");
+        assert_eq!(incoming_edges.len(), 100, "Wrong edges to the hub");
+
+        let graph = graph.into_graph();
+
+        assert_eq!(graph.len(), 100, "Wrong number of vertices");
+        assert_eq!(graph.edge_count(), 96, "Wrong number of edges");
+    }
+
+    #[test]
+    fn test_concurrent_graph_hasher() {
+        let mut graph = Graph::<(), u8, Directed, FastHashBuilder>::default();
+        let [a, b] = [(); 2].map(|_| graph.insert_vertex(()));
+
+        graph.insert_or_update_edge(a, b, 1);
+
+        let concurrent = ConcurrentGraph::from(graph.clone());
+
+        assert_eq!(concurrent.get_edge(a, b), Some(1), "Wrong weight for a → b");
+        assert_eq!(
+            concurrent.into_graph(),
+            graph,
+            "Converting back must give the same graph"
+        );
+    }
+}
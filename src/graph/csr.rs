@@ -0,0 +1,246 @@
+use super::{small_map::SmallMap, view::GraphView, Directed, Graph, Vertex, VertexIdx};
+use crate::collections::{DefaultHashBuilder, HashMap};
+use alloc::vec::Vec;
+use core::{hash::BuildHasher, marker::PhantomData, slice};
+
+/// An immutable graph in compressed sparse row form, created by [`Graph::freeze`].
+///
+/// The edges of each vertex are stored next to each other, sorted by the ID of their target,
+/// and all of them in a single array. Iterating over them only walks through contiguous memory,
+/// which makes traversals much faster than on a [`Graph`]. Algorithms that accept a [`GraphView`]
+/// run on both.
+///
+/// It hashes with the same `S` as the [`Graph`] it was created from.
+#[derive(Clone, Debug)]
+pub struct CsrGraph<V, E, D = Directed, S = DefaultHashBuilder> {
+    /// The ID and weight of each vertex by compact index, in increasing order of ID
+    ids: Vec<VertexIdx>,
+    weights: Vec<V>,
+    compact_idx: HashMap<VertexIdx, usize, S>,
+    /// The edges of compact index `i` are at `offsets[i]..offsets[i + 1]` of `targets` and `edges`
+    offsets: Vec<usize>,
+    targets: Vec<usize>,
+    edges: Vec<E>,
+    direction: PhantomData<fn() -> D>,
+}
+
+impl<V, E, D, S: BuildHasher + Default> CsrGraph<V, E, D, S> {
+    /// Retrieve an immutable reference to the weight of a vertex by its ID in `O(1)`.
+    pub fn get_vertex(&self, vertex_idx: VertexIdx) -> Option<&V> {
+        self.weights.get(*self.compact_idx.get(&vertex_idx)?)
+    }
+
+    /// Iterate over the edges from a vertex as `(to, weight)` in increasing order of `to`,
+    /// if it exists.
+    pub fn neighbors(&self, vertex_idx: VertexIdx) -> Option<CsrNeighbors<'_, E>> {
+        let range = self.edge_range(*self.compact_idx.get(&vertex_idx)?);
+
+        Some(CsrNeighbors {
+            ids: &self.ids,
+            targets: self.targets[range.clone()].iter(),
+            edges: self.edges[range].iter(),
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Iterate over the ID and weight of every vertex, in increasing order of ID.
+    pub fn vertices_iter(&self) -> impl ExactSizeIterator<Item = (VertexIdx, &V)> {
+        self.ids.iter().copied().zip(&self.weights)
+    }
+
+    /// Convert back to a mutable [`Graph`] in `O(|V| + |E|)`, keeping the vertex IDs.
+    pub fn thaw(self) -> Graph<V, E, D, S> {
+        let mut graph = Graph::default();
+
+        graph.reserve(self.len());
+
+        let mut targets = self.targets.into_iter();
+        let mut edges = self.edges.into_iter();
+
+        for (i, (vertex_idx, weight)) in self.ids.iter().zip(self.weights).enumerate() {
+            let degree = self.offsets[i + 1] - self.offsets[i];
+            let vertex_edges = targets
+                .by_ref()
+                .take(degree)
+                .map(|to| self.ids[to])
+                .zip(edges.by_ref().take(degree))
+                .collect();
+
+            graph.vertices.insert(
+                *vertex_idx,
+                Vertex {
+                    weight,
+                    edges: vertex_edges,
+                    predecessors: SmallMap::default(),
+                },
+            );
+        }
+
+        graph.rebuild_indices();
+        graph
+    }
+
+    fn edge_range(&self, compact_idx: usize) -> core::ops::Range<usize> {
+        self.offsets[compact_idx]..self.offsets[compact_idx + 1]
+    }
+}
+
+/// Converts in `O(|V| log |V| + |E| log |E|)`, keeping the vertex IDs.
+impl<V, E, D, S: BuildHasher + Default> From<Graph<V, E, D, S>> for CsrGraph<V, E, D, S> {
+    fn from(graph: Graph<V, E, D, S>) -> Self {
+        let mut vertices: Vec<_> = graph.vertices.into_iter().collect();
+
+        vertices.sort_unstable_by_key(|&(vertex_idx, _)| vertex_idx);
+
+        let ids: Vec<_> = vertices.iter().map(|&(vertex_idx, _)| vertex_idx).collect();
+        let compact_idx: HashMap<_, _, S> =
+            ids.iter().enumerate().map(|(i, &idx)| (idx, i)).collect();
+        let mut offsets = Vec::with_capacity(ids.len() + 1);
+        let mut targets = Vec::new();
+        let mut edges = Vec::new();
+        let mut weights = Vec::with_capacity(ids.len());
+
+        offsets.push(0);
+
+        for (_, vertex) in vertices {
+            let mut vertex_edges: Vec<_> = vertex
+                .edges
+                .into_iter()
+                .map(|(to_idx, weight)| (compact_idx[&to_idx], weight))
+                .collect();
+
+            vertex_edges.sort_unstable_by_key(|&(to, _)| to);
+
+            for (to, weight) in vertex_edges {
+                targets.push(to);
+                edges.push(weight);
+            }
+
+            offsets.push(targets.len());
+            weights.push(vertex.weight);
+        }
+
+        Self {
+            ids,
+            weights,
+            compact_idx,
+            offsets,
+            targets,
+            edges,
+            direction: PhantomData,
+        }
+    }
+}
+
+impl<V, E, D, S: BuildHasher + Default> GraphView for CsrGraph<V, E, D, S> {
+    type Vertex = V;
+    type Edge = E;
+    type EdgeWeight<'a>
+        = &'a E
+    where
+        Self: 'a;
+    type Neighbors<'a>
+        = CsrNeighbors<'a, E>
+    where
+        Self: 'a;
+
+    fn vertex_count(&self) -> usize {
+        self.len()
+    }
+
+    fn contains_vertex(&self, vertex_idx: VertexIdx) -> bool {
+        self.compact_idx.contains_key(&vertex_idx)
+    }
+
+    fn vertex_weight(&self, vertex_idx: VertexIdx) -> Option<&V> {
+        self.get_vertex(vertex_idx)
+    }
+
+    fn neighbors(&self, vertex_idx: VertexIdx) -> Option<CsrNeighbors<'_, E>> {
+        CsrGraph::neighbors(self, vertex_idx)
+    }
+}
+
+pub struct CsrNeighbors<'a, E> {
+    ids: &'a [VertexIdx],
+    targets: slice::Iter<'a, usize>,
+    edges: slice::Iter<'a, E>,
+}
+
+impl<'a, E> Iterator for CsrNeighbors<'a, E> {
+    type Item = (VertexIdx, &'a E);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some((self.ids[*self.targets.next()?], self.edges.next()?))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.targets.size_hint()
+    }
+}
+
+impl<'a, E> ExactSizeIterator for CsrNeighbors<'a, E> {
+    fn len(&self) -> usize {
+        self.targets.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::collections::FastHashBuilder;
+    use crate::graph::{Directed, Graph, GraphView};
+
+    #[test]
+    fn test_freeze() {
+        //  a → b ↰
+        //  ↑ ↙ ⤷-⤴
+        //  c   d
+        let mut graph = Graph::<u8, u8>::new();
+        let [a, b, c, d] = [1, 2, 3, 4].map(|weight| graph.insert_vertex(weight));
+
+        graph.insert_or_update_edges([(a, b, 1), (b, c, 3), (b, b, 2), (c, a, 4)]);
+
+        let csr = graph.clone().freeze();
+
+        assert_eq!(csr.len(), 4, "Wrong number of vertices");
+        assert_eq!(csr.edge_count(), 4, "Wrong number of edges");
+        assert_eq!(csr.get_vertex(d), Some(&4), "Wrong weight for vertex d");
+        assert_eq!(
+            csr.neighbors(b).map(Iterator::collect::<Vec<_>>),
+            Some(vec![(b, &2), (c, &3)]),
+            "The neighbors must be sorted by ID"
+        );
+        assert_eq!(
+            csr.neighbors(d).map(|n| n.len()),
+            Some(0),
+            "Vertex d has no edges"
+        );
+
+        let e = Graph::<(), ()>::new().insert_vertex(());
+
+        assert!(!csr.contains_vertex(e), "Vertex e is in another graph");
+        assert_eq!(csr.thaw(), graph, "Thawing must give back the same graph");
+
+        let mut fast = Graph::<(), u8, Directed, FastHashBuilder>::default();
+        let [x, y] = [(); 2].map(|_| fast.insert_vertex(()));
+
+        fast.insert_or_update_edge(x, y, 1);
+
+        assert_eq!(
+            fast.clone().freeze().thaw(),
+            fast,
+            "The hasher must be kept"
+        );
+    }
+}
@@ -0,0 +1,544 @@
+use super::{
+    small_map::SmallMap, view::GraphView, Directed, Direction, Graph, Undirected, Vertex, VertexIdx,
+};
+use crate::collections::{DefaultHashBuilder, HashMap};
+use alloc::vec::Vec;
+use core::{hash::BuildHasher, iter, marker::PhantomData, slice};
+
+/// A graph that stores its edges in an adjacency matrix instead of a map per vertex.
+///
+/// Looking up, inserting and removing an edge is `O(1)` without hashing, while iterating over
+/// the neighbors of a vertex is `O(|V|)` and the whole graph takes `O(|V|²)` memory.
+/// This suits dense graphs, where `|E|` is close to `|V|²`.
+///
+/// Vertices have the same kind of IDs as in [`Graph`], so they're kept when converting between the two.
+/// Algorithms that accept a [`GraphView`] run on both.
+#[derive(Clone, Debug)]
+pub struct DenseGraph<V, E, D = Directed, S = DefaultHashBuilder> {
+    /// The ID and weight of each vertex, by compact index
+    ids: Vec<VertexIdx>,
+    weights: Vec<V>,
+    compact_idx: HashMap<VertexIdx, usize, S>,
+    /// The edge from compact index `i` to `j` is at `i * stride + j`
+    matrix: Vec<Option<E>>,
+    /// The number of vertices that fit in `matrix`
+    stride: usize,
+    /// The number of edges, counted like in [`Graph::edge_count`]
+    edge_count: usize,
+    direction: PhantomData<fn() -> D>,
+}
+
+impl<V, E, D, S: Default> Default for DenseGraph<V, E, D, S> {
+    fn default() -> Self {
+        Self::empty(0)
+    }
+}
+
+impl<V, E, D> DenseGraph<V, E, D> {
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    /// Create an empty graph with room for `vertices` vertices, which takes `O(vertices²)` memory.
+    pub fn with_capacity(vertices: usize) -> Self {
+        Self::empty(vertices)
+    }
+}
+
+impl<V, E, D, S: Default> DenseGraph<V, E, D, S> {
+    fn empty(vertices: usize) -> Self {
+        Self {
+            ids: Vec::with_capacity(vertices),
+            weights: Vec::with_capacity(vertices),
+            compact_idx: HashMap::with_capacity_and_hasher(vertices, S::default()),
+            matrix: iter::repeat_with(|| None)
+                .take(vertices * vertices)
+                .collect(),
+            stride: vertices,
+            edge_count: 0,
+            direction: PhantomData,
+        }
+    }
+}
+
+impl<V, E, D, S: BuildHasher + Default> DenseGraph<V, E, D, S> {
+    /// Retrieve an immutable reference to the weight of a vertex by its ID in `O(1)`.
+    pub fn get_vertex(&self, vertex_idx: VertexIdx) -> Option<&V> {
+        self.weights.get(*self.compact_idx.get(&vertex_idx)?)
+    }
+
+    /// Retrieve a mutable reference to the weight of a vertex by its ID in `O(1)`.
+    pub fn get_mut_vertex(&mut self, vertex_idx: VertexIdx) -> Option<&mut V> {
+        self.weights.get_mut(*self.compact_idx.get(&vertex_idx)?)
+    }
+
+    /// Insert a vertex with weight `V` in amortized `O(|V|)`, since the matrix may have to grow.
+    /// Returns its ID.
+    pub fn insert_vertex(&mut self, weight: V) -> VertexIdx {
+        if self.len() == self.stride {
+            self.grow();
+        }
+
+        let idx = VertexIdx::new();
+
+        self.compact_idx.insert(idx, self.ids.len());
+        self.ids.push(idx);
+        self.weights.push(weight);
+        idx
+    }
+
+    /// Remove a vertex by its ID in `O(|V|)`. Returns its weight, if it exists.
+    ///
+    /// The last vertex takes the place of the removed one in the matrix.
+    pub fn remove_vertex(&mut self, vertex_idx: VertexIdx) -> Option<V> {
+        let removed = self.compact_idx.remove(&vertex_idx)?;
+        let last = self.len() - 1;
+        let row = &self.matrix[removed * self.stride..removed * self.stride + self.len()];
+        // A self-loop is both in the row and the column, but is counted once
+        let column = (0..self.len()).filter(|&i| i != removed);
+
+        self.edge_count -= row.iter().filter(|edge| edge.is_some()).count()
+            + column
+                .filter(|&i| self.matrix[i * self.stride + removed].is_some())
+                .count();
+
+        if removed != last {
+            for i in 0..self.len() {
+                self.matrix
+                    .swap(removed * self.stride + i, last * self.stride + i);
+            }
+
+            for i in 0..self.len() {
+                self.matrix
+                    .swap(i * self.stride + removed, i * self.stride + last);
+            }
+
+            self.compact_idx.insert(self.ids[last], removed);
+        }
+
+        for i in 0..self.len() {
+            self.matrix[last * self.stride + i] = None;
+            self.matrix[i * self.stride + last] = None;
+        }
+
+        self.ids.swap_remove(removed);
+        Some(self.weights.swap_remove(removed))
+    }
+
+    /// Retrieve an immutable reference to the weight of the edge from `from` to `to` in `O(1)`.
+    pub fn get_edge(&self, from: VertexIdx, to: VertexIdx) -> Option<&E> {
+        let position = self.position(from, to)?;
+
+        self.matrix[position].as_ref()
+    }
+
+    /// Retrieve a mutable reference to the weight of the edge from `from` to `to` in `O(1)`.
+    pub fn get_mut_edge(&mut self, from: VertexIdx, to: VertexIdx) -> Option<&mut E> {
+        let position = self.position(from, to)?;
+
+        self.matrix[position].as_mut()
+    }
+
+    /// Iterate over the edges from a vertex as `(to, weight)` in `O(|V|)`, if it exists.
+    pub fn neighbors(&self, vertex_idx: VertexIdx) -> Option<DenseNeighbors<'_, E>> {
+        let from = *self.compact_idx.get(&vertex_idx)?;
+
+        Some(DenseNeighbors {
+            ids: self.ids.iter(),
+            row: self.matrix[from * self.stride..from * self.stride + self.len()].iter(),
+        })
+    }
+
+    pub fn contains_vertex(&self, vertex_idx: VertexIdx) -> bool {
+        self.compact_idx.contains_key(&vertex_idx)
+    }
+
+    /// Whether the edge from `from` to `to` exists in this graph, in `O(1)`.
+    pub fn contains_edge(&self, from: VertexIdx, to: VertexIdx) -> bool {
+        self.get_edge(from, to).is_some()
+    }
+
+    /// Iterate over every edge as `(from, to, weight)` in `O(|V|²)`, in no particular order.
+    /// In undirected graphs, each edge is yielded in both directions.
+    pub fn edges_iter(&self) -> impl Iterator<Item = (VertexIdx, VertexIdx, &E)> {
+        self.ids.iter().flat_map(move |&from_idx| {
+            self.neighbors(from_idx)
+                .expect("The vertex exists")
+                .map(move |(to_idx, weight)| (from_idx, to_idx, weight))
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// The number of edges, counted like in [`Graph::edge_count`].
+    pub fn edge_count(&self) -> usize {
+        self.edge_count
+    }
+
+    /// Iterate over the ID and weight of every vertex, in no particular order.
+    pub fn vertices_iter(&self) -> impl ExactSizeIterator<Item = (VertexIdx, &V)> {
+        self.ids.iter().copied().zip(&self.weights)
+    }
+
+    /// The position of the edge from `from` to `to` in the matrix, if both vertices exist.
+    fn position(&self, from: VertexIdx, to: VertexIdx) -> Option<usize> {
+        Some(self.compact_idx.get(&from)? * self.stride + self.compact_idx.get(&to)?)
+    }
+
+    /// Insert or update the edge from `from` to `to` only in that direction, regardless of `D`.
+    fn insert_or_update_arc(
+        &mut self,
+        from: VertexIdx,
+        to: VertexIdx,
+        weight: E,
+    ) -> Option<&mut Self> {
+        let position = self.position(from, to)?;
+
+        if self.matrix[position].replace(weight).is_none() {
+            self.edge_count += 1;
+        }
+
+        Some(self)
+    }
+
+    /// Double the number of vertices that fit in the matrix.
+    fn grow(&mut self) {
+        let stride = (self.stride * 2).max(4);
+        let mut matrix: Vec<_> = iter::repeat_with(|| None).take(stride * stride).collect();
+
+        for from in 0..self.len() {
+            for to in 0..self.len() {
+                matrix[from * stride + to] = self.matrix[from * self.stride + to].take();
+            }
+        }
+
+        self.matrix = matrix;
+        self.stride = stride;
+    }
+}
+
+impl<V, E, D: Direction, S: BuildHasher + Default> DenseGraph<V, E, D, S> {
+    pub fn is_directed(&self) -> bool {
+        D::IS_DIRECTED
+    }
+
+    /// Remove an edge from a vertex `from` to a vertex `to` in `O(1)`.
+    /// In undirected graphs, the edge from `to` to `from` is removed as well.
+    ///
+    /// Returns that edge's weight, if it exists.
+    pub fn remove_edge(&mut self, from: VertexIdx, to: VertexIdx) -> Option<E> {
+        let position = self.position(from, to)?;
+        let weight = self.matrix[position].take()?;
+
+        self.edge_count -= 1;
+
+        if !D::IS_DIRECTED {
+            let position = self.position(to, from)?;
+
+            if self.matrix[position].take().is_some() {
+                self.edge_count -= 1;
+            }
+        }
+
+        Some(weight)
+    }
+}
+
+impl<V, E, S: BuildHasher + Default> DenseGraph<V, E, Directed, S> {
+    /// Insert or update an edge from a vertex `from` to a vertex `to` with weight `weight` in `O(1)`.
+    ///
+    /// Returns itself to allow for chaining, if the vertices specified exist in this graph.
+    pub fn insert_or_update_edge(
+        &mut self,
+        from: VertexIdx,
+        to: VertexIdx,
+        weight: E,
+    ) -> Option<&mut Self> {
+        self.insert_or_update_arc(from, to, weight)
+    }
+
+    /// A convenient method to insert or update multiple edges of type `(from, to, weight)` in `O(n)`, where `n` is the length of `edges`.
+    pub fn insert_or_update_edges(
+        &mut self,
+        edges: impl IntoIterator<Item = (VertexIdx, VertexIdx, E)>,
+    ) -> &mut Self {
+        for (from, to, weight) in edges {
+            self.insert_or_update_arc(from, to, weight);
+        }
+
+        self
+    }
+}
+
+impl<V, E: Clone, S: BuildHasher + Default> DenseGraph<V, E, Undirected, S> {
+    /// Insert or update the edge between the vertices `from` and `to` with weight `weight` in `O(1)`,
+    /// in both directions.
+    ///
+    /// Returns itself to allow for chaining, if the vertices specified exist in this graph.
+    pub fn insert_or_update_edge(
+        &mut self,
+        from: VertexIdx,
+        to: VertexIdx,
+        weight: E,
+    ) -> Option<&mut Self> {
+        self.insert_or_update_arc(to, from, weight.clone())?;
+        self.insert_or_update_arc(from, to, weight)
+    }
+
+    /// A convenient method to insert or update multiple edges of type `(from, to, weight)` in `O(n)`,
+    /// where `n` is the length of `edges`, in both directions.
+    pub fn insert_or_update_edges(
+        &mut self,
+        edges: impl IntoIterator<Item = (VertexIdx, VertexIdx, E)>,
+    ) -> &mut Self {
+        for (from, to, weight) in edges {
+            self.insert_or_update_edge(from, to, weight);
+        }
+
+        self
+    }
+}
+
+/// Converts in `O(|V|²)`, keeping the vertex IDs.
+impl<V, E, D, S: BuildHasher + Default> From<Graph<V, E, D, S>> for DenseGraph<V, E, D, S> {
+    fn from(graph: Graph<V, E, D, S>) -> Self {
+        let mut dense = Self::empty(graph.len());
+        let mut edges = Vec::new();
+
+        for (vertex_idx, vertex) in graph.vertices {
+            dense.compact_idx.insert(vertex_idx, dense.ids.len());
+            dense.ids.push(vertex_idx);
+            dense.weights.push(vertex.weight);
+            edges.extend(
+                vertex
+                    .edges
+                    .into_iter()
+                    .map(|(to_idx, weight)| (vertex_idx, to_idx, weight)),
+            );
+        }
+
+        for (from_idx, to_idx, weight) in edges {
+            dense.insert_or_update_arc(from_idx, to_idx, weight);
+        }
+
+        dense
+    }
+}
+
+/// Converts in `O(|V|²)`, keeping the vertex IDs.
+impl<V, E, D, S: BuildHasher + Default> From<DenseGraph<V, E, D, S>> for Graph<V, E, D, S> {
+    fn from(dense: DenseGraph<V, E, D, S>) -> Self {
+        let mut graph = Graph::default();
+
+        graph.reserve(dense.len());
+
+        let mut matrix = dense.matrix;
+
+        for (from, (vertex_idx, weight)) in dense.ids.iter().zip(dense.weights).enumerate() {
+            let edges = matrix[from * dense.stride..]
+                .iter_mut()
+                .zip(&dense.ids)
+                .filter_map(|(edge, &to_idx)| Some((to_idx, edge.take()?)))
+                .collect();
+
+            graph.vertices.insert(
+                *vertex_idx,
+                Vertex {
+                    weight,
+                    edges,
+                    predecessors: SmallMap::default(),
+                },
+            );
+        }
+
+        graph.rebuild_indices();
+        graph
+    }
+}
+
+impl<V, E, D, S: BuildHasher + Default> GraphView for DenseGraph<V, E, D, S> {
+    type Vertex = V;
+    type Edge = E;
+    type EdgeWeight<'a>
+        = &'a E
+    where
+        Self: 'a;
+    type Neighbors<'a>
+        = DenseNeighbors<'a, E>
+    where
+        Self: 'a;
+
+    fn vertex_count(&self) -> usize {
+        self.len()
+    }
+
+    fn contains_vertex(&self, vertex_idx: VertexIdx) -> bool {
+        DenseGraph::contains_vertex(self, vertex_idx)
+    }
+
+    fn vertex_weight(&self, vertex_idx: VertexIdx) -> Option<&V> {
+        self.get_vertex(vertex_idx)
+    }
+
+    fn neighbors(&self, vertex_idx: VertexIdx) -> Option<DenseNeighbors<'_, E>> {
+        DenseGraph::neighbors(self, vertex_idx)
+    }
+}
+
+/// The edges from a vertex of a [`DenseGraph`] as `(to, weight)`, found by scanning its row of
+/// the matrix.
+pub struct DenseNeighbors<'a, E> {
+    ids: slice::Iter<'a, VertexIdx>,
+    row: slice::Iter<'a, Option<E>>,
+}
+
+impl<'a, E> Iterator for DenseNeighbors<'a, E> {
+    type Item = (VertexIdx, &'a E);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let to_idx = *self.ids.next()?;
+
+            if let Some(weight) = self.row.next()? {
+                return Some((to_idx, weight));
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.ids.size_hint().1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DenseGraph;
+    use crate::collections::FastHashBuilder;
+    use crate::graph::{algo::dijkstra, Graph, Undirected};
+
+    #[test]
+    fn test_dense_graph() {
+        //  a → b ↰
+        //  ↑ ↙ ⤷-⤴
+        //  c   d
+        let mut graph = DenseGraph::<u8, u8>::new();
+        let [a, b, c, d] = [1, 2, 3, 4].map(|weight| graph.insert_vertex(weight));
+
+        graph.insert_or_update_edges([(a, b, 1), (b, b, 2), (b, c, 3), (c, a, 4)]);
+
+        assert_eq!(graph.get_edge(b, c), Some(&3), "Wrong weight for b → c");
+        assert_eq!(graph.get_edge(c, b), None, "The graph is directed");
+        assert_eq!(graph.edge_count(), 4, "Wrong number of edges");
+        assert!(graph.contains_edge(b, b), "Wrong self-loop");
+        assert_eq!(
+            dijkstra(&graph, a).and_then(|paths| paths.path_to(c)),
+            Some(vec![a, b, c]),
+            "Algorithms must run on dense graphs"
+        );
+
+        // Vertex d takes the place of vertex a in the matrix
+        assert_eq!(graph.remove_vertex(a), Some(1), "Wrong weight for vertex a");
+        assert_eq!(graph.get_vertex(a), None, "Vertex a has been removed");
+        assert_eq!(graph.get_vertex(d), Some(&4), "Wrong weight for vertex d");
+        assert!(!graph.contains_vertex(a), "Vertex a has been removed");
+        assert_eq!(
+            graph.edge_count(),
+            2,
+            "The edges of vertex a must be removed"
+        );
+        assert_eq!(
+            graph.neighbors(d).map(Iterator::count),
+            Some(0),
+            "Vertex d has no edges"
+        );
+        assert_eq!(
+            graph.neighbors(c).map(Iterator::count),
+            Some(0),
+            "Edge c → a has been removed"
+        );
+
+        // Grow past the initial capacity
+        let vertices: Vec<_> = (0..10).map(|weight| graph.insert_vertex(weight)).collect();
+
+        graph.insert_or_update_edge(vertices[9], d, 5);
+
+        assert_eq!(graph.get_edge(b, b), Some(&2), "Edges must survive growing");
+        assert_eq!(
+            graph.get_edge(vertices[9], d),
+            Some(&5),
+            "Wrong weight for the new edge"
+        );
+        assert_eq!(graph.remove_edge(b, b), Some(2), "Wrong weight for b → b");
+        assert_eq!(graph.remove_edge(b, b), None, "Edge b → b has been removed");
+        assert_eq!(graph.edge_count(), 2, "Wrong number of edges");
+        assert_eq!(
+            graph.edges_iter().count(),
+            graph.edge_count(),
+            "Every edge must be iterated over"
+        );
+
+        let sparse = Graph::from(graph.clone());
+        let mut edges: Vec<_> = sparse
+            .edges_iter()
+            .map(|(from, to, &weight)| (from, to, weight))
+            .collect();
+
+        edges.sort_unstable();
+        assert_eq!(
+            edges,
+            [(b, c, 3), (vertices[9], d, 5)],
+            "Wrong edges of the sparse graph"
+        );
+
+        let dense = DenseGraph::from(sparse);
+
+        assert_eq!(dense.len(), graph.len(), "The vertices must be kept");
+        assert_eq!(dense.edge_count(), 2, "Wrong number of edges");
+        assert_eq!(
+            dense.get_edge(vertices[9], d),
+            Some(&5),
+            "The edges must be kept"
+        );
+    }
+
+    #[test]
+    fn test_undirected_dense_graph() {
+        let mut graph = DenseGraph::<(), u8, Undirected>::with_capacity(2);
+        let [a, b] = [(); 2].map(|_| graph.insert_vertex(()));
+
+        graph.insert_or_update_edge(a, b, 1);
+
+        assert_eq!(
+            graph.get_edge(b, a),
+            Some(&1),
+            "Undirected edges go both ways"
+        );
+        assert_eq!(graph.edge_count(), 2, "Each edge goes both ways");
+
+        let mut fast = DenseGraph::<(), u8, Undirected, FastHashBuilder>::default();
+        let [x, y] = [(); 2].map(|_| fast.insert_vertex(()));
+
+        fast.insert_or_update_edge(x, y, 1);
+
+        let sparse = Graph::from(fast);
+
+        assert_eq!(sparse.edge_count(), 2, "The edges must be kept");
+        assert_eq!(graph.remove_edge(b, a), Some(1), "Wrong weight for b - a");
+        assert_eq!(
+            graph.edge_count(),
+            0,
+            "Both directions must have been removed"
+        );
+        assert_eq!(
+            graph.get_edge(a, b),
+            None,
+            "Both directions must have been removed"
+        );
+    }
+}
@@ -0,0 +1,21 @@
+/// The directedness of a [`Graph`](super::Graph), given as its last type parameter.
+pub trait Direction {
+    const IS_DIRECTED: bool;
+}
+
+/// Marks a graph whose edges go from a vertex to another. This is the default.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Directed;
+
+/// Marks a graph whose edges connect both of their vertices. Every edge is stored in both
+/// directions, so algorithms see each one as a pair of opposite edges with the same weight.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Undirected;
+
+impl Direction for Directed {
+    const IS_DIRECTED: bool = true;
+}
+
+impl Direction for Undirected {
+    const IS_DIRECTED: bool = false;
+}
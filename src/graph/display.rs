@@ -0,0 +1,146 @@
+use super::Graph;
+use alloc::{string::ToString, vec::Vec};
+use core::{fmt, hash::BuildHasher};
+
+/// A human-readable rendering of the adjacency of a graph, as returned by
+/// [`Graph::display_adjacency`], with one line per vertex in aligned columns:
+///
+/// ```text
+///  3 | a   | -> 4 (1), 5 (2)
+///  4 | bb  | -> 5 (3)
+/// 12 | ccc |
+/// ```
+pub struct DisplayAdjacency<'g, V, E, D, S> {
+    graph: &'g Graph<V, E, D, S>,
+    sorted: bool,
+}
+
+impl<V, E, D, S> DisplayAdjacency<'_, V, E, D, S> {
+    /// Sort the vertices, and the edges of every vertex, by ID, so that the output is
+    /// deterministic, in `O(|V| log |V| + |E| log |E|)`.
+    pub fn sorted(mut self) -> Self {
+        self.sorted = true;
+        self
+    }
+}
+
+impl<V, E, D, S> fmt::Display for DisplayAdjacency<'_, V, E, D, S>
+where
+    V: fmt::Display,
+    E: fmt::Display,
+    S: BuildHasher + Default,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut rows: Vec<_> = self
+            .graph
+            .vertices_iter()
+            .map(|(vertex_idx, vertex)| {
+                let mut edges: Vec<_> = vertex.neighbors().collect();
+
+                if self.sorted {
+                    edges.sort_unstable_by_key(|&(to_idx, _)| to_idx);
+                }
+
+                (vertex_idx, vertex.weight().to_string(), edges)
+            })
+            .collect();
+
+        if self.sorted {
+            rows.sort_unstable_by_key(|&(vertex_idx, ..)| vertex_idx);
+        }
+
+        let idx_width = rows
+            .iter()
+            .map(|(vertex_idx, ..)| vertex_idx.as_usize().to_string().len())
+            .max()
+            .unwrap_or(0);
+        let weight_width = rows
+            .iter()
+            .map(|(_, weight, _)| weight.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        for (i, (vertex_idx, weight, edges)) in rows.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+
+            write!(
+                f,
+                "{:>idx_width$} | {weight:<weight_width$} |",
+                vertex_idx.as_usize()
+            )?;
+
+            for (j, (to_idx, edge_weight)) in edges.iter().enumerate() {
+                let separator = if j == 0 { " ->" } else { "," };
+
+                write!(f, "{separator} {} ({edge_weight})", to_idx.as_usize())?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<V, E, D, S: BuildHasher + Default> Graph<V, E, D, S> {
+    /// Render the vertices of this graph with their weights and outgoing edges, one per line, in
+    /// no particular order unless [sorted](DisplayAdjacency::sorted).
+    ///
+    /// ```
+    /// # use badlgorithms::graph::Graph;
+    /// let mut graph = Graph::<&str, u32>::new();
+    /// let [a, b] = ["a", "b"].map(|label| graph.insert_vertex(label));
+    ///
+    /// graph.insert_or_update_edge(a, b, 3);
+    ///
+    /// println!("{}", graph.display_adjacency().sorted());
+    /// ```
+    pub fn display_adjacency(&self) -> DisplayAdjacency<'_, V, E, D, S> {
+        DisplayAdjacency {
+            graph: self,
+            sorted: false,
+        }
+    }
+}
+
+/// The sorted [`Graph::display_adjacency`] of the graph.
+impl<V, E, D, S> fmt::Display for Graph<V, E, D, S>
+where
+    V: fmt::Display,
+    E: fmt::Display,
+    S: BuildHasher + Default,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.display_adjacency().sorted().fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::{Graph, VertexIdx};
+    use alloc::{format, string::ToString};
+
+    #[test]
+    fn test_display() {
+        let mut graph = Graph::<&str, u8>::new();
+        let [a, b, c] = ["a", "bb", "ccc"].map(|label| graph.insert_vertex(label));
+
+        graph.insert_or_update_edges([(a, c, 2), (a, b, 1), (b, c, 3)]);
+
+        let width = c.as_usize().to_string().len();
+        let [a, b, c] = [a, b, c].map(|vertex_idx: VertexIdx| vertex_idx.as_usize());
+
+        assert_eq!(
+            graph.to_string(),
+            format!(
+                "{a:>width$} | a   | -> {b} (1), {c} (2)\n{b:>width$} | bb  | -> {c} (3)\n{c:>width$} | ccc |"
+            ),
+            "Wrong rendering"
+        );
+        assert_eq!(
+            Graph::<u8, u8>::new().to_string(),
+            "",
+            "An empty graph renders as nothing"
+        );
+    }
+}
@@ -0,0 +1,500 @@
+use super::{
+    algo::dijkstra::{self, dijkstra, DistanceHeap, ShortestPaths},
+    measure::{Measure, Ordered},
+    Directed, Direction, Graph, GraphError, VertexIdx,
+};
+use crate::collections::{BinaryHeap, DefaultHashBuilder, HashMap, HashSet, VecDeque};
+use core::{
+    cmp::{Ordering, Reverse},
+    hash::BuildHasher,
+    mem,
+};
+
+/// The connected components of an undirected graph whose edges are inserted and removed over
+/// time, so that connectivity queries don't recompute the components from scratch.
+///
+/// Every vertex is labeled with its component. [`Self::link`] relabels the smaller of the two
+/// components it joins, so in a sequence of links alone each vertex is relabeled `O(log |V|)`
+/// times. Once cuts are interleaved this no longer holds: cutting a leaf off a large component
+/// and linking it back relabels only the leaf, but a cut that splits it in half and the link that
+/// rejoins it relabel `Θ(|V|)` vertices each time.
+/// [`Self::cut`] searches from both ends of the edge at once and stops as soon as either they meet
+/// or one side runs out. If the cut splits the component, it costs about twice the smaller side,
+/// but if another path still joins the ends, the searches may walk the whole component first.
+///
+/// ```
+/// # use badlgorithms::graph::{dynamic::DynamicConnectivity, Graph};
+/// let mut graph = Graph::<(), ()>::new();
+/// let [a, b, c] = [(); 3].map(|_| graph.insert_vertex(()));
+/// let mut connectivity = DynamicConnectivity::new();
+///
+/// connectivity.link(a, b);
+/// connectivity.link(b, c);
+/// assert!(connectivity.connected(a, c));
+///
+/// connectivity.cut(a, b);
+/// assert!(!connectivity.connected(a, c));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct DynamicConnectivity {
+    adjacency: HashMap<VertexIdx, HashSet<VertexIdx>>,
+    /// The component of each vertex
+    labels: HashMap<VertexIdx, usize>,
+    /// The vertices of each component
+    components: HashMap<usize, HashSet<VertexIdx>>,
+    next_label: usize,
+}
+
+impl DynamicConnectivity {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Track the connectivity of `graph`, ignoring the direction of its edges, in
+    /// `O((|V| + |E|) log |V|)`. Its vertices without edges are tracked too.
+    pub fn from_graph<V, E, D, S>(graph: &Graph<V, E, D, S>) -> Self
+    where
+        S: BuildHasher + Default,
+    {
+        let mut connectivity = Self::new();
+
+        for (vertex_idx, vertex) in graph.vertices_iter() {
+            connectivity.insert_vertex(vertex_idx);
+
+            for neighbor_idx in vertex.neighbor_indices() {
+                connectivity.link(vertex_idx, neighbor_idx);
+            }
+        }
+
+        connectivity
+    }
+
+    /// The number of vertices, which are tracked once they're inserted or linked.
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    /// The number of connected components in `O(1)`.
+    pub fn component_count(&self) -> usize {
+        self.components.len()
+    }
+
+    /// Track a vertex without edges, in its own component, in `O(1)`. Does nothing if it's
+    /// already tracked.
+    pub fn insert_vertex(&mut self, vertex_idx: VertexIdx) {
+        if self.labels.contains_key(&vertex_idx) {
+            return;
+        }
+
+        self.labels.insert(vertex_idx, self.next_label);
+        self.components
+            .insert(self.next_label, HashSet::from([vertex_idx]));
+        self.adjacency.insert(vertex_idx, HashSet::new());
+        self.next_label += 1;
+    }
+
+    /// Insert the edge between `a` and `b`, tracking them if they aren't already. This is
+    /// amortized `O(log |V|)` over a sequence of links alone, but `O(|V|)` after cuts (see
+    /// [`Self`]).
+    ///
+    /// Returns `false` if the edge already exists or is a self-loop, which never affects
+    /// connectivity.
+    pub fn link(&mut self, a: VertexIdx, b: VertexIdx) -> bool {
+        self.insert_vertex(a);
+        self.insert_vertex(b);
+
+        if a == b || !self.adjacency.get_mut(&a).expect("a is tracked").insert(b) {
+            return false;
+        }
+
+        self.adjacency.get_mut(&b).expect("b is tracked").insert(a);
+
+        let (label_a, label_b) = (self.labels[&a], self.labels[&b]);
+
+        if label_a != label_b {
+            let (small, large) =
+                if self.components[&label_a].len() < self.components[&label_b].len() {
+                    (label_a, label_b)
+                } else {
+                    (label_b, label_a)
+                };
+            let moved = self.components.remove(&small).expect("Labels are in use");
+
+            for &vertex_idx in &moved {
+                self.labels.insert(vertex_idx, large);
+            }
+
+            self.components
+                .get_mut(&large)
+                .expect("Labels are in use")
+                .extend(moved);
+        }
+
+        true
+    }
+
+    /// Remove the edge between `a` and `b`, splitting their component if it was the last path
+    /// between them, in `O(|V'| + |E'|)`. If the component splits, `|V'|` and `|E'|` are the
+    /// vertices and edges of the smaller side. Otherwise, they are up to those of the whole
+    /// component, as the searches from `a` and `b` may cover most of it before they meet.
+    ///
+    /// Returns `false` if the edge doesn't exist.
+    pub fn cut(&mut self, a: VertexIdx, b: VertexIdx) -> bool {
+        if !self
+            .adjacency
+            .get_mut(&a)
+            .is_some_and(|neighbors| neighbors.remove(&b))
+        {
+            return false;
+        }
+
+        self.adjacency.get_mut(&b).expect("b is tracked").remove(&a);
+
+        let (mut search, mut other) = (Search::new(a), Search::new(b));
+
+        // Alternate between the searches, so that the work is bounded by the smaller side if the
+        // component splits, or by the whole component until they meet otherwise
+        let split = loop {
+            match search.step(&self.adjacency, &other.reached) {
+                Step::Met => return true,
+                Step::Exhausted => break search.reached,
+                Step::Continue => mem::swap(&mut search, &mut other),
+            }
+        };
+
+        let old_label = self.labels[&a];
+        let component = self
+            .components
+            .get_mut(&old_label)
+            .expect("Labels are in use");
+
+        for vertex_idx in &split {
+            component.remove(vertex_idx);
+            self.labels.insert(*vertex_idx, self.next_label);
+        }
+
+        self.components.insert(self.next_label, split);
+        self.next_label += 1;
+
+        true
+    }
+
+    /// Checks whether there's a path between `a` and `b` in `O(1)`. Every vertex is connected to
+    /// itself, even if it isn't tracked.
+    pub fn connected(&self, a: VertexIdx, b: VertexIdx) -> bool {
+        a == b
+            || self
+                .labels
+                .get(&a)
+                .is_some_and(|label| self.labels.get(&b) == Some(label))
+    }
+
+    /// Checks whether the edge between `a` and `b` exists in `O(1)`.
+    pub fn contains_edge(&self, a: VertexIdx, b: VertexIdx) -> bool {
+        self.adjacency
+            .get(&a)
+            .is_some_and(|neighbors| neighbors.contains(&b))
+    }
+}
+
+/// One side of the search of [`DynamicConnectivity::cut`].
+struct Search {
+    queue: VecDeque<VertexIdx>,
+    reached: HashSet<VertexIdx>,
+}
+
+enum Step {
+    /// A vertex reached by the other search was reached, so the component is intact.
+    Met,
+    /// Every vertex of this side has been reached.
+    Exhausted,
+    Continue,
+}
+
+impl Search {
+    fn new(src_idx: VertexIdx) -> Self {
+        Self {
+            queue: VecDeque::from([src_idx]),
+            reached: HashSet::from([src_idx]),
+        }
+    }
+
+    /// Expand the next vertex of the queue, looking for any vertex in `other_reached`.
+    fn step(
+        &mut self,
+        adjacency: &HashMap<VertexIdx, HashSet<VertexIdx>>,
+        other_reached: &HashSet<VertexIdx>,
+    ) -> Step {
+        let vertex_idx = self
+            .queue
+            .pop_front()
+            .expect("Exhausted searches aren't stepped");
+
+        for &neighbor_idx in &adjacency[&vertex_idx] {
+            if other_reached.contains(&neighbor_idx) {
+                return Step::Met;
+            }
+
+            if self.reached.insert(neighbor_idx) {
+                self.queue.push_back(neighbor_idx);
+            }
+        }
+
+        if self.queue.is_empty() {
+            Step::Exhausted
+        } else {
+            Step::Continue
+        }
+    }
+}
+
+/// The shortest paths from a fixed source, kept up to date as edges are inserted and their
+/// weights decreased, so that only the vertices whose distances improve are visited again instead
+/// of rerunning [`dijkstra`] on the whole graph.
+///
+/// The graph is owned, so that every change goes through [`Self::insert_or_update_edge`]. Like
+/// [`dijkstra`], the edge weights must not be less than [`Measure::zero`].
+///
+/// ```
+/// # use badlgorithms::graph::{dynamic::IncrementalSssp, Graph};
+/// let mut graph = Graph::<(), u32>::new();
+/// let [a, b, c] = [(); 3].map(|_| graph.insert_vertex(()));
+///
+/// graph.insert_or_update_edges([(a, b, 4), (b, c, 4)]);
+///
+/// let mut sssp = IncrementalSssp::new(graph, a).expect("Vertex a exists");
+///
+/// assert_eq!(sssp.paths().distance_to(c), Some(8));
+///
+/// sssp.insert_or_update_edge(a, c, 5)?;
+/// assert_eq!(sssp.paths().distance_to(c), Some(5));
+/// # Ok::<(), badlgorithms::graph::GraphError>(())
+/// ```
+pub struct IncrementalSssp<V, E, D = Directed, S = DefaultHashBuilder> {
+    graph: Graph<V, E, D, S>,
+    src_idx: VertexIdx,
+    paths: ShortestPaths<E>,
+}
+
+impl<V, E: Measure, D: Direction, S: BuildHasher + Default> IncrementalSssp<V, E, D, S> {
+    /// Find the shortest paths of `graph` from `src_idx` with [`dijkstra`].
+    ///
+    /// Returns `None` if the source vertex doesn't exist in the graph.
+    pub fn new(graph: Graph<V, E, D, S>, src_idx: VertexIdx) -> Option<Self> {
+        let paths = dijkstra(&graph, src_idx)?;
+
+        Some(Self {
+            graph,
+            src_idx,
+            paths,
+        })
+    }
+
+    pub fn graph(&self) -> &Graph<V, E, D, S> {
+        &self.graph
+    }
+
+    pub fn into_graph(self) -> Graph<V, E, D, S> {
+        self.graph
+    }
+
+    pub fn source(&self) -> VertexIdx {
+        self.src_idx
+    }
+
+    /// The current shortest paths from the source.
+    pub fn paths(&self) -> &ShortestPaths<E> {
+        &self.paths
+    }
+
+    /// Insert or update the edge from `from` to `to` with weight `weight`, in both directions in
+    /// undirected graphs, and update the shortest paths.
+    ///
+    /// A new edge or a lighter weight only revisits the vertices whose distances improve, in
+    /// `O((|V'| + |E'|) log |V'|)`. A heavier weight is free unless the edge is on the tree of
+    /// shortest paths, in which case they're found again from scratch.
+    pub fn insert_or_update_edge(
+        &mut self,
+        from: VertexIdx,
+        to: VertexIdx,
+        weight: E,
+    ) -> Result<(), GraphError> {
+        self.graph.check_edge(from, to)?;
+
+        let old_weight = self.graph.get_edge(from, to).copied();
+        let undirected = !D::IS_DIRECTED && from != to;
+
+        self.graph.insert_or_update_arc(from, to, weight);
+
+        if undirected {
+            self.graph.insert_or_update_arc(to, from, weight);
+        }
+
+        match old_weight.map(|old_weight| weight.compare(&old_weight)) {
+            Some(Ordering::Equal) => {}
+            Some(Ordering::Greater) => {
+                let on_tree = |from_idx, to_idx| self.paths.parents.get(&to_idx) == Some(&from_idx);
+
+                if on_tree(from, to) || (undirected && on_tree(to, from)) {
+                    self.paths = dijkstra(&self.graph, self.src_idx).expect("The source exists");
+                }
+            }
+            _ => {
+                let mut heap = BinaryHeap::new();
+
+                self.improve(from, to, weight, &mut heap);
+
+                if undirected {
+                    self.improve(to, from, weight, &mut heap);
+                }
+
+                dijkstra::relax(&self.graph, &mut self.paths, &mut heap, &mut ());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Relax the edge from `from` to `to`, queuing `to` if its distance improves.
+    fn improve(&mut self, from: VertexIdx, to: VertexIdx, weight: E, heap: &mut DistanceHeap<E>) {
+        let Some(distance) = self.paths.distances.get(&from) else {
+            return;
+        };
+        let new_distance = distance.add(weight);
+
+        if self
+            .paths
+            .distances
+            .get(&to)
+            .is_some_and(|old_distance| new_distance.compare(old_distance) != Ordering::Less)
+        {
+            return;
+        }
+
+        self.paths.distances.insert(to, new_distance);
+        self.paths.parents.insert(to, from);
+        heap.push(Reverse((Ordered(new_distance), to)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collections::FastHashBuilder;
+    use crate::graph::{random::RandomConfig, Undirected};
+    use fastrand::Rng;
+
+    #[test]
+    fn test_dynamic_connectivity() {
+        //  a — b — c
+        //   \_____/    d
+        let mut graph = Graph::<(), (), Undirected>::new();
+        let [a, b, c, d] = [(); 4].map(|_| graph.insert_vertex(()));
+
+        graph.insert_or_update_undirected_edge(a, b, ());
+        graph.insert_or_update_undirected_edge(b, c, ());
+        graph.insert_or_update_undirected_edge(c, a, ());
+
+        let mut connectivity = DynamicConnectivity::from_graph(&graph);
+
+        assert_eq!(connectivity.len(), 4, "Every vertex must be tracked");
+        assert_eq!(connectivity.component_count(), 2, "d is on its own");
+        assert!(connectivity.connected(a, c), "a and c are connected");
+        assert!(!connectivity.connected(a, d), "d isn't connected");
+        assert!(!connectivity.link(b, a), "The edge already exists");
+
+        assert!(connectivity.cut(a, b), "The edge exists");
+        assert!(!connectivity.cut(a, b), "The edge was removed");
+        assert!(
+            connectivity.connected(a, b),
+            "a and b are still connected through c"
+        );
+
+        assert!(connectivity.cut(c, a), "The edge exists");
+        assert!(!connectivity.connected(a, b), "a is cut off");
+        assert!(connectivity.connected(b, c), "b and c are still connected");
+        assert_eq!(connectivity.component_count(), 3, "a is on its own too");
+
+        assert!(connectivity.link(d, c), "The edge is new");
+        assert!(connectivity.connected(b, d), "d joins b and c");
+        assert!(connectivity.contains_edge(c, d), "Edges are undirected");
+        assert_eq!(
+            connectivity.component_count(),
+            2,
+            "d isn't on its own anymore"
+        );
+    }
+
+    #[test]
+    fn test_incremental_sssp() {
+        let config = RandomConfig {
+            min_vertices: 12,
+            max_vertices: 12,
+            density: 0.15,
+            self_loops: true,
+        };
+
+        for undirected in [false, true] {
+            let mut rng = Rng::with_seed(3);
+            let weight = |rng: &mut Rng| rng.u32(1..20);
+
+            if undirected {
+                let (graph, vertices) = Graph::<(), u32, Undirected, FastHashBuilder>::random(
+                    &mut rng,
+                    config,
+                    |_| (),
+                    weight,
+                );
+
+                check_incremental_sssp(graph, &vertices, &mut rng);
+            } else {
+                let (graph, vertices) = Graph::<(), u32>::random(&mut rng, config, |_| (), weight);
+
+                check_incremental_sssp(graph, &vertices, &mut rng);
+            }
+        }
+    }
+
+    /// Insert and update random edges, checking the distances against a full run of Dijkstra's
+    /// algorithm after every change.
+    fn check_incremental_sssp<D: Direction, S: BuildHasher + Default>(
+        mut graph: Graph<(), u32, D, S>,
+        vertices: &[VertexIdx],
+        rng: &mut Rng,
+    ) {
+        let src_idx = vertices[0];
+        let isolated_idx = graph.insert_vertex(());
+        let mut sssp = IncrementalSssp::new(graph, src_idx).expect("The source exists");
+
+        for _ in 0..60 {
+            let from = vertices[rng.usize(..vertices.len())];
+            let to = vertices[rng.usize(..vertices.len())];
+
+            sssp.insert_or_update_edge(from, to, rng.u32(1..20))
+                .expect("The vertices exist");
+
+            let expected = dijkstra(sssp.graph(), src_idx).expect("The source exists");
+
+            assert_eq!(
+                sssp.paths().distances(),
+                expected.distances(),
+                "The distances must match a full run of Dijkstra's algorithm"
+            );
+        }
+
+        assert_eq!(
+            sssp.paths().distance_to(isolated_idx),
+            None,
+            "The isolated vertex isn't reachable"
+        );
+        assert_eq!(
+            sssp.insert_or_update_edge(src_idx, VertexIdx(usize::MAX), 1),
+            Err(GraphError::MissingTarget(VertexIdx(usize::MAX))),
+            "The target doesn't exist"
+        );
+    }
+}
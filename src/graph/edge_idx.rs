@@ -0,0 +1,24 @@
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// The ID that the next edge will get
+static EDGE_INCR_IDX: AtomicUsize = AtomicUsize::new(0);
+
+/// The ID of an edge of a [`MultiGraph`](super::MultiGraph), unique across every graph of
+/// the program. Like [`VertexIdx`](super::VertexIdx), IDs are never reused.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct EdgeIdx(pub(crate) usize);
+
+impl EdgeIdx {
+    /// # Panics
+    ///
+    /// If every ID has been used, rather than wrapping around and reusing them.
+    pub(crate) fn new() -> Self {
+        let idx = EDGE_INCR_IDX
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |idx| {
+                idx.checked_add(1)
+            })
+            .expect("There are edge IDs left");
+
+        Self(idx)
+    }
+}
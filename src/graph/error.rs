@@ -0,0 +1,37 @@
+use super::VertexIdx;
+use core::{error, fmt};
+
+/// The reason an operation on a graph failed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GraphError {
+    /// The vertex doesn't exist.
+    MissingVertex(VertexIdx),
+    /// The vertex an edge starts from doesn't exist.
+    MissingSource(VertexIdx),
+    /// The vertex an edge points to doesn't exist.
+    MissingTarget(VertexIdx),
+    /// The edge from the first vertex to the second one doesn't exist.
+    MissingEdge(VertexIdx, VertexIdx),
+    /// The raw ID is greater than [`VertexIdx::MAX_RAW`].
+    InvalidIdx(usize),
+}
+
+impl fmt::Display for GraphError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingVertex(idx) => write!(f, "the vertex {} doesn't exist", idx.0),
+            Self::MissingSource(idx) => {
+                write!(f, "the source vertex {} of the edge doesn't exist", idx.0)
+            }
+            Self::MissingTarget(idx) => {
+                write!(f, "the target vertex {} of the edge doesn't exist", idx.0)
+            }
+            Self::MissingEdge(from, to) => {
+                write!(f, "the edge from {} to {} doesn't exist", from.0, to.0)
+            }
+            Self::InvalidIdx(idx) => write!(f, "{idx} isn't a valid vertex ID"),
+        }
+    }
+}
+
+impl error::Error for GraphError {}
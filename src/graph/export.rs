@@ -0,0 +1,16 @@
+//! Exporting graphs as text to be displayed by other tools, such as diagrams that can be pasted
+//! into Markdown. See [`io`](super::io) for the formats that can be read back too.
+//!
+//! ```
+//! # use badlgorithms::graph::{export::{to_mermaid, Orientation}, Graph};
+//! let mut graph = Graph::<&str, ()>::new();
+//! let [a, b] = ["a", "b"].map(|label| graph.insert_vertex(label));
+//!
+//! graph.insert_or_update_edge(a, b, ());
+//!
+//! let chart = to_mermaid(&graph, Orientation::TopDown, |_, &label| label, |_| None::<&str>);
+//!
+//! assert!(chart.starts_with("flowchart TD\n"));
+//! ```
+
+pub use super::io::mermaid::{to_mermaid, Orientation};
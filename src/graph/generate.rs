@@ -0,0 +1,335 @@
+//! Graphs of common shapes, e.g. for inputs of the algorithms in tests and benchmarks.
+//!
+//! Every generator returns the graph along with the IDs of its vertices, and calls its weight
+//! closures with the positions of the vertices rather than their IDs. In undirected graphs, the
+//! edge closure is called once per edge, whose weight is used for both directions.
+
+use super::{Direction, Graph, VertexIdx};
+use alloc::vec::Vec;
+use core::hash::BuildHasher;
+
+/// Which neighbors of a cell of a [`grid`] it has edges to.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Connectivity {
+    /// The cells above, below, left and right of it.
+    #[default]
+    Four,
+    /// The cells of [`Connectivity::Four`], along with the diagonal ones.
+    Eight,
+}
+
+/// Generate a `width` × `height` lattice, such as a maze or a map, in `O(width * height)`, with
+/// an edge between each pair of neighboring cells. Directed grids have edges in both directions.
+///
+/// - `vertex_weight`: `|(x, y)| -> vertex_weight`
+/// - `edge_weight`: `|(from_x, from_y), (to_x, to_y)| -> edge_weight`
+///
+/// Returns the graph along with the ID of the vertex of each cell, as `ids[y][x]`.
+///
+/// ```
+/// # use badlgorithms::graph::{algo::shortest_path_unweighted, generate::{grid, Connectivity}, Graph};
+/// let (maze, ids): (Graph<(), ()>, _) = grid(3, 2, Connectivity::Four, |_| (), |_, _| ());
+///
+/// assert_eq!(maze.edge_count(), 14);
+/// assert_eq!(shortest_path_unweighted(&maze, ids[0][0], ids[1][2]).map(|path| path.len()), Some(4));
+/// ```
+pub fn grid<V, E, D, S>(
+    width: usize,
+    height: usize,
+    connectivity: Connectivity,
+    mut vertex_weight: impl FnMut((usize, usize)) -> V,
+    mut edge_weight: impl FnMut((usize, usize), (usize, usize)) -> E,
+) -> (Graph<V, E, D, S>, Vec<Vec<VertexIdx>>)
+where
+    E: Clone,
+    D: Direction,
+    S: BuildHasher + Default,
+{
+    let mut graph = Graph::default();
+
+    graph.reserve(width * height);
+    let ids: Vec<Vec<_>> = (0..height)
+        .map(|y| {
+            (0..width)
+                .map(|x| graph.insert_vertex(vertex_weight((x, y))))
+                .collect()
+        })
+        .collect();
+    // The offsets of the neighbors of a cell that come after it, so that each pair is visited
+    // once
+    let offsets: &[(isize, isize)] = match connectivity {
+        Connectivity::Four => &[(1, 0), (0, 1)],
+        Connectivity::Eight => &[(1, 0), (-1, 1), (0, 1), (1, 1)],
+    };
+
+    for y in 0..height {
+        for x in 0..width {
+            for &(dx, dy) in offsets {
+                let (Some(to_x), Some(to_y)) = (x.checked_add_signed(dx), y.checked_add_signed(dy))
+                else {
+                    continue;
+                };
+
+                if to_x >= width || to_y >= height {
+                    continue;
+                }
+
+                let [from, to] = [(x, y), (to_x, to_y)];
+
+                if D::IS_DIRECTED {
+                    graph.insert_or_update_arc(ids[y][x], ids[to_y][to_x], edge_weight(from, to));
+                    graph.insert_or_update_arc(ids[to_y][to_x], ids[y][x], edge_weight(to, from));
+                } else {
+                    graph.connect(ids[y][x], ids[to_y][to_x], edge_weight(from, to));
+                }
+            }
+        }
+    }
+
+    (graph, ids)
+}
+
+/// Create `n` vertices, weighted by `vertex_weight` of their position, and the edges between the
+/// `pairs` of positions, weighted by `edge_weight`.
+fn from_pairs<V, E, D, S>(
+    n: usize,
+    vertex_weight: impl FnMut(usize) -> V,
+    mut edge_weight: impl FnMut(usize, usize) -> E,
+    pairs: impl IntoIterator<Item = (usize, usize)>,
+) -> (Graph<V, E, D, S>, Vec<VertexIdx>)
+where
+    E: Clone,
+    D: Direction,
+    S: BuildHasher + Default,
+{
+    let mut graph = Graph::default();
+
+    graph.reserve(n);
+    let ids: Vec<_> = (0..n)
+        .map(vertex_weight)
+        .map(|weight| graph.insert_vertex(weight))
+        .collect();
+
+    for (from, to) in pairs {
+        graph.connect(ids[from], ids[to], edge_weight(from, to));
+    }
+
+    (graph, ids)
+}
+
+/// The pairs of positions of a cycle through the positions `first..first + n`, or of a path if
+/// there are fewer than 3 of them.
+fn cycle_pairs(first: usize, n: usize) -> impl Iterator<Item = (usize, usize)> {
+    let closing = (n >= 3).then_some((first + n - 1, first));
+
+    (first..(first + n).saturating_sub(1))
+        .map(|i| (i, i + 1))
+        .chain(closing)
+}
+
+/// Generate the complete graph of `n` vertices, with an edge between every pair of them, in
+/// `O(n²)`. Directed complete graphs have edges in both directions.
+///
+/// - `vertex_weight`: `|i| -> vertex_weight`
+/// - `edge_weight`: `|from_i, to_i| -> edge_weight`
+pub fn complete<V, E, D, S>(
+    n: usize,
+    vertex_weight: impl FnMut(usize) -> V,
+    edge_weight: impl FnMut(usize, usize) -> E,
+) -> (Graph<V, E, D, S>, Vec<VertexIdx>)
+where
+    E: Clone,
+    D: Direction,
+    S: BuildHasher + Default,
+{
+    let pairs = (0..n).flat_map(|i| {
+        // Undirected graphs consider each pair once
+        let start = if D::IS_DIRECTED { 0 } else { i + 1 };
+
+        (start..n).filter(move |&j| j != i).map(move |j| (i, j))
+    });
+
+    from_pairs(n, vertex_weight, edge_weight, pairs)
+}
+
+/// Generate the path `0 → 1 → … → n - 1` of `n` vertices, in `O(n)`.
+///
+/// - `vertex_weight`: `|i| -> vertex_weight`
+/// - `edge_weight`: `|from_i, to_i| -> edge_weight`
+pub fn path<V, E, D, S>(
+    n: usize,
+    vertex_weight: impl FnMut(usize) -> V,
+    edge_weight: impl FnMut(usize, usize) -> E,
+) -> (Graph<V, E, D, S>, Vec<VertexIdx>)
+where
+    E: Clone,
+    D: Direction,
+    S: BuildHasher + Default,
+{
+    let pairs = (0..n.saturating_sub(1)).map(|i| (i, i + 1));
+
+    from_pairs(n, vertex_weight, edge_weight, pairs)
+}
+
+/// Generate the cycle `0 → 1 → … → n - 1 → 0` of `n` vertices, in `O(n)`. With fewer than 3
+/// vertices, it's a [`path`].
+///
+/// - `vertex_weight`: `|i| -> vertex_weight`
+/// - `edge_weight`: `|from_i, to_i| -> edge_weight`
+pub fn cycle<V, E, D, S>(
+    n: usize,
+    vertex_weight: impl FnMut(usize) -> V,
+    edge_weight: impl FnMut(usize, usize) -> E,
+) -> (Graph<V, E, D, S>, Vec<VertexIdx>)
+where
+    E: Clone,
+    D: Direction,
+    S: BuildHasher + Default,
+{
+    from_pairs(n, vertex_weight, edge_weight, cycle_pairs(0, n))
+}
+
+/// Generate the star of `n` vertices, with an edge from the center `0` to each of the others, in
+/// `O(n)`.
+///
+/// - `vertex_weight`: `|i| -> vertex_weight`
+/// - `edge_weight`: `|from_i, to_i| -> edge_weight`
+pub fn star<V, E, D, S>(
+    n: usize,
+    vertex_weight: impl FnMut(usize) -> V,
+    edge_weight: impl FnMut(usize, usize) -> E,
+) -> (Graph<V, E, D, S>, Vec<VertexIdx>)
+where
+    E: Clone,
+    D: Direction,
+    S: BuildHasher + Default,
+{
+    from_pairs(n, vertex_weight, edge_weight, (1..n).map(|i| (0, i)))
+}
+
+/// Generate the wheel of `n` vertices: a [`star`] with center `0`, whose other vertices form the
+/// cycle `1 → 2 → … → n - 1 → 1`, in `O(n)`.
+///
+/// - `vertex_weight`: `|i| -> vertex_weight`
+/// - `edge_weight`: `|from_i, to_i| -> edge_weight`
+pub fn wheel<V, E, D, S>(
+    n: usize,
+    vertex_weight: impl FnMut(usize) -> V,
+    edge_weight: impl FnMut(usize, usize) -> E,
+) -> (Graph<V, E, D, S>, Vec<VertexIdx>)
+where
+    E: Clone,
+    D: Direction,
+    S: BuildHasher + Default,
+{
+    let spokes = (1..n).map(|i| (0, i));
+
+    from_pairs(
+        n,
+        vertex_weight,
+        edge_weight,
+        spokes.chain(cycle_pairs(1, n.saturating_sub(1))),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{complete, cycle, grid, path, star, wheel, Connectivity};
+    use crate::graph::{tests::helpers::test_indices, Directed, Graph, Undirected};
+
+    #[test]
+    fn test_grid() {
+        let (graph, ids): (Graph<(usize, usize), usize, Undirected>, _) = grid(
+            4,
+            3,
+            Connectivity::Four,
+            |cell| cell,
+            |(from_x, from_y), (to_x, to_y)| from_x + from_y + to_x + to_y,
+        );
+
+        assert_eq!(graph.len(), 12, "Wrong number of vertices");
+        // 3 rows of 3 horizontal edges, and 2 rows of 4 vertical ones, both ways
+        assert_eq!(graph.edge_count(), 2 * (9 + 8), "Wrong number of edges");
+        assert_eq!(
+            graph.get_vertex(ids[2][1]).map(|vertex| *vertex.weight()),
+            Some((1, 2)),
+            "The IDs are indexed by row, then column"
+        );
+        assert_eq!(
+            graph.get_edge(ids[1][2], ids[1][1]),
+            Some(&5),
+            "Wrong weight of the edge"
+        );
+        assert!(
+            !graph.contains_edge(ids[0][0], ids[1][1]),
+            "Diagonal cells aren't neighbors"
+        );
+        test_indices(&graph);
+
+        let (graph, ids): (Graph<(), (usize, usize), Directed>, _) =
+            grid(3, 3, Connectivity::Eight, |_| (), |from, _| from);
+
+        // 3 rows of 2 horizontal edges, 3 columns of 2 vertical ones and 2 × 2 × 2 diagonal ones,
+        // both ways
+        assert_eq!(graph.edge_count(), 2 * (6 + 6 + 8), "Wrong number of edges");
+        assert_eq!(
+            graph
+                .get_vertex(ids[1][1])
+                .map(|vertex| vertex.neighbors().count()),
+            Some(8),
+            "The center has 8 neighbors"
+        );
+        assert_eq!(
+            graph.get_edge(ids[2][0], ids[1][1]),
+            Some(&(0, 2)),
+            "Directed edges are weighted per direction"
+        );
+        test_indices(&graph);
+    }
+
+    #[test]
+    fn test_shapes() {
+        let counts = |(graph, ids): (Graph<(), usize, Directed>, Vec<_>)| {
+            test_indices(&graph);
+            assert_eq!(ids.len(), graph.len(), "Every vertex must have an ID");
+
+            (graph.len(), graph.edge_count())
+        };
+        let weight = |from, to| from * 10 + to;
+
+        assert_eq!(
+            counts(complete(4, |_| (), weight)),
+            (4, 12),
+            "Wrong complete graph"
+        );
+        assert_eq!(counts(path(4, |_| (), weight)), (4, 3), "Wrong path");
+        assert_eq!(counts(cycle(4, |_| (), weight)), (4, 4), "Wrong cycle");
+        assert_eq!(
+            counts(cycle(2, |_| (), weight)),
+            (2, 1),
+            "Short cycles are paths"
+        );
+        assert_eq!(counts(star(4, |_| (), weight)), (4, 3), "Wrong star");
+        assert_eq!(counts(wheel(5, |_| (), weight)), (5, 8), "Wrong wheel");
+        assert_eq!(
+            counts(wheel(0, |_| (), weight)),
+            (0, 0),
+            "Wrong empty wheel"
+        );
+
+        let (undirected, ids): (Graph<usize, usize, Undirected>, _) = wheel(5, |i| i, weight);
+
+        assert_eq!(undirected.edge_count(), 16, "Undirected edges go both ways");
+        assert_eq!(
+            undirected.get_edge(ids[1], ids[4]),
+            Some(&41),
+            "The rim must be closed"
+        );
+        assert_eq!(
+            undirected.get_vertex(ids[3]).map(|vertex| *vertex.weight()),
+            Some(3),
+            "Vertices are weighted by their position"
+        );
+        test_indices(&undirected);
+    }
+}
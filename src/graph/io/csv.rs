@@ -0,0 +1,424 @@
+//! A pair of CSV files, one with a row per vertex and one with a row per edge, as exchanged with
+//! spreadsheets, databases and tools like Gephi.
+//!
+//! The first row of each file is its header. Vertices are identified by their ID column, which
+//! the source and target columns of the edges refer to. The weights of vertices and edges are
+//! mapped from and to their rows by user closures, keyed by column name. Fields are quoted as in
+//! [RFC 4180](https://www.rfc-editor.org/rfc/rfc4180), so they may contain delimiters, quotes and
+//! line breaks.
+
+use super::Error;
+use crate::{
+    collections::{BTreeSet, HashMap},
+    graph::{Direction, Graph, VertexIdx},
+};
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{fmt, hash::BuildHasher, mem};
+
+/// The fields of a row, by column name.
+pub type Record = HashMap<String, String>;
+
+/// The ID of the vertex of each ID in the vertices file.
+type Ids<S> = HashMap<String, VertexIdx, S>;
+
+/// A graph read from a file, along with the ID of the vertex of each ID in the file.
+type Parsed<V, E, D, S> = (Graph<V, E, D, S>, Ids<S>);
+
+/// The delimiter of the fields and the names of the columns that identify vertices.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Format<'a> {
+    pub delimiter: char,
+    /// The column of the vertices file with the ID of each vertex.
+    pub id: &'a str,
+    /// The column of the edges file with the ID of the vertex each edge starts from.
+    pub source: &'a str,
+    /// The column of the edges file with the ID of the vertex each edge points to.
+    pub target: &'a str,
+}
+
+/// Fields delimited by commas, and the columns `id`, `source` and `target`.
+impl Default for Format<'_> {
+    fn default() -> Self {
+        Self {
+            delimiter: ',',
+            id: "id",
+            source: "source",
+            target: "target",
+        }
+    }
+}
+
+/// Read a graph from a vertices file and an edges file, creating each vertex with `vertex_map`
+/// and each edge with `edge_map` from their rows, in `O(n)`, where `n` is the size of the files.
+/// Lines are numbered from 1 in errors.
+///
+/// Returns the graph along with the ID of the vertex of each ID in the vertices file.
+pub fn read<V, E, D, S>(
+    vertices: &str,
+    edges: &str,
+    format: Format<'_>,
+    mut vertex_map: impl FnMut(&Record) -> V,
+    mut edge_map: impl FnMut(&Record) -> E,
+) -> Result<Parsed<V, E, D, S>, Error>
+where
+    E: Clone,
+    D: Direction,
+    S: BuildHasher + Default,
+{
+    let vertex_records = records(vertices, format, &[format.id])?;
+    let edge_records = records(edges, format, &[format.source, format.target])?;
+    let mut graph = Graph::default();
+    let mut ids = Ids::with_capacity_and_hasher(vertex_records.len(), S::default());
+
+    graph.reserve(vertex_records.len());
+
+    for record in vertex_records {
+        let id = record[format.id].clone();
+
+        if ids.contains_key(&id) {
+            return Err(Error::DuplicateVertex(id));
+        }
+
+        ids.insert(id, graph.insert_vertex(vertex_map(&record)));
+    }
+
+    for record in edge_records {
+        let [from_idx, to_idx] = [format.source, format.target].map(|column| {
+            ids.get(&record[column])
+                .copied()
+                .ok_or_else(|| Error::UnknownVertex(record[column].clone()))
+        });
+        let (from_idx, to_idx) = (from_idx?, to_idx?);
+        let weight = edge_map(&record);
+
+        graph.connect(from_idx, to_idx, weight);
+    }
+
+    Ok((graph, ids))
+}
+
+/// Write `graph` as a vertices file and an edges file, getting the fields of each vertex from
+/// `vertex_record` and of each edge from `edge_record`, in `O(|V| log |V| + |E| log |E|)`.
+///
+/// Vertices get their [`VertexIdx::as_usize`] as ID. The other columns are sorted by name, and
+/// rows without a column get an empty field. In undirected graphs, each edge is written once.
+pub fn write<V, E, D, S>(
+    graph: &Graph<V, E, D, S>,
+    vertices: &mut impl fmt::Write,
+    edges: &mut impl fmt::Write,
+    format: Format<'_>,
+    mut vertex_record: impl FnMut(&V) -> Record,
+    mut edge_record: impl FnMut(&E) -> Record,
+) -> fmt::Result
+where
+    D: Direction,
+    S: BuildHasher + Default,
+{
+    let mut vertex_rows: Vec<_> = graph
+        .vertices_iter()
+        .map(|(vertex_idx, vertex)| (vertex_idx, vertex_record(vertex.weight())))
+        .collect();
+    let mut edge_rows: Vec<_> = graph
+        .edges_iter()
+        .filter(|&(from_idx, to_idx, _)| D::IS_DIRECTED || from_idx <= to_idx)
+        .map(|(from_idx, to_idx, weight)| ((from_idx, to_idx), edge_record(weight)))
+        .collect();
+
+    vertex_rows.sort_unstable_by_key(|&(vertex_idx, _)| vertex_idx);
+    edge_rows.sort_unstable_by_key(|&(key, _)| key);
+
+    write_rows(
+        vertices,
+        format.delimiter,
+        &[format.id],
+        vertex_rows
+            .iter()
+            .map(|(vertex_idx, record)| ([vertex_idx.as_usize()], record)),
+    )?;
+    write_rows(
+        edges,
+        format.delimiter,
+        &[format.source, format.target],
+        edge_rows
+            .iter()
+            .map(|((from_idx, to_idx), record)| ([from_idx.as_usize(), to_idx.as_usize()], record)),
+    )
+}
+
+/// Write the header and the rows of a file, where the first columns are `id_columns`, filled
+/// with the IDs of each row.
+fn write_rows<'r, const N: usize>(
+    writer: &mut impl fmt::Write,
+    delimiter: char,
+    id_columns: &[&str; N],
+    rows: impl Iterator<Item = ([usize; N], &'r Record)> + Clone,
+) -> fmt::Result {
+    let columns: BTreeSet<_> = rows
+        .clone()
+        .flat_map(|(_, record)| record.keys())
+        .filter(|column| !id_columns.contains(&column.as_str()))
+        .collect();
+    let header = id_columns
+        .iter()
+        .copied()
+        .chain(columns.iter().map(|column| column.as_str()));
+
+    write_row(writer, delimiter, header)?;
+
+    for (ids, record) in rows {
+        let ids = ids.map(|id| id.to_string());
+        let fields = ids.iter().map(String::as_str).chain(
+            columns
+                .iter()
+                .map(|&column| record.get(column).map_or("", String::as_str)),
+        );
+
+        write_row(writer, delimiter, fields)?;
+    }
+
+    Ok(())
+}
+
+fn write_row<'f>(
+    writer: &mut impl fmt::Write,
+    delimiter: char,
+    fields: impl Iterator<Item = &'f str>,
+) -> fmt::Result {
+    for (i, field) in fields.enumerate() {
+        if i > 0 {
+            writer.write_char(delimiter)?;
+        }
+
+        if field.contains([delimiter, '"', '\r', '\n']) {
+            write!(writer, "\"{}\"", field.replace('"', "\"\""))?;
+        } else {
+            writer.write_str(field)?;
+        }
+    }
+
+    writer.write_str("\r\n")
+}
+
+/// The rows of a file as records, checking that the header has every column of `required`. A
+/// file without a header has no rows.
+fn records(input: &str, format: Format<'_>, required: &[&str]) -> Result<Vec<Record>, Error> {
+    let mut rows = parse(input, format.delimiter)?.into_iter();
+    let Some((_, header)) = rows.next() else {
+        return Ok(Vec::new());
+    };
+
+    if let Some(column) = required
+        .iter()
+        .find(|&column| !header.iter().any(|c| c == column))
+    {
+        return Err(Error::MissingColumn(column.to_string()));
+    }
+
+    rows.map(|(line, fields)| {
+        if fields.len() != header.len() {
+            return Err(Error::FieldCount { line });
+        }
+
+        Ok(header.iter().cloned().zip(fields).collect())
+    })
+    .collect()
+}
+
+/// Split a file into rows of fields, along with the line each row starts at. Blank lines are
+/// skipped.
+fn parse(input: &str, delimiter: char) -> Result<Vec<(usize, Vec<String>)>, Error> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut chars = input.chars().peekable();
+    let (mut line, mut row_line) = (1, 1);
+    // The line where the current quoted field starts, if the parser is inside one
+    let mut quote_line = None;
+
+    let mut end_row = |row: &mut Vec<String>, field: &mut String, row_line: usize| {
+        row.push(mem::take(field));
+
+        if row.len() > 1 || !row[0].is_empty() {
+            rows.push((row_line, mem::take(row)));
+        } else {
+            row.clear();
+        }
+    };
+
+    while let Some(c) = chars.next() {
+        if quote_line.is_some() {
+            match c {
+                '"' if chars.next_if_eq(&'"').is_some() => field.push('"'),
+                '"' => quote_line = None,
+                _ => {
+                    if c == '\n' {
+                        line += 1;
+                    }
+
+                    field.push(c);
+                }
+            }
+
+            continue;
+        }
+
+        match c {
+            '"' if field.is_empty() => quote_line = Some(line),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                end_row(&mut row, &mut field, row_line);
+                line += 1;
+                row_line = line;
+            }
+            _ if c == delimiter => row.push(mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+
+    if let Some(line) = quote_line {
+        return Err(Error::UnterminatedQuote { line });
+    }
+
+    if !row.is_empty() || !field.is_empty() {
+        end_row(&mut row, &mut field, row_line);
+    }
+
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{read, write, Error, Format, Record};
+    use crate::{
+        collections::DefaultHashBuilder,
+        graph::{Directed, Undirected},
+    };
+
+    #[test]
+    fn test_csv() {
+        let vertices = "id,name\r\na,\"Smith, \"\"Al\"\"\"\nb,Bo\n\n";
+        let edges = "source,target,weight,note\na,b,1.5,\"two\nlines\"\nb,b,2,\n";
+
+        let (graph, ids) = read::<_, _, Undirected, DefaultHashBuilder>(
+            vertices,
+            edges,
+            Format::default(),
+            |record| record["name"].clone(),
+            |record| {
+                (
+                    record["weight"]
+                        .parse::<f64>()
+                        .expect("The weight is a number"),
+                    record["note"].clone(),
+                )
+            },
+        )
+        .expect("The files are valid");
+
+        assert_eq!(
+            graph
+                .get_vertex(ids["a"])
+                .map(|vertex| vertex.weight().as_str()),
+            Some("Smith, \"Al\""),
+            "Wrong name of vertex a"
+        );
+        assert_eq!(
+            graph.get_edge(ids["b"], ids["a"]),
+            Some(&(1.5, "two\nlines".to_owned())),
+            "Undirected edges go both ways"
+        );
+        assert_eq!(graph.edge_count(), 3, "Wrong number of edges");
+
+        let (mut vertices_output, mut edges_output) = (String::new(), String::new());
+
+        write(
+            &graph,
+            &mut vertices_output,
+            &mut edges_output,
+            Format::default(),
+            |name| Record::from([("name".to_owned(), name.clone())]),
+            |(weight, note)| {
+                Record::from([
+                    ("weight".to_owned(), weight.to_string()),
+                    ("note".to_owned(), note.clone()),
+                ])
+            },
+        )
+        .expect("Writing to a String doesn't fail");
+
+        assert!(
+            edges_output.starts_with("source,target,note,weight\r\n"),
+            "Wrong header of the edges"
+        );
+
+        let (round_trip, round_trip_ids) = read::<_, _, Undirected, DefaultHashBuilder>(
+            &vertices_output,
+            &edges_output,
+            Format::default(),
+            |record| record["name"].clone(),
+            |record| record["note"].clone(),
+        )
+        .expect("The output is valid");
+        let a = round_trip
+            .get_vertex(round_trip_ids[&ids["a"].as_usize().to_string()])
+            .expect("Vertex a doesn't exist");
+
+        assert_eq!(a.weight(), "Smith, \"Al\"", "Wrong name of vertex a");
+        assert_eq!(
+            a.neighbors()
+                .map(|(_, note)| note.as_str())
+                .collect::<Vec<_>>(),
+            ["two\nlines"],
+            "Wrong edges of vertex a"
+        );
+
+        let tsv = Format {
+            delimiter: '\t',
+            id: "Id",
+            ..Format::default()
+        };
+
+        let read_unit = |vertices, edges, format| {
+            read::<(), (), Directed, DefaultHashBuilder>(vertices, edges, format, |_| (), |_| ())
+        };
+
+        assert!(
+            matches!(
+                read_unit("Id\na\n", "source\ttarget\na\tz\n", tsv),
+                Err(Error::UnknownVertex(id)) if id == "z"
+            ),
+            "Vertex z doesn't exist"
+        );
+        assert!(
+            matches!(
+                read_unit("id\na\n", "", tsv),
+                Err(Error::MissingColumn(column)) if column == "Id"
+            ),
+            "The ID column is missing"
+        );
+        assert!(
+            matches!(
+                read_unit("id,x\na\n", "", Format::default()),
+                Err(Error::FieldCount { line: 2 })
+            ),
+            "The row has too few fields"
+        );
+        assert!(
+            matches!(
+                read_unit("id\n\"a\n", "", Format::default()),
+                Err(Error::UnterminatedQuote { line: 2 })
+            ),
+            "The quote isn't closed"
+        );
+        assert!(
+            matches!(
+                read_unit("id\na\na\n", "", Format::default()),
+                Err(Error::DuplicateVertex(id)) if id == "a"
+            ),
+            "Vertex a is defined twice"
+        );
+    }
+}
@@ -0,0 +1,278 @@
+//! Edge lists, with one edge per line as `from to [weight]`, as used by benchmark datasets such
+//! as [SNAP](https://snap.stanford.edu/data/) and many others.
+//!
+//! Vertices are identified by their labels, which are any fields without the separator. Vertices
+//! without edges can't be represented, so they aren't written.
+
+use super::Error;
+use crate::{
+    collections::HashMap,
+    graph::{Direction, Graph, VertexIdx},
+};
+use alloc::{borrow::ToOwned, string::String, vec::Vec};
+use core::{fmt, hash::BuildHasher};
+
+/// The ID of the vertex of each label.
+type Labels<S> = HashMap<String, VertexIdx, S>;
+
+/// A graph read from an edge list, along with the ID of the vertex of each label.
+type Parsed<E, D, S> = (Graph<String, E, D, S>, Labels<S>);
+
+/// How the lines of an edge list are split into fields.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Format<'a> {
+    /// The separator of the fields of a line, or `None` for any amount of whitespace.
+    pub separator: Option<char>,
+    /// The prefixes of the lines to skip, in addition to the blank ones.
+    pub comments: &'a [&'a str],
+}
+
+/// Fields separated by whitespace, and comments starting with `#` or `%`.
+impl Default for Format<'_> {
+    fn default() -> Self {
+        Self {
+            separator: None,
+            comments: &["#", "%"],
+        }
+    }
+}
+
+impl Format<'_> {
+    fn fields<'l>(&self, line: &'l str) -> Vec<&'l str> {
+        match self.separator {
+            Some(separator) => line.split(separator).map(str::trim).collect(),
+            None => line.split_whitespace().collect(),
+        }
+    }
+}
+
+/// Read a graph from an edge list, in `O(n)`, where `n` is the length of `input`. Each vertex is
+/// weighted by its label, and each edge by `parse_weight` of its third field, if any, which
+/// returns `None` if the field is invalid. Lines are numbered from 1 in errors.
+///
+/// Returns the graph along with the ID of the vertex of each label.
+pub fn read<E, D, S>(
+    input: &str,
+    format: Format<'_>,
+    mut parse_weight: impl FnMut(Option<&str>) -> Option<E>,
+) -> Result<Parsed<E, D, S>, Error>
+where
+    E: Clone,
+    D: Direction,
+    S: BuildHasher + Default,
+{
+    let mut graph = Graph::default();
+    let mut labels = Labels::default();
+
+    for (i, line) in input.lines().enumerate() {
+        let line_number = i + 1;
+        let trimmed = line.trim();
+
+        if trimmed.is_empty()
+            || format
+                .comments
+                .iter()
+                .any(|comment| trimmed.starts_with(comment))
+        {
+            continue;
+        }
+
+        let (from, to, weight) = match format.fields(trimmed)[..] {
+            [from, to] => (from, to, None),
+            [from, to, weight] => (from, to, Some(weight)),
+            [_] => return Err(Error::MissingTarget { line: line_number }),
+            _ => return Err(Error::ExtraField { line: line_number }),
+        };
+        let weight = parse_weight(weight).ok_or(Error::InvalidWeight { line: line_number })?;
+        let [from_idx, to_idx] = [from, to].map(|label| {
+            *labels
+                .entry(label.to_owned())
+                .or_insert_with(|| graph.insert_vertex(label.to_owned()))
+        });
+
+        graph.connect(from_idx, to_idx, weight);
+    }
+
+    Ok((graph, labels))
+}
+
+/// Write the edges of `graph` as an edge list, labeling each vertex with `vertex_label` and
+/// writing the weight of each edge with `edge_weight`, unless it returns `None`, in
+/// `O(|V| log |V| + |E| log |E|)`.
+///
+/// The edges are sorted by the IDs of their vertices. In undirected graphs, each edge is written
+/// once. Fields are separated by a space if the format has no separator.
+pub fn write<V, E, D, S, L, W>(
+    graph: &Graph<V, E, D, S>,
+    writer: &mut impl fmt::Write,
+    format: Format<'_>,
+    mut vertex_label: impl FnMut(VertexIdx, &V) -> L,
+    mut edge_weight: impl FnMut(&E) -> Option<W>,
+) -> fmt::Result
+where
+    D: Direction,
+    S: BuildHasher + Default,
+    L: fmt::Display,
+    W: fmt::Display,
+{
+    let separator = format.separator.unwrap_or(' ');
+    let labels: HashMap<_, _, S> = graph
+        .vertices_iter()
+        .map(|(vertex_idx, vertex)| (vertex_idx, vertex_label(vertex_idx, vertex.weight())))
+        .collect();
+    let mut edges: Vec<_> = graph
+        .edges_iter()
+        .filter(|&(from_idx, to_idx, _)| D::IS_DIRECTED || from_idx <= to_idx)
+        .collect();
+
+    edges.sort_unstable_by_key(|&(from_idx, to_idx, _)| (from_idx, to_idx));
+
+    for (from_idx, to_idx, weight) in edges {
+        write!(
+            writer,
+            "{}{separator}{}",
+            labels[&from_idx], labels[&to_idx]
+        )?;
+
+        if let Some(weight) = edge_weight(weight) {
+            write!(writer, "{separator}{weight}")?;
+        }
+
+        writeln!(writer)?;
+    }
+
+    Ok(())
+}
+
+impl<E: Clone, D: Direction, S: BuildHasher + Default> Graph<String, E, D, S> {
+    /// Read a graph from an edge list in the default [`Format`], like [`read`], weighting each
+    /// edge by `parse_weight` of its third field, if any.
+    ///
+    /// ```
+    /// # use badlgorithms::graph::Graph;
+    /// let graph = Graph::<String, u32>::from_edge_list_str("# comment\na b 3\nb c 5\n", |weight| {
+    ///     weight?.parse().ok()
+    /// })
+    /// .unwrap();
+    ///
+    /// assert_eq!(graph.edge_count(), 2);
+    /// ```
+    pub fn from_edge_list_str(
+        input: &str,
+        parse_weight: impl FnMut(Option<&str>) -> Option<E>,
+    ) -> Result<Self, Error> {
+        read(input, Format::default(), parse_weight).map(|(graph, _)| graph)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{read, write, Error, Format};
+    use crate::{
+        collections::DefaultHashBuilder,
+        graph::{Directed, Graph, Undirected},
+    };
+
+    #[test]
+    fn test_edge_list() {
+        let input = "# A comment\n\na b 3\nb c 5\n  % Another comment\nc a 7\n";
+        let (graph, labels) =
+            read::<u32, Directed, DefaultHashBuilder>(input, Format::default(), |weight| {
+                weight?.parse().ok()
+            })
+            .expect("The input is valid");
+
+        assert_eq!(
+            (graph.len(), graph.edge_count()),
+            (3, 3),
+            "Wrong number of vertices or edges"
+        );
+        assert_eq!(
+            graph.get_edge(labels["b"], labels["c"]),
+            Some(&5),
+            "Wrong weight of the edge"
+        );
+        assert_eq!(
+            graph
+                .get_vertex(labels["a"])
+                .map(|vertex| vertex.weight().as_str()),
+            Some("a"),
+            "Vertices must be weighted by their labels"
+        );
+
+        let mut output = String::new();
+
+        write(
+            &graph,
+            &mut output,
+            Format::default(),
+            |_, label| label.clone(),
+            |weight| Some(*weight),
+        )
+        .expect("Writing to a String doesn't fail");
+
+        let round_trip =
+            Graph::<String, u32>::from_edge_list_str(&output, |weight| weight?.parse().ok())
+                .expect("The output is valid");
+
+        assert_eq!(round_trip.edge_count(), 3, "Wrong number of edges");
+
+        let csv = Format {
+            separator: Some(','),
+            comments: &["c "],
+        };
+        let (undirected, labels) =
+            read::<(), Undirected, DefaultHashBuilder>("c unweighted\n1, 2\n2,3\n", csv, |_| {
+                Some(())
+            })
+            .expect("The input is valid");
+
+        assert_eq!(undirected.edge_count(), 4, "Undirected edges go both ways");
+        assert!(
+            undirected.contains_edge(labels["2"], labels["1"]),
+            "Undirected edges go both ways"
+        );
+
+        let mut output = String::new();
+
+        write(
+            &undirected,
+            &mut output,
+            csv,
+            |_, label| label.clone(),
+            |_| None::<u8>,
+        )
+        .expect("Writing to a String doesn't fail");
+        assert_eq!(
+            output.lines().count(),
+            2,
+            "Undirected edges must be written once"
+        );
+
+        let unit = |_: Option<&str>| Some(());
+
+        assert!(
+            matches!(
+                read::<u32, Directed, DefaultHashBuilder>("a b\n", Format::default(), |weight| {
+                    weight?.parse().ok()
+                }),
+                Err(Error::InvalidWeight { line: 1 })
+            ),
+            "The weight is missing"
+        );
+        assert!(
+            matches!(
+                read::<(), Directed, DefaultHashBuilder>("a b\nc\n", Format::default(), unit),
+                Err(Error::MissingTarget { line: 2 })
+            ),
+            "The target is missing"
+        );
+        assert!(
+            matches!(
+                read::<(), Directed, DefaultHashBuilder>("a b c d\n", Format::default(), unit),
+                Err(Error::ExtraField { line: 1 })
+            ),
+            "There are too many fields"
+        );
+    }
+}
@@ -0,0 +1,469 @@
+//! [GraphML](http://graphml.graphdrawing.org/) files, as read and written by Gephi, NetworkX,
+//! yEd and others.
+//!
+//! The weights of vertices and edges are mapped from and to the `<data>` attributes of their
+//! elements by user closures, keyed by the `attr.name` of their `<key>`. Every attribute is
+//! written as a string. Nested graphs, hyperedges and ports aren't supported.
+
+use super::Error;
+use crate::graph::{Direction, Graph, VertexIdx};
+use quick_xml::{
+    events::{BytesDecl, BytesStart, BytesText, Event},
+    Reader, Writer,
+};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    hash::BuildHasher,
+    io::{self, BufRead, Write},
+};
+
+/// The attributes of a node or an edge, by name.
+pub type Attributes = HashMap<String, String>;
+
+/// The ID of the vertex of each GraphML node ID.
+type NodeIds<S> = HashMap<String, VertexIdx, S>;
+
+/// A graph read from a file, along with the ID of the vertex of each ID in the file.
+type Parsed<V, E, D, S> = (Graph<V, E, D, S>, NodeIds<S>);
+
+/// Read a graph from a GraphML file, creating each vertex with `vertex_map` and each edge with
+/// `edge_map` from their attributes, in `O(n)`, where `n` is the size of the file.
+/// Attributes that a node or edge doesn't have take the `<default>` of their key, if any.
+///
+/// The `edgedefault` of the file is ignored: the edges are directed or not depending on `D`.
+/// Fails with [`Error::DuplicateVertex`] if two nodes have the same ID.
+///
+/// Returns the graph along with the ID of the vertex of each GraphML node ID.
+pub fn read<V, E, D, S, R>(
+    reader: R,
+    mut vertex_map: impl FnMut(&Attributes) -> V,
+    mut edge_map: impl FnMut(&Attributes) -> E,
+) -> Result<Parsed<V, E, D, S>, Error>
+where
+    E: Clone,
+    D: Direction,
+    S: BuildHasher + Default,
+    R: BufRead,
+{
+    let mut reader = Reader::from_reader(reader);
+    let mut parser = Parser::default();
+    let mut buf = Vec::new();
+
+    loop {
+        match reader.read_event_into(&mut buf)? {
+            Event::Start(element) => parser.start(&element)?,
+            Event::Empty(element) => {
+                parser.start(&element)?;
+                parser.end(element.local_name().as_ref());
+            }
+            Event::End(element) => parser.end(element.local_name().as_ref()),
+            Event::Text(text) => parser.text.push_str(&text.unescape()?),
+            Event::CData(text) => parser
+                .text
+                .push_str(&text.decode().map_err(quick_xml::Error::from)?),
+            Event::Eof => break,
+            _ => {}
+        }
+
+        buf.clear();
+    }
+
+    let mut graph = Graph::default();
+    let mut ids = HashMap::with_capacity_and_hasher(parser.nodes.len(), S::default());
+
+    graph.reserve(parser.nodes.len());
+
+    for (id, attributes) in parser.nodes {
+        if ids.contains_key(&id) {
+            return Err(Error::DuplicateVertex(id));
+        }
+
+        ids.insert(id, graph.insert_vertex(vertex_map(&attributes)));
+    }
+
+    for (source, target, attributes) in parser.edges {
+        let from_idx = *ids.get(&source).ok_or(Error::UnknownVertex(source))?;
+        let to_idx = *ids.get(&target).ok_or(Error::UnknownVertex(target))?;
+        let weight = edge_map(&attributes);
+
+        graph.connect(from_idx, to_idx, weight);
+    }
+
+    Ok((graph, ids))
+}
+
+/// Write `graph` as a GraphML file, getting the attributes of each vertex from `vertex_attributes`
+/// and of each edge from `edge_attributes`, in `O(|V| log |V| + |E| log |E|)`.
+///
+/// Nodes get the ID `n<vertex ID>`. In undirected graphs, each edge is written once.
+pub fn write<V, E, D, S, W>(
+    graph: &Graph<V, E, D, S>,
+    writer: W,
+    mut vertex_attributes: impl FnMut(&V) -> Attributes,
+    mut edge_attributes: impl FnMut(&E) -> Attributes,
+) -> io::Result<()>
+where
+    D: Direction,
+    S: BuildHasher + Default,
+    W: Write,
+{
+    let mut vertices: Vec<_> = graph.vertices.iter().collect();
+
+    vertices.sort_unstable_by_key(|&(&vertex_idx, _)| vertex_idx);
+
+    let nodes: Vec<_> = vertices
+        .iter()
+        .map(|&(&vertex_idx, vertex)| (vertex_idx, sorted(vertex_attributes(&vertex.weight))))
+        .collect();
+    let mut edges = Vec::new();
+
+    for &(&from_idx, vertex) in &vertices {
+        let mut neighbors: Vec<_> = vertex.neighbors().collect();
+
+        neighbors.sort_unstable_by_key(|&(to_idx, _)| to_idx);
+
+        for (to_idx, weight) in neighbors {
+            if D::IS_DIRECTED || from_idx <= to_idx {
+                edges.push((from_idx, to_idx, sorted(edge_attributes(weight))));
+            }
+        }
+    }
+
+    // Every distinct attribute name gets a key, numbered across nodes and edges
+    let node_names: BTreeSet<_> = nodes.iter().flat_map(|(_, attrs)| attrs.keys()).collect();
+    let edge_names: BTreeSet<_> = edges
+        .iter()
+        .flat_map(|(_, _, attrs)| attrs.keys())
+        .collect();
+    let node_keys: HashMap<_, _> = node_names
+        .iter()
+        .enumerate()
+        .map(|(i, &name)| (name, format!("k{i}")))
+        .collect();
+    let edge_keys: HashMap<_, _> = edge_names
+        .iter()
+        .enumerate()
+        .map(|(i, &name)| (name, format!("k{}", node_keys.len() + i)))
+        .collect();
+
+    let mut writer = Writer::new_with_indent(writer, b' ', 2);
+
+    writer.write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))?;
+    writer
+        .create_element("graphml")
+        .with_attribute(("xmlns", "http://graphml.graphdrawing.org/xmlns"))
+        .write_inner_content(|writer| {
+            for (target, names, keys) in [
+                ("node", &node_names, &node_keys),
+                ("edge", &edge_names, &edge_keys),
+            ] {
+                for &name in names {
+                    writer
+                        .create_element("key")
+                        .with_attributes([
+                            ("id", keys[name].as_str()),
+                            ("for", target),
+                            ("attr.name", name.as_str()),
+                            ("attr.type", "string"),
+                        ])
+                        .write_empty()?;
+                }
+            }
+
+            let edge_default = if D::IS_DIRECTED {
+                "directed"
+            } else {
+                "undirected"
+            };
+
+            writer
+                .create_element("graph")
+                .with_attribute(("edgedefault", edge_default))
+                .write_inner_content(|writer| {
+                    for (vertex_idx, attrs) in &nodes {
+                        writer
+                            .create_element("node")
+                            .with_attribute(("id", node_id(*vertex_idx).as_str()))
+                            .write_inner_content(|writer| write_data(writer, attrs, &node_keys))?;
+                    }
+
+                    for (from_idx, to_idx, attrs) in &edges {
+                        writer
+                            .create_element("edge")
+                            .with_attributes([
+                                ("source", node_id(*from_idx).as_str()),
+                                ("target", node_id(*to_idx).as_str()),
+                            ])
+                            .write_inner_content(|writer| write_data(writer, attrs, &edge_keys))?;
+                    }
+
+                    Ok(())
+                })?;
+
+            Ok(())
+        })?;
+
+    Ok(())
+}
+
+fn sorted(attributes: Attributes) -> BTreeMap<String, String> {
+    attributes.into_iter().collect()
+}
+
+fn node_id(vertex_idx: VertexIdx) -> String {
+    format!("n{}", vertex_idx.0)
+}
+
+fn write_data<W: Write>(
+    writer: &mut Writer<W>,
+    attributes: &BTreeMap<String, String>,
+    keys: &HashMap<&String, String>,
+) -> io::Result<()> {
+    for (name, value) in attributes {
+        writer
+            .create_element("data")
+            .with_attribute(("key", keys[name].as_str()))
+            .write_text_content(BytesText::new(value))?;
+    }
+
+    Ok(())
+}
+
+/// The nodes and edges of a GraphML file, with their attributes by name.
+#[derive(Default)]
+struct Parser {
+    /// The `for` and `attr.name` of each key, by ID
+    keys: HashMap<String, (String, String)>,
+    /// The default value of each key, by ID
+    defaults: HashMap<String, String>,
+    nodes: Vec<(String, Attributes)>,
+    edges: Vec<(String, String, Attributes)>,
+    /// The node or edge currently being read, which is the last one of its kind
+    element: Option<Element>,
+    /// The ID of the key currently being defined
+    key: Option<String>,
+    /// The key of the `<data>` currently being read
+    data_key: Option<String>,
+    /// The text read since the last `<data>` or `<default>` started
+    text: String,
+}
+
+#[derive(Clone, Copy)]
+enum Element {
+    Node,
+    Edge,
+}
+
+impl Parser {
+    fn start(&mut self, element: &BytesStart) -> Result<(), Error> {
+        match element.local_name().as_ref() {
+            b"key" => {
+                let id = required(element, "key", "id")?;
+                let target = attribute(element, "for")?.unwrap_or_else(|| "all".to_owned());
+                let name = attribute(element, "attr.name")?.unwrap_or_else(|| id.clone());
+
+                self.keys.insert(id.clone(), (target, name));
+                self.key = Some(id);
+            }
+            b"default" => self.text.clear(),
+            b"node" => {
+                let attributes = self.defaults_for("node");
+
+                self.nodes
+                    .push((required(element, "node", "id")?, attributes));
+                self.element = Some(Element::Node);
+            }
+            b"edge" => {
+                let attributes = self.defaults_for("edge");
+
+                self.edges.push((
+                    required(element, "edge", "source")?,
+                    required(element, "edge", "target")?,
+                    attributes,
+                ));
+                self.element = Some(Element::Edge);
+            }
+            b"data" => {
+                self.data_key = Some(required(element, "data", "key")?);
+                self.text.clear();
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn end(&mut self, name: &[u8]) {
+        match name {
+            b"key" => self.key = None,
+            b"node" | b"edge" => self.element = None,
+            b"default" => {
+                if let Some(key) = &self.key {
+                    self.defaults
+                        .insert(key.clone(), std::mem::take(&mut self.text));
+                }
+            }
+            b"data" => {
+                let Some(key) = self.data_key.take() else {
+                    return;
+                };
+                let name = self
+                    .keys
+                    .get(&key)
+                    .map_or(key.clone(), |(_, name)| name.clone());
+                let value = std::mem::take(&mut self.text);
+
+                // Data outside of nodes and edges is about the whole graph
+                let attributes = match self.element {
+                    Some(Element::Node) => self.nodes.last_mut().map(|(_, attrs)| attrs),
+                    Some(Element::Edge) => self.edges.last_mut().map(|(_, _, attrs)| attrs),
+                    None => None,
+                };
+
+                if let Some(attributes) = attributes {
+                    attributes.insert(name, value);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// The default attributes of every `target`, which is either `"node"` or `"edge"`.
+    fn defaults_for(&self, target: &str) -> Attributes {
+        self.defaults
+            .iter()
+            .filter_map(|(key, value)| {
+                let (key_target, name) = &self.keys[key];
+
+                (key_target == target || key_target == "all").then(|| (name.clone(), value.clone()))
+            })
+            .collect()
+    }
+}
+
+fn attribute(element: &BytesStart, name: &str) -> Result<Option<String>, Error> {
+    match element
+        .try_get_attribute(name)
+        .map_err(quick_xml::Error::from)?
+    {
+        Some(attribute) => Ok(Some(attribute.unescape_value()?.into_owned())),
+        None => Ok(None),
+    }
+}
+
+fn required(
+    element: &BytesStart,
+    element_name: &'static str,
+    name: &'static str,
+) -> Result<String, Error> {
+    attribute(element, name)?.ok_or(Error::MissingField {
+        object: element_name,
+        field: name,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{read, write, Attributes, Error};
+    use crate::{
+        collections::DefaultHashBuilder,
+        graph::{Directed, Undirected},
+    };
+
+    #[test]
+    fn test_graphml() {
+        let file = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <graphml xmlns="http://graphml.graphdrawing.org/xmlns">
+              <key id="d0" for="node" attr.name="color" attr.type="string">
+                <default>gray</default>
+              </key>
+              <key id="d1" for="edge" attr.name="weight" attr.type="double"/>
+              <graph id="G" edgedefault="directed">
+                <data key="d0">ignored</data>
+                <node id="a"><data key="d0">red &amp; blue</data></node>
+                <node id="b"/>
+                <edge source="a" target="b"><data key="d1">1.5</data></edge>
+                <edge source="b" target="b"><data key="d1">2</data></edge>
+              </graph>
+            </graphml>"#;
+
+        let (graph, ids) = read::<_, _, Undirected, DefaultHashBuilder, _>(
+            file.as_bytes(),
+            |attrs| attrs["color"].clone(),
+            |attrs| {
+                attrs["weight"]
+                    .parse::<f64>()
+                    .expect("The weight is a number")
+            },
+        )
+        .expect("The file is valid");
+
+        let a = graph.get_vertex(ids["a"]).expect("Node a doesn't exist");
+        let b = graph.get_vertex(ids["b"]).expect("Node b doesn't exist");
+
+        assert_eq!(a.weight(), "red & blue", "Wrong color for node a");
+        assert_eq!(b.weight(), "gray", "Node b must have the default color");
+        assert!(b.is_adjacent(ids["a"]), "Undirected edges go both ways");
+        assert!(b.is_adjacent(ids["b"]), "Wrong self-loop");
+
+        let mut output = Vec::new();
+
+        write(
+            &graph,
+            &mut output,
+            |color| Attributes::from([("color".to_owned(), color.clone())]),
+            |weight| Attributes::from([("weight".to_owned(), weight.to_string())]),
+        )
+        .expect("Writing to a Vec doesn't fail");
+
+        let output = String::from_utf8(output).expect("The output is UTF-8");
+
+        assert_eq!(
+            output.matches("<edge ").count(),
+            2,
+            "Undirected edges must be written once"
+        );
+
+        let (round_trip, round_trip_ids) = read::<_, _, Undirected, DefaultHashBuilder, _>(
+            output.as_bytes(),
+            |attrs| attrs["color"].clone(),
+            |attrs| {
+                attrs["weight"]
+                    .parse::<f64>()
+                    .expect("The weight is a number")
+            },
+        )
+        .expect("The output is valid");
+        let a = round_trip
+            .get_vertex(round_trip_ids[&format!("n{}", ids["a"].0)])
+            .expect("Node a doesn't exist");
+
+        assert_eq!(a.weight(), "red & blue", "Wrong color for node a");
+        assert_eq!(
+            a.neighbors().map(|(_, weight)| *weight).collect::<Vec<_>>(),
+            [1.5],
+            "Wrong edges of node a"
+        );
+
+        let dangling =
+            r#"<graphml><graph><node id="a"/><edge source="a" target="z"/></graph></graphml>"#;
+
+        assert!(
+            matches!(
+                read::<(), (), Directed, DefaultHashBuilder, _>(dangling.as_bytes(), |_| (), |_| ()),
+                Err(Error::UnknownVertex(id)) if id == "z"
+            ),
+            "Node z doesn't exist"
+        );
+
+        let duplicate = r#"<graphml><graph><node id="a"/><node id="a"/></graph></graphml>"#;
+
+        assert!(
+            matches!(
+                read::<(), (), Directed, DefaultHashBuilder, _>(duplicate.as_bytes(), |_| (), |_| ()),
+                Err(Error::DuplicateVertex(id)) if id == "a"
+            ),
+            "Node a is defined twice"
+        );
+    }
+}
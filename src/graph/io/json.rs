@@ -0,0 +1,272 @@
+//! The node-link JSON format, as used by D3 and NetworkX:
+//!
+//! ```json
+//! {
+//!   "directed": true,
+//!   "multigraph": false,
+//!   "graph": {},
+//!   "nodes": [{ "id": 0, "color": "red" }, { "id": 1 }],
+//!   "links": [{ "source": 0, "target": 1, "weight": 3 }]
+//! }
+//! ```
+//!
+//! The weights of vertices and edges are mapped from and to the other fields of their objects
+//! by user closures. Links refer to nodes by their `id`, not by their position in `nodes`.
+
+use super::Error;
+use crate::graph::{Direction, Graph, VertexIdx};
+use serde_json::{json, Map, Value};
+use std::{
+    collections::HashMap,
+    hash::BuildHasher,
+    io::{self, Read, Write},
+};
+
+/// The fields of a node or a link, by name.
+pub type Attributes = Map<String, Value>;
+
+/// The ID of the vertex of each node ID. IDs that aren't strings are keyed by their JSON,
+/// so the node `{"id": 3}` has the key `"3"`.
+type NodeIds<S> = HashMap<String, VertexIdx, S>;
+
+/// A graph read from a file, along with the ID of the vertex of each ID in the file.
+type Parsed<V, E, D, S> = (Graph<V, E, D, S>, NodeIds<S>);
+
+/// Read a graph in the node-link format, creating each vertex with `vertex_map` and each edge with
+/// `edge_map` from all the fields of their objects, in `O(n)`, where `n` is the size of the input.
+/// The links may also be under `"edges"`, as newer versions of NetworkX write them.
+///
+/// The `directed` field is ignored: the edges are directed or not depending on `D`.
+/// Fails with [`Error::DuplicateVertex`] if two nodes have the same ID.
+///
+/// Returns the graph along with the ID of the vertex of each node ID.
+pub fn read<V, E, D, S, R>(
+    reader: R,
+    mut vertex_map: impl FnMut(&Attributes) -> V,
+    mut edge_map: impl FnMut(&Attributes) -> E,
+) -> Result<Parsed<V, E, D, S>, Error>
+where
+    E: Clone,
+    D: Direction,
+    S: BuildHasher + Default,
+    R: Read,
+{
+    let document: Value = serde_json::from_reader(reader)?;
+    let nodes = array(&document, "nodes")?;
+    let links = array(&document, "links").or_else(|_| array(&document, "edges"))?;
+
+    let mut graph = Graph::default();
+    let mut ids = HashMap::with_capacity_and_hasher(nodes.len(), S::default());
+
+    graph.reserve(nodes.len());
+
+    for node in nodes {
+        let attributes = object(node, "node")?;
+        let id = field(attributes, "node", "id")?;
+
+        if ids.contains_key(&id) {
+            return Err(Error::DuplicateVertex(id));
+        }
+
+        ids.insert(id, graph.insert_vertex(vertex_map(attributes)));
+    }
+
+    for link in links {
+        let attributes = object(link, "link")?;
+        let source = field(attributes, "link", "source")?;
+        let target = field(attributes, "link", "target")?;
+        let from_idx = *ids.get(&source).ok_or(Error::UnknownVertex(source))?;
+        let to_idx = *ids.get(&target).ok_or(Error::UnknownVertex(target))?;
+        let weight = edge_map(attributes);
+
+        graph.connect(from_idx, to_idx, weight);
+    }
+
+    Ok((graph, ids))
+}
+
+/// Write `graph` in the node-link format, getting the fields of each vertex from `vertex_attributes`
+/// and of each edge from `edge_attributes`, in `O(|V| log |V| + |E| log |E|)`.
+///
+/// Nodes get their vertex ID as their `id`, overriding any `id`, `source` or `target` field
+/// returned by the closures. In undirected graphs, each edge is written once.
+pub fn write<V, E, D, S, W>(
+    graph: &Graph<V, E, D, S>,
+    writer: W,
+    mut vertex_attributes: impl FnMut(&V) -> Attributes,
+    mut edge_attributes: impl FnMut(&E) -> Attributes,
+) -> io::Result<()>
+where
+    D: Direction,
+    S: BuildHasher + Default,
+    W: Write,
+{
+    let mut vertices: Vec<_> = graph.vertices.iter().collect();
+
+    vertices.sort_unstable_by_key(|&(&vertex_idx, _)| vertex_idx);
+
+    let mut nodes = Vec::with_capacity(vertices.len());
+    let mut links = Vec::new();
+
+    for &(&from_idx, vertex) in &vertices {
+        let mut attributes = vertex_attributes(&vertex.weight);
+
+        attributes.insert("id".to_owned(), from_idx.0.into());
+        nodes.push(Value::Object(attributes));
+
+        let mut neighbors: Vec<_> = vertex.neighbors().collect();
+
+        neighbors.sort_unstable_by_key(|&(to_idx, _)| to_idx);
+
+        for (to_idx, weight) in neighbors {
+            if D::IS_DIRECTED || from_idx <= to_idx {
+                let mut attributes = edge_attributes(weight);
+
+                attributes.insert("source".to_owned(), from_idx.0.into());
+                attributes.insert("target".to_owned(), to_idx.0.into());
+                links.push(Value::Object(attributes));
+            }
+        }
+    }
+
+    let document = json!({
+        "directed": D::IS_DIRECTED,
+        "multigraph": false,
+        "graph": {},
+        "nodes": nodes,
+        "links": links,
+    });
+
+    serde_json::to_writer(writer, &document)?;
+    Ok(())
+}
+
+fn array<'a>(document: &'a Value, field: &'static str) -> Result<&'a Vec<Value>, Error> {
+    document
+        .get(field)
+        .and_then(Value::as_array)
+        .ok_or(Error::MissingField {
+            object: "graph",
+            field,
+        })
+}
+
+fn object<'a>(value: &'a Value, object: &'static str) -> Result<&'a Attributes, Error> {
+    value.as_object().ok_or(Error::NotAnObject(object))
+}
+
+/// The value of an ID field as a key of [`NodeIds`].
+fn field(
+    attributes: &Attributes,
+    object: &'static str,
+    field: &'static str,
+) -> Result<String, Error> {
+    match attributes.get(field) {
+        Some(Value::String(id)) => Ok(id.clone()),
+        Some(Value::Null) | None => Err(Error::MissingField { object, field }),
+        Some(id) => Ok(id.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{read, write, Attributes, Error};
+    use crate::{
+        collections::DefaultHashBuilder,
+        graph::{Directed, Undirected},
+    };
+    use serde_json::{json, Value};
+
+    #[test]
+    fn test_json() {
+        let document = json!({
+            "directed": false,
+            "nodes": [{ "id": "a", "color": "red" }, { "id": 7 }],
+            "edges": [
+                { "source": "a", "target": 7, "weight": 1.5 },
+                { "source": 7, "target": 7, "weight": 2 },
+            ],
+        })
+        .to_string();
+
+        let color = |attrs: &Attributes| {
+            attrs
+                .get("color")
+                .and_then(Value::as_str)
+                .map(str::to_owned)
+        };
+        let weight = |attrs: &Attributes| attrs["weight"].as_f64().expect("The weight is a number");
+        let (graph, ids) =
+            read::<_, _, Undirected, DefaultHashBuilder, _>(document.as_bytes(), color, weight)
+                .expect("The document is valid");
+
+        let a = graph.get_vertex(ids["a"]).expect("Node a doesn't exist");
+        let b = graph.get_vertex(ids["7"]).expect("Node 7 doesn't exist");
+
+        assert_eq!(a.weight().as_deref(), Some("red"), "Wrong color for node a");
+        assert_eq!(b.weight(), &None, "Node 7 has no color");
+        assert!(b.is_adjacent(ids["a"]), "Undirected edges go both ways");
+        assert!(b.is_adjacent(ids["7"]), "Wrong self-loop");
+
+        let mut output = Vec::new();
+
+        write(
+            &graph,
+            &mut output,
+            |color| {
+                color
+                    .iter()
+                    .map(|color| ("color".to_owned(), color.as_str().into()))
+                    .collect()
+            },
+            |&weight| Attributes::from_iter([("weight".to_owned(), weight.into())]),
+        )
+        .expect("Writing to a Vec doesn't fail");
+
+        let output: Value = serde_json::from_slice(&output).expect("The output is JSON");
+
+        assert_eq!(output["directed"], false, "The graph is undirected");
+        assert_eq!(
+            output["links"].as_array().map(Vec::len),
+            Some(2),
+            "Undirected edges must be written once"
+        );
+
+        let (round_trip, round_trip_ids) = read::<_, _, Undirected, DefaultHashBuilder, _>(
+            output.to_string().as_bytes(),
+            color,
+            weight,
+        )
+        .expect("The output is valid");
+        let a = round_trip
+            .get_vertex(round_trip_ids[&ids["a"].0.to_string()])
+            .expect("Node a doesn't exist");
+
+        assert_eq!(a.weight().as_deref(), Some("red"), "Wrong color for node a");
+        assert_eq!(
+            a.neighbors().map(|(_, weight)| *weight).collect::<Vec<_>>(),
+            [1.5],
+            "Wrong edges of node a"
+        );
+
+        let dangling = json!({ "nodes": [{ "id": 0 }], "links": [{ "source": 0, "target": 1 }] });
+
+        assert!(
+            matches!(
+                read::<(), (), Directed, DefaultHashBuilder, _>(dangling.to_string().as_bytes(), |_| (), |_| ()),
+                Err(Error::UnknownVertex(id)) if id == "1"
+            ),
+            "Node 1 doesn't exist"
+        );
+
+        let duplicate = json!({ "nodes": [{ "id": 0 }, { "id": 0 }], "links": [] });
+
+        assert!(
+            matches!(
+                read::<(), (), Directed, DefaultHashBuilder, _>(duplicate.to_string().as_bytes(), |_| (), |_| ()),
+                Err(Error::DuplicateVertex(id)) if id == "0"
+            ),
+            "Node 0 is defined twice"
+        );
+    }
+}
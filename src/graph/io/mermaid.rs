@@ -0,0 +1,183 @@
+//! [Mermaid](https://mermaid.js.org/syntax/flowchart.html) flowcharts, which Markdown renderers
+//! such as GitHub's draw from a fenced `mermaid` code block.
+//!
+//! Only writing is supported. Each vertex becomes a node with the ID `v<vertex_idx>`, so the
+//! labels can be any text; quotes and other special characters are escaped.
+
+use crate::graph::{Direction, Graph, VertexIdx};
+use alloc::{string::String, vec::Vec};
+use core::{fmt, hash::BuildHasher};
+
+/// The direction the flowchart is drawn in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Orientation {
+    #[default]
+    TopDown,
+    BottomUp,
+    LeftRight,
+    RightLeft,
+}
+
+impl Orientation {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::TopDown => "TD",
+            Self::BottomUp => "BT",
+            Self::LeftRight => "LR",
+            Self::RightLeft => "RL",
+        }
+    }
+}
+
+/// A label as a quoted Mermaid string, with the characters Mermaid would interpret escaped as
+/// entity codes.
+struct Label<L>(L);
+
+impl<L: fmt::Display> fmt::Display for Label<L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use fmt::Write;
+
+        struct Escaper<'a, 'b>(&'a mut fmt::Formatter<'b>);
+
+        impl fmt::Write for Escaper<'_, '_> {
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                for c in s.chars() {
+                    match c {
+                        '"' => self.0.write_str("#quot;")?,
+                        '#' => self.0.write_str("#35;")?,
+                        '<' => self.0.write_str("#lt;")?,
+                        '>' => self.0.write_str("#gt;")?,
+                        '\n' => self.0.write_str("<br>")?,
+                        c => self.0.write_char(c)?,
+                    }
+                }
+
+                Ok(())
+            }
+        }
+
+        f.write_char('"')?;
+        write!(Escaper(f), "{}", self.0)?;
+        f.write_char('"')
+    }
+}
+
+/// Write `graph` as a Mermaid flowchart, labeling each vertex with `vertex_label` and each edge
+/// with `edge_label`, unless it returns `None`, in `O(|V| log |V| + |E| log |E|)`.
+///
+/// The vertices and edges are sorted by their IDs. Edges of directed graphs are drawn as arrows,
+/// and those of undirected graphs as lines, once per edge.
+pub fn write<V, E, D, S, L, W>(
+    graph: &Graph<V, E, D, S>,
+    writer: &mut impl fmt::Write,
+    orientation: Orientation,
+    mut vertex_label: impl FnMut(VertexIdx, &V) -> L,
+    mut edge_label: impl FnMut(&E) -> Option<W>,
+) -> fmt::Result
+where
+    D: Direction,
+    S: BuildHasher + Default,
+    L: fmt::Display,
+    W: fmt::Display,
+{
+    let arrow = if D::IS_DIRECTED { "-->" } else { "---" };
+    let mut vertices: Vec<_> = graph.vertices_iter().collect();
+    let mut edges: Vec<_> = graph
+        .edges_iter()
+        .filter(|&(from_idx, to_idx, _)| D::IS_DIRECTED || from_idx <= to_idx)
+        .collect();
+
+    vertices.sort_unstable_by_key(|&(vertex_idx, _)| vertex_idx);
+    edges.sort_unstable_by_key(|&(from_idx, to_idx, _)| (from_idx, to_idx));
+
+    writeln!(writer, "flowchart {}", orientation.as_str())?;
+
+    for (vertex_idx, vertex) in vertices {
+        writeln!(
+            writer,
+            "    v{}[{}]",
+            vertex_idx.as_usize(),
+            Label(vertex_label(vertex_idx, vertex.weight()))
+        )?;
+    }
+
+    for (from_idx, to_idx, weight) in edges {
+        write!(writer, "    v{} {arrow}", from_idx.as_usize())?;
+
+        if let Some(label) = edge_label(weight) {
+            write!(writer, "|{}|", Label(label))?;
+        }
+
+        writeln!(writer, " v{}", to_idx.as_usize())?;
+    }
+
+    Ok(())
+}
+
+/// Write `graph` as a Mermaid flowchart like [`write`], into a new string.
+///
+/// ```
+/// # use badlgorithms::graph::{io::mermaid::{to_mermaid, Orientation}, Graph};
+/// let mut graph = Graph::<&str, u32>::new();
+/// let [a, b] = ["a", "b"].map(|label| graph.insert_vertex(label));
+///
+/// graph.insert_or_update_edge(a, b, 3);
+///
+/// let chart = to_mermaid(&graph, Orientation::LeftRight, |_, &label| label, |&weight| {
+///     Some(weight)
+/// });
+///
+/// assert!(chart.starts_with("flowchart LR\n"));
+/// assert!(chart.ends_with(&format!("v{} -->|\"3\"| v{}\n", a.as_usize(), b.as_usize())));
+/// ```
+pub fn to_mermaid<V, E, D, S, L, W>(
+    graph: &Graph<V, E, D, S>,
+    orientation: Orientation,
+    vertex_label: impl FnMut(VertexIdx, &V) -> L,
+    edge_label: impl FnMut(&E) -> Option<W>,
+) -> String
+where
+    D: Direction,
+    S: BuildHasher + Default,
+    L: fmt::Display,
+    W: fmt::Display,
+{
+    let mut output = String::new();
+
+    write(graph, &mut output, orientation, vertex_label, edge_label)
+        .expect("Writing to a String doesn't fail");
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{to_mermaid, Orientation};
+    use crate::graph::{Graph, Undirected};
+    use alloc::format;
+
+    #[test]
+    fn test_mermaid() {
+        //  a — b
+        let mut graph = Graph::<&str, u8, Undirected>::new();
+        let [a, b] = ["a \"quoted\"", "<b> #1"].map(|label| graph.insert_vertex(label));
+
+        graph.insert_or_update_undirected_edge(a, b, 7);
+
+        let chart = to_mermaid(
+            &graph,
+            Orientation::default(),
+            |_, &label| label,
+            |_| None::<u8>,
+        );
+        let [a, b] = [a, b].map(|vertex_idx| vertex_idx.as_usize());
+
+        assert_eq!(
+            chart,
+            format!(
+                "flowchart TD\n    v{a}[\"a #quot;quoted#quot;\"]\n    v{b}[\"#lt;b#gt; #35;1\"]\n    v{a} --- v{b}\n"
+            ),
+            "Wrong flowchart"
+        );
+    }
+}
@@ -0,0 +1,108 @@
+//! Reading and writing graphs in the file formats of other tools.
+//!
+//! Every reader fails with the same [`Error`], whatever the format.
+
+use alloc::string::String;
+use core::{error, fmt};
+
+pub mod csv;
+pub mod edge_list;
+#[cfg(feature = "graphml")]
+pub mod graphml;
+#[cfg(feature = "json")]
+pub mod json;
+pub mod mermaid;
+
+/// The reason a file couldn't be read as a graph. Lines are numbered from 1.
+///
+/// Some variants only exist with the feature of their format, so matching on it needs a wildcard.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// The file isn't valid XML, or it couldn't be read.
+    #[cfg(feature = "graphml")]
+    Xml(quick_xml::Error),
+    /// The input isn't valid JSON, or it couldn't be read.
+    #[cfg(feature = "json")]
+    Json(serde_json::Error),
+    /// A quoted field starting at the line isn't closed.
+    UnterminatedQuote { line: usize },
+    /// The row starting at the line doesn't have as many fields as the header.
+    FieldCount { line: usize },
+    /// The header of a file doesn't have a column that identifies vertices.
+    MissingColumn(String),
+    /// A line has fewer than two fields, so it has no target vertex.
+    MissingTarget { line: usize },
+    /// A line has more than three fields.
+    ExtraField { line: usize },
+    /// The weight of a line couldn't be parsed.
+    InvalidWeight { line: usize },
+    /// A required field or attribute is missing or has the wrong type, such as the `id` of a
+    /// node.
+    MissingField {
+        object: &'static str,
+        field: &'static str,
+    },
+    /// A node or a link isn't a JSON object.
+    NotAnObject(&'static str),
+    /// Two vertices have the same ID.
+    DuplicateVertex(String),
+    /// An edge points to a vertex ID that isn't defined.
+    UnknownVertex(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            #[cfg(feature = "graphml")]
+            Self::Xml(err) => write!(f, "invalid GraphML: {err}"),
+            #[cfg(feature = "json")]
+            Self::Json(err) => write!(f, "invalid JSON: {err}"),
+            Self::UnterminatedQuote { line } => {
+                write!(f, "the quoted field at line {line} isn't closed")
+            }
+            Self::FieldCount { line } => {
+                write!(
+                    f,
+                    "the row at line {line} has a different number of fields than the header"
+                )
+            }
+            Self::MissingColumn(column) => write!(f, "the header has no {column:?} column"),
+            Self::MissingTarget { line } => write!(f, "line {line} has no target vertex"),
+            Self::ExtraField { line } => write!(f, "line {line} has more than three fields"),
+            Self::InvalidWeight { line } => write!(f, "line {line} has an invalid weight"),
+            Self::MissingField { object, field } => {
+                write!(f, "a {object} has no valid `{field}` field")
+            }
+            Self::NotAnObject(object) => write!(f, "a {object} isn't an object"),
+            Self::DuplicateVertex(id) => write!(f, "the vertex ID {id:?} is defined twice"),
+            Self::UnknownVertex(id) => write!(f, "an edge points to the unknown vertex {id:?}"),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            #[cfg(feature = "graphml")]
+            Self::Xml(err) => Some(err),
+            #[cfg(feature = "json")]
+            Self::Json(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "graphml")]
+impl From<quick_xml::Error> for Error {
+    fn from(err: quick_xml::Error) -> Self {
+        Self::Xml(err)
+    }
+}
+
+#[cfg(feature = "json")]
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Json(err)
+    }
+}
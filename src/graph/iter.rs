@@ -1,52 +1,182 @@
-use super::{Vertex, VertexIdx};
-use std::collections::hash_map::{
+use super::{small_map, DefaultHashBuilder, Vertex, VertexIdx};
+use crate::collections::hash_map::{
     IntoIter as HashMapIntoIter, Iter as HashMapIter, IterMut as HashMapIterMut,
+    Keys as HashMapKeys,
 };
 
-pub struct IntoVerticesIterator<V, E>(pub(super) HashMapIntoIter<VertexIdx, Vertex<V, E>>);
+pub struct IntoVerticesIterator<V, E, S = DefaultHashBuilder>(
+    pub(super) HashMapIntoIter<VertexIdx, Vertex<V, E, S>>,
+);
 
-impl<V, E> Iterator for IntoVerticesIterator<V, E> {
-    type Item = (VertexIdx, Vertex<V, E>);
+impl<V, E, S> Iterator for IntoVerticesIterator<V, E, S> {
+    type Item = (VertexIdx, Vertex<V, E, S>);
 
     fn next(&mut self) -> Option<Self::Item> {
         self.0.next()
     }
 }
 
-impl<V, E> ExactSizeIterator for IntoVerticesIterator<V, E> {
+impl<V, E, S> ExactSizeIterator for IntoVerticesIterator<V, E, S> {
     fn len(&self) -> usize {
         self.0.len()
     }
 }
 
-pub struct VerticesIter<'a, V, E>(pub(super) HashMapIter<'a, VertexIdx, Vertex<V, E>>);
+pub struct VerticesIter<'a, V, E, S = DefaultHashBuilder>(
+    pub(super) HashMapIter<'a, VertexIdx, Vertex<V, E, S>>,
+);
 
-impl<'a, V, E> Iterator for VerticesIter<'a, V, E> {
-    type Item = (VertexIdx, &'a Vertex<V, E>);
+impl<'a, V, E, S> Iterator for VerticesIter<'a, V, E, S> {
+    type Item = (VertexIdx, &'a Vertex<V, E, S>);
 
     fn next(&mut self) -> Option<Self::Item> {
         self.0.next().map(|(idx, v)| (*idx, v))
     }
 }
 
-impl<'a, V, E> ExactSizeIterator for VerticesIter<'a, V, E> {
+impl<'a, V, E, S> ExactSizeIterator for VerticesIter<'a, V, E, S> {
     fn len(&self) -> usize {
         self.0.len()
     }
 }
 
-pub struct VerticesIterMut<'a, V, E>(pub(super) HashMapIterMut<'a, VertexIdx, Vertex<V, E>>);
+pub struct VertexIndices<'a, V, E, S = DefaultHashBuilder>(
+    pub(super) HashMapKeys<'a, VertexIdx, Vertex<V, E, S>>,
+);
 
-impl<'a, V, E> Iterator for VerticesIterMut<'a, V, E> {
-    type Item = (VertexIdx, &'a mut Vertex<V, E>);
+impl<V, E, S> Iterator for VertexIndices<'_, V, E, S> {
+    type Item = VertexIdx;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().copied()
+    }
+}
+
+impl<V, E, S> ExactSizeIterator for VertexIndices<'_, V, E, S> {
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+pub struct VerticesIterMut<'a, V, E, S = DefaultHashBuilder>(
+    pub(super) HashMapIterMut<'a, VertexIdx, Vertex<V, E, S>>,
+);
+
+impl<'a, V, E, S> Iterator for VerticesIterMut<'a, V, E, S> {
+    type Item = (VertexIdx, &'a mut Vertex<V, E, S>);
 
     fn next(&mut self) -> Option<Self::Item> {
         self.0.next().map(|(idx, v)| (*idx, v))
     }
 }
 
-impl<'a, V, E> ExactSizeIterator for VerticesIterMut<'a, V, E> {
+impl<'a, V, E, S> ExactSizeIterator for VerticesIterMut<'a, V, E, S> {
     fn len(&self) -> usize {
         self.0.len()
     }
 }
+
+pub struct EdgesIter<'a, V, E, S = DefaultHashBuilder> {
+    pub(super) vertices: HashMapIter<'a, VertexIdx, Vertex<V, E, S>>,
+    /// The remaining edges of the current vertex
+    pub(super) edges: Option<(VertexIdx, small_map::Iter<'a, VertexIdx, E>)>,
+    pub(super) len: usize,
+}
+
+impl<'a, V, E, S> Iterator for EdgesIter<'a, V, E, S> {
+    type Item = (VertexIdx, VertexIdx, &'a E);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((from_idx, edges)) = &mut self.edges {
+                if let Some((to_idx, weight)) = edges.next() {
+                    self.len -= 1;
+                    return Some((*from_idx, *to_idx, weight));
+                }
+            }
+
+            let (from_idx, vertex) = self.vertices.next()?;
+            self.edges = Some((*from_idx, vertex.edges.iter()));
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, V, E, S> ExactSizeIterator for EdgesIter<'a, V, E, S> {
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+pub struct EdgesIterMut<'a, V, E, S = DefaultHashBuilder> {
+    pub(super) vertices: HashMapIterMut<'a, VertexIdx, Vertex<V, E, S>>,
+    /// The remaining edges of the current vertex
+    pub(super) edges: Option<(VertexIdx, small_map::IterMut<'a, VertexIdx, E>)>,
+    pub(super) len: usize,
+}
+
+impl<'a, V, E, S> Iterator for EdgesIterMut<'a, V, E, S> {
+    type Item = (VertexIdx, VertexIdx, &'a mut E);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((from_idx, edges)) = &mut self.edges {
+                if let Some((to_idx, weight)) = edges.next() {
+                    self.len -= 1;
+                    return Some((*from_idx, *to_idx, weight));
+                }
+            }
+
+            let (from_idx, vertex) = self.vertices.next()?;
+            self.edges = Some((*from_idx, vertex.edges.iter_mut()));
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, V, E, S> ExactSizeIterator for EdgesIterMut<'a, V, E, S> {
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+pub struct IntoEdgesIterator<V, E, S = DefaultHashBuilder> {
+    pub(super) vertices: HashMapIntoIter<VertexIdx, Vertex<V, E, S>>,
+    /// The remaining edges of the current vertex
+    pub(super) edges: Option<(VertexIdx, small_map::IntoIter<VertexIdx, E>)>,
+    pub(super) len: usize,
+}
+
+impl<V, E, S> Iterator for IntoEdgesIterator<V, E, S> {
+    type Item = (VertexIdx, VertexIdx, E);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((from_idx, edges)) = &mut self.edges {
+                if let Some((to_idx, weight)) = edges.next() {
+                    self.len -= 1;
+                    return Some((*from_idx, to_idx, weight));
+                }
+            }
+
+            let (from_idx, vertex) = self.vertices.next()?;
+            self.edges = Some((from_idx, vertex.edges.into_iter()));
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<V, E, S> ExactSizeIterator for IntoEdgesIterator<V, E, S> {
+    fn len(&self) -> usize {
+        self.len
+    }
+}
@@ -0,0 +1,85 @@
+use core::cmp::Ordering;
+
+/// An edge weight that can be summed and compared, such as a distance or a cost, so that
+/// shortest path algorithms like [`dijkstra`](super::algo::dijkstra) and [`prim`](super::algo::prim)
+/// work with custom types, e.g. lexicographic `(time, money)` costs.
+///
+/// It's implemented for every integer and float primitive. Floats are compared with
+/// [`f64::total_cmp`], so `NaN` is greater than every other value.
+pub trait Measure: Copy {
+    /// The identity of [`Self::add`], i.e. the length of an empty path.
+    fn zero() -> Self;
+
+    fn add(self, other: Self) -> Self;
+
+    /// A total order of the values.
+    fn compare(&self, other: &Self) -> Ordering;
+}
+
+macro_rules! impl_measure_int {
+    ($($int:ty),*) => {
+        $(
+            impl Measure for $int {
+                fn zero() -> Self {
+                    0
+                }
+
+                fn add(self, other: Self) -> Self {
+                    self + other
+                }
+
+                fn compare(&self, other: &Self) -> Ordering {
+                    self.cmp(other)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_measure_float {
+    ($($float:ty),*) => {
+        $(
+            impl Measure for $float {
+                fn zero() -> Self {
+                    0.0
+                }
+
+                fn add(self, other: Self) -> Self {
+                    self + other
+                }
+
+                fn compare(&self, other: &Self) -> Ordering {
+                    self.total_cmp(other)
+                }
+            }
+        )*
+    };
+}
+
+impl_measure_int!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+impl_measure_float!(f32, f64);
+
+/// Orders a [`Measure`] by [`Measure::compare`], so that it can be stored in ordered collections
+/// such as [`BinaryHeap`](alloc::collections::BinaryHeap).
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Ordered<M>(pub M);
+
+impl<M: Measure> PartialEq for Ordered<M> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<M: Measure> Eq for Ordered<M> {}
+
+impl<M: Measure> PartialOrd for Ordered<M> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<M: Measure> Ord for Ordered<M> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.compare(&other.0)
+    }
+}
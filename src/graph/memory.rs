@@ -0,0 +1,162 @@
+//! Approximations of the heap memory used by a graph, for capacity planning.
+//!
+//! Hash tables are estimated as one entry and one control byte per slot of their capacity, which
+//! is how both `std` and `hashbrown` lay them out, ignoring the few bytes of alignment padding.
+
+use super::{small_map::SmallMap, Graph, Vertex, VertexIdx};
+use core::{hash::BuildHasher, mem::size_of};
+
+/// The approximate heap memory of a graph, in bytes, as returned by [`Graph::memory_usage`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MemoryStats {
+    /// The used entries of the table of vertices, which include the weights of the vertices and
+    /// the edges stored inline.
+    pub vertices: usize,
+    /// The used entries of the edge tables of vertices with too many edges to store inline, and of
+    /// the tables of predecessors.
+    pub edges: usize,
+    /// The unused capacity of all of the above tables.
+    pub overhead: usize,
+    /// The heap memory owned by the weights of the vertices and edges, as measured by the closures
+    /// of [`Graph::memory_usage_with`]. Zero for [`Graph::memory_usage`].
+    pub payload: usize,
+}
+
+impl MemoryStats {
+    /// The sum of every category, in bytes.
+    pub fn total(&self) -> usize {
+        self.vertices + self.edges + self.overhead + self.payload
+    }
+}
+
+/// The `(used, unused)` bytes of a hash table with `len` entries of type `T` and room for
+/// `capacity`.
+fn table_bytes<T>(len: usize, capacity: usize) -> (usize, usize) {
+    let slot = size_of::<T>() + 1;
+
+    (len * slot, capacity.saturating_sub(len) * slot)
+}
+
+impl<V, E, D, S: BuildHasher + Default> Graph<V, E, D, S> {
+    /// Approximate the heap memory of this graph, excluding any memory owned by the weights, in
+    /// `O(|V| + |E|)`. See [`Self::memory_usage_with`] to include it.
+    ///
+    /// ```
+    /// # use badlgorithms::graph::Graph;
+    /// let mut graph = Graph::<(), u64>::new();
+    /// let [a, b] = [(); 2].map(|_| graph.insert_vertex(()));
+    ///
+    /// graph.insert_or_update_edge(a, b, 1).unwrap();
+    ///
+    /// let stats = graph.memory_usage();
+    ///
+    /// assert!(stats.vertices > 0 && stats.payload == 0);
+    /// ```
+    pub fn memory_usage(&self) -> MemoryStats {
+        self.memory_usage_with(|_| 0, |_| 0)
+    }
+
+    /// Approximate the heap memory of this graph like [`Self::memory_usage`], adding the bytes
+    /// `vertex_size` and `edge_size` return for the heap memory owned by each weight, such as the
+    /// buffer of a `String`, in `O(|V| + |E|)`.
+    pub fn memory_usage_with(
+        &self,
+        mut vertex_size: impl FnMut(&V) -> usize,
+        mut edge_size: impl FnMut(&E) -> usize,
+    ) -> MemoryStats {
+        let (vertices, mut overhead) = table_bytes::<(VertexIdx, Vertex<V, E, S>)>(
+            self.vertices.len(),
+            self.vertices.capacity(),
+        );
+        let mut stats = MemoryStats {
+            vertices,
+            ..MemoryStats::default()
+        };
+
+        for vertex in self.vertices.values() {
+            if let SmallMap::Heap(predecessors) = &vertex.predecessors {
+                let (predecessors, predecessors_overhead) =
+                    table_bytes::<VertexIdx>(predecessors.len(), predecessors.capacity());
+
+                stats.edges += predecessors;
+                overhead += predecessors_overhead;
+            }
+
+            if let SmallMap::Heap(edges) = &vertex.edges {
+                let (edges, edges_overhead) =
+                    table_bytes::<(VertexIdx, E)>(edges.len(), edges.capacity());
+
+                stats.edges += edges;
+                overhead += edges_overhead;
+            }
+
+            stats.payload += vertex_size(&vertex.weight)
+                + vertex
+                    .edges
+                    .iter()
+                    .map(|(_, weight)| edge_size(weight))
+                    .sum::<usize>();
+        }
+
+        stats.overhead = overhead;
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::{small_map::INLINE_CAPACITY, Graph};
+    use alloc::string::{String, ToString};
+
+    #[test]
+    fn test_memory_usage() {
+        let mut graph = Graph::<String, String>::new();
+
+        assert_eq!(
+            graph.memory_usage().total(),
+            0,
+            "An empty graph doesn't allocate"
+        );
+
+        let [a, b] = ["a", "b"].map(|label| graph.insert_vertex(label.to_string()));
+
+        graph
+            .insert_or_update_edge(a, b, "edge".to_string())
+            .expect("The vertices exist");
+
+        let inline = graph.memory_usage();
+
+        assert!(inline.vertices > 0, "The vertices are on the heap");
+        assert_eq!(
+            inline.edges, 0,
+            "Few edges and predecessors are stored inline"
+        );
+        assert_eq!(inline.payload, 0, "The weights aren't measured");
+
+        // Spill the edges of `a` to the heap
+        for i in 0..INLINE_CAPACITY {
+            let to = graph.insert_vertex(i.to_string());
+
+            graph
+                .insert_or_update_edge(a, to, String::new())
+                .expect("The vertices exist");
+        }
+
+        let spilled = graph.memory_usage_with(String::capacity, String::capacity);
+
+        assert!(
+            spilled.edges > inline.edges,
+            "Edges that don't fit inline are on the heap"
+        );
+        assert_eq!(
+            spilled.payload,
+            ["a", "b", "edge"].map(str::len).iter().sum::<usize>() + INLINE_CAPACITY,
+            "Wrong size of the weights"
+        );
+        assert_eq!(
+            spilled.total(),
+            spilled.vertices + spilled.edges + spilled.overhead + spilled.payload,
+            "The total is the sum of every category"
+        );
+    }
+}
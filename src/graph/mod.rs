@@ -1,35 +1,253 @@
-use iter::{IntoVerticesIterator, VerticesIter, VerticesIterMut};
-use std::collections::HashMap;
+use crate::collections::{HashMap, HashSet};
+use algo::Edge;
+use alloc::vec::Vec;
+use core::{
+    hash::{BuildHasher, Hash},
+    marker::PhantomData,
+    ops::{Index, IndexMut},
+};
+use iter::{
+    EdgesIter, EdgesIterMut, IntoEdgesIterator, IntoVerticesIterator, VertexIndices, VerticesIter,
+    VerticesIterMut,
+};
+use small_map::SmallMap;
+use vertex::Predecessors;
 
-pub use {vertex::Vertex, vertex_idx::VertexIdx};
+pub use {
+    crate::collections::{DefaultHashBuilder, FastHashBuilder},
+    csr::{CsrGraph, CsrNeighbors},
+    dense::{DenseGraph, DenseNeighbors},
+    direction::{Directed, Direction, Undirected},
+    edge_idx::EdgeIdx,
+    error::GraphError,
+    measure::Measure,
+    memory::MemoryStats,
+    multi::MultiGraph,
+    persistent::PersistentGraph,
+    vertex::Vertex,
+    vertex_idx::VertexIdx,
+    view::GraphView,
+};
+
+#[cfg(feature = "std")]
+pub use concurrent::ConcurrentGraph;
 
 pub mod algo;
+pub mod builder;
+#[cfg(feature = "std")]
+pub mod concurrent;
+pub mod csr;
+pub mod dense;
+pub mod direction;
+pub mod display;
+pub mod dynamic;
+pub mod edge_idx;
+pub mod error;
+pub mod export;
+pub mod generate;
+pub mod io;
 pub mod iter;
+pub mod measure;
+pub mod memory;
+pub mod multi;
+pub mod persistent;
+#[cfg(feature = "petgraph")]
+pub mod petgraph;
+pub mod props;
+pub mod random;
+#[cfg(feature = "serde")]
+mod serde;
+mod small_map;
+pub mod stats;
 pub mod vertex;
 pub mod vertex_idx;
+pub mod view;
+pub mod visit;
+
+/// A vertex removed by [`Graph::remove_vertex`], along with the `(from, weight)` of the edges to it.
+pub type RemovedVertex<V, E, S = DefaultHashBuilder> = (Vertex<V, E, S>, Vec<(VertexIdx, E)>);
+
+/// A graph with vertices of weight `V` and edges of weight `E`, which is [`Directed`] by default.
+/// Pass [`Undirected`] as `D` for a graph whose edges connect both of their vertices.
+///
+/// Two graphs are equal if they have the same vertex IDs, vertex weights and edges.
+///
+/// Every map of the graph hashes with `S`, which can be swapped for a faster hasher such as
+/// [`FastHashBuilder`] when the graph doesn't face untrusted input. Only [`Graph::new`] and
+/// [`Graph::with_capacity`] are limited to the default hasher; use [`Graph::default`] otherwise.
+///
+/// The edges to each vertex are only tracked once [`Graph::with_reverse_index`] is called, so
+/// that graphs that never look them up don't pay for them.
+#[derive(Clone, Debug)]
+pub struct Graph<V, E, D = Directed, S = DefaultHashBuilder> {
+    pub(crate) vertices: HashMap<VertexIdx, Vertex<V, E, S>, S>,
+    /// The number of edges, kept up to date by every method that inserts or removes them
+    edge_count: usize,
+    /// Whether the predecessors of every vertex are kept in sync with the edges. They're empty
+    /// otherwise.
+    reverse_index: bool,
+    direction: PhantomData<fn() -> D>,
+}
+
+impl<V, E, D, S: Default> Default for Graph<V, E, D, S> {
+    fn default() -> Self {
+        Self {
+            vertices: HashMap::default(),
+            edge_count: 0,
+            reverse_index: false,
+            direction: PhantomData,
+        }
+    }
+}
 
-#[derive(Debug, Default)]
-pub struct Graph<V, E> {
-    pub(crate) vertices: HashMap<VertexIdx, Vertex<V, E>>,
+impl<V: PartialEq, E: PartialEq, D, S: BuildHasher + Default> PartialEq for Graph<V, E, D, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.vertices == other.vertices
+    }
 }
 
-impl<V, E> Graph<V, E> {
+impl<V: Eq, E: Eq, D, S: BuildHasher + Default> Eq for Graph<V, E, D, S> {}
+
+impl<V, E, D> Graph<V, E, D> {
     pub fn new() -> Self {
         Self {
             vertices: HashMap::new(),
+            edge_count: 0,
+            reverse_index: false,
+            direction: PhantomData,
+        }
+    }
+
+    /// Create an empty graph with room for at least `vertices` vertices without reallocating.
+    pub fn with_capacity(vertices: usize) -> Self {
+        Self {
+            vertices: HashMap::with_capacity(vertices),
+            edge_count: 0,
+            reverse_index: false,
+            direction: PhantomData,
+        }
+    }
+}
+
+impl<V, E, D, S: BuildHasher + Default> Graph<V, E, D, S> {
+    /// Create a graph from its vertices, such as those returned by [`Self::into_parts`],
+    /// in `O(|V| + |E|)`, without a reverse index.
+    ///
+    /// Fails with [`GraphError::MissingTarget`] if an edge points to a vertex that doesn't exist.
+    pub fn from_parts(
+        vertices: HashMap<VertexIdx, Vertex<V, E, S>, S>,
+    ) -> Result<Self, GraphError> {
+        for vertex in vertices.values() {
+            if let Some(&to_idx) = vertex.edges.keys().find(|idx| !vertices.contains_key(*idx)) {
+                return Err(GraphError::MissingTarget(to_idx));
+            }
+        }
+
+        let mut graph = Self {
+            vertices,
+            edge_count: 0,
+            reverse_index: false,
+            direction: PhantomData,
+        };
+
+        graph.rebuild_indices();
+        Ok(graph)
+    }
+
+    /// Convert to the vertices of this graph, each keeping its own edges.
+    /// Use [`Self::from_parts`] to convert them back.
+    pub fn into_parts(self) -> HashMap<VertexIdx, Vertex<V, E, S>, S> {
+        self.vertices
+    }
+
+    /// Track the edges to every vertex from now on, so that [`Self::incoming_edges`],
+    /// [`Self::predecessors`] and [`Self::in_degree`] take `O(1)` instead of scanning every vertex,
+    /// at the cost of memory and of slower edge updates. Builds the index in `O(|V| + |E|)`,
+    /// unless the graph already has it.
+    pub fn with_reverse_index(mut self) -> Self {
+        if !self.reverse_index {
+            self.reverse_index = true;
+            self.rebuild_indices();
+        }
+
+        self
+    }
+
+    /// Whether the edges to every vertex are tracked, see [`Self::with_reverse_index`].
+    pub fn has_reverse_index(&self) -> bool {
+        self.reverse_index
+    }
+
+    /// Reserve room for at least `additional` more vertices without reallocating.
+    pub fn reserve(&mut self, additional: usize) {
+        self.vertices.reserve(additional);
+    }
+
+    /// The number of vertices this graph can have without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.vertices.capacity()
+    }
+
+    /// Shrink the capacity of this graph and of the edges of every vertex as much as possible,
+    /// in `O(|V| + |E|)`.
+    pub fn shrink_to_fit(&mut self) {
+        self.vertices.shrink_to_fit();
+
+        for vertex in self.vertices.values_mut() {
+            vertex.edges.shrink_to_fit();
+            vertex.predecessors.shrink_to_fit();
         }
     }
 
+    /// Remove every vertex and edge, keeping the allocated memory for the vertices.
+    pub fn clear(&mut self) {
+        self.vertices.clear();
+        self.edge_count = 0;
+    }
+
+    /// Remove every edge in `O(|V| + |E|)`, keeping the vertices and the allocated memory for their edges.
+    pub fn clear_edges(&mut self) {
+        for vertex in self.vertices.values_mut() {
+            vertex.edges.clear();
+            vertex.predecessors.clear();
+        }
+
+        self.edge_count = 0;
+    }
+
     /// Retrieve an immutable reference to a vertex by its ID in `O(1)`.
-    pub fn get_vertex(&self, vertex_idx: VertexIdx) -> Option<&Vertex<V, E>> {
+    pub fn get_vertex(&self, vertex_idx: VertexIdx) -> Option<&Vertex<V, E, S>> {
         self.vertices.get(&vertex_idx)
     }
 
     /// Retrieve a mutable reference to a vertex by its ID in `O(1)`.
-    pub fn get_mut_vertex(&mut self, vertex_idx: VertexIdx) -> Option<&mut Vertex<V, E>> {
+    pub fn get_mut_vertex(&mut self, vertex_idx: VertexIdx) -> Option<&mut Vertex<V, E, S>> {
         self.vertices.get_mut(&vertex_idx)
     }
 
+    /// Whether the vertex `vertex_idx` exists in this graph, in `O(1)`.
+    pub fn contains_vertex(&self, vertex_idx: VertexIdx) -> bool {
+        self.vertices.contains_key(&vertex_idx)
+    }
+
+    /// Whether the edge from `from` to `to` exists in this graph, in `O(1)`.
+    pub fn contains_edge(&self, from: VertexIdx, to: VertexIdx) -> bool {
+        self.vertices
+            .get(&from)
+            .is_some_and(|vertex| vertex.is_adjacent(to))
+    }
+
+    /// Retrieve an immutable reference to the weight of the edge from `from` to `to` in `O(1)`.
+    pub fn get_edge(&self, from: VertexIdx, to: VertexIdx) -> Option<&E> {
+        self.vertices.get(&from)?.edges.get(&to)
+    }
+
+    /// Retrieve a mutable reference to the weight of the edge from `from` to `to` in `O(1)`.
+    /// In undirected graphs, only that direction is affected.
+    pub fn get_edge_mut(&mut self, from: VertexIdx, to: VertexIdx) -> Option<&mut E> {
+        self.vertices.get_mut(&from)?.edges.get_mut(&to)
+    }
+
     /// Insert a vertex with weight `V` in `O(1)`. Returns its ID.
     ///
     /// Use [`Self::insert_edge`] or [`Self::insert_edges`] to define its edges.
@@ -39,7 +257,8 @@ impl<V, E> Graph<V, E> {
         self.vertices.insert(
             idx,
             Vertex {
-                edges: HashMap::new(),
+                edges: SmallMap::default(),
+                predecessors: SmallMap::default(),
                 weight,
             },
         );
@@ -47,90 +266,101 @@ impl<V, E> Graph<V, E> {
         idx
     }
 
-    /// Insert or update a vertex with weight `V` and a specified ID in `O(1)`. Returns `self`.
+    /// Insert a vertex with weight `V` and room for at least `edges` edges in `O(1)`. Returns its ID.
     ///
-    /// Use [`Self::insert_edge`] or [`Self::insert_edges`] to define its edges.
-    pub fn insert_or_update_vertex(&mut self, weight: V, idx: VertexIdx) -> &mut Self {
-        let edges = self
-            .vertices
-            .remove(&idx)
-            .map(|v| v.edges)
-            .unwrap_or_default();
-
-        self.vertices.insert(idx, Vertex { edges, weight });
-        self
-    }
-
-    /// Remove a vertex by its ID in `O(|V|)`. Returns the [`Vertex`] itself, if it exists.
-    pub fn remove_vertex(&mut self, vertex_idx: VertexIdx) -> Option<Vertex<V, E>> {
-        let removed_vertex = self.vertices.remove(&vertex_idx)?;
+    /// Use [`Vertex::reserve_edges`] to make room for more edges of an existing vertex.
+    pub fn insert_vertex_with_capacity(&mut self, weight: V, edges: usize) -> VertexIdx {
+        let idx = VertexIdx::new();
 
-        for vertex in &mut self.vertices {
-            vertex.1.edges.remove(&vertex_idx);
-        }
+        self.vertices.insert(
+            idx,
+            Vertex {
+                edges: SmallMap::with_capacity(edges),
+                predecessors: SmallMap::default(),
+                weight,
+            },
+        );
 
-        Some(removed_vertex)
+        idx
     }
 
-    /// Insert or update an edge from a vertex `from` to a vertex `to` with weight `weight` in `O(1)`.
-    ///
-    /// To obtain the old edge weight (if any):
-    /// ```
-    /// # fn example() -> Option<()> {
-    /// # use badlgorithms::graph::Graph;
-    /// #
-    /// let mut graph = Graph::<u8, u16>::new();
-    /// let a_idx = graph.insert_vertex(10);
-    ///
-    /// graph.insert_or_update_edge(a_idx, a_idx, 3)?;
-    /// let (_, old_edge) = graph.get_vertex(a_idx)?.neighbors().next()?;
-    /// assert_eq!(*old_edge, 3);
-    ///
-    /// graph.insert_or_update_edge(a_idx, a_idx, 50)?;
-    /// let (_, new_edge) = graph.get_vertex(a_idx)?.neighbors().next()?;
-    /// assert_eq!(*new_edge, 50);
-    /// # Some(())
-    /// # }
-    /// ```
+    /// Update the weight of the vertex `idx` in `O(1)`, keeping its edges. Returns `self`.
     ///
-    /// Returns itself to allow for chaining [`Self::insert_edge`], if the vertices specified exist in this graph.
-    pub fn insert_or_update_edge(
-        &mut self,
-        from: VertexIdx,
-        to: VertexIdx,
-        weight: E,
-    ) -> Option<&mut Self> {
-        if !self.vertices.contains_key(&to) {
-            return None;
-        }
+    /// Fails with [`GraphError::MissingVertex`] if the vertex doesn't exist. New vertices only get
+    /// their IDs from [`Self::insert_vertex`], so that a handle to a removed vertex never
+    /// resolves again.
+    pub fn update_vertex(&mut self, weight: V, idx: VertexIdx) -> Result<&mut Self, GraphError> {
+        *self
+            .vertices
+            .get_mut(&idx)
+            .ok_or(GraphError::MissingVertex(idx))?
+            .weight_mut() = weight;
 
-        self.vertices.get_mut(&from)?.edges.insert(to, weight);
-        Some(self)
+        Ok(self)
     }
 
-    /// A convenient method to insert or update multiple edges of type `(from, to, weight)` in `O(n)`, where `n` is the length of `edges`.
+    /// Insert a vertex with weight `V` and the ID of a vertex of another graph in `O(1)`, for
+    /// graphs built from another one, which keep the IDs of their source.
+    pub(crate) fn insert_vertex_with_idx(&mut self, weight: V, idx: VertexIdx) {
+        self.vertices.insert(
+            idx,
+            Vertex {
+                edges: SmallMap::default(),
+                predecessors: SmallMap::default(),
+                weight,
+            },
+        );
+    }
+
+    /// Remove a vertex and its edges by its ID in `O(deg)` with a reverse index, where `deg` is the
+    /// number of edges from and to that vertex, or in `O(|V| + deg)` otherwise.
     ///
-    /// Returns itself to allow for chaining [`Self::insert_edges`], if you are into it ¯\\\_(ツ)\_/¯
-    pub fn insert_or_update_edges(
-        &mut self,
-        edges: impl IntoIterator<Item = (VertexIdx, VertexIdx, E)>,
-    ) -> &mut Self {
-        for (from, to, weight) in edges {
-            if self.vertices.contains_key(&to) {
-                if let Some(vertex) = self.vertices.get_mut(&from) {
-                    vertex.edges.insert(to, weight);
+    /// Returns the [`Vertex`] itself, which keeps its own edges, along with the `(from, weight)` of
+    /// the edges to it from other vertices, if it exists.
+    pub fn remove_vertex(&mut self, vertex_idx: VertexIdx) -> Option<RemovedVertex<V, E, S>> {
+        let removed_vertex = self.vertices.remove(&vertex_idx)?;
+        let incoming_edges: Vec<_> = if self.reverse_index {
+            for to_idx in removed_vertex.edges.keys() {
+                if let Some(to_vertex) = self.vertices.get_mut(to_idx) {
+                    to_vertex.predecessors.remove(&vertex_idx);
                 }
             }
-        }
 
-        self
+            removed_vertex
+                .predecessors
+                .keys()
+                .filter_map(|&from_idx| {
+                    let weight = self
+                        .vertices
+                        .get_mut(&from_idx)?
+                        .edges
+                        .remove(&vertex_idx)
+                        .expect("Predecessors have an edge to the vertex");
+
+                    Some((from_idx, weight))
+                })
+                .collect()
+        } else {
+            self.vertices
+                .iter_mut()
+                .filter_map(|(&from_idx, from_vertex)| {
+                    Some((from_idx, from_vertex.edges.remove(&vertex_idx)?))
+                })
+                .collect()
+        };
+
+        self.edge_count -= removed_vertex.edges.len() + incoming_edges.len();
+        Some((removed_vertex, incoming_edges))
     }
 
-    /// Remove an edge from a vertex `from` to a vertex `to` in `O(1)`.
-    ///
-    /// Returns that edge's weight, if it exists.
-    pub fn remove_edge(&mut self, from: VertexIdx, to: VertexIdx) -> Option<E> {
-        self.vertices.get_mut(&from)?.edges.remove(&to)
+    /// Like [`Self::remove_vertex`], but fails with [`GraphError::MissingVertex`] if the vertex
+    /// doesn't exist.
+    pub fn try_remove_vertex(
+        &mut self,
+        vertex_idx: VertexIdx,
+    ) -> Result<RemovedVertex<V, E, S>, GraphError> {
+        self.remove_vertex(vertex_idx)
+            .ok_or(GraphError::MissingVertex(vertex_idx))
     }
 
     pub fn len(&self) -> usize {
@@ -143,7 +373,7 @@ impl<V, E> Graph<V, E> {
 
     /// Filter and optionally map to new types, vertices and edges of this graph in `O(|V||E|)`.
     ///
-    /// - `vertex_map`: `|vertex_idx| -> Option<new_vertex_weight>`
+    /// - `vertex_map`: `|vertex_idx, old_vertex| -> Option<new_vertex_weight>`
     ///     - Return `None` to exclude the vertex with ID `vertex_idx`.
     ///     - The mapped vertex is guaranteed to have the same ID.
     /// - `edge_map`: `|(from_vertex_idx, from_vertex), (to_vertex_idx, to_vertex), old_edge_weight| -> Option<new_edge_weight>`
@@ -153,16 +383,23 @@ impl<V, E> Graph<V, E> {
         &'g self,
         mut vertex_map: F,
         mut edge_map: G,
-    ) -> Graph<NV, NE>
+    ) -> Graph<NV, NE, D, S>
     where
-        F: FnMut(VertexIdx) -> Option<NV>,
-        G: FnMut((VertexIdx, &Vertex<NV, NE>), (VertexIdx, &Vertex<NV, NE>), &'g E) -> Option<NE>,
+        F: FnMut(VertexIdx, &'g Vertex<V, E, S>) -> Option<NV>,
+        G: FnMut(
+            (VertexIdx, &Vertex<NV, NE, S>),
+            (VertexIdx, &Vertex<NV, NE, S>),
+            &'g E,
+        ) -> Option<NE>,
     {
-        let mut graph = Graph::new();
+        let mut graph = Graph {
+            reverse_index: self.reverse_index,
+            ..Graph::default()
+        };
 
-        for &vertex_idx in self.vertices.keys() {
-            if let Some(new_vertex_weight) = vertex_map(vertex_idx) {
-                graph.insert_or_update_vertex(new_vertex_weight, vertex_idx);
+        for (&vertex_idx, old_vertex) in &self.vertices {
+            if let Some(new_vertex_weight) = vertex_map(vertex_idx, old_vertex) {
+                graph.insert_vertex_with_idx(new_vertex_weight, vertex_idx);
             }
         }
 
@@ -187,7 +424,7 @@ impl<V, E> Graph<V, E> {
                     (to_vertex_idx, to_vertex),
                     old_edge,
                 ) {
-                    graph.insert_or_update_edge(from_vertex_idx, to_vertex_idx, new_edge_weight);
+                    graph.insert_or_update_arc(from_vertex_idx, to_vertex_idx, new_edge_weight);
                 }
             }
         }
@@ -195,139 +432,2162 @@ impl<V, E> Graph<V, E> {
         graph
     }
 
-    pub fn into_vertices_iter(self) -> IntoVerticesIterator<V, E> {
-        IntoVerticesIterator(self.vertices.into_iter())
+    /// The number of edges from a vertex in `O(1)`, if it exists.
+    pub fn out_degree(&self, vertex_idx: VertexIdx) -> Option<usize> {
+        Some(self.get_vertex(vertex_idx)?.degree())
     }
 
-    pub fn vertices_iter(&self) -> VerticesIter<V, E> {
-        VerticesIter(self.vertices.iter())
+    /// The number of edges to a vertex, if it exists, in `O(1)` with a reverse index or in
+    /// `O(|V|)` otherwise.
+    pub fn in_degree(&self, vertex_idx: VertexIdx) -> Option<usize> {
+        if self.reverse_index {
+            Some(self.get_vertex(vertex_idx)?.predecessors.len())
+        } else {
+            Some(self.incoming_edges(vertex_idx)?.count())
+        }
     }
 
-    pub fn vertices_iter_mut(&mut self) -> VerticesIterMut<V, E> {
-        VerticesIterMut(self.vertices.iter_mut())
+    /// The out-degrees of every vertex in non-increasing order, in `O(|V| log |V|)`.
+    /// In undirected graphs, this is the usual degree sequence, with self-loops counted once.
+    pub fn degree_sequence(&self) -> Vec<usize> {
+        let mut degrees: Vec<usize> = self.vertices.values().map(Vertex::degree).collect();
+
+        degrees.sort_unstable_by(|a, b| b.cmp(a));
+        degrees
     }
-}
 
-impl<V, E> IntoIterator for Graph<V, E> {
-    type IntoIter = IntoVerticesIterator<V, E>;
-    type Item = (VertexIdx, Vertex<V, E>);
+    /// Iterate over the edges to a vertex as `(from, weight)`, if it exists. With a reverse index,
+    /// this takes `O(1)` per edge, and every vertex is scanned in `O(|V|)` otherwise.
+    pub fn incoming_edges(
+        &self,
+        vertex_idx: VertexIdx,
+    ) -> Option<impl Iterator<Item = (VertexIdx, &E)>> {
+        let vertex = self.vertices.get(&vertex_idx)?;
+        let indexed = self.reverse_index.then(|| {
+            vertex.predecessors.keys().map(move |from_idx| {
+                let weight = &self.vertices[from_idx].edges[&vertex_idx];
 
-    fn into_iter(self) -> Self::IntoIter {
-        self.into_vertices_iter()
+                (*from_idx, weight)
+            })
+        });
+        let scanned = (!self.reverse_index).then(|| {
+            self.vertices
+                .iter()
+                .filter_map(move |(&from_idx, from_vertex)| {
+                    Some((from_idx, from_vertex.edges.get(&vertex_idx)?))
+                })
+        });
+
+        Some(
+            indexed
+                .into_iter()
+                .flatten()
+                .chain(scanned.into_iter().flatten()),
+        )
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::{Graph, Vertex, VertexIdx};
-    use helpers::test_neighbors;
+    /// Iterate over the IDs of the vertices with an edge to a vertex, if it exists, like
+    /// [`Self::incoming_edges`].
+    pub fn predecessors(
+        &self,
+        vertex_idx: VertexIdx,
+    ) -> Option<impl Iterator<Item = VertexIdx> + '_> {
+        Some(
+            self.incoming_edges(vertex_idx)?
+                .map(|(from_idx, _)| from_idx),
+        )
+    }
 
-    #[test]
-    fn test_send_sync() {
-        fn is_send<S: Send>() {}
-        fn is_sync<S: Sync>() {}
+    /// Keep only the vertices for which `keep` returns `true`, along with the edges between them,
+    /// in place in `O(|V| + |E|)`.
+    ///
+    /// - `keep`: `|vertex_idx, vertex_weight| -> bool`
+    pub fn retain_vertices<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(VertexIdx, &mut V) -> bool,
+    {
+        let mut removed = HashSet::new();
 
-        is_send::<Graph<String, String>>();
-        is_sync::<Graph<String, String>>();
-    }
+        self.vertices.retain(|&vertex_idx, vertex| {
+            let is_kept = keep(vertex_idx, &mut vertex.weight);
 
-    #[test]
-    fn test_relationships() {
-        // The graph should look like this:
-        //   [a: 23]-→[b: 1]
-        //     ↑ ↓   ↖   ↑
-        //   a[c: 7]  [d: 9] ↰
-        //               ⤷---⤴
-        let mut graph = Graph::<u16, ()>::new();
+            if !is_kept {
+                removed.insert(vertex_idx);
+                self.edge_count -= vertex.edges.len();
+            }
 
-        let a_idx = graph.insert_vertex(23);
-        let b_idx = graph.insert_vertex(1);
-        let c_idx = graph.insert_vertex(7);
-        let d_idx = graph.insert_vertex(9);
+            is_kept
+        });
 
-        graph.insert_or_update_edge(a_idx, b_idx, ());
-        graph.insert_or_update_edges([
-            (a_idx, b_idx, ()),
-            (a_idx, c_idx, ()),
-            (c_idx, a_idx, ()),
-            (d_idx, a_idx, ()),
-            (d_idx, b_idx, ()),
-            (d_idx, d_idx, ()),
-        ]);
+        if !removed.is_empty() {
+            for vertex in self.vertices.values_mut() {
+                let degree = vertex.edges.len();
 
-        let temp_idx = graph.insert_vertex(u16::MAX);
+                vertex.edges.retain(|to_idx, _| !removed.contains(to_idx));
+                self.edge_count -= degree - vertex.edges.len();
+                vertex
+                    .predecessors
+                    .retain(|from_idx, _| !removed.contains(from_idx));
+            }
+        }
+    }
 
-        assert_eq!(
-            graph.remove_vertex(temp_idx).map(|x| x.into_weight()),
-            Some(u16::MAX),
-            "Vertex temp has been already removed"
-        );
+    /// Keep only the edges for which `keep` returns `true`, in place in `O(|V| + |E|)`.
+    /// In undirected graphs, each direction of an edge is kept or removed on its own, so `keep`
+    /// should decide the same for both.
+    ///
+    /// - `keep`: `|from_vertex_idx, to_vertex_idx, edge_weight| -> bool`
+    pub fn retain_edges<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(VertexIdx, VertexIdx, &mut E) -> bool,
+    {
+        let mut removed = Vec::new();
 
-        assert!(
-            graph
-                .insert_or_update_edge(temp_idx, temp_idx, ())
-                .is_none(),
-            "There can't be edges in non-existent vertices"
-        );
+        for (&from_vertex_idx, vertex) in &mut self.vertices {
+            vertex.edges.retain(|&to_vertex_idx, weight| {
+                let is_kept = keep(from_vertex_idx, to_vertex_idx, weight);
 
-        let a = graph.get_vertex(a_idx).expect("Vertex a doesn't exist");
-        let b = graph.get_vertex(b_idx).expect("Vertex b doesn't exist");
-        let c = graph.get_vertex(c_idx).expect("Vertex c doesn't exist");
-        let d = graph.get_vertex(d_idx).expect("Vertex d doesn't exist");
+                if !is_kept {
+                    removed.push((from_vertex_idx, to_vertex_idx));
+                }
 
-        assert_eq!(*a.weight(), 23, "Wrong weight for vertex a");
-        assert_eq!(*b.weight(), 1, "Wrong weight for vertex b");
-        assert_eq!(*c.weight(), 7, "Wrong weight for vertex c");
-        assert_eq!(*d.weight(), 9, "Wrong weight for vertex d");
+                is_kept
+            });
+        }
 
-        test_neighbors(a, &[b_idx, c_idx]);
-        test_neighbors(b, &[]);
-        test_neighbors(c, &[a_idx]);
-        test_neighbors(d, &[b_idx, a_idx, d_idx]);
+        self.edge_count -= removed.len();
+
+        for (from_vertex_idx, to_vertex_idx) in removed {
+            if let Some(vertex) = self.vertices.get_mut(&to_vertex_idx) {
+                vertex.predecessors.remove(&from_vertex_idx);
+            }
+        }
     }
 
-    #[test]
-    fn test_filter_map() {
-        // The graph will look like this:
-        //
-        //              6
-        //         _________
-        //        /         \
-        //        ↓    3    |
-        //   [c: 8] ------> [a: 3]
-        //    ↑   |
-        //  2 |   | 4
-        //    |   ↓
-        //   [b: 6] ↰
-        //     ⤷---⤴
-        //       8
-        let mut graph = Graph::<u16, u8>::new();
+    /// Filter and mutate the vertices and edges of this graph in place in `O(|V| + |E|)`, like
+    /// [`Self::filter_map`] without allocating a new graph when the weight types stay the same.
+    ///
+    /// - `vertex_fn`: `|vertex_idx, vertex_weight| -> bool`
+    ///     - Return `false` to remove the vertex with ID `vertex_idx` and its edges.
+    /// - `edge_fn`: `|from_vertex_idx, to_vertex_idx, edge_weight| -> bool`
+    ///     - It is called only if `vertex_fn` returned `true` for both vertices of the edge.
+    ///     - Return `false` to remove the edge `(from_vertex_idx, to_vertex_idx)`.
+    pub fn retain_map_in_place<F, G>(&mut self, vertex_fn: F, edge_fn: G)
+    where
+        F: FnMut(VertexIdx, &mut V) -> bool,
+        G: FnMut(VertexIdx, VertexIdx, &mut E) -> bool,
+    {
+        self.retain_vertices(vertex_fn);
+        self.retain_edges(edge_fn);
+    }
 
-        let a_idx = graph.insert_vertex(3);
-        let b_idx = graph.insert_vertex(6);
-        let c_idx = graph.insert_vertex(8);
+    /// Map the weights of every vertex and edge of this graph to new types in `O(|V| + |E|)`,
+    /// keeping the structure and the vertex IDs.
+    ///
+    /// - `vertex_map`: `|vertex_idx, old_vertex_weight| -> new_vertex_weight`
+    /// - `edge_map`: `|from_vertex_idx, to_vertex_idx, old_edge_weight| -> new_edge_weight`
+    ///
+    /// Use [`Self::filter_map`] to exclude vertices or edges.
+    pub fn map<'g, F, G, NV, NE>(
+        &'g self,
+        mut vertex_map: F,
+        mut edge_map: G,
+    ) -> Graph<NV, NE, D, S>
+    where
+        F: FnMut(VertexIdx, &'g V) -> NV,
+        G: FnMut(VertexIdx, VertexIdx, &'g E) -> NE,
+    {
+        let vertices = self
+            .vertices
+            .iter()
+            .map(|(&from_vertex_idx, vertex)| {
+                let edges = vertex
+                    .edges
+                    .iter()
+                    .map(|(&to_vertex_idx, weight)| {
+                        (
+                            to_vertex_idx,
+                            edge_map(from_vertex_idx, to_vertex_idx, weight),
+                        )
+                    })
+                    .collect();
+                let vertex = Vertex {
+                    weight: vertex_map(from_vertex_idx, &vertex.weight),
+                    edges,
+                    predecessors: vertex.predecessors.keys().map(|&idx| (idx, ())).collect(),
+                };
 
-        graph.insert_or_update_edges([
-            (b_idx, c_idx, 2),
-            (c_idx, b_idx, 4),
-            (a_idx, c_idx, 6),
-            (c_idx, a_idx, 3),
-            (b_idx, b_idx, 8),
-        ]);
+                (from_vertex_idx, vertex)
+            })
+            .collect();
 
-        // The graph should look like this:
-        //
-        //              1
-        //         _________
-        //        /         \
-        //        ↓    2    |
-        //   [c: 4] ------> [b: 3]
-        let mapped_graph: Graph<u16, i8> = graph.filter_map(
-            |idx| {
-                let weight = graph.get_vertex(idx)?.weight();
+        Graph {
+            vertices,
+            edge_count: self.edge_count,
+            reverse_index: self.reverse_index,
+            direction: PhantomData,
+        }
+    }
 
-                (weight % 2 == 0).then_some(weight / 2)
-            },
+    /// Create a graph with the same vertices and every edge reversed, in `O(|V| + |E|)`.
+    /// The vertices keep their IDs.
+    pub fn transpose(&self) -> Graph<&V, &E, D, S> {
+        let mut graph = Graph {
+            vertices: self
+                .vertices
+                .iter()
+                .map(|(&vertex_idx, vertex)| {
+                    // The successors become the predecessors
+                    let predecessors = match self.reverse_index {
+                        true => vertex.edges.keys().map(|&idx| (idx, ())).collect(),
+                        false => SmallMap::default(),
+                    };
+                    let vertex = Vertex {
+                        edges: SmallMap::default(),
+                        predecessors,
+                        weight: &vertex.weight,
+                    };
+
+                    (vertex_idx, vertex)
+                })
+                .collect(),
+            edge_count: self.edge_count,
+            reverse_index: self.reverse_index,
+            direction: PhantomData,
+        };
+
+        for (&from_vertex_idx, vertex) in &self.vertices {
+            for (&to_vertex_idx, weight) in &vertex.edges {
+                graph
+                    .vertices
+                    .get_mut(&to_vertex_idx)
+                    .expect("Edges point to existing vertices")
+                    .edges
+                    .insert(from_vertex_idx, weight);
+            }
+        }
+
+        graph
+    }
+
+    /// Create a graph with the same vertices and exactly the edges that don't exist in this graph,
+    /// in `O(|V|²)`. The vertices keep their IDs.
+    ///
+    /// - `default_edge`: `|from, to| -> weight`, the weight of each new edge
+    /// - `self_loops`: whether the missing self-loops are included too
+    pub fn complement<F>(&self, mut default_edge: F, self_loops: bool) -> Graph<&V, E, D, S>
+    where
+        F: FnMut(VertexIdx, VertexIdx) -> E,
+    {
+        let mut edges = Vec::new();
+
+        for (&from_vertex_idx, vertex) in &self.vertices {
+            for &to_vertex_idx in self.vertices.keys() {
+                if (self_loops || from_vertex_idx != to_vertex_idx)
+                    && !vertex.edges.contains_key(&to_vertex_idx)
+                {
+                    edges.push((from_vertex_idx, to_vertex_idx));
+                }
+            }
+        }
+
+        let mut graph = Graph {
+            vertices: self
+                .vertices
+                .iter()
+                .map(|(&vertex_idx, vertex)| {
+                    let vertex = Vertex {
+                        edges: SmallMap::default(),
+                        predecessors: SmallMap::default(),
+                        weight: &vertex.weight,
+                    };
+
+                    (vertex_idx, vertex)
+                })
+                .collect(),
+            edge_count: 0,
+            reverse_index: self.reverse_index,
+            direction: PhantomData,
+        };
+
+        for (from_vertex_idx, to_vertex_idx) in edges {
+            let weight = default_edge(from_vertex_idx, to_vertex_idx);
+
+            graph.insert_or_update_arc(from_vertex_idx, to_vertex_idx, weight);
+        }
+
+        graph
+    }
+
+    /// Create the adjacency matrix of this graph in `O(|V|² + |V| log |V|)`, where the entry at
+    /// row `i` and column `j` is the weight of the edge from the `i`-th vertex to the `j`-th one.
+    ///
+    /// Returns the IDs of the vertices, sorted, along with the matrix.
+    pub fn to_adjacency_matrix(&self) -> (Vec<VertexIdx>, Vec<Vec<Option<&E>>>) {
+        let mut ids: Vec<_> = self.vertices.keys().copied().collect();
+
+        ids.sort_unstable();
+
+        let matrix = ids
+            .iter()
+            .map(|from_idx| {
+                let edges = &self.vertices[from_idx].edges;
+
+                ids.iter().map(|to_idx| edges.get(to_idx)).collect()
+            })
+            .collect();
+
+        (ids, matrix)
+    }
+
+    /// Like [`Self::transpose`], but consumes the graph instead of borrowing its weights.
+    pub fn into_transpose(self) -> Self {
+        let mut edges = Vec::new();
+        let mut graph = Graph {
+            vertices: self
+                .vertices
+                .into_iter()
+                .map(|(vertex_idx, vertex)| {
+                    edges.extend(
+                        vertex
+                            .edges
+                            .into_iter()
+                            .map(|(to_vertex_idx, weight)| (to_vertex_idx, vertex_idx, weight)),
+                    );
+
+                    let vertex = Vertex {
+                        edges: SmallMap::default(),
+                        predecessors: SmallMap::default(),
+                        weight: vertex.weight,
+                    };
+
+                    (vertex_idx, vertex)
+                })
+                .collect(),
+            edge_count: 0,
+            reverse_index: self.reverse_index,
+            direction: PhantomData,
+        };
+
+        for (from_vertex_idx, to_vertex_idx, weight) in edges {
+            graph.insert_or_update_arc(from_vertex_idx, to_vertex_idx, weight);
+        }
+
+        graph
+    }
+
+    /// Convert to an immutable [`CsrGraph`] for faster traversals, keeping the vertex IDs.
+    /// Use [`CsrGraph::thaw`] to convert it back.
+    pub fn freeze(self) -> CsrGraph<V, E, D, S> {
+        CsrGraph::from(self)
+    }
+
+    pub fn into_vertices_iter(self) -> IntoVerticesIterator<V, E, S> {
+        IntoVerticesIterator(self.vertices.into_iter())
+    }
+
+    pub fn vertices_iter(&self) -> VerticesIter<'_, V, E, S> {
+        VerticesIter(self.vertices.iter())
+    }
+
+    /// Iterate over the IDs of the vertices, in no particular order.
+    ///
+    /// Collect them first to mutate the graph while iterating:
+    /// ```
+    /// # use badlgorithms::graph::Graph;
+    /// #
+    /// let mut graph = Graph::<u8, ()>::new();
+    /// graph.insert_vertex(1);
+    /// graph.insert_vertex(2);
+    ///
+    /// for vertex_idx in graph.vertex_indices().collect::<Vec<_>>() {
+    ///     let new_idx = graph.insert_vertex(0);
+    ///
+    ///     graph.insert_or_update_edge(vertex_idx, new_idx, ());
+    /// }
+    ///
+    /// assert_eq!(graph.len(), 4);
+    /// ```
+    pub fn vertex_indices(&self) -> VertexIndices<'_, V, E, S> {
+        VertexIndices(self.vertices.keys())
+    }
+
+    pub fn vertices_iter_mut(&mut self) -> VerticesIterMut<'_, V, E, S> {
+        VerticesIterMut(self.vertices.iter_mut())
+    }
+
+    /// Like [`Self::vertices_iter`], but iterates across threads with [`rayon`].
+    #[cfg(feature = "parallel")]
+    pub fn par_vertices_iter(
+        &self,
+    ) -> impl rayon::iter::ParallelIterator<Item = (VertexIdx, &Vertex<V, E, S>)>
+    where
+        V: Sync,
+        E: Sync,
+        S: Sync,
+    {
+        use rayon::prelude::*;
+
+        self.vertices.par_iter().map(|(&idx, vertex)| (idx, vertex))
+    }
+
+    /// Like [`Self::vertices_iter_mut`], but iterates across threads with [`rayon`].
+    #[cfg(feature = "parallel")]
+    pub fn par_vertices_iter_mut(
+        &mut self,
+    ) -> impl rayon::iter::ParallelIterator<Item = (VertexIdx, &mut Vertex<V, E, S>)>
+    where
+        V: Send,
+        E: Send,
+        S: Send,
+    {
+        use rayon::prelude::*;
+
+        self.vertices
+            .par_iter_mut()
+            .map(|(&idx, vertex)| (idx, vertex))
+    }
+
+    /// Like [`Self::edges_iter`], but iterates across threads with [`rayon`].
+    /// The edges of each vertex are iterated by the same thread.
+    #[cfg(feature = "parallel")]
+    pub fn par_edges_iter(
+        &self,
+    ) -> impl rayon::iter::ParallelIterator<Item = (VertexIdx, VertexIdx, &E)>
+    where
+        V: Sync,
+        E: Sync,
+        S: Sync,
+    {
+        use rayon::prelude::*;
+
+        self.vertices
+            .par_iter()
+            .flat_map_iter(|(&from_idx, vertex)| {
+                vertex
+                    .edges
+                    .iter()
+                    .map(move |(&to_idx, weight)| (from_idx, to_idx, weight))
+            })
+    }
+
+    /// Iterate over every edge of the graph as `(from, to, weight)`, in no particular order.
+    /// In undirected graphs, each edge is yielded once in each direction.
+    pub fn edges_iter(&self) -> EdgesIter<'_, V, E, S> {
+        EdgesIter {
+            len: self.edge_count(),
+            vertices: self.vertices.iter(),
+            edges: None,
+        }
+    }
+
+    /// Like [`Self::edges_iter`], but the edge weights are mutable.
+    pub fn edges_iter_mut(&mut self) -> EdgesIterMut<'_, V, E, S> {
+        EdgesIterMut {
+            len: self.edge_count(),
+            vertices: self.vertices.iter_mut(),
+            edges: None,
+        }
+    }
+
+    /// Like [`Self::edges_iter`], but consumes the graph, dropping the vertex weights.
+    pub fn into_edges_iter(self) -> IntoEdgesIterator<V, E, S> {
+        IntoEdgesIterator {
+            len: self.edge_count(),
+            vertices: self.vertices.into_iter(),
+            edges: None,
+        }
+    }
+
+    /// The number of edges in `O(1)`. In undirected graphs, each edge is counted once in each
+    /// direction, except for self-loops.
+    pub fn edge_count(&self) -> usize {
+        self.edge_count
+    }
+
+    /// Check that both vertices of the edge from `from` to `to` exist.
+    fn check_edge(&self, from: VertexIdx, to: VertexIdx) -> Result<(), GraphError> {
+        if !self.contains_vertex(from) {
+            Err(GraphError::MissingSource(from))
+        } else if !self.contains_vertex(to) {
+            Err(GraphError::MissingTarget(to))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Insert or update the edge from `from` to `to` only in that direction, regardless of `D`.
+    fn insert_or_update_arc(
+        &mut self,
+        from: VertexIdx,
+        to: VertexIdx,
+        weight: E,
+    ) -> Option<&mut Self> {
+        if !self.contains_vertex(to) {
+            return None;
+        }
+
+        if self
+            .vertices
+            .get_mut(&from)?
+            .edges
+            .insert(to, weight)
+            .is_none()
+        {
+            self.edge_count += 1;
+        }
+
+        if self.reverse_index {
+            self.vertices
+                .get_mut(&to)
+                .expect("The vertex exists")
+                .predecessors
+                .insert(from, ());
+        }
+
+        Some(self)
+    }
+
+    /// Recompute the number of edges and, with a reverse index, the predecessors of every vertex
+    /// from the edges, in `O(|V| + |E|)`.
+    pub(crate) fn rebuild_indices(&mut self) {
+        let mut predecessors: HashMap<VertexIdx, Predecessors<S>> = HashMap::new();
+
+        self.edge_count = 0;
+
+        for (&from_idx, vertex) in &self.vertices {
+            self.edge_count += vertex.edges.len();
+
+            if self.reverse_index {
+                for &to_idx in vertex.edges.keys() {
+                    predecessors.entry(to_idx).or_default().insert(from_idx, ());
+                }
+            }
+        }
+
+        for (vertex_idx, vertex) in &mut self.vertices {
+            vertex.predecessors = predecessors.remove(vertex_idx).unwrap_or_default();
+        }
+    }
+}
+
+impl<V: Default, E, D, S: BuildHasher + Default> Graph<V, E, D, S> {
+    /// Create a vertex with the default weight for each distinct label in `edges`.
+    ///
+    /// Returns the graph, the ID of each label, and `edges` with their labels replaced by IDs.
+    fn with_labeled_vertices<K: Hash + Eq>(
+        edges: impl IntoIterator<Item = (K, K, E)>,
+    ) -> (Self, HashMap<K, VertexIdx>, Vec<Edge<E>>) {
+        let mut graph = Self::default();
+        let mut labels = HashMap::new();
+        let edges = edges
+            .into_iter()
+            .map(|(from, to, weight)| {
+                let from_idx = *labels
+                    .entry(from)
+                    .or_insert_with(|| graph.insert_vertex(V::default()));
+                let to_idx = *labels
+                    .entry(to)
+                    .or_insert_with(|| graph.insert_vertex(V::default()));
+
+                (from_idx, to_idx, weight)
+            })
+            .collect();
+
+        (graph, labels, edges)
+    }
+}
+
+impl<V, E, D, S: BuildHasher + Default> Graph<V, E, D, S> {
+    /// Insert a vertex for each item of `vertices`, whose edges point to other items by position.
+    ///
+    /// Returns the graph and the edges with their positions replaced by IDs.
+    fn with_positional_edges(
+        vertices: impl IntoIterator<Item = (V, Vec<(usize, E)>)>,
+    ) -> (Self, Vec<Edge<E>>) {
+        let mut graph = Self::default();
+        let mut ids = Vec::new();
+        let mut positional_edges = Vec::new();
+
+        for (weight, edges) in vertices {
+            let vertex_idx = graph.insert_vertex(weight);
+
+            ids.push(vertex_idx);
+            positional_edges.push((vertex_idx, edges));
+        }
+
+        let edges = positional_edges
+            .into_iter()
+            .flat_map(|(from_idx, edges)| {
+                let ids = &ids;
+
+                edges
+                    .into_iter()
+                    .filter_map(move |(to, weight)| Some((from_idx, *ids.get(to)?, weight)))
+            })
+            .collect();
+
+        (graph, edges)
+    }
+}
+
+impl<V, E, D: Direction, S: BuildHasher + Default> Graph<V, E, D, S> {
+    pub fn is_directed(&self) -> bool {
+        D::IS_DIRECTED
+    }
+
+    /// Insert or update the edge from `from` to `to`, along with the reverse one if the graph is
+    /// undirected, so that readers and generators don't depend on `D`.
+    fn connect(&mut self, from: VertexIdx, to: VertexIdx, weight: E) -> Option<&mut Self>
+    where
+        E: Clone,
+    {
+        if D::IS_DIRECTED {
+            self.insert_or_update_arc(from, to, weight)
+        } else {
+            self.insert_or_update_undirected_edge(from, to, weight)
+        }
+    }
+
+    /// Remove an edge from a vertex `from` to a vertex `to` in `O(1)`.
+    /// In undirected graphs, the edge from `to` to `from` is removed as well.
+    ///
+    /// Returns that edge's weight, if it exists.
+    pub fn remove_edge(&mut self, from: VertexIdx, to: VertexIdx) -> Option<E> {
+        let weight = self.remove_arc(from, to)?;
+
+        if !D::IS_DIRECTED && from != to {
+            self.remove_arc(to, from);
+        }
+
+        Some(weight)
+    }
+
+    /// Like [`Self::remove_edge`], but fails with the reason the edge couldn't be removed.
+    pub fn try_remove_edge(&mut self, from: VertexIdx, to: VertexIdx) -> Result<E, GraphError> {
+        self.check_edge(from, to)?;
+        self.remove_edge(from, to)
+            .ok_or(GraphError::MissingEdge(from, to))
+    }
+
+    /// Remove the edges from `a` to `b` and from `b` to `a` in `O(1)`, even in directed graphs.
+    ///
+    /// Returns the weight of the edge from `a` to `b`, or of the one from `b` to `a` if only that
+    /// exists.
+    pub fn remove_undirected_edge(&mut self, a: VertexIdx, b: VertexIdx) -> Option<E> {
+        let forward = self.remove_arc(a, b);
+        let backward = if a != b { self.remove_arc(b, a) } else { None };
+
+        forward.or(backward)
+    }
+
+    /// Remove the edge from `from` to `to` only in that direction, regardless of `D`.
+    fn remove_arc(&mut self, from: VertexIdx, to: VertexIdx) -> Option<E> {
+        let weight = self.vertices.get_mut(&from)?.edges.remove(&to)?;
+
+        if let Some(to_vertex) = self.vertices.get_mut(&to) {
+            to_vertex.predecessors.remove(&from);
+        }
+
+        self.edge_count -= 1;
+        Some(weight)
+    }
+
+    /// Merge the vertex `b` into the vertex `a` in `O(deg(b))`, redirecting every edge of `b`
+    /// to `a`. Edges between `a` and `b`, as well as self-loops of `b`, become self-loops of `a`.
+    ///
+    /// - `merge_vertex`: `|a_weight, b_weight|`, which updates the weight of `a`
+    /// - `merge_edge`: `|a_edge_weight, b_edge_weight| -> merged_edge_weight`
+    ///     - It is called when both `a` and `b` have an edge to or from the same vertex.
+    ///
+    /// Returns itself to allow for chaining, if both vertices exist and are different.
+    pub fn merge_vertices<F, G>(
+        &mut self,
+        a: VertexIdx,
+        b: VertexIdx,
+        merge_vertex: F,
+        mut merge_edge: G,
+    ) -> Option<&mut Self>
+    where
+        F: FnOnce(&mut V, V),
+        G: FnMut(E, E) -> E,
+    {
+        if a == b || !self.contains_vertex(a) {
+            return None;
+        }
+
+        let (b_vertex, incoming_edges) = self.remove_vertex(b)?;
+        let redirect = |idx: VertexIdx| if idx == b { a } else { idx };
+
+        merge_vertex(&mut self.vertices.get_mut(&a)?.weight, b_vertex.weight);
+
+        for (to_idx, weight) in b_vertex.edges {
+            self.merge_arc(a, redirect(to_idx), weight, &mut merge_edge);
+        }
+
+        for (from_idx, weight) in incoming_edges {
+            // In undirected graphs, the edge between `a` and `b` was already merged from `b`'s side
+            if !D::IS_DIRECTED && from_idx == a {
+                continue;
+            }
+
+            self.merge_arc(redirect(from_idx), a, weight, &mut merge_edge);
+        }
+
+        Some(self)
+    }
+
+    /// Contract the edge from `from` to `to` in `O(deg(to))`, merging `to` into `from` like
+    /// [`Self::merge_vertices`]. The edges between them in either direction are removed instead of
+    /// becoming self-loops.
+    ///
+    /// Returns the weight of the contracted edge, if it exists.
+    pub fn contract_edge<F, G>(
+        &mut self,
+        from: VertexIdx,
+        to: VertexIdx,
+        merge_vertex: F,
+        merge_edge: G,
+    ) -> Option<E>
+    where
+        F: FnOnce(&mut V, V),
+        G: FnMut(E, E) -> E,
+    {
+        if from == to {
+            return None;
+        }
+
+        let weight = self.remove_edge(from, to)?;
+
+        self.remove_edge(to, from);
+        self.merge_vertices(from, to, merge_vertex, merge_edge);
+
+        Some(weight)
+    }
+
+    /// Insert the edge from `from` to `to`, merging it with the existing one, if any.
+    fn merge_arc(
+        &mut self,
+        from: VertexIdx,
+        to: VertexIdx,
+        weight: E,
+        merge_edge: &mut impl FnMut(E, E) -> E,
+    ) {
+        let weight = match self
+            .vertices
+            .get_mut(&from)
+            .and_then(|vertex| vertex.edges.remove(&to))
+        {
+            Some(old_weight) => {
+                self.edge_count -= 1;
+                merge_edge(old_weight, weight)
+            }
+            None => weight,
+        };
+
+        self.insert_or_update_arc(from, to, weight);
+    }
+}
+
+impl<V, E, S: BuildHasher + Default> Graph<V, E, Directed, S> {
+    /// Create a graph from edges of type `(from, to, weight)` between vertices with labels of type `K`,
+    /// in `O(n)`, where `n` is the length of `edges`. Each distinct label gets a vertex with the default weight.
+    ///
+    /// ```
+    /// # use badlgorithms::graph::Graph;
+    /// #
+    /// let (graph, labels) = Graph::<(), u8>::from_edges([("a", "b", 1), ("b", "c", 2)]);
+    ///
+    /// assert_eq!(graph.len(), 3);
+    /// assert!(graph.get_vertex(labels["a"]).unwrap().is_adjacent(labels["b"]));
+    /// ```
+    ///
+    /// Returns the graph along with the ID of the vertex of each label.
+    pub fn from_edges<K: Hash + Eq>(
+        edges: impl IntoIterator<Item = (K, K, E)>,
+    ) -> (Self, HashMap<K, VertexIdx>)
+    where
+        V: Default,
+    {
+        let (mut graph, labels, edges) = Self::with_labeled_vertices(edges);
+
+        graph.insert_or_update_edges(edges);
+        (graph, labels)
+    }
+
+    /// Insert or update an edge from a vertex `from` to a vertex `to` with weight `weight` in `O(1)`.
+    ///
+    /// To obtain the old edge weight (if any):
+    /// ```
+    /// # fn example() -> Option<()> {
+    /// # use badlgorithms::graph::Graph;
+    /// #
+    /// let mut graph = Graph::<u8, u16>::new();
+    /// let a_idx = graph.insert_vertex(10);
+    ///
+    /// graph.insert_or_update_edge(a_idx, a_idx, 3)?;
+    /// let (_, old_edge) = graph.get_vertex(a_idx)?.neighbors().next()?;
+    /// assert_eq!(*old_edge, 3);
+    ///
+    /// graph.insert_or_update_edge(a_idx, a_idx, 50)?;
+    /// let (_, new_edge) = graph.get_vertex(a_idx)?.neighbors().next()?;
+    /// assert_eq!(*new_edge, 50);
+    /// # Some(())
+    /// # }
+    /// ```
+    ///
+    /// Returns itself to allow for chaining [`Self::insert_edge`], if the vertices specified exist in this graph.
+    pub fn insert_or_update_edge(
+        &mut self,
+        from: VertexIdx,
+        to: VertexIdx,
+        weight: E,
+    ) -> Option<&mut Self> {
+        self.insert_or_update_arc(from, to, weight)
+    }
+
+    /// Like [`Self::insert_or_update_edge`], but fails with the reason the edge couldn't be inserted.
+    pub fn try_insert_or_update_edge(
+        &mut self,
+        from: VertexIdx,
+        to: VertexIdx,
+        weight: E,
+    ) -> Result<&mut Self, GraphError> {
+        self.check_edge(from, to)?;
+
+        Ok(self
+            .insert_or_update_arc(from, to, weight)
+            .expect("The vertices exist"))
+    }
+
+    /// A convenient method to insert or update multiple edges of type `(from, to, weight)` in `O(n)`, where `n` is the length of `edges`.
+    ///
+    /// Returns itself to allow for chaining [`Self::insert_edges`], if you are into it ¯\\\_(ツ)\_/¯
+    pub fn insert_or_update_edges(
+        &mut self,
+        edges: impl IntoIterator<Item = (VertexIdx, VertexIdx, E)>,
+    ) -> &mut Self {
+        for (from, to, weight) in edges {
+            self.insert_or_update_arc(from, to, weight);
+        }
+
+        self
+    }
+
+    /// Insert or update the edge from a vertex `from` to a vertex `to` in `O(1)`, with the weight
+    /// returned by `update`, which receives the old weight, if any.
+    ///
+    /// ```
+    /// # use badlgorithms::graph::Graph;
+    /// #
+    /// let mut graph = Graph::<(), u32>::new();
+    /// let [a, b] = [(); 2].map(|_| graph.insert_vertex(()));
+    ///
+    /// for _ in 0..3 {
+    ///     graph.update_edge_with(a, b, |count| count.unwrap_or(0) + 1);
+    /// }
+    ///
+    /// assert_eq!(graph.get_edge(a, b), Some(&3));
+    /// ```
+    ///
+    /// Returns itself to allow for chaining, if the vertices specified exist in this graph.
+    pub fn update_edge_with<F>(
+        &mut self,
+        from: VertexIdx,
+        to: VertexIdx,
+        update: F,
+    ) -> Option<&mut Self>
+    where
+        F: FnOnce(Option<E>) -> E,
+    {
+        if !self.contains_vertex(from) || !self.contains_vertex(to) {
+            return None;
+        }
+
+        let weight = update(self.remove_arc(from, to));
+
+        self.insert_or_update_arc(from, to, weight)
+    }
+
+    /// Create a graph with a vertex for each weight of `vertex_weights` and an edge for each
+    /// `Some` entry of `matrix`, where the entry at row `i` and column `j` is the weight of the
+    /// edge from the `i`-th vertex to the `j`-th one, in `O(|V|²)`. Entries past the number of
+    /// vertices are ignored.
+    ///
+    /// Returns the graph along with the ID of each vertex, in order.
+    pub fn from_adjacency_matrix<R>(
+        matrix: impl IntoIterator<Item = R>,
+        vertex_weights: impl IntoIterator<Item = V>,
+    ) -> (Self, Vec<VertexIdx>)
+    where
+        R: IntoIterator<Item = Option<E>>,
+    {
+        let mut graph = Self::default();
+        let ids: Vec<_> = vertex_weights
+            .into_iter()
+            .map(|weight| graph.insert_vertex(weight))
+            .collect();
+
+        for (row, &from_idx) in matrix.into_iter().zip(&ids) {
+            for (weight, &to_idx) in row.into_iter().zip(&ids) {
+                if let Some(weight) = weight {
+                    graph.insert_or_update_arc(from_idx, to_idx, weight);
+                }
+            }
+        }
+
+        (graph, ids)
+    }
+}
+
+impl<V, E: Clone, S: BuildHasher + Default> Graph<V, E, Undirected, S> {
+    /// Like `from_adjacency_matrix` of directed graphs, but only the entries on and above
+    /// the diagonal are read, so the matrix is treated as symmetric.
+    pub fn from_adjacency_matrix<R>(
+        matrix: impl IntoIterator<Item = R>,
+        vertex_weights: impl IntoIterator<Item = V>,
+    ) -> (Self, Vec<VertexIdx>)
+    where
+        R: IntoIterator<Item = Option<E>>,
+    {
+        let mut graph = Self::default();
+        let ids: Vec<_> = vertex_weights
+            .into_iter()
+            .map(|weight| graph.insert_vertex(weight))
+            .collect();
+
+        for (i, (row, &from_idx)) in matrix.into_iter().zip(&ids).enumerate() {
+            for (weight, &to_idx) in row.into_iter().zip(&ids).skip(i) {
+                if let Some(weight) = weight {
+                    graph.insert_or_update_edge(from_idx, to_idx, weight);
+                }
+            }
+        }
+
+        (graph, ids)
+    }
+
+    /// Create a graph from edges of type `(from, to, weight)` between vertices with labels of type `K`,
+    /// in `O(n)`, where `n` is the length of `edges`. Each distinct label gets a vertex with the default weight,
+    /// and each edge is inserted in both directions.
+    ///
+    /// Returns the graph along with the ID of the vertex of each label.
+    pub fn from_edges<K: Hash + Eq>(
+        edges: impl IntoIterator<Item = (K, K, E)>,
+    ) -> (Self, HashMap<K, VertexIdx>)
+    where
+        V: Default,
+    {
+        let (mut graph, labels, edges) = Self::with_labeled_vertices(edges);
+
+        graph.insert_or_update_edges(edges);
+        (graph, labels)
+    }
+
+    /// Insert or update the edge between the vertices `from` and `to` with weight `weight` in `O(1)`,
+    /// in both directions.
+    ///
+    /// Returns itself to allow for chaining [`Self::insert_or_update_edge`], if the vertices specified exist in this graph.
+    pub fn insert_or_update_edge(
+        &mut self,
+        from: VertexIdx,
+        to: VertexIdx,
+        weight: E,
+    ) -> Option<&mut Self> {
+        self.insert_or_update_undirected_edge(from, to, weight)
+    }
+
+    /// Like [`Self::insert_or_update_edge`], but fails with the reason the edge couldn't be inserted.
+    pub fn try_insert_or_update_edge(
+        &mut self,
+        from: VertexIdx,
+        to: VertexIdx,
+        weight: E,
+    ) -> Result<&mut Self, GraphError> {
+        self.check_edge(from, to)?;
+
+        Ok(self
+            .insert_or_update_undirected_edge(from, to, weight)
+            .expect("The vertices exist"))
+    }
+
+    /// A convenient method to insert or update multiple edges of type `(from, to, weight)` in `O(n)`,
+    /// where `n` is the length of `edges`, in both directions.
+    pub fn insert_or_update_edges(
+        &mut self,
+        edges: impl IntoIterator<Item = (VertexIdx, VertexIdx, E)>,
+    ) -> &mut Self {
+        for (from, to, weight) in edges {
+            self.insert_or_update_edge(from, to, weight);
+        }
+
+        self
+    }
+
+    /// Insert or update the edge between the vertices `from` and `to` in `O(1)`, in both
+    /// directions, with the weight returned by `update`, which receives the old weight of the edge
+    /// from `from` to `to`, if any.
+    ///
+    /// Returns itself to allow for chaining, if the vertices specified exist in this graph.
+    pub fn update_edge_with<F>(
+        &mut self,
+        from: VertexIdx,
+        to: VertexIdx,
+        update: F,
+    ) -> Option<&mut Self>
+    where
+        F: FnOnce(Option<E>) -> E,
+    {
+        if !self.contains_vertex(from) || !self.contains_vertex(to) {
+            return None;
+        }
+
+        let weight = update(self.remove_arc(from, to));
+
+        self.insert_or_update_edge(from, to, weight)
+    }
+}
+
+impl<V, E: Clone, D, S: BuildHasher + Default> Graph<V, E, D, S> {
+    /// Insert or update the edges from `a` to `b` and from `b` to `a` with weight `weight` in `O(1)`,
+    /// even in directed graphs. Either both edges are inserted or none.
+    ///
+    /// Returns itself to allow for chaining, if the vertices specified exist in this graph.
+    pub fn insert_or_update_undirected_edge(
+        &mut self,
+        a: VertexIdx,
+        b: VertexIdx,
+        weight: E,
+    ) -> Option<&mut Self> {
+        if !self.contains_vertex(a) || !self.contains_vertex(b) {
+            return None;
+        }
+
+        if a != b {
+            self.insert_or_update_arc(b, a, weight.clone());
+        }
+
+        self.insert_or_update_arc(a, b, weight)
+    }
+}
+
+impl<V, E, D, S: BuildHasher + Default> IntoIterator for Graph<V, E, D, S> {
+    type IntoIter = IntoVerticesIterator<V, E, S>;
+    type Item = (VertexIdx, Vertex<V, E, S>);
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.into_vertices_iter()
+    }
+}
+
+impl<V, E, D, S: BuildHasher + Default> Index<VertexIdx> for Graph<V, E, D, S> {
+    type Output = Vertex<V, E, S>;
+
+    /// Retrieve an immutable reference to a vertex by its ID in `O(1)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the vertex doesn't exist. Use [`Graph::get_vertex`] otherwise.
+    fn index(&self, vertex_idx: VertexIdx) -> &Vertex<V, E, S> {
+        self.get_vertex(vertex_idx)
+            .unwrap_or_else(|| panic!("The vertex {vertex_idx:?} doesn't exist"))
+    }
+}
+
+impl<V, E, D, S: BuildHasher + Default> IndexMut<VertexIdx> for Graph<V, E, D, S> {
+    /// Retrieve a mutable reference to a vertex by its ID in `O(1)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the vertex doesn't exist. Use [`Graph::get_mut_vertex`] otherwise.
+    fn index_mut(&mut self, vertex_idx: VertexIdx) -> &mut Vertex<V, E, S> {
+        self.get_mut_vertex(vertex_idx)
+            .unwrap_or_else(|| panic!("The vertex {vertex_idx:?} doesn't exist"))
+    }
+}
+
+impl<V, E, S: BuildHasher + Default> Extend<Edge<E>> for Graph<V, E, Directed, S> {
+    /// Insert or update every edge, like [`Self::insert_or_update_edges`].
+    fn extend<T: IntoIterator<Item = Edge<E>>>(&mut self, edges: T) {
+        self.insert_or_update_edges(edges);
+    }
+}
+
+impl<V, E: Clone, S: BuildHasher + Default> Extend<Edge<E>> for Graph<V, E, Undirected, S> {
+    /// Insert or update every edge in both directions, like [`Self::insert_or_update_edges`].
+    fn extend<T: IntoIterator<Item = Edge<E>>>(&mut self, edges: T) {
+        self.insert_or_update_edges(edges);
+    }
+}
+
+/// Create a graph from a vertex weight and the edges of each vertex, given as
+/// `(position of the target in the iterator, weight)`. Edges to positions past the end are ignored.
+impl<V, E, S: BuildHasher + Default> FromIterator<(V, Vec<(usize, E)>)>
+    for Graph<V, E, Directed, S>
+{
+    fn from_iter<T: IntoIterator<Item = (V, Vec<(usize, E)>)>>(vertices: T) -> Self {
+        let (mut graph, edges) = Self::with_positional_edges(vertices);
+
+        graph.insert_or_update_edges(edges);
+        graph
+    }
+}
+
+/// Create a graph from a vertex weight and the edges of each vertex, given as
+/// `(position of the target in the iterator, weight)`, in both directions.
+/// Edges to positions past the end are ignored.
+impl<V, E: Clone, S: BuildHasher + Default> FromIterator<(V, Vec<(usize, E)>)>
+    for Graph<V, E, Undirected, S>
+{
+    fn from_iter<T: IntoIterator<Item = (V, Vec<(usize, E)>)>>(vertices: T) -> Self {
+        let (mut graph, edges) = Self::with_positional_edges(vertices);
+
+        graph.insert_or_update_edges(edges);
+        graph
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        algo,
+        small_map::{SmallMap, INLINE_CAPACITY},
+        FastHashBuilder, Graph, GraphError, Undirected, Vertex, VertexIdx,
+    };
+    use crate::collections::HashMap;
+    use helpers::{test_indices, test_neighbors};
+
+    #[test]
+    fn test_send_sync() {
+        fn is_send<S: Send>() {}
+        fn is_sync<S: Sync>() {}
+
+        is_send::<Graph<String, String>>();
+        is_sync::<Graph<String, String>>();
+        is_send::<Graph<String, String, Undirected>>();
+        is_sync::<Graph<String, String, Undirected>>();
+    }
+
+    #[test]
+    fn test_relationships() {
+        // The graph should look like this:
+        //   [a: 23]-→[b: 1]
+        //     ↑ ↓   ↖   ↑
+        //   a[c: 7]  [d: 9] ↰
+        //               ⤷---⤴
+        let mut graph = Graph::<u16, ()>::new();
+
+        let a_idx = graph.insert_vertex(23);
+        let b_idx = graph.insert_vertex(1);
+        let c_idx = graph.insert_vertex(7);
+        let d_idx = graph.insert_vertex(9);
+
+        graph.insert_or_update_edge(a_idx, b_idx, ());
+        graph.insert_or_update_edges([
+            (a_idx, b_idx, ()),
+            (a_idx, c_idx, ()),
+            (c_idx, a_idx, ()),
+            (d_idx, a_idx, ()),
+            (d_idx, b_idx, ()),
+            (d_idx, d_idx, ()),
+        ]);
+
+        let temp_idx = graph.insert_vertex(u16::MAX);
+
+        assert_eq!(
+            graph
+                .remove_vertex(temp_idx)
+                .map(|(vertex, _)| vertex.into_weight()),
+            Some(u16::MAX),
+            "Vertex temp has been already removed"
+        );
+
+        assert!(
+            graph
+                .insert_or_update_edge(temp_idx, temp_idx, ())
+                .is_none(),
+            "There can't be edges in non-existent vertices"
+        );
+
+        let a = graph.get_vertex(a_idx).expect("Vertex a doesn't exist");
+        let b = graph.get_vertex(b_idx).expect("Vertex b doesn't exist");
+        let c = graph.get_vertex(c_idx).expect("Vertex c doesn't exist");
+        let d = graph.get_vertex(d_idx).expect("Vertex d doesn't exist");
+
+        assert_eq!(*a.weight(), 23, "Wrong weight for vertex a");
+        assert_eq!(*b.weight(), 1, "Wrong weight for vertex b");
+        assert_eq!(*c.weight(), 7, "Wrong weight for vertex c");
+        assert_eq!(*d.weight(), 9, "Wrong weight for vertex d");
+
+        test_neighbors(a, &[b_idx, c_idx]);
+        test_neighbors(b, &[]);
+        test_neighbors(c, &[a_idx]);
+        test_neighbors(d, &[b_idx, a_idx, d_idx]);
+    }
+
+    #[test]
+    fn test_undirected() {
+        //  a - b   c ↰
+        //            ⤴
+        let mut graph = Graph::<(), u8, Undirected>::new();
+        let [a, b, c] = [(); 3].map(|_| graph.insert_vertex(()));
+
+        graph.insert_or_update_edges([(a, b, 1), (c, c, 2)]);
+
+        assert!(!graph.is_directed(), "The graph is undirected");
+        assert!(
+            Graph::<(), ()>::new().is_directed(),
+            "Graphs are directed by default"
+        );
+
+        let a_vertex = graph.get_vertex(a).expect("Vertex a doesn't exist");
+        let b_vertex = graph.get_vertex(b).expect("Vertex b doesn't exist");
+        let c_vertex = graph.get_vertex(c).expect("Vertex c doesn't exist");
+
+        test_neighbors(a_vertex, &[b]);
+        test_neighbors(b_vertex, &[a]);
+        test_neighbors(c_vertex, &[c]);
+
+        assert_eq!(graph.remove_edge(b, a), Some(1), "Wrong weight for b - a");
+        assert_eq!(
+            graph.remove_edge(a, b),
+            None,
+            "Both directions must have been removed"
+        );
+        assert_eq!(graph.remove_edge(c, c), Some(2), "Wrong weight for c - c");
+    }
+
+    #[test]
+    fn test_edges_iter() {
+        //  a → b ↰
+        //  ↑ ↙ ⤷-⤴
+        //  c
+        let mut graph = Graph::<(), u8>::new();
+        let [a, b, c] = [(); 3].map(|_| graph.insert_vertex(()));
+
+        graph.insert_or_update_edges([(a, b, 1), (b, b, 2), (b, c, 3), (c, a, 4)]);
+
+        let mut edges = graph.edges_iter();
+
+        assert_eq!(edges.len(), 4, "Wrong number of edges");
+        edges.next();
+        assert_eq!(edges.len(), 3, "Wrong number of remaining edges");
+
+        for (_, _, weight) in graph.edges_iter_mut() {
+            *weight *= 10;
+        }
+
+        let mut edges: Vec<_> = graph.into_edges_iter().collect();
+
+        edges.sort_unstable();
+
+        assert_eq!(
+            edges,
+            [(a, b, 10), (b, b, 20), (b, c, 30), (c, a, 40)],
+            "Wrong edges"
+        );
+    }
+
+    #[test]
+    fn test_transpose() {
+        //  a → b ↰
+        //  ↑ ↙ ⤷-⤴
+        //  c
+        let mut graph = Graph::<u8, u8>::new();
+        let [a, b, c] = [1, 2, 3].map(|weight| graph.insert_vertex(weight));
+
+        graph.insert_or_update_edges([(a, b, 1), (b, b, 2), (b, c, 3), (c, a, 4)]);
+
+        let mut edges: Vec<_> = graph
+            .transpose()
+            .into_edges_iter()
+            .map(|(from_idx, to_idx, weight)| (from_idx, to_idx, *weight))
+            .collect();
+
+        edges.sort_unstable();
+
+        assert_eq!(
+            edges,
+            [(a, c, 4), (b, a, 1), (b, b, 2), (c, b, 3)],
+            "Wrong edges of the transpose"
+        );
+
+        let transpose = graph.into_transpose();
+
+        assert_eq!(
+            transpose.get_vertex(c).map(|vertex| *vertex.weight()),
+            Some(3),
+            "The vertices must keep their IDs and weights"
+        );
+
+        let mut edges: Vec<_> = transpose.into_edges_iter().collect();
+
+        edges.sort_unstable();
+
+        assert_eq!(
+            edges,
+            [(a, c, 4), (b, a, 1), (b, b, 2), (c, b, 3)],
+            "Wrong edges of the owned transpose"
+        );
+    }
+
+    #[test]
+    fn test_from_edges() {
+        let (graph, labels) =
+            Graph::<(), u8, Undirected>::from_edges([("a", "b", 1), ("b", "c", 2), ("c", "c", 3)]);
+
+        assert_eq!(graph.len(), 3, "Each label must have one vertex");
+        assert_eq!(labels.len(), 3, "Each label must have an ID");
+
+        let b = graph
+            .get_vertex(labels["b"])
+            .expect("Vertex b doesn't exist");
+        let c = graph
+            .get_vertex(labels["c"])
+            .expect("Vertex c doesn't exist");
+
+        test_neighbors(b, &[labels["a"], labels["c"]]);
+        test_neighbors(c, &[labels["b"], labels["c"]]);
+    }
+
+    #[test]
+    fn test_capacity() {
+        let mut graph = Graph::<(), ()>::with_capacity(10);
+
+        assert!(
+            graph.vertices.capacity() >= 10,
+            "Not enough vertex capacity"
+        );
+
+        graph.reserve(100);
+        assert!(
+            graph.vertices.capacity() >= 100,
+            "Not enough vertex capacity"
+        );
+
+        let a_idx = graph.insert_vertex_with_capacity((), 5);
+        let a = graph.get_mut_vertex(a_idx).expect("Vertex a doesn't exist");
+
+        assert!(a.edge_capacity() >= 5, "Not enough edge capacity");
+
+        a.reserve_edges(50);
+        assert!(a.edge_capacity() >= 50, "Not enough edge capacity");
+    }
+
+    #[test]
+    fn test_clone_eq() {
+        let mut graph = Graph::<u8, u8>::new();
+        let [a, b] = [1, 2].map(|weight| graph.insert_vertex(weight));
+
+        graph.insert_or_update_edge(a, b, 3);
+
+        let mut clone = graph.clone();
+
+        assert_eq!(clone, graph, "A clone must be equal to the original");
+
+        clone.insert_or_update_edge(a, b, 4);
+        assert_ne!(clone, graph, "The edge weights are different");
+
+        clone.insert_or_update_edge(a, b, 3);
+        assert_eq!(clone, graph, "The edge weights are the same again");
+
+        clone.update_vertex(5, b).expect("b exists");
+        assert_ne!(clone, graph, "The vertex weights are different");
+
+        let mut other = Graph::<u8, u8>::new();
+        let [c, d] = [1, 2].map(|weight| other.insert_vertex(weight));
+
+        other.insert_or_update_edge(c, d, 3);
+        assert_ne!(other, graph, "The vertex IDs are different");
+    }
+
+    #[test]
+    fn test_stale_vertex_idx() {
+        let mut graph = Graph::<u8, ()>::new();
+        let a_idx = graph.insert_vertex(1);
+
+        graph.remove_vertex(a_idx);
+
+        let b_idx = graph.insert_vertex(2);
+
+        assert_ne!(a_idx, b_idx, "IDs must never be reused");
+        assert!(
+            graph.get_vertex(a_idx).is_none(),
+            "A removed vertex can't be retrieved"
+        );
+        assert!(
+            graph.insert_or_update_edge(a_idx, b_idx, ()).is_none(),
+            "There can't be edges from removed vertices"
+        );
+        assert_eq!(
+            graph.update_vertex(3, a_idx).err(),
+            Some(GraphError::MissingVertex(a_idx)),
+            "A removed vertex can't be inserted again"
+        );
+        assert!(
+            graph.get_vertex(a_idx).is_none(),
+            "A removed vertex can't be retrieved"
+        );
+    }
+
+    #[test]
+    fn test_retain() {
+        //  a → b ↰
+        //  ↑ ↙ ⤷-⤴
+        //  c
+        let mut graph = Graph::<u8, u8>::new();
+        let [a, b, c] = [1, 2, 3].map(|weight| graph.insert_vertex(weight));
+
+        graph.insert_or_update_edges([(a, b, 1), (b, b, 2), (b, c, 3), (c, a, 4)]);
+        graph.retain_edges(|from_idx, to_idx, weight| {
+            *weight *= 10;
+            from_idx != to_idx
+        });
+
+        let mut edges: Vec<_> = graph
+            .edges_iter()
+            .map(|(from, to, &weight)| (from, to, weight))
+            .collect();
+
+        edges.sort_unstable();
+        assert_eq!(edges, [(a, b, 10), (b, c, 30), (c, a, 40)], "Wrong edges");
+
+        graph.retain_vertices(|_, weight| *weight != 2);
+
+        let mut edges: Vec<_> = graph.into_edges_iter().collect();
+
+        edges.sort_unstable();
+        assert_eq!(edges, [(c, a, 40)], "The edges of b must have been removed");
+    }
+
+    #[test]
+    fn test_retain_map_in_place() {
+        //  a → b ↰
+        //  ↑ ↙ ⤷-⤴
+        //  c
+        let mut graph = Graph::<u8, u8>::new();
+        let [a, b, c] = [1, 2, 3].map(|weight| graph.insert_vertex(weight));
+
+        graph.insert_or_update_edges([(a, b, 1), (b, b, 2), (b, c, 3), (c, a, 4)]);
+
+        let mut edge_calls = 0;
+
+        graph.retain_map_in_place(
+            |_, weight| {
+                *weight *= 10;
+                *weight != 20
+            },
+            |_, _, weight| {
+                edge_calls += 1;
+                *weight += 1;
+                true
+            },
+        );
+
+        assert_eq!(edge_calls, 1, "Edges of removed vertices must be skipped");
+        assert_eq!(
+            graph.get_vertex(c).map(|vertex| *vertex.weight()),
+            Some(30),
+            "Wrong weight of vertex c"
+        );
+        assert_eq!(graph.get_edge(c, a), Some(&5), "Wrong weight of the edge");
+        test_indices(&graph);
+    }
+
+    #[test]
+    fn test_extend_from_iter() {
+        // a → b → c, with an edge from c past the end
+        let mut graph: Graph<char, u8> = [
+            ('a', vec![(1, 1)]),
+            ('b', vec![(2, 2)]),
+            ('c', vec![(3, 3)]),
+        ]
+        .into_iter()
+        .collect();
+
+        let mut vertices: Vec<_> = graph
+            .vertices_iter()
+            .map(|(idx, vertex)| (*vertex.weight(), idx))
+            .collect();
+
+        vertices.sort_unstable();
+
+        let [(_, a), (_, b), (_, c)] = vertices[..] else {
+            panic!("There must be 3 vertices");
+        };
+
+        graph.extend([(c, a, 4)]);
+
+        let mut edges: Vec<_> = graph.into_edges_iter().collect();
+
+        edges.sort_unstable();
+        assert_eq!(edges, [(a, b, 1), (b, c, 2), (c, a, 4)], "Wrong edges");
+
+        let graph: Graph<(), u8, Undirected> =
+            [((), vec![(1, 1)]), ((), vec![])].into_iter().collect();
+
+        assert_eq!(graph.edges_iter().len(), 2, "Undirected edges go both ways");
+    }
+
+    #[test]
+    fn test_predecessors_index() {
+        //  a → b ↰
+        //  ↑ ↙ ⤷-⤴
+        //  c → d
+        let mut graph = Graph::<u8, u8>::new();
+        let [a, b, c, d] = [1, 2, 3, 4].map(|weight| graph.insert_vertex(weight));
+
+        graph.insert_or_update_edges([(a, b, 1), (b, b, 2), (b, c, 3), (c, a, 4), (c, d, 5)]);
+        test_indices(&graph);
+
+        let mut graph = graph.with_reverse_index();
+
+        assert!(graph.has_reverse_index(), "The index was just built");
+        test_indices(&graph);
+
+        let mut incoming: Vec<_> = graph
+            .incoming_edges(b)
+            .expect("Vertex b exists")
+            .map(|(from_idx, &weight)| (from_idx, weight))
+            .collect();
+
+        incoming.sort_unstable();
+        assert_eq!(incoming, [(a, 1), (b, 2)], "Wrong incoming edges of b");
+
+        test_indices(&graph.transpose());
+        test_indices(&graph.clone().into_transpose());
+        test_indices(&graph.map(|_, _| (), |_, _, _| ()));
+
+        graph.remove_edge(b, b);
+        test_indices(&graph);
+
+        graph.retain_edges(|_, to_idx, _| to_idx != d);
+        test_indices(&graph);
+
+        graph.update_vertex(10, c).expect("c exists");
+        test_indices(&graph);
+
+        graph.retain_vertices(|vertex_idx, _| vertex_idx != a);
+        test_indices(&graph);
+
+        graph.remove_vertex(c);
+        test_indices(&graph);
+
+        let mut undirected = Graph::<(), u8, Undirected>::new().with_reverse_index();
+        let [e, f] = [(); 2].map(|_| undirected.insert_vertex(()));
+
+        undirected.insert_or_update_edge(e, f, 1);
+        test_indices(&undirected);
+
+        undirected.remove_edge(f, e);
+        test_indices(&undirected);
+    }
+
+    #[test]
+    fn test_predecessors_spill() {
+        let mut graph = Graph::<(), ()>::new().with_reverse_index();
+        let to_idx = graph.insert_vertex(());
+        let sources: Vec<_> = (0..=INLINE_CAPACITY)
+            .map(|_| graph.insert_vertex(()))
+            .collect();
+
+        for &from_idx in &sources[..INLINE_CAPACITY] {
+            graph.insert_or_update_edge(from_idx, to_idx, ());
+        }
+
+        assert!(
+            matches!(graph[to_idx].predecessors, SmallMap::Inline { .. }),
+            "Few predecessors are stored inline"
+        );
+
+        graph.insert_or_update_edge(sources[INLINE_CAPACITY], to_idx, ());
+
+        assert!(
+            matches!(graph[to_idx].predecessors, SmallMap::Heap(_)),
+            "Too many predecessors for the inline storage are moved to the heap"
+        );
+        assert_eq!(
+            graph[to_idx].in_degree(),
+            INLINE_CAPACITY + 1,
+            "No predecessor is lost when moving them"
+        );
+        test_indices(&graph);
+
+        graph.remove_vertex(sources[0]);
+        graph.remove_edge(sources[1], to_idx);
+
+        assert_eq!(
+            graph[to_idx].in_degree(),
+            INLINE_CAPACITY - 1,
+            "Removed sources aren't predecessors anymore"
+        );
+        test_indices(&graph);
+    }
+
+    #[test]
+    fn test_degrees() {
+        //  a → b ↰
+        //  ↑ ↙ ⤷-⤴
+        //  c
+        let mut graph = Graph::<(), ()>::new();
+        let [a, b, c] = [(); 3].map(|_| graph.insert_vertex(()));
+
+        graph.insert_or_update_edges([(a, b, ()), (b, b, ()), (b, c, ()), (c, a, ())]);
+
+        assert_eq!(graph.out_degree(b), Some(2), "Wrong out-degree of b");
+        assert_eq!(graph.in_degree(b), Some(2), "Wrong in-degree of b");
+        assert_eq!(graph.in_degree(c), Some(1), "Wrong in-degree of c");
+        assert_eq!(graph.degree_sequence(), [2, 1, 1], "Wrong degree sequence");
+
+        let (_, incoming_edges) = graph.remove_vertex(a).expect("Vertex a exists");
+
+        assert_eq!(incoming_edges, [(c, ())], "Wrong incoming edges of a");
+        assert_eq!(graph.out_degree(a), None, "Vertex a has been removed");
+        assert_eq!(graph.out_degree(c), Some(0), "Edge c → a has been removed");
+    }
+
+    #[test]
+    fn test_contract_edge() {
+        //  a → b → c
+        //  ↓ ↗ ↓
+        //  d   e
+        let mut graph = Graph::<u8, u8>::new();
+        let [a, b, c, d, e] = [1, 2, 3, 4, 5].map(|weight| graph.insert_vertex(weight));
+
+        graph.insert_or_update_edges([(a, b, 1), (b, c, 2), (a, d, 3), (d, b, 4), (b, e, 5)]);
+
+        assert_eq!(
+            graph.contract_edge(a, b, |a, b| *a += b, |x, y| x + y),
+            Some(1),
+            "Wrong weight for a → b"
+        );
+        assert_eq!(
+            graph.get_vertex(b).map(Vertex::weight),
+            None,
+            "Vertex b has been merged"
+        );
+        assert_eq!(
+            graph.get_vertex(a).map(Vertex::weight),
+            Some(&3),
+            "Wrong merged weight"
+        );
+
+        let mut edges: Vec<_> = graph
+            .edges_iter()
+            .map(|(from, to, &weight)| (from, to, weight))
+            .collect();
+
+        edges.sort_unstable();
+        assert_eq!(
+            edges,
+            [(a, c, 2), (a, d, 3), (a, e, 5), (d, a, 4)],
+            "Wrong edges after the contraction"
+        );
+        test_indices(&graph);
+
+        // Both a → d and d → a become self-loops, merged into one
+        graph.merge_vertices(a, d, |_, _| {}, |x, y| x.max(y));
+
+        assert_eq!(
+            graph.get_vertex(a).and_then(|vertex| vertex.edges.get(&a)),
+            Some(&4),
+            "Wrong self-loop"
+        );
+        assert!(
+            graph.merge_vertices(a, a, |_, _| {}, |x, _| x).is_none(),
+            "A vertex can't be merged with itself"
+        );
+        test_indices(&graph);
+
+        //  a -2- b
+        //   1\ /3
+        //     c
+        let mut undirected = Graph::<(), u8, Undirected>::new();
+        let [a, b, c] = [(); 3].map(|_| undirected.insert_vertex(()));
+
+        undirected.insert_or_update_edges([(a, b, 2), (a, c, 1), (b, c, 3)]);
+        undirected.merge_vertices(a, b, |_, _| {}, |x, y| x + y);
+
+        assert_eq!(
+            undirected.get_edge(a, a),
+            Some(&2),
+            "The edge between a and b must become a single self-loop"
+        );
+        assert_eq!(undirected.get_edge(c, a), Some(&4), "Wrong merged edge");
+        assert_eq!(
+            undirected.edge_count(),
+            3,
+            "Wrong number of edges after the merge"
+        );
+        test_indices(&undirected);
+    }
+
+    #[test]
+    fn test_complement() {
+        //  a → b
+        //  ↑ ↙
+        //  c
+        let mut graph = Graph::<u8, u8>::new();
+        let [a, b, c] = [1, 2, 3].map(|weight| graph.insert_vertex(weight));
+
+        graph.insert_or_update_edges([(a, b, 1), (b, c, 2), (c, a, 3)]);
+
+        let complement = graph.complement(|_, _| 0, false);
+        let mut edges: Vec<_> = complement
+            .edges_iter()
+            .map(|(from, to, _)| (from, to))
+            .collect();
+        let mut expected = [(b, a), (c, b), (a, c)];
+
+        edges.sort_unstable();
+        expected.sort_unstable();
+        assert_eq!(
+            edges, expected,
+            "The complement contains the reversed cycle"
+        );
+        assert_eq!(
+            complement.get_vertex(a).map(Vertex::weight),
+            Some(&&1),
+            "The vertices keep their weights"
+        );
+        test_indices(&complement);
+
+        let complement = graph.complement(|_, _| 0, true);
+
+        assert_eq!(complement.edges_iter().len(), 6, "Self-loops are included");
+        test_indices(&complement);
+    }
+
+    #[test]
+    fn test_edge_count() {
+        let mut graph = Graph::<(), u8, Undirected>::new();
+        let [a, b, c] = [(); 3].map(|_| graph.insert_vertex(()));
+
+        graph.insert_or_update_edges([(a, b, 1), (b, c, 2), (c, c, 3), (a, b, 4)]);
+        assert_eq!(graph.edge_count(), 5, "Self-loops are counted once");
+
+        graph.remove_edge(b, a);
+        assert_eq!(graph.edge_count(), 3, "Both directions are removed");
+
+        graph.remove_vertex(c);
+        assert_eq!(graph.edge_count(), 0, "The edges of c are removed");
+        test_indices(&graph);
+    }
+
+    #[test]
+    fn test_undirected_edge() {
+        let mut graph = Graph::<(), u8>::new();
+        let [a, b, c] = [(); 3].map(|_| graph.insert_vertex(()));
+
+        graph
+            .insert_or_update_undirected_edge(a, b, 1)
+            .and_then(|graph| graph.insert_or_update_undirected_edge(c, c, 2))
+            .expect("The vertices exist");
+
+        test_neighbors(graph.get_vertex(a).expect("a exists"), &[b]);
+        test_neighbors(graph.get_vertex(b).expect("b exists"), &[a]);
+        test_neighbors(graph.get_vertex(c).expect("c exists"), &[c]);
+        assert_eq!(graph.edge_count(), 3, "Wrong number of edges");
+
+        assert_eq!(
+            graph.remove_undirected_edge(b, a),
+            Some(1),
+            "Wrong weight for b → a"
+        );
+        assert_eq!(
+            graph.remove_undirected_edge(c, c),
+            Some(2),
+            "Wrong weight for c → c"
+        );
+        assert_eq!(graph.edge_count(), 0, "Both directions are removed");
+        test_indices(&graph);
+
+        let missing = Graph::<(), u8>::new().insert_vertex(());
+
+        assert!(
+            graph
+                .insert_or_update_undirected_edge(a, missing, 3)
+                .is_none(),
+            "The vertex doesn't exist"
+        );
+        assert_eq!(graph.edge_count(), 0, "No edge is inserted");
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_par_iter() {
+        use rayon::prelude::*;
+
+        let mut graph = Graph::<u32, u32>::new();
+        let [a, b, c] = [1, 2, 3].map(|weight| graph.insert_vertex(weight));
+
+        graph.insert_or_update_edges([(a, b, 1), (b, c, 2), (c, a, 3), (a, c, 4)]);
+        graph
+            .par_vertices_iter_mut()
+            .for_each(|(_, vertex)| *vertex.weight_mut() *= 10);
+
+        assert_eq!(
+            graph
+                .par_vertices_iter()
+                .map(|(_, vertex)| *vertex.weight())
+                .sum::<u32>(),
+            60,
+            "Every vertex weight is multiplied"
+        );
+        assert_eq!(
+            graph
+                .par_edges_iter()
+                .map(|(_, _, &weight)| weight)
+                .sum::<u32>(),
+            10,
+            "Wrong sum of edge weights"
+        );
+        assert_eq!(
+            graph.par_edges_iter().count(),
+            graph.edge_count(),
+            "Every edge is yielded once"
+        );
+    }
+
+    #[test]
+    fn test_clear() {
+        let mut graph = Graph::<(), ()>::with_capacity(16);
+        let [a, b] = [(); 2].map(|_| graph.insert_vertex(()));
+
+        graph.insert_or_update_edge(a, b, ());
+        graph[a].reserve_edges(16);
+        graph.clear_edges();
+
+        assert_eq!(graph.len(), 2, "The vertices are kept");
+        assert_eq!(graph.edge_count(), 0, "The edges are removed");
+        assert!(graph[a].edge_capacity() >= 16, "The edge capacity is kept");
+        test_indices(&graph);
+
+        graph.shrink_to_fit();
+        assert!(
+            graph[a].edge_capacity() < 16,
+            "The edge capacity is released"
+        );
+
+        graph.clear();
+        assert!(graph.is_empty(), "The vertices are removed");
+        assert!(graph.capacity() >= 2, "The capacity is kept");
+
+        graph.shrink_to_fit();
+        assert_eq!(graph.capacity(), 0, "The capacity is released");
+    }
+
+    #[test]
+    fn test_parts() {
+        let mut graph = Graph::<u8, u8>::new();
+        let [a, b, c] = [1, 2, 3].map(|weight| graph.insert_vertex(weight));
+
+        graph.insert_or_update_edges([(a, b, 1), (b, c, 2)]);
+
+        let mut parts = graph.clone().into_parts();
+
+        assert_eq!(
+            Graph::from_parts(parts.clone()).as_ref(),
+            Ok(&graph),
+            "The graph is the same after a round trip"
+        );
+
+        parts.remove(&c);
+        assert_eq!(
+            Graph::<u8, u8>::from_parts(parts),
+            Err(GraphError::MissingTarget(c)),
+            "The edge b → c points to a missing vertex"
+        );
+    }
+
+    #[test]
+    fn test_graph_error() {
+        let mut graph = Graph::<(), ()>::new();
+        let [a, b] = [(); 2].map(|_| graph.insert_vertex(()));
+        let missing = Graph::<(), ()>::new().insert_vertex(());
+
+        assert_eq!(
+            graph.try_insert_or_update_edge(missing, a, ()).err(),
+            Some(GraphError::MissingSource(missing)),
+            "The source vertex doesn't exist"
+        );
+        assert_eq!(
+            graph.try_insert_or_update_edge(a, missing, ()).err(),
+            Some(GraphError::MissingTarget(missing)),
+            "The target vertex doesn't exist"
+        );
+        assert_eq!(
+            graph.try_remove_edge(a, b),
+            Err(GraphError::MissingEdge(a, b)),
+            "The edge doesn't exist yet"
+        );
+
+        graph
+            .try_insert_or_update_edge(a, b, ())
+            .expect("Both vertices exist");
+
+        assert_eq!(graph.try_remove_edge(a, b), Ok(()), "The edge exists");
+        assert!(graph.try_remove_vertex(b).is_ok(), "Vertex b exists");
+        assert_eq!(
+            graph.try_remove_vertex(b).err(),
+            Some(GraphError::MissingVertex(b)),
+            "Vertex b was removed"
+        );
+    }
+
+    #[test]
+    fn test_contains() {
+        let mut graph = Graph::<(), (), Undirected>::new();
+        let [a, b, c] = [(); 3].map(|_| graph.insert_vertex(()));
+
+        graph.insert_or_update_edge(a, b, ());
+        graph.remove_vertex(c);
+
+        assert!(graph.contains_vertex(a), "Vertex a exists");
+        assert!(!graph.contains_vertex(c), "Vertex c was removed");
+        assert!(
+            graph.contains_edge(a, b) && graph.contains_edge(b, a),
+            "The edge a - b exists in both directions"
+        );
+        assert!(!graph.contains_edge(a, c), "The edge a - c doesn't exist");
+    }
+
+    #[test]
+    fn test_get_edge() {
+        let mut graph = Graph::<(), u8>::new();
+        let [a, b] = [(); 2].map(|_| graph.insert_vertex(()));
+
+        graph.insert_or_update_edge(a, b, 1);
+
+        if let Some(weight) = graph.get_edge_mut(a, b) {
+            *weight += 1;
+        }
+
+        assert_eq!(graph.get_edge(a, b), Some(&2), "Wrong weight for a → b");
+        assert_eq!(graph.get_edge(b, a), None, "The edge b → a doesn't exist");
+    }
+
+    #[test]
+    fn test_update_edge_with() {
+        let mut graph = Graph::<(), u8, Undirected>::new();
+        let [a, b] = [(); 2].map(|_| graph.insert_vertex(()));
+
+        graph
+            .update_edge_with(a, b, |weight| weight.unwrap_or(0) + 1)
+            .and_then(|graph| graph.update_edge_with(b, a, |weight| weight.unwrap_or(0) + 1))
+            .expect("The vertices exist");
+
+        assert_eq!(graph.get_edge(a, b), Some(&2), "Wrong weight for a → b");
+        assert_eq!(graph.get_edge(b, a), Some(&2), "Wrong weight for b → a");
+        assert_eq!(graph.edge_count(), 2, "Wrong number of edges");
+        test_indices(&graph);
+    }
+
+    #[test]
+    fn test_sorted_neighbors() {
+        let mut graph = Graph::<(), u8>::new();
+        let [a, b, c, d] = [(); 4].map(|_| graph.insert_vertex(()));
+
+        graph.insert_or_update_edges([(a, b, 3), (a, c, 1), (a, d, 3)]);
+
+        let by_weight: Vec<_> = graph[a].neighbors_by_weight().collect();
+        let by_idx: Vec<_> = graph[a].neighbors_by_idx().map(|(idx, _)| idx).collect();
+        let by_reverse_weight: Vec<_> = graph[a]
+            .neighbors_sorted_by(|(_, x), (_, y)| y.cmp(x))
+            .map(|(_, &weight)| weight)
+            .collect();
+
+        assert_eq!(
+            by_weight,
+            [(c, &1), (b, &3), (d, &3)],
+            "Ties are broken by ID"
+        );
+        assert_eq!(by_idx, [b, c, d], "Wrong order by ID");
+        assert_eq!(by_reverse_weight, [3, 3, 1], "Wrong custom order");
+    }
+
+    #[test]
+    fn test_index() {
+        let mut graph = Graph::<u8, ()>::new();
+        let a = graph.insert_vertex(1);
+
+        *graph[a].weight_mut() += 1;
+        assert_eq!(*graph[a].weight(), 2, "Wrong weight for a");
+    }
+
+    #[test]
+    #[should_panic(expected = "doesn't exist")]
+    fn test_index_missing() {
+        let graph = Graph::<u8, ()>::new();
+        let missing = Graph::<u8, ()>::new().insert_vertex(0);
+
+        let _ = &graph[missing];
+    }
+
+    #[test]
+    fn test_adjacency_matrix() {
+        //  a → b ⇄ c
+        let mut graph = Graph::<(), usize>::new();
+        let [a, b, c] = [(); 3].map(|_| graph.insert_vertex(()));
+
+        graph.insert_or_update_edges([(a, b, 1), (b, c, 2), (c, b, 3)]);
+
+        let (ids, matrix) = graph.to_adjacency_matrix();
+
+        assert_eq!(ids, [a, b, c], "The vertices must be sorted by ID");
+        assert_eq!(
+            matrix,
+            [
+                [None, Some(&1), None],
+                [None, None, Some(&2)],
+                [None, Some(&3), None],
+            ],
+            "Wrong matrix"
+        );
+
+        let (directed, ids) = Graph::<&str, usize>::from_adjacency_matrix(
+            matrix
+                .iter()
+                .map(|row| row.iter().map(|weight| weight.copied())),
+            ["a", "b", "c"],
+        );
+
+        assert_eq!(directed.edge_count(), 3, "Wrong number of edges");
+        assert_eq!(directed.get_edge(ids[2], ids[1]), Some(&3), "Wrong weight");
+        test_indices(&directed);
+
+        // The entries below the diagonal are ignored
+        let (undirected, ids) = Graph::<(), usize, Undirected>::from_adjacency_matrix(
+            [vec![Some(1), Some(2)], vec![Some(3), None], vec![Some(4)]],
+            [(), ()],
+        );
+
+        assert_eq!(undirected.edge_count(), 3, "Wrong number of edges");
+        assert_eq!(
+            undirected.get_edge(ids[1], ids[0]),
+            Some(&2),
+            "Wrong weight"
+        );
+        assert_eq!(
+            undirected.get_edge(ids[0], ids[0]),
+            Some(&1),
+            "Wrong self-loop"
+        );
+        test_indices(&undirected);
+    }
+
+    #[test]
+    fn test_hasher() {
+        let mut graph = Graph::<usize, usize, Undirected, FastHashBuilder>::default();
+        let [a, b, c] = [0, 1, 2].map(|weight| graph.insert_vertex(weight));
+
+        graph.insert_or_update_edges([(a, b, 1), (b, c, 2)]);
+        graph.remove_vertex(c);
+
+        assert_eq!(
+            graph.get_edge(b, a),
+            Some(&1),
+            "Undirected edges go both ways"
+        );
+        assert_eq!(graph.edge_count(), 2, "Wrong number of edges");
+        test_indices(&graph);
+
+        // The algorithms use the hasher of the graph
+        let cores: HashMap<_, _, FastHashBuilder> = algo::k_core(&graph);
+
+        assert_eq!((cores[&a], cores[&b]), (1, 1), "Wrong core numbers");
+        assert_eq!(
+            algo::shortest_path_unweighted(&graph, a, b),
+            Some(vec![a, b]),
+            "Wrong path"
+        );
+
+        let mapped = graph.map(|_, weight| weight * 10, |_, _, weight| *weight);
+
+        assert_eq!(mapped[b].weight(), &10, "Wrong mapped weight");
+        assert_eq!(mapped, mapped.clone(), "A clone must be equal to the graph");
+        test_indices(&mapped);
+    }
+
+    #[test]
+    fn test_map() {
+        //  a → b ↰
+        //  ↑ ↙ ⤷-⤴
+        //  c
+        let mut graph = Graph::<u8, u8>::new();
+        let [a, b, c] = [1, 2, 3].map(|weight| graph.insert_vertex(weight));
+
+        graph.insert_or_update_edges([(a, b, 1), (b, b, 2), (b, c, 3), (c, a, 4)]);
+
+        let mapped = graph.map(
+            |_, &weight| u16::from(weight) * 100,
+            |from_idx, to_idx, &weight| (from_idx == to_idx, weight),
+        );
+
+        assert_eq!(
+            mapped.get_vertex(c).map(|vertex| *vertex.weight()),
+            Some(300),
+            "Wrong weight for vertex c"
+        );
+
+        let mut edges: Vec<_> = mapped.into_edges_iter().collect();
+
+        edges.sort_unstable();
+
+        assert_eq!(
+            edges,
+            [
+                (a, b, (false, 1)),
+                (b, b, (true, 2)),
+                (b, c, (false, 3)),
+                (c, a, (false, 4))
+            ],
+            "Wrong edges"
+        );
+    }
+
+    #[test]
+    fn test_filter_map() {
+        // The graph will look like this:
+        //
+        //              6
+        //         _________
+        //        /         \
+        //        ↓    3    |
+        //   [c: 8] ------> [a: 3]
+        //    ↑   |
+        //  2 |   | 4
+        //    |   ↓
+        //   [b: 6] ↰
+        //     ⤷---⤴
+        //       8
+        let mut graph = Graph::<u16, u8>::new();
+
+        let a_idx = graph.insert_vertex(3);
+        let b_idx = graph.insert_vertex(6);
+        let c_idx = graph.insert_vertex(8);
+
+        graph.insert_or_update_edges([
+            (b_idx, c_idx, 2),
+            (c_idx, b_idx, 4),
+            (a_idx, c_idx, 6),
+            (c_idx, a_idx, 3),
+            (b_idx, b_idx, 8),
+        ]);
+
+        // The graph should look like this:
+        //
+        //              1
+        //         _________
+        //        /         \
+        //        ↓    2    |
+        //   [c: 4] ------> [b: 3]
+        let mapped_graph: Graph<u16, i8> = graph.filter_map(
+            |_, vertex| {
+                let weight = vertex.weight();
+
+                (weight % 2 == 0).then_some(weight / 2)
+            },
             |(from_vertex_idx, _), (to_vertex_idx, _), old_edge_weight| {
                 // Remove cycles
                 (from_vertex_idx != to_vertex_idx).then_some(*old_edge_weight as i8 / 2)
@@ -353,9 +2613,50 @@ mod tests {
         test_neighbors(c, &[b_idx]);
     }
 
-    mod helpers {
-        use super::{Vertex, VertexIdx};
-        use std::{collections::HashSet, fmt::Debug};
+    pub(crate) mod helpers {
+        use super::{Graph, Vertex, VertexIdx};
+        use crate::collections::HashSet;
+        use core::{fmt::Debug, hash::BuildHasher};
+
+        /// Checks that the predecessors of every vertex match the edges of the graph, and that
+        /// they're only tracked with a reverse index.
+        pub fn test_indices<V, E, D, S: BuildHasher + Default>(graph: &Graph<V, E, D, S>) {
+            for (vertex_idx, vertex) in graph.vertices_iter() {
+                let expected: HashSet<VertexIdx> = graph
+                    .vertices_iter()
+                    .filter(|(_, from)| from.is_adjacent(vertex_idx))
+                    .map(|(from_idx, _)| from_idx)
+                    .collect();
+                let tracked: HashSet<VertexIdx> = vertex.predecessors.keys().copied().collect();
+
+                assert_eq!(
+                    graph
+                        .predecessors(vertex_idx)
+                        .expect("The vertex exists")
+                        .collect::<HashSet<_>>(),
+                    expected,
+                    "Wrong predecessors of {vertex_idx:?}"
+                );
+                assert_eq!(
+                    tracked,
+                    if graph.has_reverse_index() {
+                        expected
+                    } else {
+                        HashSet::new()
+                    },
+                    "Wrong reverse index of {vertex_idx:?}"
+                );
+            }
+
+            assert_eq!(
+                graph.edge_count(),
+                graph
+                    .vertices_iter()
+                    .map(|(_, vertex)| vertex.degree())
+                    .sum::<usize>(),
+                "Wrong number of edges"
+            );
+        }
 
         pub fn test_neighbors<V: Debug, E: Debug>(vertex: &Vertex<V, E>, expected: &[VertexIdx]) {
             let neighbors = vertex.neighbors().map(|(id, _)| id).collect::<HashSet<_>>();
@@ -0,0 +1,321 @@
+use super::{Directed, Direction, EdgeIdx, Graph, VertexIdx};
+use crate::collections::{DefaultHashBuilder, HashMap, HashSet};
+use alloc::vec::Vec;
+use core::{hash::BuildHasher, marker::PhantomData};
+
+/// A graph that allows multiple edges between the same pair of vertices, each with its own
+/// [`EdgeIdx`] and weight, so they can be retrieved and removed individually.
+///
+/// In undirected multigraphs, each edge is stored once and belongs to both of its vertices.
+///
+/// Like in [`Graph`], every map hashes with `S` and only [`MultiGraph::new`] is limited to the
+/// default hasher.
+#[derive(Clone, Debug)]
+pub struct MultiGraph<V, E, D = Directed, S = DefaultHashBuilder> {
+    vertices: HashMap<VertexIdx, MultiVertex<V, S>, S>,
+    /// The `(from, to, weight)` of each edge
+    edges: HashMap<EdgeIdx, (VertexIdx, VertexIdx, E), S>,
+    direction: PhantomData<fn() -> D>,
+}
+
+#[derive(Clone, Debug)]
+struct MultiVertex<V, S = DefaultHashBuilder> {
+    weight: V,
+    /// The edges from this vertex, or touching it in undirected graphs
+    out_edges: HashSet<EdgeIdx, S>,
+    /// The edges to this vertex. Unused in undirected graphs
+    in_edges: HashSet<EdgeIdx, S>,
+}
+
+impl<V, E, D, S: Default> Default for MultiGraph<V, E, D, S> {
+    fn default() -> Self {
+        Self {
+            vertices: HashMap::default(),
+            edges: HashMap::default(),
+            direction: PhantomData,
+        }
+    }
+}
+
+impl<V, E, D> MultiGraph<V, E, D> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<V, E, D, S: BuildHasher + Default> MultiGraph<V, E, D, S> {
+    /// Retrieve an immutable reference to the weight of a vertex by its ID in `O(1)`.
+    pub fn get_vertex(&self, vertex_idx: VertexIdx) -> Option<&V> {
+        Some(&self.vertices.get(&vertex_idx)?.weight)
+    }
+
+    /// Retrieve a mutable reference to the weight of a vertex by its ID in `O(1)`.
+    pub fn get_mut_vertex(&mut self, vertex_idx: VertexIdx) -> Option<&mut V> {
+        Some(&mut self.vertices.get_mut(&vertex_idx)?.weight)
+    }
+
+    /// Insert a vertex with weight `V` in `O(1)`. Returns its ID.
+    pub fn insert_vertex(&mut self, weight: V) -> VertexIdx {
+        let idx = VertexIdx::new();
+
+        self.vertices.insert(
+            idx,
+            MultiVertex {
+                weight,
+                out_edges: HashSet::default(),
+                in_edges: HashSet::default(),
+            },
+        );
+
+        idx
+    }
+
+    /// Retrieve the `(from, to, weight)` of an edge by its ID in `O(1)`.
+    pub fn get_edge(&self, edge_idx: EdgeIdx) -> Option<(VertexIdx, VertexIdx, &E)> {
+        let (from, to, weight) = self.edges.get(&edge_idx)?;
+
+        Some((*from, *to, weight))
+    }
+
+    /// Retrieve a mutable reference to the weight of an edge by its ID in `O(1)`.
+    pub fn get_mut_edge(&mut self, edge_idx: EdgeIdx) -> Option<&mut E> {
+        Some(&mut self.edges.get_mut(&edge_idx)?.2)
+    }
+
+    pub fn len(&self) -> usize {
+        self.vertices.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.vertices.is_empty()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+}
+
+impl<V, E, D: Direction, S: BuildHasher + Default> MultiGraph<V, E, D, S> {
+    pub fn is_directed(&self) -> bool {
+        D::IS_DIRECTED
+    }
+
+    /// Insert a new edge from a vertex `from` to a vertex `to` with weight `weight` in `O(1)`,
+    /// even if there are already edges between them.
+    ///
+    /// Returns the ID of the edge, if the vertices specified exist in this graph.
+    pub fn insert_edge(&mut self, from: VertexIdx, to: VertexIdx, weight: E) -> Option<EdgeIdx> {
+        if !self.vertices.contains_key(&from) || !self.vertices.contains_key(&to) {
+            return None;
+        }
+
+        let edge_idx = EdgeIdx::new();
+
+        self.attach(edge_idx, from, to);
+        self.edges.insert(edge_idx, (from, to, weight));
+
+        Some(edge_idx)
+    }
+
+    /// Remove an edge by its ID in `O(1)`, leaving the other edges between its vertices intact.
+    ///
+    /// Returns its weight, if it exists.
+    pub fn remove_edge(&mut self, edge_idx: EdgeIdx) -> Option<E> {
+        let (from, to, weight) = self.edges.remove(&edge_idx)?;
+
+        self.detach(edge_idx, from, to);
+
+        Some(weight)
+    }
+
+    /// Remove a vertex and all of its edges by its ID in `O(deg)`. Returns its weight, if it exists.
+    pub fn remove_vertex(&mut self, vertex_idx: VertexIdx) -> Option<V> {
+        let vertex = self.vertices.remove(&vertex_idx)?;
+
+        for edge_idx in vertex.out_edges.into_iter().chain(vertex.in_edges) {
+            if let Some((from, to, _)) = self.edges.remove(&edge_idx) {
+                self.detach(edge_idx, from, to);
+            }
+        }
+
+        Some(vertex.weight)
+    }
+
+    /// Iterate over the edges from a vertex as `(edge, to, weight)` in `O(1)`, if it exists.
+    /// In undirected graphs, `to` is the other vertex of each edge touching it.
+    pub fn neighbors(
+        &self,
+        vertex_idx: VertexIdx,
+    ) -> Option<impl Iterator<Item = (EdgeIdx, VertexIdx, &E)>> {
+        let vertex = self.vertices.get(&vertex_idx)?;
+
+        Some(vertex.out_edges.iter().map(move |edge_idx| {
+            let (from, to, weight) = &self.edges[edge_idx];
+
+            (
+                *edge_idx,
+                if *from == vertex_idx { *to } else { *from },
+                weight,
+            )
+        }))
+    }
+
+    /// Iterate over the edges from a vertex `from` to a vertex `to` as `(edge, weight)`, in `O(deg(from))`.
+    pub fn edges_between(
+        &self,
+        from: VertexIdx,
+        to: VertexIdx,
+    ) -> impl Iterator<Item = (EdgeIdx, &E)> {
+        self.neighbors(from)
+            .into_iter()
+            .flatten()
+            .filter(move |&(_, neighbor_idx, _)| neighbor_idx == to)
+            .map(|(edge_idx, _, weight)| (edge_idx, weight))
+    }
+
+    fn attach(&mut self, edge_idx: EdgeIdx, from: VertexIdx, to: VertexIdx) {
+        for (vertex_idx, is_from) in [(from, true), (to, false)] {
+            if let Some(vertex) = self.vertices.get_mut(&vertex_idx) {
+                if is_from || !D::IS_DIRECTED {
+                    vertex.out_edges.insert(edge_idx);
+                } else {
+                    vertex.in_edges.insert(edge_idx);
+                }
+            }
+        }
+    }
+
+    fn detach(&mut self, edge_idx: EdgeIdx, from: VertexIdx, to: VertexIdx) {
+        for vertex_idx in [from, to] {
+            if let Some(vertex) = self.vertices.get_mut(&vertex_idx) {
+                vertex.out_edges.remove(&edge_idx);
+                vertex.in_edges.remove(&edge_idx);
+            }
+        }
+    }
+}
+
+/// Converts in `O(|V| + |E|)`, keeping the vertex IDs. Each edge gets a new ID. In undirected
+/// graphs, the two directions of an edge become a single edge.
+impl<V, E, D, S> From<Graph<V, E, D, S>> for MultiGraph<V, E, D, S>
+where
+    D: Direction,
+    S: BuildHasher + Default,
+{
+    fn from(graph: Graph<V, E, D, S>) -> Self {
+        let mut multi = Self::default();
+        let mut edges = Vec::new();
+
+        for (vertex_idx, vertex) in graph.vertices {
+            multi.vertices.insert(
+                vertex_idx,
+                MultiVertex {
+                    weight: vertex.weight,
+                    out_edges: HashSet::default(),
+                    in_edges: HashSet::default(),
+                },
+            );
+            edges.extend(
+                vertex
+                    .edges
+                    .into_iter()
+                    .filter(|&(to_idx, _)| D::IS_DIRECTED || vertex_idx <= to_idx)
+                    .map(|(to_idx, weight)| (vertex_idx, to_idx, weight)),
+            );
+        }
+
+        for (from_idx, to_idx, weight) in edges {
+            multi.insert_edge(from_idx, to_idx, weight);
+        }
+
+        multi
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MultiGraph;
+    use crate::collections::FastHashBuilder;
+    use crate::graph::{Graph, Undirected};
+
+    #[test]
+    fn test_multi_graph() {
+        // Two parallel edges a → b, and an edge b → c
+        let mut graph = MultiGraph::<(), u8>::new();
+        let [a, b, c] = [(); 3].map(|_| graph.insert_vertex(()));
+
+        let first = graph.insert_edge(a, b, 1).expect("Both vertices exist");
+        let second = graph.insert_edge(a, b, 2).expect("Both vertices exist");
+
+        graph.insert_edge(b, c, 3);
+
+        assert_ne!(first, second, "Parallel edges must have different IDs");
+        assert_eq!(graph.edge_count(), 3, "Wrong number of edges");
+        assert_eq!(
+            graph.edges_between(a, b).count(),
+            2,
+            "Wrong number of parallel edges"
+        );
+        assert_eq!(
+            graph.edges_between(b, a).count(),
+            0,
+            "The graph is directed"
+        );
+
+        assert_eq!(
+            graph.remove_edge(first),
+            Some(1),
+            "Wrong weight for the first edge"
+        );
+        assert_eq!(
+            graph.remove_edge(first),
+            None,
+            "The first edge has been removed"
+        );
+        assert_eq!(
+            graph.edges_between(a, b).collect::<Vec<_>>(),
+            [(second, &2)],
+            "The second edge must remain"
+        );
+
+        assert_eq!(graph.remove_vertex(b), Some(()), "Vertex b exists");
+        assert_eq!(
+            graph.edge_count(),
+            0,
+            "The edges of b must have been removed"
+        );
+        assert_eq!(
+            graph.neighbors(a).map(Iterator::count),
+            Some(0),
+            "Vertex a has no edges"
+        );
+    }
+
+    #[test]
+    fn test_undirected_multi_graph() {
+        let mut graph = Graph::<(), u8, Undirected, FastHashBuilder>::default();
+        let [a, b] = [(); 2].map(|_| graph.insert_vertex(()));
+
+        graph.insert_or_update_edge(a, b, 1);
+
+        let mut multi = MultiGraph::from(graph);
+
+        multi.insert_edge(b, a, 2);
+
+        assert_eq!(
+            multi.edge_count(),
+            2,
+            "Both directions must become one edge"
+        );
+        assert_eq!(
+            multi.edges_between(a, b).count(),
+            2,
+            "Undirected edges go both ways"
+        );
+        assert_eq!(
+            multi.edges_between(b, a).count(),
+            2,
+            "Undirected edges go both ways"
+        );
+    }
+}
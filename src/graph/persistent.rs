@@ -0,0 +1,607 @@
+use super::{view::GraphView, Graph, VertexIdx};
+use alloc::{sync::Arc, vec::Vec};
+use core::{mem, slice};
+
+/// An immutable directed graph, whose mutations return a new graph that shares most of its
+/// structure with the old one, so both remain usable. Keeping every version of an evolving graph
+/// costs `O(log |V|)` memory per mutation instead of a full copy.
+///
+/// The vertices and the edges of each vertex are stored in hash array mapped tries, so lookups and
+/// mutations take `O(log₃₂ |V|)`. Cloning a graph takes `O(1)`.
+pub struct PersistentGraph<V, E> {
+    vertices: Trie<PersistentVertex<V, E>>,
+    edge_count: usize,
+}
+
+struct PersistentVertex<V, E> {
+    weight: V,
+    edges: Trie<E>,
+    predecessors: Trie<()>,
+}
+
+impl<V, E> Clone for PersistentGraph<V, E> {
+    fn clone(&self) -> Self {
+        Self {
+            vertices: self.vertices.clone(),
+            edge_count: self.edge_count,
+        }
+    }
+}
+
+impl<V: Clone, E> Clone for PersistentVertex<V, E> {
+    fn clone(&self) -> Self {
+        Self {
+            weight: self.weight.clone(),
+            edges: self.edges.clone(),
+            predecessors: self.predecessors.clone(),
+        }
+    }
+}
+
+impl<V, E> Default for PersistentGraph<V, E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V, E> PersistentGraph<V, E> {
+    pub fn new() -> Self {
+        Self {
+            vertices: Trie::new(),
+            edge_count: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.vertices.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.vertices.len == 0
+    }
+
+    /// The number of edges in `O(1)`.
+    pub fn edge_count(&self) -> usize {
+        self.edge_count
+    }
+
+    pub fn contains_vertex(&self, vertex_idx: VertexIdx) -> bool {
+        self.vertices.get(vertex_idx).is_some()
+    }
+
+    /// Retrieve an immutable reference to the weight of a vertex by its ID.
+    pub fn get_vertex(&self, vertex_idx: VertexIdx) -> Option<&V> {
+        Some(&self.vertices.get(vertex_idx)?.weight)
+    }
+
+    /// Retrieve an immutable reference to the weight of the edge from `from` to `to`.
+    pub fn get_edge(&self, from: VertexIdx, to: VertexIdx) -> Option<&E> {
+        self.vertices.get(from)?.edges.get(to)
+    }
+
+    /// Iterate over the edges from a vertex as `(to, weight)`, if it exists.
+    pub fn neighbors(&self, vertex_idx: VertexIdx) -> Option<Neighbors<'_, E>> {
+        Some(Neighbors(self.vertices.get(vertex_idx)?.edges.iter()))
+    }
+
+    /// Iterate over the IDs of the vertices with an edge to a vertex, if it exists.
+    pub fn predecessors(
+        &self,
+        vertex_idx: VertexIdx,
+    ) -> Option<impl Iterator<Item = VertexIdx> + '_> {
+        Some(
+            self.vertices
+                .get(vertex_idx)?
+                .predecessors
+                .iter()
+                .map(|(idx, _)| idx),
+        )
+    }
+
+    /// Iterate over every vertex as `(vertex_idx, weight)`, in no particular order.
+    pub fn vertices_iter(&self) -> impl Iterator<Item = (VertexIdx, &V)> {
+        self.vertices
+            .iter()
+            .map(|(idx, vertex)| (idx, &vertex.weight))
+    }
+}
+
+impl<V: Clone, E: Clone> PersistentGraph<V, E> {
+    /// Create a graph with a new vertex of weight `weight`. Returns it along with the vertex's ID.
+    pub fn insert_vertex(&self, weight: V) -> (Self, VertexIdx) {
+        let idx = VertexIdx::new();
+        let mut graph = self.clone();
+
+        graph.vertices.insert(
+            idx,
+            PersistentVertex {
+                weight,
+                edges: Trie::new(),
+                predecessors: Trie::new(),
+            },
+        );
+
+        (graph, idx)
+    }
+
+    /// Create a graph where the weight of a vertex is `weight`, if it exists.
+    pub fn update_vertex(&self, vertex_idx: VertexIdx, weight: V) -> Option<Self> {
+        let mut graph = self.clone();
+
+        graph.vertices.get_mut(vertex_idx)?.weight = weight;
+        Some(graph)
+    }
+
+    /// Create a graph with the edge from `from` to `to` inserted or updated with weight `weight`,
+    /// if the vertices specified exist.
+    pub fn insert_or_update_edge(&self, from: VertexIdx, to: VertexIdx, weight: E) -> Option<Self> {
+        if !self.contains_vertex(to) {
+            return None;
+        }
+
+        let mut graph = self.clone();
+
+        if graph
+            .vertices
+            .get_mut(from)?
+            .edges
+            .insert(to, weight)
+            .is_none()
+        {
+            graph.edge_count += 1;
+        }
+
+        graph
+            .vertices
+            .get_mut(to)
+            .expect("The vertex exists")
+            .predecessors
+            .insert(from, ());
+        Some(graph)
+    }
+
+    /// Create a graph without the edge from `from` to `to`.
+    ///
+    /// Returns it along with that edge's weight, if it exists.
+    pub fn remove_edge(&self, from: VertexIdx, to: VertexIdx) -> Option<(Self, E)> {
+        self.get_edge(from, to)?;
+
+        let mut graph = self.clone();
+        let weight = graph.vertices.get_mut(from)?.edges.remove(to)?;
+
+        if let Some(to_vertex) = graph.vertices.get_mut(to) {
+            to_vertex.predecessors.remove(from);
+        }
+
+        graph.edge_count -= 1;
+        Some((graph, weight))
+    }
+
+    /// Create a graph without a vertex and its edges, in `O(deg log |V|)`, where `deg` is the
+    /// number of edges from and to that vertex.
+    ///
+    /// Returns it along with the weight of the vertex, if it exists.
+    pub fn remove_vertex(&self, vertex_idx: VertexIdx) -> Option<(Self, V)> {
+        self.vertices.get(vertex_idx)?;
+
+        let mut graph = self.clone();
+        let removed_vertex = graph.vertices.remove(vertex_idx)?;
+
+        for (from_idx, _) in removed_vertex.predecessors.iter() {
+            if let Some(from_vertex) = graph.vertices.get_mut(from_idx) {
+                from_vertex.edges.remove(vertex_idx);
+                graph.edge_count -= 1;
+            }
+        }
+
+        for (to_idx, _) in removed_vertex.edges.iter() {
+            if let Some(to_vertex) = graph.vertices.get_mut(to_idx) {
+                to_vertex.predecessors.remove(vertex_idx);
+            }
+        }
+
+        graph.edge_count -= removed_vertex.edges.len;
+        Some((graph, removed_vertex.weight))
+    }
+
+    /// Convert to a [`Graph`] with the same vertex IDs, in `O(|V| + |E|)`.
+    pub fn to_graph(&self) -> Graph<V, E> {
+        let mut graph: Graph<V, E> = Graph::with_capacity(self.len());
+
+        for (vertex_idx, vertex) in self.vertices.iter() {
+            graph.insert_vertex_with_idx(vertex.weight.clone(), vertex_idx);
+        }
+
+        for (from_idx, vertex) in self.vertices.iter() {
+            for (to_idx, weight) in vertex.edges.iter() {
+                graph.insert_or_update_edge(from_idx, to_idx, weight.clone());
+            }
+        }
+
+        graph
+    }
+}
+
+impl<V: Clone, E: Clone> From<&Graph<V, E>> for PersistentGraph<V, E> {
+    fn from(graph: &Graph<V, E>) -> Self {
+        let mut persistent = Self::new();
+
+        for (vertex_idx, vertex) in graph.vertices_iter() {
+            persistent.vertices.insert(
+                vertex_idx,
+                PersistentVertex {
+                    weight: vertex.weight().clone(),
+                    edges: vertex
+                        .neighbors()
+                        .map(|(to_idx, weight)| (to_idx, weight.clone()))
+                        .collect(),
+                    predecessors: Trie::new(),
+                },
+            );
+        }
+
+        for (from_idx, to_idx, _) in graph.edges_iter() {
+            persistent
+                .vertices
+                .get_mut(to_idx)
+                .expect("Edges point to existing vertices")
+                .predecessors
+                .insert(from_idx, ());
+        }
+
+        persistent.edge_count = graph.edge_count();
+        persistent
+    }
+}
+
+impl<V, E> GraphView for PersistentGraph<V, E> {
+    type Vertex = V;
+    type Edge = E;
+    type EdgeWeight<'a>
+        = &'a E
+    where
+        Self: 'a;
+    type Neighbors<'a>
+        = Neighbors<'a, E>
+    where
+        Self: 'a;
+
+    fn vertex_count(&self) -> usize {
+        self.len()
+    }
+
+    fn contains_vertex(&self, vertex_idx: VertexIdx) -> bool {
+        PersistentGraph::contains_vertex(self, vertex_idx)
+    }
+
+    fn vertex_weight(&self, vertex_idx: VertexIdx) -> Option<&V> {
+        self.get_vertex(vertex_idx)
+    }
+
+    fn neighbors(&self, vertex_idx: VertexIdx) -> Option<Neighbors<'_, E>> {
+        PersistentGraph::neighbors(self, vertex_idx)
+    }
+}
+
+/// The edges from a vertex of a [`PersistentGraph`] as `(to, weight)`.
+pub struct Neighbors<'a, E>(TrieIter<'a, E>);
+
+impl<'a, E> Iterator for Neighbors<'a, E> {
+    type Item = (VertexIdx, &'a E);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+}
+
+/// The number of bits of the ID that pick a child at each level of a [`Trie`]
+const BITS: usize = 5;
+const MASK: usize = (1 << BITS) - 1;
+
+/// A hash array mapped trie keyed by [`VertexIdx`]. The IDs are used as their own hashes, since
+/// they are unique and sequential, which keeps the trie balanced.
+struct Trie<T> {
+    root: Arc<Node<T>>,
+    len: usize,
+}
+
+enum Node<T> {
+    /// The children whose bit is set in `bitmap`, in the order of their bit
+    Branch {
+        bitmap: u32,
+        children: Vec<Arc<Node<T>>>,
+    },
+    Leaf(VertexIdx, T),
+}
+
+impl<T> Clone for Trie<T> {
+    fn clone(&self) -> Self {
+        Self {
+            root: Arc::clone(&self.root),
+            len: self.len,
+        }
+    }
+}
+
+impl<T: Clone> Clone for Node<T> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Branch { bitmap, children } => Self::Branch {
+                bitmap: *bitmap,
+                children: children.clone(),
+            },
+            Self::Leaf(idx, value) => Self::Leaf(*idx, value.clone()),
+        }
+    }
+}
+
+/// The bit of the child of a branch at depth `depth` that `idx` belongs to.
+fn bit(idx: VertexIdx, depth: usize) -> u32 {
+    1 << ((idx.0 >> (depth * BITS)) & MASK)
+}
+
+/// The position of the child with bit `bit` among the children of a branch.
+fn position(bitmap: u32, bit: u32) -> usize {
+    (bitmap & (bit - 1)).count_ones() as usize
+}
+
+impl<T> Trie<T> {
+    fn new() -> Self {
+        Self {
+            root: Arc::new(Node::Branch {
+                bitmap: 0,
+                children: Vec::new(),
+            }),
+            len: 0,
+        }
+    }
+
+    fn get(&self, idx: VertexIdx) -> Option<&T> {
+        let mut node = &*self.root;
+        let mut depth = 0;
+
+        loop {
+            match node {
+                Node::Branch { bitmap, children } => {
+                    let bit = bit(idx, depth);
+
+                    if bitmap & bit == 0 {
+                        return None;
+                    }
+
+                    node = &children[position(*bitmap, bit)];
+                    depth += 1;
+                }
+                Node::Leaf(leaf_idx, value) => return (*leaf_idx == idx).then_some(value),
+            }
+        }
+    }
+
+    fn iter(&self) -> TrieIter<'_, T> {
+        TrieIter {
+            stack: Vec::from([slice::from_ref(&self.root).iter()]),
+        }
+    }
+}
+
+impl<T: Clone> Trie<T> {
+    /// Retrieve a mutable reference to a value, copying the nodes on its path that are shared
+    /// with other tries.
+    fn get_mut(&mut self, idx: VertexIdx) -> Option<&mut T> {
+        self.get(idx)?;
+
+        let mut node = Arc::make_mut(&mut self.root);
+        let mut depth = 0;
+
+        loop {
+            match node {
+                Node::Branch { bitmap, children } => {
+                    let position = position(*bitmap, bit(idx, depth));
+
+                    node = Arc::make_mut(&mut children[position]);
+                    depth += 1;
+                }
+                Node::Leaf(_, value) => return Some(value),
+            }
+        }
+    }
+
+    /// Returns the old value, if any.
+    fn insert(&mut self, idx: VertexIdx, value: T) -> Option<T> {
+        if let Some(old_value) = self.get_mut(idx) {
+            return Some(mem::replace(old_value, value));
+        }
+
+        let mut node = Arc::make_mut(&mut self.root);
+        let mut depth = 0;
+
+        self.len += 1;
+
+        loop {
+            let Node::Branch { bitmap, children } = node else {
+                unreachable!("Leaves are replaced by branches before being descended into");
+            };
+            let bit = bit(idx, depth);
+            let position = position(*bitmap, bit);
+
+            if *bitmap & bit == 0 {
+                *bitmap |= bit;
+                children.insert(position, Arc::new(Node::Leaf(idx, value)));
+
+                return None;
+            }
+
+            let child = Arc::make_mut(&mut children[position]);
+
+            // Both IDs share the same path so far, so the leaf moves one level down
+            if let Node::Leaf(leaf_idx, _) = child {
+                let leaf_bit = self::bit(*leaf_idx, depth + 1);
+                let leaf = mem::replace(
+                    child,
+                    Node::Branch {
+                        bitmap: leaf_bit,
+                        children: Vec::new(),
+                    },
+                );
+
+                if let Node::Branch { children, .. } = child {
+                    children.push(Arc::new(leaf));
+                }
+            }
+
+            node = child;
+            depth += 1;
+        }
+    }
+
+    /// Returns the removed value, if any.
+    fn remove(&mut self, idx: VertexIdx) -> Option<T> {
+        self.get(idx)?;
+        self.len -= 1;
+
+        Some(Self::remove_from(Arc::make_mut(&mut self.root), idx, 0))
+    }
+
+    /// Remove `idx`, which exists, from the branch `node` at depth `depth`.
+    fn remove_from(node: &mut Node<T>, idx: VertexIdx, depth: usize) -> T {
+        let Node::Branch { bitmap, children } = node else {
+            unreachable!("Only branches are descended into");
+        };
+        let bit = bit(idx, depth);
+        let position = position(*bitmap, bit);
+
+        // The value is `None` if the child is the leaf itself, which is taken out below
+        let value = match &*children[position] {
+            Node::Leaf(..) => None,
+            Node::Branch { .. } => Some(Self::remove_from(
+                Arc::make_mut(&mut children[position]),
+                idx,
+                depth + 1,
+            )),
+        };
+        let is_empty = matches!(&*children[position], Node::Branch { bitmap: 0, .. });
+
+        match value {
+            Some(value) if !is_empty => value,
+            value => {
+                *bitmap &= !bit;
+
+                let removed = children.remove(position);
+
+                value.unwrap_or_else(|| match Arc::unwrap_or_clone(removed) {
+                    Node::Leaf(_, value) => value,
+                    Node::Branch { .. } => unreachable!("The child is a leaf"),
+                })
+            }
+        }
+    }
+}
+
+impl<T: Clone> FromIterator<(VertexIdx, T)> for Trie<T> {
+    fn from_iter<I: IntoIterator<Item = (VertexIdx, T)>>(iter: I) -> Self {
+        let mut trie = Self::new();
+
+        for (idx, value) in iter {
+            trie.insert(idx, value);
+        }
+
+        trie
+    }
+}
+
+struct TrieIter<'a, T> {
+    /// The remaining children of every branch on the path to the current node
+    stack: Vec<slice::Iter<'a, Arc<Node<T>>>>,
+}
+
+impl<'a, T> Iterator for TrieIter<'a, T> {
+    type Item = (VertexIdx, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let children = self.stack.last_mut()?;
+            let Some(node) = children.next() else {
+                self.stack.pop();
+                continue;
+            };
+
+            match &**node {
+                Node::Branch { children, .. } => self.stack.push(children.iter()),
+                Node::Leaf(idx, value) => return Some((*idx, value)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::algo::breadth_first_search_compact;
+
+    #[test]
+    fn test_trie() {
+        let mut graph = Graph::<(), ()>::new();
+        let ids: Vec<_> = (0..2000).map(|_| graph.insert_vertex(())).collect();
+        let mut trie: Trie<usize> = ids.iter().enumerate().map(|(i, &idx)| (idx, i)).collect();
+        let snapshot = trie.clone();
+
+        for &idx in ids.iter().step_by(2) {
+            trie.remove(idx);
+        }
+
+        assert_eq!(trie.len, 1000, "Half of the IDs are removed");
+        assert_eq!(trie.iter().count(), 1000, "Wrong number of iterated values");
+        assert_eq!(trie.get(ids[1]), Some(&1), "Odd IDs are kept");
+        assert_eq!(trie.get(ids[2]), None, "Even IDs are removed");
+        assert_eq!(
+            snapshot.iter().count(),
+            2000,
+            "The snapshot isn't affected by the removals"
+        );
+        assert_eq!(
+            snapshot.get(ids[2]),
+            Some(&2),
+            "Wrong value in the snapshot"
+        );
+    }
+
+    #[test]
+    fn test_persistent_graph() {
+        let empty = PersistentGraph::<u8, u8>::new();
+        let (graph, a) = empty.insert_vertex(1);
+        let (graph, b) = graph.insert_vertex(2);
+        let (graph, c) = graph.insert_vertex(3);
+        let v1 = graph
+            .insert_or_update_edge(a, b, 1)
+            .and_then(|graph| graph.insert_or_update_edge(b, c, 2))
+            .expect("The vertices exist");
+        let (v2, removed) = v1.remove_vertex(b).expect("Vertex b exists");
+
+        assert_eq!(removed, 2, "Wrong weight for b");
+        assert!(empty.is_empty(), "The first version is still empty");
+        assert_eq!(
+            (v1.len(), v1.edge_count()),
+            (3, 2),
+            "The second version keeps its vertices and edges"
+        );
+        assert_eq!((v2.len(), v2.edge_count()), (2, 0), "Wrong third version");
+        assert_eq!(v1.get_edge(a, b), Some(&1), "Wrong weight for a → b");
+        assert_eq!(
+            v1.predecessors(c).map(Iterator::collect::<Vec<_>>),
+            Some(vec![b]),
+            "Wrong predecessors of c"
+        );
+
+        let bfs = breadth_first_search_compact(&v1, a).expect("Vertex a exists");
+
+        assert_eq!(bfs.path_to(c), Some(vec![a, b, c]), "Wrong path to c");
+
+        let graph = v1.to_graph();
+
+        assert_eq!(
+            PersistentGraph::from(&graph).to_graph(),
+            graph,
+            "The graph is the same after a round trip"
+        );
+    }
+}
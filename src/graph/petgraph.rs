@@ -0,0 +1,262 @@
+//! Conversions between [`Graph`] and the graphs of [`petgraph`], with the `petgraph` feature.
+//!
+//! Undirected graphs convert to and from undirected [`petgraph`] graphs, with each edge once.
+
+use super::{Directed, Direction, Graph, Undirected, VertexIdx};
+use crate::collections::HashMap;
+use alloc::vec::Vec;
+use core::hash::BuildHasher;
+use petgraph::graph::NodeIndex;
+use petgraph::graphmap::{GraphMap, NodeTrait};
+
+/// Maps a [`Direction`] to the matching [`petgraph::EdgeType`].
+pub trait PetgraphDirection: Direction {
+    type EdgeType: petgraph::EdgeType;
+}
+
+/// The [`petgraph::Graph`] that a [`Graph`] with direction `D` converts to and from.
+pub type Petgraph<V, E, D> = petgraph::Graph<V, E, <D as PetgraphDirection>::EdgeType>;
+
+impl PetgraphDirection for Directed {
+    type EdgeType = petgraph::Directed;
+}
+
+impl PetgraphDirection for Undirected {
+    type EdgeType = petgraph::Undirected;
+}
+
+impl<V, E, D: PetgraphDirection, S: BuildHasher + Default> Graph<V, E, D, S> {
+    /// Convert to a [`petgraph::Graph`] in `O(|V| log |V| + |E|)`, keeping the weights. The
+    /// vertices are added in the order of their IDs.
+    ///
+    /// Returns the graph along with the index of the node of each vertex.
+    pub fn into_petgraph(self) -> (Petgraph<V, E, D>, HashMap<VertexIdx, NodeIndex, S>) {
+        let mut vertices: Vec<_> = self.vertices.into_iter().collect();
+        let mut graph = petgraph::Graph::with_capacity(vertices.len(), self.edge_count);
+        let mut nodes = HashMap::with_capacity_and_hasher(vertices.len(), S::default());
+
+        vertices.sort_unstable_by_key(|&(vertex_idx, _)| vertex_idx);
+
+        let edges: Vec<_> = vertices
+            .into_iter()
+            .flat_map(|(vertex_idx, vertex)| {
+                nodes.insert(vertex_idx, graph.add_node(vertex.weight));
+
+                vertex
+                    .edges
+                    .into_iter()
+                    .filter(move |&(to_idx, _)| D::IS_DIRECTED || vertex_idx <= to_idx)
+                    .map(move |(to_idx, weight)| (vertex_idx, to_idx, weight))
+            })
+            .collect();
+
+        for (from_idx, to_idx, weight) in edges {
+            graph.add_edge(nodes[&from_idx], nodes[&to_idx], weight);
+        }
+
+        (graph, nodes)
+    }
+
+    /// Convert to a [`GraphMap`] in `O(|V| + |E|)`, whose nodes are the vertex weights. Vertices
+    /// with the same weight become a single node.
+    pub fn into_graph_map(self) -> GraphMap<V, E, D::EdgeType>
+    where
+        V: NodeTrait,
+    {
+        let mut graph = GraphMap::with_capacity(self.vertices.len(), self.edge_count);
+        let weights: HashMap<_, _, S> = self
+            .vertices
+            .iter()
+            .map(|(&vertex_idx, vertex)| (vertex_idx, vertex.weight))
+            .collect();
+
+        for (vertex_idx, vertex) in self.vertices {
+            graph.add_node(vertex.weight);
+
+            for (to_idx, weight) in vertex.edges {
+                if D::IS_DIRECTED || vertex_idx <= to_idx {
+                    graph.add_edge(vertex.weight, weights[&to_idx], weight);
+                }
+            }
+        }
+
+        graph
+    }
+}
+
+impl<V, E: Clone, D: PetgraphDirection, S: BuildHasher + Default> Graph<V, E, D, S> {
+    /// Convert from a [`petgraph::Graph`] in `O(|V| + |E|)`, keeping the weights. Of parallel
+    /// edges, only the last one is kept.
+    ///
+    /// Returns the graph along with the ID of the vertex of each node, as `ids[node.index()]`.
+    pub fn from_petgraph(graph: Petgraph<V, E, D>) -> (Self, Vec<VertexIdx>) {
+        let (nodes, edges) = graph.into_nodes_edges();
+        let mut result = Self::default();
+
+        result.reserve(nodes.len());
+
+        let ids: Vec<_> = nodes
+            .into_iter()
+            .map(|node| result.insert_vertex(node.weight))
+            .collect();
+
+        for edge in edges {
+            let (from, to) = (edge.source().index(), edge.target().index());
+
+            result.connect(ids[from], ids[to], edge.weight);
+        }
+
+        (result, ids)
+    }
+
+    /// Convert from a [`GraphMap`] in `O(|V| + |E|)`, with the nodes as the vertex weights.
+    ///
+    /// Returns the graph along with the ID of the vertex of each node.
+    pub fn from_graph_map(graph: GraphMap<V, E, D::EdgeType>) -> (Self, HashMap<V, VertexIdx, S>)
+    where
+        V: NodeTrait,
+    {
+        let nodes: Vec<V> = graph.nodes().collect();
+        let (result, ids) = Self::from_petgraph(graph.into_graph());
+        let ids = nodes.into_iter().zip(ids).collect();
+
+        (result, ids)
+    }
+}
+
+impl<V, E, D: PetgraphDirection, S: BuildHasher + Default> From<Graph<V, E, D, S>>
+    for Petgraph<V, E, D>
+{
+    fn from(graph: Graph<V, E, D, S>) -> Self {
+        graph.into_petgraph().0
+    }
+}
+
+impl<V, E: Clone, D: PetgraphDirection, S: BuildHasher + Default> From<Petgraph<V, E, D>>
+    for Graph<V, E, D, S>
+{
+    fn from(graph: Petgraph<V, E, D>) -> Self {
+        Self::from_petgraph(graph).0
+    }
+}
+
+impl<V: NodeTrait, E, D: PetgraphDirection, S: BuildHasher + Default> From<Graph<V, E, D, S>>
+    for GraphMap<V, E, D::EdgeType>
+{
+    fn from(graph: Graph<V, E, D, S>) -> Self {
+        graph.into_graph_map()
+    }
+}
+
+impl<V: NodeTrait, E: Clone, D: PetgraphDirection, S: BuildHasher + Default>
+    From<GraphMap<V, E, D::EdgeType>> for Graph<V, E, D, S>
+{
+    fn from(graph: GraphMap<V, E, D::EdgeType>) -> Self {
+        Self::from_graph_map(graph).0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::{Graph, Undirected};
+    use petgraph::graphmap::{DiGraphMap, UnGraphMap};
+
+    #[test]
+    fn test_petgraph() {
+        let mut graph = Graph::<&str, u8>::new();
+        let [a, b, c] = ["a", "b", "c"].map(|weight| graph.insert_vertex(weight));
+
+        graph.insert_or_update_edges([(a, b, 1), (b, c, 2), (c, a, 3), (c, c, 4)]);
+
+        let (petgraph, nodes) = graph.clone().into_petgraph();
+
+        assert_eq!(petgraph.node_count(), 3, "Wrong number of nodes");
+        assert_eq!(petgraph.edge_count(), 4, "Wrong number of edges");
+        assert_eq!(petgraph[nodes[&b]], "b", "Wrong node weight");
+        assert_eq!(
+            petgraph
+                .find_edge(nodes[&c], nodes[&a])
+                .map(|edge| petgraph[edge]),
+            Some(3),
+            "Wrong edge weight"
+        );
+
+        let (converted, ids) = Graph::<&str, u8>::from_petgraph(petgraph);
+        let new_idx = |vertex_idx| ids[nodes[&vertex_idx].index()];
+
+        assert_eq!(converted.edge_count(), 4, "Wrong number of edges");
+
+        for (vertex_idx, vertex) in graph.vertices_iter() {
+            assert_eq!(converted[new_idx(vertex_idx)].weight(), vertex.weight());
+
+            for (to_idx, weight) in vertex.neighbors() {
+                assert_eq!(
+                    converted.get_edge(new_idx(vertex_idx), new_idx(to_idx)),
+                    Some(weight),
+                    "The round trip must keep the edges"
+                );
+            }
+        }
+
+        let mut undirected = Graph::<u8, u8, Undirected>::new();
+        let [x, y, z] = [1, 2, 3].map(|weight| undirected.insert_vertex(weight));
+
+        undirected.insert_or_update_edges([(x, y, 1), (y, z, 2), (z, z, 3)]);
+
+        let petgraph = petgraph::Graph::from(undirected);
+
+        assert_eq!(petgraph.edge_count(), 3, "Every edge is added once");
+
+        let (converted, ids) = Graph::<u8, u8, Undirected>::from_petgraph(petgraph);
+
+        assert_eq!(converted.edge_count(), 5, "Each edge goes both ways");
+        assert_eq!(
+            converted.get_edge(ids[1], ids[0]),
+            Some(&1),
+            "Wrong edge weight"
+        );
+        assert_eq!(
+            converted.get_edge(ids[2], ids[2]),
+            Some(&3),
+            "Wrong self-loop"
+        );
+    }
+
+    #[test]
+    fn test_graph_map() {
+        let mut graph = Graph::<char, u8>::new();
+        let [a, b, c] = ['a', 'b', 'c'].map(|weight| graph.insert_vertex(weight));
+
+        graph.insert_or_update_edges([(a, b, 1), (b, c, 2), (c, a, 3)]);
+
+        let map = DiGraphMap::from(graph);
+
+        assert_eq!(map.edge_weight('c', 'a'), Some(&3), "Wrong edge weight");
+        assert_eq!(map.edge_weight('a', 'c'), None, "The edges are directed");
+
+        let (converted, ids) = Graph::<char, u8>::from_graph_map(map.clone());
+
+        assert_eq!(converted.len(), 3, "Wrong number of vertices");
+        assert_eq!(converted[ids[&'b']].weight(), &'b', "Wrong vertex weight");
+        assert_eq!(converted.get_edge(ids[&'b'], ids[&'c']), Some(&2));
+
+        let round_trip = DiGraphMap::from(converted);
+
+        let mut edges: Vec<_> = round_trip.all_edges().collect();
+        let mut expected: Vec<_> = map.all_edges().collect();
+
+        edges.sort_unstable();
+        expected.sort_unstable();
+        assert_eq!(edges, expected, "The round trip must keep the graph");
+
+        let mut undirected = UnGraphMap::new();
+
+        undirected.add_edge(1, 2, 'x');
+        undirected.add_edge(3, 2, 'y');
+
+        let converted = Graph::<u8, char, Undirected>::from(undirected);
+
+        assert_eq!(converted.edge_count(), 4, "Each edge goes both ways");
+        assert_eq!(UnGraphMap::from(converted).edge_weight(2, 3), Some(&'y'));
+    }
+}
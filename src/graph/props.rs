@@ -0,0 +1,355 @@
+use super::{Graph, Vertex, VertexIdx};
+use crate::collections::{hash_map, HashMap};
+use core::{
+    hash::BuildHasher,
+    ops::{Index, IndexMut},
+};
+
+/// A side table of values of type `T` for the vertices of a graph, such as colors, distances or
+/// labels, so that algorithms can attach data to vertices without cloning the graph.
+///
+/// The map doesn't borrow the graph, so it's kept in sync by the caller, e.g. with
+/// [`Self::retain_graph`] after removing vertices.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VertexMap<T> {
+    values: HashMap<VertexIdx, T>,
+}
+
+impl<T> Default for VertexMap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> VertexMap<T> {
+    pub fn new() -> Self {
+        Self {
+            values: HashMap::new(),
+        }
+    }
+
+    /// Create an empty map with room for at least `capacity` values without reallocating.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            values: HashMap::with_capacity(capacity),
+        }
+    }
+
+    /// Create a map with the value `f(vertex_idx, vertex)` for every vertex of a graph, in `O(|V|)`.
+    pub fn from_graph<V, E, D, S: BuildHasher + Default>(
+        graph: &Graph<V, E, D, S>,
+        mut f: impl FnMut(VertexIdx, &Vertex<V, E, S>) -> T,
+    ) -> Self {
+        graph
+            .vertices_iter()
+            .map(|(vertex_idx, vertex)| (vertex_idx, f(vertex_idx, vertex)))
+            .collect()
+    }
+
+    /// Create a map with the value `value` for every vertex of a graph, in `O(|V|)`.
+    pub fn filled<V, E, D, S: BuildHasher + Default>(graph: &Graph<V, E, D, S>, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_graph(graph, |_, _| value.clone())
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn contains(&self, vertex_idx: VertexIdx) -> bool {
+        self.values.contains_key(&vertex_idx)
+    }
+
+    /// Retrieve an immutable reference to the value of a vertex in `O(1)`.
+    pub fn get(&self, vertex_idx: VertexIdx) -> Option<&T> {
+        self.values.get(&vertex_idx)
+    }
+
+    /// Retrieve a mutable reference to the value of a vertex in `O(1)`.
+    pub fn get_mut(&mut self, vertex_idx: VertexIdx) -> Option<&mut T> {
+        self.values.get_mut(&vertex_idx)
+    }
+
+    /// Insert or update the value of a vertex in `O(1)`. Returns its previous value, if any.
+    pub fn insert(&mut self, vertex_idx: VertexIdx, value: T) -> Option<T> {
+        self.values.insert(vertex_idx, value)
+    }
+
+    /// Remove the value of a vertex in `O(1)`. Returns it, if any.
+    pub fn remove(&mut self, vertex_idx: VertexIdx) -> Option<T> {
+        self.values.remove(&vertex_idx)
+    }
+
+    /// Remove the values of the vertices that don't exist in a graph, in `O(len)`.
+    pub fn retain_graph<V, E, D, S: BuildHasher + Default>(&mut self, graph: &Graph<V, E, D, S>) {
+        self.values
+            .retain(|&vertex_idx, _| graph.contains_vertex(vertex_idx));
+    }
+
+    /// Iterate over the values as `(vertex_idx, value)`, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (VertexIdx, &T)> {
+        self.values
+            .iter()
+            .map(|(&vertex_idx, value)| (vertex_idx, value))
+    }
+
+    /// Iterate over the values as `(vertex_idx, value)`, in no particular order.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (VertexIdx, &mut T)> {
+        self.values
+            .iter_mut()
+            .map(|(&vertex_idx, value)| (vertex_idx, value))
+    }
+
+    /// Iterate over the vertices of a graph that have a value, as `(vertex_idx, vertex, value)`,
+    /// in `O(|V|)`.
+    pub fn zip<'a, V, E, D, S: BuildHasher + Default>(
+        &'a self,
+        graph: &'a Graph<V, E, D, S>,
+    ) -> impl Iterator<Item = (VertexIdx, &'a Vertex<V, E, S>, &'a T)> {
+        graph.vertices_iter().filter_map(|(vertex_idx, vertex)| {
+            Some((vertex_idx, vertex, self.values.get(&vertex_idx)?))
+        })
+    }
+}
+
+impl<T> Index<VertexIdx> for VertexMap<T> {
+    type Output = T;
+
+    /// # Panics
+    ///
+    /// If the vertex has no value.
+    fn index(&self, vertex_idx: VertexIdx) -> &Self::Output {
+        self.get(vertex_idx).expect("The vertex has a value")
+    }
+}
+
+impl<T> IndexMut<VertexIdx> for VertexMap<T> {
+    /// # Panics
+    ///
+    /// If the vertex has no value.
+    fn index_mut(&mut self, vertex_idx: VertexIdx) -> &mut Self::Output {
+        self.get_mut(vertex_idx).expect("The vertex has a value")
+    }
+}
+
+impl<T> FromIterator<(VertexIdx, T)> for VertexMap<T> {
+    fn from_iter<I: IntoIterator<Item = (VertexIdx, T)>>(iter: I) -> Self {
+        Self {
+            values: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<T> Extend<(VertexIdx, T)> for VertexMap<T> {
+    fn extend<I: IntoIterator<Item = (VertexIdx, T)>>(&mut self, iter: I) {
+        self.values.extend(iter);
+    }
+}
+
+impl<T> IntoIterator for VertexMap<T> {
+    type Item = (VertexIdx, T);
+    type IntoIter = hash_map::IntoIter<VertexIdx, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.values.into_iter()
+    }
+}
+
+/// A side table of values of type `T` for the edges of a graph, like [`VertexMap`], where an edge
+/// is identified by its `(from, to)` vertices.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EdgeMap<T> {
+    values: HashMap<(VertexIdx, VertexIdx), T>,
+}
+
+impl<T> Default for EdgeMap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> EdgeMap<T> {
+    pub fn new() -> Self {
+        Self {
+            values: HashMap::new(),
+        }
+    }
+
+    /// Create an empty map with room for at least `capacity` values without reallocating.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            values: HashMap::with_capacity(capacity),
+        }
+    }
+
+    /// Create a map with the value `f(from, to, weight)` for every edge of a graph, in
+    /// `O(|V| + |E|)`.
+    pub fn from_graph<V, E, D, S: BuildHasher + Default>(
+        graph: &Graph<V, E, D, S>,
+        mut f: impl FnMut(VertexIdx, VertexIdx, &E) -> T,
+    ) -> Self {
+        graph
+            .edges_iter()
+            .map(|(from, to, weight)| ((from, to), f(from, to, weight)))
+            .collect()
+    }
+
+    /// Create a map with the value `value` for every edge of a graph, in `O(|V| + |E|)`.
+    pub fn filled<V, E, D, S: BuildHasher + Default>(graph: &Graph<V, E, D, S>, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_graph(graph, |_, _, _| value.clone())
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn contains(&self, from: VertexIdx, to: VertexIdx) -> bool {
+        self.values.contains_key(&(from, to))
+    }
+
+    /// Retrieve an immutable reference to the value of the edge from `from` to `to` in `O(1)`.
+    pub fn get(&self, from: VertexIdx, to: VertexIdx) -> Option<&T> {
+        self.values.get(&(from, to))
+    }
+
+    /// Retrieve a mutable reference to the value of the edge from `from` to `to` in `O(1)`.
+    pub fn get_mut(&mut self, from: VertexIdx, to: VertexIdx) -> Option<&mut T> {
+        self.values.get_mut(&(from, to))
+    }
+
+    /// Insert or update the value of the edge from `from` to `to` in `O(1)`. Returns its previous
+    /// value, if any.
+    pub fn insert(&mut self, from: VertexIdx, to: VertexIdx, value: T) -> Option<T> {
+        self.values.insert((from, to), value)
+    }
+
+    /// Remove the value of the edge from `from` to `to` in `O(1)`. Returns it, if any.
+    pub fn remove(&mut self, from: VertexIdx, to: VertexIdx) -> Option<T> {
+        self.values.remove(&(from, to))
+    }
+
+    /// Remove the values of the edges that don't exist in a graph, in `O(len)`.
+    pub fn retain_graph<V, E, D, S: BuildHasher + Default>(&mut self, graph: &Graph<V, E, D, S>) {
+        self.values
+            .retain(|&(from, to), _| graph.contains_edge(from, to));
+    }
+
+    /// Iterate over the values as `(from, to, value)`, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (VertexIdx, VertexIdx, &T)> {
+        self.values
+            .iter()
+            .map(|(&(from, to), value)| (from, to, value))
+    }
+
+    /// Iterate over the values as `(from, to, value)`, in no particular order.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (VertexIdx, VertexIdx, &mut T)> {
+        self.values
+            .iter_mut()
+            .map(|(&(from, to), value)| (from, to, value))
+    }
+}
+
+impl<T> Index<(VertexIdx, VertexIdx)> for EdgeMap<T> {
+    type Output = T;
+
+    /// # Panics
+    ///
+    /// If the edge has no value.
+    fn index(&self, (from, to): (VertexIdx, VertexIdx)) -> &Self::Output {
+        self.get(from, to).expect("The edge has a value")
+    }
+}
+
+impl<T> IndexMut<(VertexIdx, VertexIdx)> for EdgeMap<T> {
+    /// # Panics
+    ///
+    /// If the edge has no value.
+    fn index_mut(&mut self, (from, to): (VertexIdx, VertexIdx)) -> &mut Self::Output {
+        self.get_mut(from, to).expect("The edge has a value")
+    }
+}
+
+impl<T> FromIterator<((VertexIdx, VertexIdx), T)> for EdgeMap<T> {
+    fn from_iter<I: IntoIterator<Item = ((VertexIdx, VertexIdx), T)>>(iter: I) -> Self {
+        Self {
+            values: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<T> Extend<((VertexIdx, VertexIdx), T)> for EdgeMap<T> {
+    fn extend<I: IntoIterator<Item = ((VertexIdx, VertexIdx), T)>>(&mut self, iter: I) {
+        self.values.extend(iter);
+    }
+}
+
+impl<T> IntoIterator for EdgeMap<T> {
+    type Item = ((VertexIdx, VertexIdx), T);
+    type IntoIter = hash_map::IntoIter<(VertexIdx, VertexIdx), T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.values.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_props() {
+        //  a → b → c
+        let mut graph = Graph::<&str, usize>::new();
+        let [a, b, c] = ["a", "b", "c"].map(|weight| graph.insert_vertex(weight));
+
+        graph.insert_or_update_edges([(a, b, 1), (b, c, 2)]);
+
+        let mut colors = VertexMap::filled(&graph, 0);
+
+        colors[b] = 1;
+        assert_eq!(colors.len(), 3, "Every vertex has a value");
+        assert_eq!(
+            (colors.get(a), colors.get(b)),
+            (Some(&0), Some(&1)),
+            "Wrong values"
+        );
+
+        let labels = VertexMap::from_graph(&graph, |_, vertex| vertex.weight().len());
+        let mut zipped: Vec<_> = labels
+            .zip(&graph)
+            .map(|(_, vertex, &len)| (*vertex.weight(), len))
+            .collect();
+
+        zipped.sort_unstable();
+        assert_eq!(
+            zipped,
+            [("a", 1), ("b", 1), ("c", 1)],
+            "Wrong zipped values"
+        );
+
+        let mut flows = EdgeMap::from_graph(&graph, |_, _, &weight| weight * 10);
+
+        assert_eq!(flows[(b, c)], 20, "Wrong value of the edge");
+        assert!(flows.get(c, b).is_none(), "The edge doesn't exist");
+
+        graph.remove_vertex(c);
+        colors.retain_graph(&graph);
+        flows.retain_graph(&graph);
+
+        assert!(!colors.contains(c), "The vertex was removed");
+        assert_eq!(flows.len(), 1, "The edge was removed");
+    }
+}
@@ -0,0 +1,328 @@
+//! Random graphs from a seeded [`Rng`], e.g. to property-test or fuzz code that consumes graphs.
+
+use super::{Direction, Graph, VertexIdx};
+use alloc::vec::Vec;
+use core::hash::BuildHasher;
+use fastrand::Rng;
+
+#[cfg(feature = "arbitrary")]
+use arbitrary::{Arbitrary, Unstructured};
+#[cfg(feature = "proptest")]
+use {
+    core::fmt::Debug,
+    proptest::{collection::vec, prelude::*},
+};
+
+/// The shape of the graphs generated by [`Graph::random`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RandomConfig {
+    /// The least number of vertices.
+    pub min_vertices: usize,
+    /// The most number of vertices.
+    pub max_vertices: usize,
+    /// The probability of an edge between each pair of vertices, from 0 to 1.
+    pub density: f64,
+    /// Whether vertices may have edges to themselves.
+    pub self_loops: bool,
+}
+
+/// Up to 16 vertices, with a quarter of the possible edges, and no self-loops.
+impl Default for RandomConfig {
+    fn default() -> Self {
+        Self {
+            min_vertices: 0,
+            max_vertices: 16,
+            density: 0.25,
+            self_loops: false,
+        }
+    }
+}
+
+impl<V, E: Clone, D: Direction, S: BuildHasher + Default> Graph<V, E, D, S> {
+    /// Generate a random graph in the Erdős–Rényi `G(n, p)` model, in `O(|V|²)`: a number of
+    /// vertices in the range of `config`, with an edge between each pair of them with probability
+    /// `config.density`. In undirected graphs, each pair is considered once.
+    ///
+    /// The weights are generated by `vertex_weight` and `edge_weight`, so that the whole graph is
+    /// determined by the seed of `rng`.
+    ///
+    /// Returns the graph along with the IDs of its vertices in the order they were created.
+    ///
+    /// # Panics
+    ///
+    /// If `config.min_vertices` is greater than `config.max_vertices`.
+    ///
+    /// ```
+    /// # use badlgorithms::graph::{random::RandomConfig, Graph};
+    /// # use fastrand::Rng;
+    /// let config = RandomConfig {
+    ///     min_vertices: 5,
+    ///     max_vertices: 10,
+    ///     ..RandomConfig::default()
+    /// };
+    /// let (graph, _) = Graph::<(), u8>::random(&mut Rng::with_seed(7), config, |_| (), |rng| rng.u8(..));
+    ///
+    /// assert!((5..=10).contains(&graph.len()));
+    /// ```
+    pub fn random(
+        rng: &mut Rng,
+        config: RandomConfig,
+        mut vertex_weight: impl FnMut(&mut Rng) -> V,
+        mut edge_weight: impl FnMut(&mut Rng) -> E,
+    ) -> (Self, Vec<VertexIdx>) {
+        assert!(
+            config.min_vertices <= config.max_vertices,
+            "The minimum number of vertices is greater than the maximum"
+        );
+
+        let vertex_count = rng.usize(config.min_vertices..=config.max_vertices);
+        let mut graph = Self::default();
+
+        graph.reserve(vertex_count);
+
+        let vertices: Vec<_> = (0..vertex_count)
+            .map(|_| graph.insert_vertex(vertex_weight(rng)))
+            .collect();
+
+        for (i, &from_idx) in vertices.iter().enumerate() {
+            // Undirected graphs consider each pair once, from the earlier vertex
+            let start = if D::IS_DIRECTED { 0 } else { i };
+
+            for &to_idx in &vertices[start..] {
+                if (from_idx == to_idx && !config.self_loops) || rng.f64() >= config.density {
+                    continue;
+                }
+
+                graph.connect(from_idx, to_idx, edge_weight(rng));
+            }
+        }
+
+        (graph, vertices)
+    }
+}
+
+/// Vertices with arbitrary weights, then an arbitrary number of arbitrary edges between them,
+/// bounded by the remaining data. In undirected graphs, each edge is inserted in both directions.
+#[cfg(feature = "arbitrary")]
+impl<'a, V, E, D, S> Arbitrary<'a> for Graph<V, E, D, S>
+where
+    V: Arbitrary<'a>,
+    E: Arbitrary<'a> + Clone,
+    D: Direction,
+    S: BuildHasher + Default,
+{
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        let vertex_count = u.arbitrary_len::<V>()?;
+        let mut graph = Self::default();
+
+        graph.reserve(vertex_count);
+
+        let vertices = (0..vertex_count)
+            .map(|_| Ok(graph.insert_vertex(u.arbitrary()?)))
+            .collect::<arbitrary::Result<Vec<_>>>()?;
+
+        if vertices.is_empty() {
+            return Ok(graph);
+        }
+
+        // Decided up front, as picking among one vertex or a zero-sized weight consumes no data
+        let edge_count = u.arbitrary_len::<(usize, usize, E)>()?;
+
+        for _ in 0..edge_count {
+            let from_idx = vertices[u.choose_index(vertices.len())?];
+            let to_idx = vertices[u.choose_index(vertices.len())?];
+
+            graph.connect(from_idx, to_idx, u.arbitrary()?);
+        }
+
+        Ok(graph)
+    }
+}
+
+/// A [`proptest`] strategy for graphs generated by [`Graph::random`] with `config`, with weights
+/// from `vertex_weight` and `edge_weight`.
+///
+/// The number of vertices and the weights shrink like vectors, and the edges follow a random
+/// seed. `config.max_vertices²` edge weights are generated for each graph, whether used or not.
+///
+/// # Panics
+///
+/// If `config.min_vertices` is greater than `config.max_vertices`.
+///
+/// ```
+/// # use badlgorithms::graph::{random::{strategy, RandomConfig}, Graph};
+/// # use proptest::prelude::*;
+/// let graphs = strategy(RandomConfig::default(), Just(()), 1..10u32);
+///
+/// proptest!(|(graph in graphs)| {
+///     let graph: Graph<(), u32> = graph;
+///
+///     prop_assert!(graph.len() <= 16);
+/// });
+/// ```
+#[cfg(feature = "proptest")]
+pub fn strategy<V, E, D, S>(
+    config: RandomConfig,
+    vertex_weight: impl Strategy<Value = V>,
+    edge_weight: impl Strategy<Value = E>,
+) -> impl Strategy<Value = Graph<V, E, D, S>>
+where
+    V: Debug,
+    E: Clone + Debug,
+    D: Direction + Debug,
+    S: BuildHasher + Default + Debug,
+{
+    assert!(
+        config.min_vertices <= config.max_vertices,
+        "The minimum number of vertices is greater than the maximum"
+    );
+
+    let edge_count = config.max_vertices * config.max_vertices;
+
+    (
+        vec(vertex_weight, config.min_vertices..=config.max_vertices),
+        vec(edge_weight, edge_count),
+        any::<u64>(),
+    )
+        .prop_map(move |(vertex_weights, edge_weights, seed)| {
+            let config = RandomConfig {
+                min_vertices: vertex_weights.len(),
+                max_vertices: vertex_weights.len(),
+                ..config
+            };
+            let (mut vertex_weights, mut edge_weights) =
+                (vertex_weights.into_iter(), edge_weights.into_iter());
+
+            Graph::random(
+                &mut Rng::with_seed(seed),
+                config,
+                |_| vertex_weights.next().expect("A weight per vertex"),
+                |_| edge_weights.next().expect("A weight per pair of vertices"),
+            )
+            .0
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RandomConfig;
+    use crate::graph::{tests::helpers::test_indices, Directed, Graph, Undirected};
+    use fastrand::Rng;
+
+    #[test]
+    fn test_random() {
+        let config = RandomConfig {
+            min_vertices: 3,
+            max_vertices: 8,
+            ..RandomConfig::default()
+        };
+        let generate = |seed| {
+            Graph::<u8, u8>::random(
+                &mut Rng::with_seed(seed),
+                config,
+                |rng| rng.u8(..),
+                |rng| rng.u8(..),
+            )
+        };
+        let (graph, vertices) = generate(42);
+        let (same_seed, same_vertices) = generate(42);
+        let weights = |graph: &Graph<u8, u8>, vertices: &[_]| {
+            let mut edges: Vec<_> = graph
+                .edges_iter()
+                .map(|(from, to, &weight)| {
+                    let position = |idx| vertices.iter().position(|&v| v == idx);
+
+                    (position(from), position(to), weight)
+                })
+                .collect();
+
+            edges.sort_unstable();
+            edges
+        };
+
+        assert!((3..=8).contains(&graph.len()), "Wrong number of vertices");
+        assert_eq!(
+            weights(&graph, &vertices),
+            weights(&same_seed, &same_vertices),
+            "The same seed must generate the same graph"
+        );
+        test_indices(&graph);
+
+        let complete = RandomConfig {
+            min_vertices: 5,
+            max_vertices: 5,
+            density: 1.0,
+            self_loops: false,
+        };
+        let (directed, _) =
+            Graph::<(), (), Directed>::random(&mut Rng::with_seed(1), complete, |_| (), |_| ());
+        let (undirected, _) =
+            Graph::<(), (), Undirected>::random(&mut Rng::with_seed(1), complete, |_| (), |_| ());
+
+        assert_eq!(directed.edge_count(), 20, "Every pair must be connected");
+        assert_eq!(
+            undirected.edge_count(),
+            20,
+            "Every pair must be connected both ways"
+        );
+        test_indices(&undirected);
+
+        let empty = RandomConfig {
+            density: 0.0,
+            self_loops: true,
+            ..complete
+        };
+        let (graph, _) = Graph::<(), ()>::random(&mut Rng::with_seed(1), empty, |_| (), |_| ());
+
+        assert_eq!(graph.edge_count(), 0, "No pair must be connected");
+    }
+
+    #[cfg(feature = "arbitrary")]
+    #[test]
+    fn test_arbitrary() {
+        use arbitrary::{Arbitrary, Unstructured};
+
+        let data: Vec<u8> = (0..=255).cycle().take(1024).collect();
+        let graph = Graph::<u8, u8, Undirected>::arbitrary(&mut Unstructured::new(&data))
+            .expect("There is enough data");
+
+        assert!(graph.edge_count() > 0, "The data must turn into edges");
+        test_indices(&graph);
+
+        let empty = Graph::<u8, u8>::arbitrary(&mut Unstructured::new(&[]))
+            .expect("No data is an empty graph");
+
+        assert!(empty.is_empty(), "No data must generate no vertices");
+
+        let single = Graph::<(), ()>::arbitrary(&mut Unstructured::new(&[1, 1]))
+            .expect("Edges without data terminate");
+
+        assert_eq!(single.len(), 1, "Wrong number of vertices");
+        test_indices(&single);
+    }
+
+    #[cfg(feature = "proptest")]
+    #[test]
+    fn test_strategy() {
+        use super::strategy;
+        use proptest::prelude::*;
+
+        let config = RandomConfig {
+            min_vertices: 2,
+            self_loops: true,
+            ..RandomConfig::default()
+        };
+        let graphs = strategy(config, 0..100u8, Just('e'));
+
+        proptest!(|(graph in graphs)| {
+            let graph: Graph<u8, char, Undirected> = graph;
+
+            prop_assert!((2..=16).contains(&graph.len()), "Wrong number of vertices");
+            prop_assert!(
+                graph.vertices_iter().all(|(_, vertex)| *vertex.weight() < 100),
+                "The vertex weights must come from their strategy"
+            );
+            test_indices(&graph);
+        });
+    }
+}
@@ -0,0 +1,105 @@
+use super::{Graph, Vertex, VertexIdx};
+use crate::collections::HashMap;
+use core::hash::BuildHasher;
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+impl Serialize for VertexIdx {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+/// Vertices created after deserializing an ID never get the same ID, since the global counter
+/// is moved past it. Fails for IDs greater than [`VertexIdx::MAX_RAW`], like
+/// [`VertexIdx::from_raw`], so that untrusted data can't exhaust the counter.
+impl<'de> Deserialize<'de> for VertexIdx {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Self::from_raw(usize::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+/// A graph is serialized as a map from the ID of each vertex to its weight and edges.
+impl<V: Serialize, E: Serialize, D, H> Serialize for Graph<V, E, D, H> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.vertices.serialize(serializer)
+    }
+}
+
+/// Vertices keep their IDs. Fails if an edge points to a vertex that doesn't exist.
+impl<'de, V, E, D, H> Deserialize<'de> for Graph<V, E, D, H>
+where
+    V: Deserialize<'de>,
+    E: Deserialize<'de>,
+    H: BuildHasher + Default,
+{
+    fn deserialize<De: Deserializer<'de>>(deserializer: De) -> Result<Self, De::Error> {
+        let vertices = HashMap::<VertexIdx, Vertex<V, E, H>, H>::deserialize(deserializer)?;
+
+        Self::from_parts(vertices).map_err(De::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::{Graph, Undirected, VertexIdx};
+
+    #[test]
+    fn test_serde() {
+        let mut graph = Graph::<String, u8, Undirected>::new();
+        let [a, b, c] = ["a", "b", "c"].map(|weight| graph.insert_vertex(weight.to_owned()));
+
+        graph.insert_or_update_edges([(a, b, 1), (b, c, 2)]);
+
+        let json = serde_json::to_string(&graph).expect("The graph can be serialized");
+        let deserialized: Graph<String, u8, Undirected> =
+            serde_json::from_str(&json).expect("The graph can be deserialized");
+
+        assert_eq!(deserialized, graph, "The round-trip must keep the IDs");
+
+        // Pretend that the graph was serialized by another process, with higher IDs
+        let shifted = json.replace(&format!("\"{}\"", c.0), "\"1000000000\"");
+        let mut deserialized: Graph<String, u8, Undirected> =
+            serde_json::from_str(&shifted).expect("The graph can be deserialized");
+
+        assert!(
+            deserialized.insert_vertex(String::new()).0 > 1_000_000_000,
+            "New vertices must get IDs past the deserialized ones"
+        );
+
+        let missing = format!(
+            r#"{{"{}":{{"weight":"a","edges":{{"1000000001":1}}}}}}"#,
+            a.0
+        );
+
+        assert!(
+            serde_json::from_str::<Graph<String, u8>>(&missing).is_err(),
+            "Edges must point to existing vertices"
+        );
+        assert!(
+            serde_json::from_str::<Graph<String, u8>>(&format!(
+                r#"{{"{}":{{"weight":"a","edges":{{}}}}}}"#,
+                usize::MAX
+            ))
+            .is_err(),
+            "The last ID can't be deserialized"
+        );
+
+        let near_max = (usize::MAX - 1).to_string();
+
+        assert!(
+            serde_json::from_str::<VertexIdx>(&near_max).is_err(),
+            "IDs near the end can't be deserialized"
+        );
+        assert!(
+            serde_json::from_str::<Graph<String, u8>>(&format!(
+                r#"{{"{near_max}":{{"weight":"a","edges":{{}}}}}}"#
+            ))
+            .is_err(),
+            "IDs near the end can't be deserialized in a graph"
+        );
+        assert!(
+            graph.insert_vertex(String::new()).0 < VertexIdx::MAX_RAW,
+            "The counter must not have moved"
+        );
+    }
+}
@@ -0,0 +1,439 @@
+use crate::collections::{hash_map, HashMap};
+use core::{
+    array, fmt,
+    hash::{BuildHasher, Hash},
+    iter, mem,
+    ops::Index,
+    slice,
+};
+
+/// The number of entries a [`SmallMap`] stores inline before moving them to a [`HashMap`].
+pub(crate) const INLINE_CAPACITY: usize = 4;
+
+/// A map that stores up to [`INLINE_CAPACITY`] entries in place and more in a [`HashMap`], so
+/// that small maps, such as the edges of most vertices, don't allocate. Inline entries are found
+/// by a linear scan, which is faster than hashing for so few of them.
+#[derive(Clone)]
+pub(crate) enum SmallMap<K, V, S> {
+    /// The first `len` entries are `Some`, and the rest `None`
+    Inline {
+        entries: [Option<(K, V)>; INLINE_CAPACITY],
+        len: usize,
+    },
+    Heap(HashMap<K, V, S>),
+}
+
+impl<K, V, S> Default for SmallMap<K, V, S> {
+    fn default() -> Self {
+        Self::Inline {
+            entries: array::from_fn(|_| None),
+            len: 0,
+        }
+    }
+}
+
+impl<K, V, S> SmallMap<K, V, S> {
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Inline { len, .. } => *len,
+            Self::Heap(map) => map.len(),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        match self {
+            Self::Inline { .. } => INLINE_CAPACITY,
+            Self::Heap(map) => map.capacity(),
+        }
+    }
+
+    /// Remove every entry, keeping the allocated memory, if any.
+    pub fn clear(&mut self) {
+        match self {
+            Self::Inline { .. } => *self = Self::default(),
+            Self::Heap(map) => map.clear(),
+        }
+    }
+
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        match self {
+            Self::Inline { entries, len } => Iter::Inline(entries[..*len].iter()),
+            Self::Heap(map) => Iter::Heap(map.iter()),
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        match self {
+            Self::Inline { entries, len } => IterMut::Inline(entries[..*len].iter_mut()),
+            Self::Heap(map) => IterMut::Heap(map.iter_mut()),
+        }
+    }
+
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        Keys(self.iter())
+    }
+}
+
+impl<K: Hash + Eq, V, S: BuildHasher + Default> SmallMap<K, V, S> {
+    /// Create an empty map with room for at least `capacity` entries without reallocating.
+    pub fn with_capacity(capacity: usize) -> Self {
+        if capacity <= INLINE_CAPACITY {
+            Self::default()
+        } else {
+            Self::Heap(HashMap::with_capacity_and_hasher(capacity, S::default()))
+        }
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        match self {
+            Self::Inline { .. } => self.iter().find(|(k, _)| *k == key).map(|(_, v)| v),
+            Self::Heap(map) => map.get(key),
+        }
+    }
+
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        match self {
+            Self::Inline { .. } => self.iter_mut().find(|(k, _)| *k == key).map(|(_, v)| v),
+            Self::Heap(map) => map.get_mut(key),
+        }
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Insert or update the value of `key`. Returns its previous value, if any.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(old_value) = self.get_mut(&key) {
+            return Some(mem::replace(old_value, value));
+        }
+
+        match self {
+            Self::Inline { entries, len } if *len < INLINE_CAPACITY => {
+                entries[*len] = Some((key, value));
+                *len += 1;
+            }
+            Self::Inline { .. } => {
+                self.spill(INLINE_CAPACITY + 1);
+
+                if let Self::Heap(map) = self {
+                    map.insert(key, value);
+                }
+            }
+            Self::Heap(map) => {
+                map.insert(key, value);
+            }
+        }
+
+        None
+    }
+
+    /// Remove `key`. Returns its value, if any.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        match self {
+            Self::Inline { entries, len } => {
+                let position = entries[..*len]
+                    .iter()
+                    .position(|entry| entry.as_ref().is_some_and(|(k, _)| k == key))?;
+
+                // The last entry takes the place of the removed one
+                *len -= 1;
+                entries.swap(position, *len);
+                entries[*len].take().map(|(_, value)| value)
+            }
+            Self::Heap(map) => map.remove(key),
+        }
+    }
+
+    /// Keep only the entries for which `keep` returns `true`.
+    pub fn retain(&mut self, mut keep: impl FnMut(&K, &mut V) -> bool) {
+        match self {
+            Self::Inline { entries, len } => {
+                let mut kept = 0;
+
+                for i in 0..*len {
+                    let is_kept = entries[i]
+                        .as_mut()
+                        .is_some_and(|(key, value)| keep(key, value));
+
+                    if is_kept {
+                        entries.swap(kept, i);
+                        kept += 1;
+                    } else {
+                        entries[i] = None;
+                    }
+                }
+
+                *len = kept;
+            }
+            Self::Heap(map) => map.retain(|key, value| keep(key, value)),
+        }
+    }
+
+    /// Reserve room for at least `additional` more entries without reallocating.
+    pub fn reserve(&mut self, additional: usize) {
+        match self {
+            Self::Inline { len, .. } if *len + additional <= INLINE_CAPACITY => {}
+            Self::Inline { len, .. } => {
+                let capacity = *len + additional;
+
+                self.spill(capacity);
+            }
+            Self::Heap(map) => map.reserve(additional),
+        }
+    }
+
+    /// Shrink the capacity as much as possible, moving the entries back in place if they fit.
+    pub fn shrink_to_fit(&mut self) {
+        match self {
+            Self::Inline { .. } => {}
+            Self::Heap(map) if map.len() <= INLINE_CAPACITY => {
+                *self = mem::take(map).into_iter().collect();
+            }
+            Self::Heap(map) => map.shrink_to_fit(),
+        }
+    }
+
+    /// Move the inline entries to a [`HashMap`] with room for at least `capacity` entries.
+    fn spill(&mut self, capacity: usize) {
+        let mut map = HashMap::with_capacity_and_hasher(capacity, S::default());
+
+        map.extend(mem::take(self));
+        *self = Self::Heap(map);
+    }
+}
+
+impl<K: Hash + Eq, V: PartialEq, S: BuildHasher + Default> PartialEq for SmallMap<K, V, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len()
+            && self
+                .iter()
+                .all(|(key, value)| other.get(key) == Some(value))
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug, S> fmt::Debug for SmallMap<K, V, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: Hash + Eq, V, S: BuildHasher + Default> Index<&K> for SmallMap<K, V, S> {
+    type Output = V;
+
+    fn index(&self, key: &K) -> &V {
+        self.get(key).expect("The key exists")
+    }
+}
+
+impl<K: Hash + Eq, V, S: BuildHasher + Default> FromIterator<(K, V)> for SmallMap<K, V, S> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let mut map = Self::with_capacity(iter.size_hint().0);
+
+        for (key, value) in iter {
+            map.insert(key, value);
+        }
+
+        map
+    }
+}
+
+impl<K, V, S> IntoIterator for SmallMap<K, V, S> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> IntoIter<K, V> {
+        match self {
+            Self::Inline { entries, .. } => IntoIter::Inline(entries.into_iter().flatten()),
+            Self::Heap(map) => IntoIter::Heap(map.into_iter()),
+        }
+    }
+}
+
+impl<'a, K, V, S> IntoIterator for &'a SmallMap<K, V, S> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Iter<'a, K, V> {
+        self.iter()
+    }
+}
+
+impl<'a, K, V, S> IntoIterator for &'a mut SmallMap<K, V, S> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> IterMut<'a, K, V> {
+        self.iter_mut()
+    }
+}
+
+pub enum Iter<'a, K, V> {
+    Inline(slice::Iter<'a, Option<(K, V)>>),
+    Heap(hash_map::Iter<'a, K, V>),
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Self::Inline(entries) => entries.next()?.as_ref().map(|(key, value)| (key, value)),
+            Self::Heap(entries) => entries.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            Self::Inline(entries) => entries.size_hint(),
+            Self::Heap(entries) => entries.size_hint(),
+        }
+    }
+}
+
+impl<K, V> ExactSizeIterator for Iter<'_, K, V> {}
+
+impl<K, V> Clone for Iter<'_, K, V> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Inline(entries) => Self::Inline(entries.clone()),
+            Self::Heap(entries) => Self::Heap(entries.clone()),
+        }
+    }
+}
+
+pub enum IterMut<'a, K, V> {
+    Inline(slice::IterMut<'a, Option<(K, V)>>),
+    Heap(hash_map::IterMut<'a, K, V>),
+}
+
+impl<'a, K, V> Iterator for IterMut<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Self::Inline(entries) => entries.next()?.as_mut().map(|(key, value)| (&*key, value)),
+            Self::Heap(entries) => entries.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            Self::Inline(entries) => entries.size_hint(),
+            Self::Heap(entries) => entries.size_hint(),
+        }
+    }
+}
+
+impl<K, V> ExactSizeIterator for IterMut<'_, K, V> {}
+
+pub enum IntoIter<K, V> {
+    Inline(iter::Flatten<array::IntoIter<Option<(K, V)>, INLINE_CAPACITY>>),
+    Heap(hash_map::IntoIter<K, V>),
+}
+
+impl<K, V> Iterator for IntoIter<K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Self::Inline(entries) => entries.next(),
+            Self::Heap(entries) => entries.next(),
+        }
+    }
+}
+
+pub struct Keys<'a, K, V>(Iter<'a, K, V>);
+
+impl<'a, K, V> Iterator for Keys<'a, K, V> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(key, _)| key)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<K, V> ExactSizeIterator for Keys<'_, K, V> {}
+
+#[cfg(feature = "serde")]
+impl<K: serde::Serialize, V: serde::Serialize, S> serde::Serialize for SmallMap<K, V, S> {
+    fn serialize<Ser: serde::Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        serializer.collect_map(self.iter())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, K, V, S> serde::Deserialize<'de> for SmallMap<K, V, S>
+where
+    K: serde::Deserialize<'de> + Hash + Eq,
+    V: serde::Deserialize<'de>,
+    S: BuildHasher + Default,
+{
+    fn deserialize<De: serde::Deserializer<'de>>(deserializer: De) -> Result<Self, De::Error> {
+        let map = HashMap::<K, V, S>::deserialize(deserializer)?;
+
+        Ok(map.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SmallMap, INLINE_CAPACITY};
+    use crate::collections::DefaultHashBuilder;
+
+    #[test]
+    fn test_small_map() {
+        let mut map = SmallMap::<usize, usize, DefaultHashBuilder>::default();
+
+        for i in 0..INLINE_CAPACITY {
+            assert_eq!(map.insert(i, i * 10), None, "The key is new");
+        }
+
+        assert!(
+            matches!(map, SmallMap::Inline { .. }),
+            "The entries fit in place"
+        );
+        assert_eq!(map.insert(1, 11), Some(10), "The key exists");
+        assert_eq!(map.remove(&0), Some(0), "The key exists");
+        assert_eq!(map.remove(&0), None, "The key was removed");
+        assert_eq!(
+            map.get(&3),
+            Some(&30),
+            "The last entry moved to the removed place"
+        );
+
+        map.insert(4, 40);
+        map.insert(5, 50);
+
+        assert!(
+            matches!(map, SmallMap::Heap(_)),
+            "The entries don't fit in place"
+        );
+        assert_eq!(map.len(), INLINE_CAPACITY + 1, "Wrong number of entries");
+
+        let mut spilled = map.clone();
+
+        map.retain(|&key, _| key % 2 == 1);
+        map.shrink_to_fit();
+
+        assert!(
+            matches!(map, SmallMap::Inline { .. }),
+            "The entries fit in place again"
+        );
+        assert_eq!(map[&1], 11, "Wrong value");
+        assert_eq!(map.keys().count(), 3, "Wrong keys");
+
+        spilled.retain(|&key, _| key % 2 == 1);
+        assert_eq!(map, spilled, "Maps with the same entries must be equal");
+
+        let mut entries: Vec<_> = map.into_iter().collect();
+
+        entries.sort_unstable();
+        assert_eq!(entries, [(1, 11), (3, 30), (5, 50)], "Wrong entries");
+    }
+}
@@ -0,0 +1,180 @@
+//! Descriptive statistics of the degrees and the density of a graph, as plain numbers and vectors
+//! that are ready to print or plot.
+
+use super::{Graph, Vertex, VertexIdx};
+use crate::collections::HashMap;
+use alloc::{vec, vec::Vec};
+use core::hash::BuildHasher;
+
+/// Which edges of a vertex are counted as its degree. They're the same in undirected graphs.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Degree {
+    /// The edges from the vertex.
+    #[default]
+    Out,
+    /// The edges to the vertex.
+    In,
+}
+
+/// The `kind` degrees of every vertex in no particular order, in `O(|V|)`, or in `O(|V| + |E|)`
+/// for in-degrees without a reverse index.
+fn degrees<V, E, D, S>(graph: &Graph<V, E, D, S>, kind: Degree) -> Vec<usize>
+where
+    S: BuildHasher + Default,
+{
+    match kind {
+        Degree::Out => graph.vertices.values().map(Vertex::degree).collect(),
+        Degree::In if graph.has_reverse_index() => {
+            graph.vertices.values().map(Vertex::in_degree).collect()
+        }
+        Degree::In => {
+            let mut in_degrees: HashMap<VertexIdx, usize, S> =
+                graph.vertices.keys().map(|&idx| (idx, 0)).collect();
+
+            for (_, to_idx, _) in graph.edges_iter() {
+                *in_degrees.get_mut(&to_idx).expect("The vertex exists") += 1;
+            }
+
+            in_degrees.into_values().collect()
+        }
+    }
+}
+
+/// The `kind` degrees of every vertex in non-increasing order, in `O(|V| log |V|)`, like
+/// [`Graph::degree_sequence`] for out-degrees. In-degrees take `O(|E|)` more without a reverse
+/// index.
+pub fn degree_sequence<V, E, D, S>(graph: &Graph<V, E, D, S>, kind: Degree) -> Vec<usize>
+where
+    S: BuildHasher + Default,
+{
+    let mut sequence = degrees(graph, kind);
+
+    sequence.sort_unstable_by(|a, b| b.cmp(a));
+    sequence
+}
+
+/// The number of vertices with each `kind` degree, indexed by degree up to the maximum one, in
+/// `O(|V|)`, or in `O(|V| + |E|)` for in-degrees without a reverse index. It's empty for an empty
+/// graph.
+///
+/// ```
+/// # use badlgorithms::graph::{stats::{degree_histogram, Degree}, Graph};
+/// let mut graph = Graph::<(), ()>::new();
+/// let [a, b, c] = [(); 3].map(|_| graph.insert_vertex(()));
+///
+/// graph.insert_or_update_edges([(a, b, ()), (a, c, ())]);
+///
+/// assert_eq!(degree_histogram(&graph, Degree::Out), [2, 0, 1]);
+/// assert_eq!(degree_histogram(&graph, Degree::In), [1, 2]);
+/// ```
+pub fn degree_histogram<V, E, D, S>(graph: &Graph<V, E, D, S>, kind: Degree) -> Vec<usize>
+where
+    S: BuildHasher + Default,
+{
+    let degrees = degrees(graph, kind);
+    let Some(&max_degree) = degrees.iter().max() else {
+        return Vec::new();
+    };
+    let mut histogram = vec![0; max_degree + 1];
+
+    for degree in degrees {
+        histogram[degree] += 1;
+    }
+
+    histogram
+}
+
+/// The ratio of the edges to the edges of a complete graph without self-loops, from 0 to 1, in
+/// `O(1)`. Self-loops are counted as edges, so graphs with them may exceed 1.
+///
+/// Graphs with fewer than two vertices have a density of zero.
+pub fn density<V, E, D, S>(graph: &Graph<V, E, D, S>) -> f64
+where
+    S: BuildHasher + Default,
+{
+    let vertex_count = graph.vertices.len();
+
+    if vertex_count < 2 {
+        return 0.0;
+    }
+
+    // Undirected edges are counted in both directions, like the pairs of the complete graph
+    graph.edge_count() as f64 / (vertex_count * (vertex_count - 1)) as f64
+}
+
+/// The mean out-degree of the vertices, in `O(1)`, which is also the mean in-degree. In undirected
+/// graphs, this is the usual average degree, with self-loops counted once.
+///
+/// An empty graph has an average degree of zero.
+pub fn average_degree<V, E, D, S>(graph: &Graph<V, E, D, S>) -> f64
+where
+    S: BuildHasher + Default,
+{
+    if graph.vertices.is_empty() {
+        return 0.0;
+    }
+
+    graph.edge_count() as f64 / graph.vertices.len() as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Undirected;
+
+    #[test]
+    fn test_stats() {
+        //  a → b → c    d
+        //   \_____↗
+        let mut graph = Graph::<(), ()>::new();
+        let [a, b, c, _] = [(); 4].map(|_| graph.insert_vertex(()));
+
+        graph.insert_or_update_edges([(a, b, ()), (b, c, ()), (a, c, ())]);
+
+        assert_eq!(
+            degree_sequence(&graph, Degree::Out),
+            graph.degree_sequence(),
+            "Out-degrees must match the method"
+        );
+        assert_eq!(
+            degree_sequence(&graph, Degree::In),
+            [2, 1, 0, 0],
+            "Wrong in-degree sequence"
+        );
+        assert_eq!(
+            degree_histogram(&graph, Degree::Out),
+            [2, 1, 1],
+            "Wrong out-degree histogram"
+        );
+        assert_eq!(density(&graph), 0.25, "3 of the 12 possible edges exist");
+        assert_eq!(average_degree(&graph), 0.75, "Wrong average degree");
+
+        let mut undirected = Graph::<(), (), Undirected>::new();
+        let [a, b, c] = [(); 3].map(|_| undirected.insert_vertex(()));
+
+        undirected.insert_or_update_undirected_edge(a, b, ());
+        undirected.insert_or_update_undirected_edge(b, c, ());
+        undirected.insert_or_update_undirected_edge(c, a, ());
+
+        assert_eq!(density(&undirected), 1.0, "The triangle is complete");
+        assert_eq!(
+            average_degree(&undirected),
+            2.0,
+            "Every vertex has two edges"
+        );
+        assert_eq!(
+            degree_histogram(&undirected, Degree::In),
+            degree_histogram(&undirected, Degree::Out),
+            "In- and out-degrees are the same"
+        );
+
+        let empty = Graph::<(), ()>::new();
+
+        assert!(
+            degree_histogram(&empty, Degree::Out).is_empty(),
+            "An empty graph has no degrees"
+        );
+        assert_eq!(density(&empty), 0.0, "An empty graph has no density");
+        assert_eq!(average_degree(&empty), 0.0, "An empty graph has no degree");
+    }
+}
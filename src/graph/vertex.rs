@@ -1,14 +1,40 @@
-use super::VertexIdx;
-use std::collections::HashMap;
+use super::{small_map::SmallMap, VertexIdx};
+use crate::collections::DefaultHashBuilder;
+use alloc::vec::Vec;
+use core::{cmp::Ordering, hash::BuildHasher};
 
-#[derive(Debug)]
-pub struct Vertex<V, E> {
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "V: serde::Serialize, E: serde::Serialize",
+        deserialize = "V: serde::Deserialize<'de>, E: serde::Deserialize<'de>, S: BuildHasher + Default"
+    ))
+)]
+pub struct Vertex<V, E, S = DefaultHashBuilder> {
     pub(crate) weight: V,
-    /// Edges from `self` to the vertices/keys with weight the value of an entry
-    pub(crate) edges: HashMap<VertexIdx, E>,
+    /// Edges from `self` to the vertices/keys with weight the value of an entry, stored in place
+    /// while there are few of them
+    pub(crate) edges: SmallMap<VertexIdx, E, S>,
+    /// The vertices with an edge to `self`, kept in sync with their `edges` in graphs with a
+    /// reverse index and stored in place while there are few of them
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) predecessors: Predecessors<S>,
 }
 
-impl<V, E> Vertex<V, E> {
+/// A set of vertices, as a map to `()`.
+pub(crate) type Predecessors<S> = SmallMap<VertexIdx, (), S>;
+
+impl<V: PartialEq, E: PartialEq, S: BuildHasher + Default> PartialEq for Vertex<V, E, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.weight == other.weight && self.edges == other.edges
+    }
+}
+
+impl<V: Eq, E: Eq, S: BuildHasher + Default> Eq for Vertex<V, E, S> {}
+
+impl<V, E, S: BuildHasher + Default> Vertex<V, E, S> {
     pub fn weight(&self) -> &V {
         &self.weight
     }
@@ -25,6 +51,11 @@ impl<V, E> Vertex<V, E> {
         self.edges.iter().map(|(k, v)| (*k, v))
     }
 
+    /// Iterate over the IDs of the vertices this vertex has an edge to.
+    pub fn neighbor_indices(&self) -> impl Iterator<Item = VertexIdx> + '_ {
+        self.edges.keys().copied()
+    }
+
     pub fn neighbors_mut(&mut self) -> impl Iterator<Item = (VertexIdx, &mut E)> {
         self.edges.iter_mut().map(|(k, v)| (*k, v))
     }
@@ -33,7 +64,72 @@ impl<V, E> Vertex<V, E> {
         self.edges.into_iter()
     }
 
+    /// Iterate over the neighbors in the order of `compare`, in `O(deg log deg)` when called.
+    ///
+    /// - `compare`: `|(a_vertex_idx, a_edge_weight), (b_vertex_idx, b_edge_weight)| -> Ordering`
+    pub fn neighbors_sorted_by<F>(&self, mut compare: F) -> impl Iterator<Item = (VertexIdx, &E)>
+    where
+        F: FnMut((VertexIdx, &E), (VertexIdx, &E)) -> Ordering,
+    {
+        let mut neighbors: Vec<_> = self.neighbors().collect();
+
+        neighbors.sort_unstable_by(|&a, &b| compare(a, b));
+        neighbors.into_iter()
+    }
+
+    /// Iterate over the neighbors in increasing order of their edge weight, and then of their ID,
+    /// in `O(deg log deg)` when called.
+    pub fn neighbors_by_weight(&self) -> impl Iterator<Item = (VertexIdx, &E)>
+    where
+        E: Ord,
+    {
+        self.neighbors_sorted_by(|(a_idx, a), (b_idx, b)| a.cmp(b).then(a_idx.cmp(&b_idx)))
+    }
+
+    /// Iterate over the neighbors in increasing order of their ID, in `O(deg log deg)` when called.
+    pub fn neighbors_by_idx(&self) -> impl Iterator<Item = (VertexIdx, &E)> {
+        self.neighbors_sorted_by(|(a_idx, _), (b_idx, _)| a_idx.cmp(&b_idx))
+    }
+
+    /// The number of edges this vertex can have without reallocating.
+    pub fn edge_capacity(&self) -> usize {
+        self.edges.capacity()
+    }
+
+    /// Reserve room for at least `additional` more edges from this vertex without reallocating.
+    pub fn reserve_edges(&mut self, additional: usize) {
+        self.edges.reserve(additional);
+    }
+
+    /// Shrink the capacity of the edges from this vertex as much as possible.
+    pub fn shrink_edges_to_fit(&mut self) {
+        self.edges.shrink_to_fit();
+    }
+
+    /// The number of edges from this vertex, in `O(1)`. In undirected graphs, this is its degree,
+    /// with a self-loop counted once.
+    pub fn degree(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// The number of edges to this vertex, in `O(1)`.
+    ///
+    /// This is only valid if its graph tracks the edges to every vertex, as a
+    /// [`Graph`](super::Graph) with a [reverse index](super::Graph::with_reverse_index) and
+    /// a `ConcurrentGraph` do. It's 0 otherwise, so use
+    /// [`Graph::in_degree`](super::Graph::in_degree) when unsure.
+    pub fn in_degree(&self) -> usize {
+        self.predecessors.len()
+    }
+
+    /// Iterate over the IDs of the vertices with an edge to this vertex, in `O(1)` per vertex.
+    /// Valid under the same conditions as [`Self::in_degree`], and empty otherwise.
+    pub fn predecessors(&self) -> impl Iterator<Item = VertexIdx> + '_ {
+        self.predecessors.keys().copied()
+    }
+
+    /// Whether there's an edge from this vertex to `to`, in `O(1)`.
     pub fn is_adjacent(&self, to: VertexIdx) -> bool {
-        self.edges.iter().any(|e| *e.0 == to)
+        self.edges.contains_key(&to)
     }
 }
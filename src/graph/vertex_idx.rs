@@ -1,25 +1,102 @@
-use std::sync::atomic::AtomicUsize;
-use std::sync::atomic::Ordering;
+use super::GraphError;
+use core::sync::atomic::AtomicUsize;
+use core::sync::atomic::Ordering;
 
+/// The ID that the next vertex will get
+static VERTEX_INCR_IDX: AtomicUsize = AtomicUsize::new(0);
+
+/// The ID of a vertex, unique across every graph of the program.
+///
+/// IDs come from a global counter that never hands one out twice, and a graph only gets new
+/// vertices through [`Graph::insert_vertex`](super::Graph::insert_vertex), so a handle to a
+/// removed vertex can't resolve again: [`Graph::get_vertex`](super::Graph::get_vertex) returns
+/// `None` for it. Graphs built from the vertices of another one, such as copies and
+/// [`Graph::from_parts`](super::Graph::from_parts), keep the IDs of their source.
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
 pub struct VertexIdx(pub(crate) usize);
 
 impl VertexIdx {
+    /// # Panics
+    ///
+    /// If every ID has been used, rather than wrapping around and reusing them.
     pub(crate) fn new() -> Self {
-        pub(crate) static VERTEX_INCR_IDX: AtomicUsize = AtomicUsize::new(0);
-
-        let mut idx = VERTEX_INCR_IDX.load(Ordering::Relaxed);
-
-        // If it fails, another thread got first our idx. Try to get the new idx
-        while let Err(new_idx) = VERTEX_INCR_IDX.compare_exchange(
-            idx,
-            (idx + 1) % usize::MAX,
-            Ordering::Relaxed,
-            Ordering::Relaxed,
-        ) {
-            idx = new_idx;
-        }
+        let idx = VERTEX_INCR_IDX
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |idx| {
+                idx.checked_add(1)
+            })
+            .expect("There are vertex IDs left");
 
         Self(idx)
     }
+
+    /// The greatest raw ID accepted by [`Self::from_raw`], so that the global counter always has
+    /// room for the vertices created afterwards.
+    pub const MAX_RAW: usize = usize::MAX / 2;
+
+    /// Reconstruct an ID from [`Self::as_usize`], e.g. one stored in a database or a file.
+    ///
+    /// The global counter is moved past `idx`, so that it's never handed out to a new vertex. The
+    /// ID refers to a vertex only in a graph that already has it, e.g. one deserialized from the
+    /// same source. Fails with [`GraphError::InvalidIdx`] if `idx` is greater than [`Self::MAX_RAW`],
+    /// without moving the counter, so that an untrusted ID can't exhaust it.
+    pub fn from_raw(idx: usize) -> Result<Self, GraphError> {
+        if idx > Self::MAX_RAW {
+            return Err(GraphError::InvalidIdx(idx));
+        }
+
+        VERTEX_INCR_IDX.fetch_max(idx + 1, Ordering::Relaxed);
+
+        Ok(Self(idx))
+    }
+
+    /// The ID as a number, which can be turned back into an ID with [`Self::from_raw`].
+    pub fn as_usize(self) -> usize {
+        self.0
+    }
+}
+
+impl From<VertexIdx> for usize {
+    fn from(idx: VertexIdx) -> Self {
+        idx.as_usize()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Graph;
+
+    #[test]
+    fn test_from_raw() {
+        let idx = VertexIdx::new();
+
+        assert_eq!(VertexIdx::from_raw(idx.as_usize()), Ok(idx), "Wrong ID");
+
+        let raw = idx.as_usize() + 1000;
+
+        assert_eq!(
+            VertexIdx::from_raw(raw).map(VertexIdx::as_usize),
+            Ok(raw),
+            "Wrong ID"
+        );
+        assert!(VertexIdx::new().as_usize() > raw, "The ID was reused");
+        assert_eq!(
+            VertexIdx::from_raw(usize::MAX),
+            Err(GraphError::InvalidIdx(usize::MAX)),
+            "The last ID would exhaust the counter"
+        );
+        assert_eq!(
+            VertexIdx::from_raw(usize::MAX - 1),
+            Err(GraphError::InvalidIdx(usize::MAX - 1)),
+            "IDs near the end would exhaust the counter"
+        );
+
+        let mut graph = Graph::<(), ()>::new();
+        let vertex_idx = graph.insert_vertex(());
+
+        assert!(
+            graph.get_vertex(vertex_idx).is_some(),
+            "The counter must have room left"
+        );
+    }
 }
@@ -0,0 +1,217 @@
+use super::{small_map, Graph, VertexIdx};
+use core::{borrow::Borrow, hash::BuildHasher, iter};
+
+/// Read-only access to the structure of a graph, so that algorithms can run on any of its
+/// representations, such as [`Graph`] and [`CsrGraph`](super::CsrGraph), or on a
+/// [`MappedGraph`] of them.
+pub trait GraphView {
+    type Vertex;
+    type Edge;
+    /// The weight of an edge as yielded by [`Self::neighbors`]: a reference to a stored weight,
+    /// or a weight computed on the fly.
+    type EdgeWeight<'a>: Borrow<Self::Edge>
+    where
+        Self: 'a;
+    type Neighbors<'a>: Iterator<Item = (VertexIdx, Self::EdgeWeight<'a>)>
+    where
+        Self: 'a;
+
+    /// The number of vertices.
+    fn vertex_count(&self) -> usize;
+
+    fn contains_vertex(&self, vertex_idx: VertexIdx) -> bool;
+
+    /// The weight of a vertex, if it exists.
+    fn vertex_weight(&self, vertex_idx: VertexIdx) -> Option<&Self::Vertex>;
+
+    /// The edges from a vertex as `(to, weight)`, if it exists.
+    fn neighbors(&self, vertex_idx: VertexIdx) -> Option<Self::Neighbors<'_>>;
+}
+
+type GraphNeighbors<'a, E> =
+    iter::Map<small_map::Iter<'a, VertexIdx, E>, fn((&'a VertexIdx, &'a E)) -> (VertexIdx, &'a E)>;
+
+impl<V, E, D, S: BuildHasher + Default> GraphView for Graph<V, E, D, S> {
+    type Vertex = V;
+    type Edge = E;
+    type EdgeWeight<'a>
+        = &'a E
+    where
+        Self: 'a;
+    type Neighbors<'a>
+        = GraphNeighbors<'a, E>
+    where
+        Self: 'a;
+
+    fn vertex_count(&self) -> usize {
+        self.len()
+    }
+
+    fn contains_vertex(&self, vertex_idx: VertexIdx) -> bool {
+        self.vertices.contains_key(&vertex_idx)
+    }
+
+    fn vertex_weight(&self, vertex_idx: VertexIdx) -> Option<&V> {
+        Some(self.get_vertex(vertex_idx)?.weight())
+    }
+
+    fn neighbors(&self, vertex_idx: VertexIdx) -> Option<GraphNeighbors<'_, E>> {
+        Some(
+            self.get_vertex(vertex_idx)?
+                .edges
+                .iter()
+                .map(|(&to_idx, weight)| (to_idx, weight)),
+        )
+    }
+}
+
+/// A [`GraphView`] of another one with every edge weight mapped by a closure on the fly, so that
+/// algorithms can run on derived weights, e.g. [`dijkstra`](super::algo::dijkstra) on
+/// `1.0 / bandwidth`, without building a transformed copy of the graph. The vertex weights are
+/// those of the wrapped graph.
+///
+/// The closure is called every time an edge is visited, so it should be cheap.
+///
+/// ```
+/// # use badlgorithms::graph::{algo::dijkstra, view::MappedGraph, Graph};
+/// let mut graph = Graph::<(), f64>::new();
+/// let [a, b, c] = [(); 3].map(|_| graph.insert_vertex(()));
+///
+/// // Bandwidths: the fastest route to `c` goes through `b`
+/// graph.insert_or_update_edges([(a, b, 100.0), (b, c, 100.0), (a, c, 10.0)]);
+///
+/// let latencies = MappedGraph::new(&graph, |_, _, &bandwidth| 1.0 / bandwidth);
+/// let paths = dijkstra(&latencies, a).unwrap();
+///
+/// assert_eq!(paths.path_to(c), Some(vec![a, b, c]));
+/// ```
+pub struct MappedGraph<'g, G, F> {
+    graph: &'g G,
+    edge_map: F,
+}
+
+impl<'g, G, F, NE> MappedGraph<'g, G, F>
+where
+    G: GraphView,
+    F: Fn(VertexIdx, VertexIdx, &G::Edge) -> NE,
+{
+    /// Wrap `graph`, mapping the weight of every edge with
+    /// `edge_map`: `|from_vertex_idx, to_vertex_idx, old_edge_weight| -> new_edge_weight`.
+    pub fn new(graph: &'g G, edge_map: F) -> Self {
+        Self { graph, edge_map }
+    }
+
+    /// The wrapped graph.
+    pub fn inner(&self) -> &'g G {
+        self.graph
+    }
+}
+
+impl<G, F, NE> GraphView for MappedGraph<'_, G, F>
+where
+    G: GraphView,
+    F: Fn(VertexIdx, VertexIdx, &G::Edge) -> NE,
+{
+    type Vertex = G::Vertex;
+    type Edge = NE;
+    type EdgeWeight<'a>
+        = NE
+    where
+        Self: 'a;
+    type Neighbors<'a>
+        = MappedNeighbors<'a, G, F>
+    where
+        Self: 'a;
+
+    fn vertex_count(&self) -> usize {
+        self.graph.vertex_count()
+    }
+
+    fn contains_vertex(&self, vertex_idx: VertexIdx) -> bool {
+        self.graph.contains_vertex(vertex_idx)
+    }
+
+    fn vertex_weight(&self, vertex_idx: VertexIdx) -> Option<&G::Vertex> {
+        self.graph.vertex_weight(vertex_idx)
+    }
+
+    fn neighbors(&self, vertex_idx: VertexIdx) -> Option<Self::Neighbors<'_>> {
+        Some(MappedNeighbors {
+            from_idx: vertex_idx,
+            neighbors: self.graph.neighbors(vertex_idx)?,
+            edge_map: &self.edge_map,
+        })
+    }
+}
+
+/// The edges from a vertex of a [`MappedGraph`] as `(to, mapped_weight)`.
+pub struct MappedNeighbors<'a, G: GraphView + 'a, F> {
+    from_idx: VertexIdx,
+    neighbors: G::Neighbors<'a>,
+    edge_map: &'a F,
+}
+
+impl<'a, G, F, NE> Iterator for MappedNeighbors<'a, G, F>
+where
+    G: GraphView + 'a,
+    F: Fn(VertexIdx, VertexIdx, &G::Edge) -> NE,
+{
+    type Item = (VertexIdx, NE);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (to_idx, weight) = self.neighbors.next()?;
+
+        Some((
+            to_idx,
+            (self.edge_map)(self.from_idx, to_idx, weight.borrow()),
+        ))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.neighbors.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GraphView, MappedGraph};
+    use crate::graph::{algo::depth_first_search, CsrGraph, Graph};
+
+    #[test]
+    fn test_mapped_graph() {
+        //  a → b → c
+        let mut graph = Graph::<char, u32>::new();
+        let [a, b, c] = ['a', 'b', 'c'].map(|weight| graph.insert_vertex(weight));
+
+        graph.insert_or_update_edges([(a, b, 2), (b, c, 3)]);
+
+        let csr = CsrGraph::from(graph.clone());
+        let missing = graph.insert_vertex('d');
+        let mapped = MappedGraph::new(&csr, |from_idx, _, &weight| (weight * 10, from_idx == a));
+        let neighbors: Vec<_> = mapped.neighbors(a).expect("The vertex exists").collect();
+
+        assert_eq!(neighbors, [(b, (20, true))], "Wrong mapped edges");
+        assert_eq!(
+            mapped.vertex_weight(c),
+            Some(&'c'),
+            "Vertex weights are kept"
+        );
+        assert!(
+            mapped.neighbors(missing).is_none(),
+            "The vertex doesn't exist"
+        );
+
+        let nested = MappedGraph::new(&mapped, |_, _, &(weight, _)| weight + 1);
+
+        assert_eq!(
+            nested.neighbors(b).map(Iterator::collect::<Vec<_>>),
+            Some(vec![(c, 31)]),
+            "Mapped graphs can be mapped again"
+        );
+        assert_eq!(
+            depth_first_search(&nested, a),
+            Some(vec![a, b, c]),
+            "Algorithms must run on mapped graphs"
+        );
+    }
+}
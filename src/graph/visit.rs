@@ -0,0 +1,233 @@
+use super::{GraphView, VertexIdx};
+use crate::collections::{HashSet, VecDeque};
+use alloc::vec;
+use core::borrow::Borrow;
+
+/// Receives the events of a traversal by [`bfs_visit`], [`dfs_visit`] or
+/// [`dijkstra_visit`](super::algo::dijkstra_visit), e.g. to trace or animate its steps. Every event
+/// does nothing by default, so only the relevant ones need to be implemented, and `()` ignores
+/// them all.
+pub trait Visitor<E> {
+    /// The vertex `vertex_idx` is reached for the first time.
+    fn discover_vertex(&mut self, _vertex_idx: VertexIdx) {}
+
+    /// The vertex `vertex_idx` is taken from the queue to examine its edges. Not reported by DFS.
+    fn examine_vertex(&mut self, _vertex_idx: VertexIdx) {}
+
+    /// The edge from `from` to `to` is about to be followed, whether `to` has been discovered or not.
+    fn examine_edge(&mut self, _from: VertexIdx, _to: VertexIdx, _weight: &E) {}
+
+    /// The edge from `from` to `to` discovered `to`, so it belongs to the traversal tree.
+    fn tree_edge(&mut self, _from: VertexIdx, _to: VertexIdx, _weight: &E) {}
+
+    /// The edge from `from` to `to` shortened the distance to `to`. Only reported by Dijkstra.
+    fn edge_relaxed(&mut self, _from: VertexIdx, _to: VertexIdx, _weight: &E) {}
+
+    /// The edge from `from` to `to` didn't shorten the distance to `to`. Only reported by Dijkstra.
+    fn edge_not_relaxed(&mut self, _from: VertexIdx, _to: VertexIdx, _weight: &E) {}
+
+    /// The distance to the vertex `vertex_idx` is now `distance`, which may still be shortened
+    /// until the vertex is finished. Only reported by Dijkstra.
+    fn distance_updated(&mut self, _vertex_idx: VertexIdx, _distance: &E) {}
+
+    /// Every edge of the vertex `vertex_idx` has been examined.
+    fn finish_vertex(&mut self, _vertex_idx: VertexIdx) {}
+}
+
+impl<E> Visitor<E> for () {}
+
+/// Performs BFS on a graph `graph` with source vertex `src_idx` in `O(|V'| + |E'|)`, where `|V'|`
+/// and `|E'|` are the vertices and the edges reached, reporting its events to `visitor`.
+///
+/// A vertex is finished once all of its edges have been examined, before the next vertex of the
+/// queue is visited.
+///
+/// Returns `None` if the source vertex doesn't exist in the graph.
+pub fn bfs_visit<G, Vis>(graph: &G, src_idx: VertexIdx, visitor: &mut Vis) -> Option<()>
+where
+    G: GraphView,
+    Vis: Visitor<G::Edge>,
+{
+    if !graph.contains_vertex(src_idx) {
+        return None;
+    }
+
+    let mut discovered = HashSet::from([src_idx]);
+    let mut queue = VecDeque::from([src_idx]);
+
+    visitor.discover_vertex(src_idx);
+
+    while let Some(vertex_idx) = queue.pop_front() {
+        visitor.examine_vertex(vertex_idx);
+
+        let neighbors = graph
+            .neighbors(vertex_idx)
+            .expect("Edges point to existing vertices");
+
+        for (neighbor_idx, weight) in neighbors {
+            let weight = weight.borrow();
+
+            visitor.examine_edge(vertex_idx, neighbor_idx, weight);
+
+            if discovered.insert(neighbor_idx) {
+                visitor.tree_edge(vertex_idx, neighbor_idx, weight);
+                visitor.discover_vertex(neighbor_idx);
+                queue.push_back(neighbor_idx);
+            }
+        }
+
+        visitor.finish_vertex(vertex_idx);
+    }
+
+    Some(())
+}
+
+/// Performs DFS on a graph `graph` with source vertex `src_idx` in `O(|V'| + |E'|)`, where `|V'|`
+/// and `|E'|` are the vertices and the edges reached, reporting its events to `visitor`.
+///
+/// A vertex is finished once all of its descendants have been finished, so the finished vertices
+/// are in postorder.
+///
+/// Returns `None` if the source vertex doesn't exist in the graph.
+pub fn dfs_visit<G, Vis>(graph: &G, src_idx: VertexIdx, visitor: &mut Vis) -> Option<()>
+where
+    G: GraphView,
+    Vis: Visitor<G::Edge>,
+{
+    let mut discovered = HashSet::from([src_idx]);
+    let mut stack = vec![(src_idx, graph.neighbors(src_idx)?)];
+
+    visitor.discover_vertex(src_idx);
+
+    while let Some((vertex_idx, neighbors)) = stack.last_mut() {
+        let vertex_idx = *vertex_idx;
+        let Some((neighbor_idx, weight)) = neighbors.next() else {
+            stack.pop();
+            visitor.finish_vertex(vertex_idx);
+            continue;
+        };
+
+        let weight = weight.borrow();
+
+        visitor.examine_edge(vertex_idx, neighbor_idx, weight);
+
+        if discovered.insert(neighbor_idx) {
+            visitor.tree_edge(vertex_idx, neighbor_idx, weight);
+            visitor.discover_vertex(neighbor_idx);
+            stack.push((
+                neighbor_idx,
+                graph
+                    .neighbors(neighbor_idx)
+                    .expect("Edges point to existing vertices"),
+            ));
+        }
+    }
+
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Graph;
+
+    #[derive(Debug, PartialEq, Eq)]
+    enum Event {
+        Discover(VertexIdx),
+        Dequeue(VertexIdx),
+        Examine(VertexIdx, VertexIdx),
+        Tree(VertexIdx, VertexIdx),
+        Finish(VertexIdx),
+    }
+
+    #[derive(Default)]
+    struct Recorder(Vec<Event>);
+
+    impl<E> Visitor<E> for Recorder {
+        fn discover_vertex(&mut self, vertex_idx: VertexIdx) {
+            self.0.push(Event::Discover(vertex_idx));
+        }
+
+        fn examine_vertex(&mut self, vertex_idx: VertexIdx) {
+            self.0.push(Event::Dequeue(vertex_idx));
+        }
+
+        fn examine_edge(&mut self, from: VertexIdx, to: VertexIdx, _: &E) {
+            self.0.push(Event::Examine(from, to));
+        }
+
+        fn tree_edge(&mut self, from: VertexIdx, to: VertexIdx, _: &E) {
+            self.0.push(Event::Tree(from, to));
+        }
+
+        fn finish_vertex(&mut self, vertex_idx: VertexIdx) {
+            self.0.push(Event::Finish(vertex_idx));
+        }
+    }
+
+    #[test]
+    fn test_visit() {
+        //  a → b → c
+        //  ↑-------⤶
+        let mut graph = Graph::<(), ()>::new();
+        let [a, b, c] = [(); 3].map(|_| graph.insert_vertex(()));
+
+        graph.insert_or_update_edges([(a, b, ()), (b, c, ()), (c, a, ())]);
+
+        let mut bfs = Recorder::default();
+
+        bfs_visit(&graph, a, &mut bfs).expect("The source vertex exists");
+        assert_eq!(
+            bfs.0,
+            [
+                Event::Discover(a),
+                Event::Dequeue(a),
+                Event::Examine(a, b),
+                Event::Tree(a, b),
+                Event::Discover(b),
+                Event::Finish(a),
+                Event::Dequeue(b),
+                Event::Examine(b, c),
+                Event::Tree(b, c),
+                Event::Discover(c),
+                Event::Finish(b),
+                Event::Dequeue(c),
+                Event::Examine(c, a),
+                Event::Finish(c),
+            ],
+            "Wrong BFS events"
+        );
+
+        let mut dfs = Recorder::default();
+
+        dfs_visit(&graph, a, &mut dfs).expect("The source vertex exists");
+        assert_eq!(
+            dfs.0,
+            [
+                Event::Discover(a),
+                Event::Examine(a, b),
+                Event::Tree(a, b),
+                Event::Discover(b),
+                Event::Examine(b, c),
+                Event::Tree(b, c),
+                Event::Discover(c),
+                Event::Examine(c, a),
+                Event::Finish(c),
+                Event::Finish(b),
+                Event::Finish(a),
+            ],
+            "Wrong DFS events"
+        );
+
+        let missing = Graph::<(), ()>::new().insert_vertex(());
+
+        assert!(
+            bfs_visit(&graph, missing, &mut Recorder::default()).is_none(),
+            "The source vertex doesn't exist"
+        );
+        assert!(
+            dfs_visit(&graph, missing, &mut Recorder::default()).is_none(),
+            "The source vertex doesn't exist"
+        );
+    }
+}
@@ -1 +1,6 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+mod collections;
 pub mod graph;